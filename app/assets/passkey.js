@@ -0,0 +1,95 @@
+// Cérémonies WebAuthn : conversion des options JSON du serveur vers l'API
+// `navigator.credentials`, et renvoi des réponses encodées en base64url.
+(function () {
+  function decode(value) {
+    const base64 = value.replace(/-/g, "+").replace(/_/g, "/");
+    const padded = base64 + "=".repeat((4 - (base64.length % 4)) % 4);
+    return Uint8Array.from(atob(padded), (c) => c.charCodeAt(0));
+  }
+
+  function encode(buffer) {
+    const bytes = new Uint8Array(buffer);
+    let binary = "";
+    bytes.forEach((b) => (binary += String.fromCharCode(b)));
+    return btoa(binary).replace(/\+/g, "-").replace(/\//g, "_").replace(/=+$/, "");
+  }
+
+  async function post(url, body) {
+    const response = await fetch(url, {
+      method: "POST",
+      headers: { "Content-Type": "application/json" },
+      body: body === undefined ? undefined : JSON.stringify(body),
+    });
+
+    if (!response.ok) {
+      throw new Error("La clé d'accès n'a pas pu être vérifiée");
+    }
+
+    return response.status === 204 ? null : response.json();
+  }
+
+  async function register(name) {
+    const options = await post("/profile/passkeys/options");
+
+    const credential = await navigator.credentials.create({
+      publicKey: {
+        ...options,
+        challenge: decode(options.challenge),
+        user: { ...options.user, id: decode(options.user.id) },
+        excludeCredentials: options.excludeCredentials.map((descriptor) => ({
+          ...descriptor,
+          id: decode(descriptor.id),
+        })),
+      },
+    });
+
+    return post("/profile/passkeys", {
+      name,
+      credential_id: encode(credential.rawId),
+      client_data_json: encode(credential.response.clientDataJSON),
+      attestation_object: encode(credential.response.attestationObject),
+    });
+  }
+
+  async function authenticate() {
+    const options = await post("/login/passkey/options");
+
+    const credential = await navigator.credentials.get({
+      publicKey: { ...options, challenge: decode(options.challenge) },
+    });
+
+    await post("/login/passkey", {
+      credential_id: encode(credential.rawId),
+      client_data_json: encode(credential.response.clientDataJSON),
+      authenticator_data: encode(credential.response.authenticatorData),
+      signature: encode(credential.response.signature),
+    });
+  }
+
+  function report(element, error) {
+    const output = element.querySelector("[data-passkey-error]");
+    if (output) {
+      output.textContent = error.message;
+    }
+  }
+
+  document.addEventListener("click", (event) => {
+    const button = event.target.closest("[data-passkey-login]");
+    if (!button) return;
+
+    event.preventDefault();
+    authenticate()
+      .then(() => window.location.assign("/"))
+      .catch((error) => report(button.parentElement, error));
+  });
+
+  document.addEventListener("submit", (event) => {
+    const form = event.target.closest("[data-passkey-register]");
+    if (!form) return;
+
+    event.preventDefault();
+    register(new FormData(form).get("name"))
+      .then(() => window.location.reload())
+      .catch((error) => report(form, error));
+  });
+})();
@@ -1,42 +1,195 @@
 use actix_web::{
     cookie::{time::OffsetDateTime, Cookie, Expiration},
     post,
-    web::{Data, Form},
-    HttpResponse, Responder,
+    web::{Data, Form, Json, ReqData},
+    HttpRequest, HttpResponse, Responder,
 };
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
 
 use signuis_core::{
-    forms::authentication::CredentialForm, models::session::Session,
-    services::authentication::AuthenticateWithCredential, Signuis,
+    forms::authentication::{
+        CredentialForm, PasskeyAssertionForm, PasskeyRegistrationForm, SecondFactorForm,
+    },
+    models::session::Session,
+    services::authentication::{
+        AuthenticateWithCredential, AuthenticateWithPasskey, AuthenticationOutcome,
+        BeginPasskeyAuthentication, BeginPasskeyRegistration, FinishPasskeyRegistration,
+        VerifySecondFactor,
+    },
+    Signuis,
 };
 
 use crate::error::ServerError;
 
+const SESSION_TOKEN_COOKIE: &str = "SIGNUIS_SESSION_TOKEN";
+const PENDING_AUTHENTICATION_COOKIE: &str = "SIGNUIS_PENDING_AUTHENTICATION";
+
+/// Cookie sécurisé, inaccessible aux scripts, expirant à la date donnée.
+fn secure_cookie(name: &'static str, value: String, expires_at: DateTime<Utc>) -> Cookie<'static> {
+    Cookie::build(name, value)
+        .secure(true)
+        .http_only(true)
+        .path("/")
+        .expires(Expiration::DateTime(
+            OffsetDateTime::from_unix_timestamp(expires_at.timestamp()).unwrap(),
+        ))
+        .finish()
+}
+
 #[post("/login")]
 pub async fn authenticate_with_credential(
     Form(form): Form<CredentialForm>,
-    session: Data<Session>,
+    session: ReqData<Session>,
     sg: Data<Signuis>,
 ) -> Result<impl Responder, actix_web::Error> {
-    let session = session.get_ref().clone();
+    let session = session.into_inner();
+
+    let outcome = sg
+        .auth
+        .execute(AuthenticateWithCredential::new(form, session))
+        .await
+        .map_err(ServerError::from)?;
+
+    Ok(match outcome {
+        AuthenticationOutcome::Authenticated(user_session) => HttpResponse::SeeOther()
+            .insert_header(("Location", "/"))
+            .cookie(secure_cookie(
+                SESSION_TOKEN_COOKIE,
+                user_session.token,
+                user_session.expires_at,
+            ))
+            .finish(),
+        AuthenticationOutcome::SecondFactorRequired(pending) => HttpResponse::SeeOther()
+            .insert_header(("Location", "/login/second-factor"))
+            .cookie(secure_cookie(
+                PENDING_AUTHENTICATION_COOKIE,
+                pending.token,
+                pending.expires_at,
+            ))
+            .finish(),
+        AuthenticationOutcome::SecondFactorEnrolmentRequired(user_session) => {
+            HttpResponse::SeeOther()
+                .insert_header(("Location", "/profile"))
+                .cookie(secure_cookie(
+                    SESSION_TOKEN_COOKIE,
+                    user_session.token,
+                    user_session.expires_at,
+                ))
+                .finish()
+        }
+    })
+}
+
+#[derive(Deserialize)]
+pub struct SecondFactorCode {
+    pub code: String,
+}
+
+#[post("/login/second-factor")]
+pub async fn verify_second_factor(
+    Form(SecondFactorCode { code }): Form<SecondFactorCode>,
+    req: HttpRequest,
+    sg: Data<Signuis>,
+) -> Result<impl Responder, actix_web::Error> {
+    let Some(token) = req.cookie(PENDING_AUTHENTICATION_COOKIE) else {
+        return Ok(HttpResponse::SeeOther()
+            .insert_header(("Location", "/login"))
+            .finish());
+    };
 
     let user_session = sg
-        .service
-        .send(AuthenticateWithCredential::new(form, session))
+        .auth
+        .execute(VerifySecondFactor {
+            form: SecondFactorForm {
+                token: token.value().to_owned(),
+                code,
+            },
+        })
         .await
-        .map_err(ServerError::from)?
         .map_err(ServerError::from)?;
 
-    let tok_cookie = Cookie::build("SIGNUIS_SESSION_TOKEN", user_session.token)
-        .secure(true)
-        .http_only(true)
-        .expires(Expiration::DateTime(
-            OffsetDateTime::from_unix_timestamp(user_session.expires_at.timestamp()).unwrap(),
-        ))
+    let mut pending_cookie = Cookie::build(PENDING_AUTHENTICATION_COOKIE, "")
+        .path("/")
         .finish();
+    pending_cookie.make_removal();
 
     Ok(HttpResponse::SeeOther()
         .insert_header(("Location", "/"))
-        .cookie(tok_cookie)
+        .cookie(secure_cookie(
+            SESSION_TOKEN_COOKIE,
+            user_session.token,
+            user_session.expires_at,
+        ))
+        .cookie(pending_cookie)
         .finish())
 }
+
+/// Options de la cérémonie de connexion par clé d'accès.
+#[post("/login/passkey/options")]
+pub async fn begin_passkey_authentication(
+    sg: Data<Signuis>,
+) -> Result<impl Responder, actix_web::Error> {
+    let options = sg
+        .auth
+        .execute(BeginPasskeyAuthentication)
+        .await
+        .map_err(ServerError::from)?;
+
+    Ok(Json(options))
+}
+
+#[post("/login/passkey")]
+pub async fn authenticate_with_passkey(
+    Json(form): Json<PasskeyAssertionForm>,
+    sg: Data<Signuis>,
+) -> Result<impl Responder, actix_web::Error> {
+    let user_session = sg
+        .auth
+        .execute(AuthenticateWithPasskey { form })
+        .await
+        .map_err(ServerError::from)?;
+
+    Ok(HttpResponse::NoContent()
+        .cookie(secure_cookie(
+            SESSION_TOKEN_COOKIE,
+            user_session.token,
+            user_session.expires_at,
+        ))
+        .finish())
+}
+
+/// Options de la cérémonie d'enregistrement d'une clé d'accès.
+#[post("/profile/passkeys/options")]
+pub async fn begin_passkey_registration(
+    session: ReqData<Session>,
+    sg: Data<Signuis>,
+) -> Result<impl Responder, actix_web::Error> {
+    let options = sg
+        .auth
+        .execute(BeginPasskeyRegistration {
+            session: session.into_inner(),
+        })
+        .await
+        .map_err(ServerError::from)?;
+
+    Ok(Json(options))
+}
+
+#[post("/profile/passkeys")]
+pub async fn finish_passkey_registration(
+    Json(form): Json<PasskeyRegistrationForm>,
+    session: ReqData<Session>,
+    sg: Data<Signuis>,
+) -> Result<impl Responder, actix_web::Error> {
+    let id = sg
+        .auth
+        .execute(FinishPasskeyRegistration {
+            form,
+            session: session.into_inner(),
+        })
+        .await
+        .map_err(ServerError::from)?;
+
+    Ok(Json(id))
+}
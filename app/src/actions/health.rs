@@ -0,0 +1,39 @@
+use actix_web::{get, http::StatusCode, web::Data, HttpResponse, Responder};
+
+use signuis_core::{health::HealthReport, Signuis};
+
+/// Réponse JSON du rapport, en 503 si le service n'est pas dans l'état attendu.
+fn health_response(report: HealthReport, healthy: bool) -> HttpResponse {
+    let status = if healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    HttpResponse::build(status).json(report)
+}
+
+/// Le service est en vie : ses acteurs répondent.
+#[get("/healthz")]
+pub async fn healthz(sg: Data<Signuis>) -> impl Responder {
+    let report = sg.liveness();
+    let live = report.is_live();
+    health_response(report, live)
+}
+
+/// Le service est prêt à traiter des requêtes.
+#[get("/readyz")]
+pub async fn readyz(sg: Data<Signuis>) -> impl Responder {
+    let report = sg.health().await;
+    let ready = report.is_ready();
+    health_response(report, ready)
+}
+
+/// Métriques au format texte de Prometheus, servies uniquement sur l'adresse
+/// d'écoute dédiée (`metrics.bind`).
+#[get("/metrics")]
+pub async fn metrics(sg: Data<Signuis>) -> impl Responder {
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(sg.metrics().await)
+}
@@ -1,4 +1,9 @@
 //! Actions are requests handled by the actix framework.
 pub mod auth;
+pub mod health;
 
-pub use auth::authenticate_with_credential;
+pub use auth::{
+    authenticate_with_credential, authenticate_with_passkey, begin_passkey_authentication,
+    begin_passkey_registration, finish_passkey_registration, verify_second_factor,
+};
+pub use health::{healthz, metrics, readyz};
@@ -0,0 +1,233 @@
+use leptos::{server, server_fn::codec::GetUrl, ServerFnError};
+use signuis_core::models::{
+    pagination::{Page, PageRequest},
+    passkey::{Passkey, PasskeyId},
+    personal_data::PersonalDataArchive,
+    second_factor::TotpEnrolment,
+    user::UserProfile,
+};
+
+#[server]
+pub async fn get_my_profile() -> Result<UserProfile, ServerFnError> {
+    use actix_web::web::Data;
+    use leptos_actix::extract;
+    use signuis_core::{services::account::GetMyProfile, Signuis};
+
+    let (sg,): (Data<Signuis>,) = extract().await?;
+    let session = super::current_session().await?;
+
+    sg.account
+        .execute(GetMyProfile { session })
+        .await
+        .map_err(super::server_error)
+}
+
+#[server]
+pub async fn update_profile(
+    username: String,
+    email: String,
+    locale: String,
+) -> Result<UserProfile, ServerFnError> {
+    use actix_web::web::Data;
+    use leptos_actix::extract;
+    use signuis_core::{
+        forms::account::UpdateProfileForm, services::account::UpdateProfile, Signuis,
+    };
+
+    let (sg,): (Data<Signuis>,) = extract().await?;
+    let session = super::current_session().await?;
+
+    let form = UpdateProfileForm {
+        username: Some(username),
+        email: Some(email),
+        locale: Some(locale),
+        avatar: None,
+    };
+
+    sg.account
+        .execute(UpdateProfile { form, session })
+        .await
+        .map_err(super::server_error)
+}
+
+#[server]
+pub async fn change_password(
+    current_password: String,
+    new_password: String,
+    confirm_password: String,
+) -> Result<(), ServerFnError> {
+    use actix_web::web::Data;
+    use leptos_actix::extract;
+    use signuis_core::{
+        forms::account::ChangePasswordForm, services::account::ChangePassword, Signuis,
+    };
+
+    let (sg,): (Data<Signuis>,) = extract().await?;
+    let session = super::current_session().await?;
+
+    let form = ChangePasswordForm {
+        current_password,
+        new_password,
+        confirm_password,
+    };
+
+    sg.account
+        .execute(ChangePassword { form, session })
+        .await
+        .map_err(super::server_error)
+}
+
+#[server(endpoint = "export_my_data", input = GetUrl)]
+pub async fn export_my_data() -> Result<PersonalDataArchive, ServerFnError> {
+    use actix_web::web::Data;
+    use leptos_actix::extract;
+    use signuis_core::{services::account::ExportMyData, Signuis};
+
+    let (sg,): (Data<Signuis>,) = extract().await?;
+    let session = super::current_session().await?;
+
+    sg.account
+        .execute(ExportMyData { session })
+        .await
+        .map_err(super::server_error)
+}
+
+#[server]
+pub async fn delete_my_account() -> Result<(), ServerFnError> {
+    use actix_web::web::Data;
+    use leptos_actix::extract;
+    use signuis_core::{services::account::DeleteMyAccount, Signuis};
+
+    let (sg,): (Data<Signuis>,) = extract().await?;
+    let session = super::current_session().await?;
+
+    sg.account
+        .execute(DeleteMyAccount { session })
+        .await
+        .map_err(super::server_error)?;
+
+    leptos_actix::redirect("/");
+
+    Ok(())
+}
+
+#[server]
+pub async fn reset_password(
+    token: String,
+    new_password: String,
+    confirm_password: String,
+) -> Result<(), ServerFnError> {
+    use actix_web::web::Data;
+    use leptos_actix::extract;
+    use signuis_core::{
+        forms::account::ResetPasswordForm, services::account::ResetPassword, Signuis,
+    };
+
+    let (sg,): (Data<Signuis>,) = extract().await?;
+
+    let form = ResetPasswordForm {
+        token,
+        new_password,
+        confirm_password,
+    };
+
+    sg.account
+        .execute(ResetPassword { form })
+        .await
+        .map_err(super::server_error)?;
+
+    leptos_actix::redirect("/login");
+
+    Ok(())
+}
+
+#[server]
+pub async fn begin_totp_enrolment() -> Result<TotpEnrolment, ServerFnError> {
+    use actix_web::web::Data;
+    use leptos_actix::extract;
+    use signuis_core::{services::authentication::BeginTotpEnrolment, Signuis};
+
+    let (sg,): (Data<Signuis>,) = extract().await?;
+    let session = super::current_session().await?;
+
+    sg.auth
+        .execute(BeginTotpEnrolment { session })
+        .await
+        .map_err(super::server_error)
+}
+
+/// Confirme l'inscription au second facteur, et retourne les codes de récupération.
+#[server]
+pub async fn confirm_totp_enrolment(code: String) -> Result<Vec<String>, ServerFnError> {
+    use actix_web::web::Data;
+    use leptos_actix::extract;
+    use signuis_core::{services::authentication::ConfirmTotpEnrolment, Signuis};
+
+    let (sg,): (Data<Signuis>,) = extract().await?;
+    let session = super::current_session().await?;
+
+    sg.auth
+        .execute(ConfirmTotpEnrolment { code, session })
+        .await
+        .map_err(super::server_error)
+}
+
+#[server]
+pub async fn regenerate_recovery_codes(code: String) -> Result<Vec<String>, ServerFnError> {
+    use actix_web::web::Data;
+    use leptos_actix::extract;
+    use signuis_core::{services::authentication::RegenerateRecoveryCodes, Signuis};
+
+    let (sg,): (Data<Signuis>,) = extract().await?;
+    let session = super::current_session().await?;
+
+    sg.auth
+        .execute(RegenerateRecoveryCodes { code, session })
+        .await
+        .map_err(super::server_error)
+}
+
+#[server]
+pub async fn disable_second_factor(code: String) -> Result<(), ServerFnError> {
+    use actix_web::web::Data;
+    use leptos_actix::extract;
+    use signuis_core::{services::authentication::DisableSecondFactor, Signuis};
+
+    let (sg,): (Data<Signuis>,) = extract().await?;
+    let session = super::current_session().await?;
+
+    sg.auth
+        .execute(DisableSecondFactor { code, session })
+        .await
+        .map_err(super::server_error)
+}
+
+#[server]
+pub async fn list_my_passkeys(page: PageRequest) -> Result<Page<Passkey>, ServerFnError> {
+    use actix_web::web::Data;
+    use leptos_actix::extract;
+    use signuis_core::{services::authentication::ListMyPasskeys, Signuis};
+
+    let (sg,): (Data<Signuis>,) = extract().await?;
+    let session = super::current_session().await?;
+
+    sg.auth
+        .execute(ListMyPasskeys { page, session })
+        .await
+        .map_err(super::server_error)
+}
+
+#[server]
+pub async fn delete_my_passkey(id: PasskeyId) -> Result<(), ServerFnError> {
+    use actix_web::web::Data;
+    use leptos_actix::extract;
+    use signuis_core::{services::authentication::DeleteMyPasskey, Signuis};
+
+    let (sg,): (Data<Signuis>,) = extract().await?;
+    let session = super::current_session().await?;
+
+    sg.auth
+        .execute(DeleteMyPasskey { id, session })
+        .await
+        .map_err(super::server_error)
+}
@@ -0,0 +1,178 @@
+use leptos::{server, ServerFnError};
+use signuis_core::models::{
+    pagination::PageRequest,
+    user::{UserAccountPage, UserId, UserRole, UserSearchQuery},
+};
+
+#[server]
+pub async fn list_user_accounts(
+    query: UserSearchQuery,
+    page: PageRequest,
+) -> Result<UserAccountPage, ServerFnError> {
+    use actix_web::web::Data;
+    use leptos_actix::extract;
+    use signuis_core::{services::account::ListUserAccounts, Signuis};
+
+    let (sg,): (Data<Signuis>,) = extract().await?;
+    let session = super::current_session().await?;
+
+    sg.account
+        .execute(ListUserAccounts {
+            query,
+            page,
+            session,
+        })
+        .await
+        .map_err(super::server_error)
+}
+
+#[server]
+pub async fn change_user_role(user_id: UserId, role: UserRole) -> Result<(), ServerFnError> {
+    use actix_web::web::Data;
+    use leptos_actix::extract;
+    use signuis_core::{services::account::ChangeUserRole, Signuis};
+
+    let (sg,): (Data<Signuis>,) = extract().await?;
+    let session = super::current_session().await?;
+
+    sg.account
+        .execute(ChangeUserRole {
+            user_id,
+            role,
+            session,
+        })
+        .await
+        .map_err(super::server_error)
+}
+
+#[server]
+pub async fn set_user_activation(user_id: UserId, activated: bool) -> Result<(), ServerFnError> {
+    use actix_web::web::Data;
+    use leptos_actix::extract;
+    use signuis_core::{services::account::SetUserActivation, Signuis};
+
+    let (sg,): (Data<Signuis>,) = extract().await?;
+    let session = super::current_session().await?;
+
+    sg.account
+        .execute(SetUserActivation {
+            user_id,
+            activated,
+            session,
+        })
+        .await
+        .map_err(super::server_error)
+}
+
+/// Impose un changement de mot de passe, et retourne le jeton de réinitialisation
+/// à transmettre à l'utilisateur.
+#[server]
+pub async fn force_password_reset(user_id: UserId) -> Result<String, ServerFnError> {
+    use actix_web::web::Data;
+    use leptos_actix::extract;
+    use signuis_core::{services::account::ForcePasswordReset, Signuis};
+
+    let (sg,): (Data<Signuis>,) = extract().await?;
+    let session = super::current_session().await?;
+
+    let ticket = sg
+        .account
+        .execute(ForcePasswordReset { user_id, session })
+        .await
+        .map_err(super::server_error)?;
+
+    Ok(ticket.token)
+}
+
+/// Ouvre une session au nom de l'utilisateur, en remplacement de celle de l'administrateur.
+#[server]
+pub async fn impersonate_user(user_id: UserId, reason: String) -> Result<(), ServerFnError> {
+    use actix_web::{
+        cookie::{time::OffsetDateTime, Cookie, Expiration},
+        http::header::{HeaderValue, SET_COOKIE},
+        web::Data,
+    };
+    use leptos::expect_context;
+    use leptos_actix::{extract, ResponseOptions};
+    use signuis_core::{
+        forms::account::ImpersonateUserForm, services::account::ImpersonateUser, Signuis,
+    };
+
+    let (sg,): (Data<Signuis>,) = extract().await?;
+    let session = super::current_session().await?;
+
+    let user_session = sg
+        .account
+        .execute(ImpersonateUser {
+            form: ImpersonateUserForm { user_id, reason },
+            session,
+        })
+        .await
+        .map_err(super::server_error)?;
+
+    let expires_at = OffsetDateTime::from_unix_timestamp(user_session.expires_at.timestamp())
+        .map_err(|error| ServerFnError::new(error.to_string()))?;
+
+    let cookie = Cookie::build("SIGNUIS_SESSION_TOKEN", user_session.token)
+        .secure(true)
+        .http_only(true)
+        .path("/")
+        .expires(Expiration::DateTime(expires_at))
+        .finish();
+
+    let response = expect_context::<ResponseOptions>();
+    response.insert_header(
+        SET_COOKIE,
+        HeaderValue::from_str(&cookie.to_string())
+            .map_err(|error| ServerFnError::new(error.to_string()))?,
+    );
+
+    leptos_actix::redirect("/");
+
+    Ok(())
+}
+
+/// Indique si la session courante est une session d'assistance.
+#[server]
+pub async fn is_impersonating() -> Result<bool, ServerFnError> {
+    let session = super::current_session().await?;
+
+    Ok(session.impersonator_id().is_some())
+}
+
+/// Met fin à la session d'assistance ; l'administrateur doit se reconnecter.
+#[server]
+pub async fn end_impersonation() -> Result<(), ServerFnError> {
+    use actix_web::{
+        cookie::Cookie,
+        http::header::{HeaderValue, SET_COOKIE},
+        web::Data,
+    };
+    use leptos::expect_context;
+    use leptos_actix::{extract, ResponseOptions};
+    use signuis_core::{services::account::EndImpersonation, Signuis};
+
+    let (sg,): (Data<Signuis>,) = extract().await?;
+    let session = super::current_session().await?;
+
+    sg.account
+        .execute(EndImpersonation { session })
+        .await
+        .map_err(super::server_error)?;
+
+    let mut cookie = Cookie::build("SIGNUIS_SESSION_TOKEN", "")
+        .path("/")
+        .finish();
+    cookie.make_removal();
+
+    let response = expect_context::<ResponseOptions>();
+    response.insert_header(
+        SET_COOKIE,
+        HeaderValue::from_str(&cookie.to_string())
+            .map_err(|error| ServerFnError::new(error.to_string()))?,
+    );
+
+    leptos_actix::redirect("/login");
+
+    Ok(())
+}
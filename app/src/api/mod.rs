@@ -1 +1,40 @@
+pub mod account;
+pub mod admin;
 pub mod reporting;
+
+#[cfg(feature = "ssr")]
+use leptos::ServerFnError;
+#[cfg(feature = "ssr")]
+use signuis_core::models::session::Session;
+
+/// Récupère la session associée à la requête par le middleware de session.
+#[cfg(feature = "ssr")]
+pub async fn current_session() -> Result<Session, ServerFnError> {
+    use actix_web::{HttpMessage, HttpRequest};
+
+    let req: HttpRequest = leptos_actix::extract().await?;
+
+    let session = req
+        .extensions()
+        .get::<Session>()
+        .cloned()
+        .unwrap_or(Session::Anonymous);
+
+    Ok(session)
+}
+
+/// Convertit une erreur du système Signuis en erreur de fonction serveur.
+#[cfg(feature = "ssr")]
+pub fn server_error(error: signuis_core::error::Error) -> ServerFnError {
+    use signuis_core::{error::ErrorKind, tracing::error};
+
+    // les causes ne sont pas transmises au client, mais journalisées
+    if matches!(
+        error.kind,
+        ErrorKind::InternalError | ErrorKind::DatabaseError
+    ) {
+        error!(target: "signuis::app", "{}", error.chain());
+    }
+
+    ServerFnError::new(error.kind.to_string())
+}
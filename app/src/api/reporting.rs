@@ -1,15 +1,77 @@
 use leptos::{server, ServerFnError};
-use signuis_core::models::nuisance_family::NuisanceFamily;
+use signuis_core::models::{
+    administrative_area::{AdministrativeArea, AreaLevel},
+    nuisance_family::NuisanceFamily,
+    pagination::{Page, PageRequest},
+    statistics::{ReportStatistic, ReportStatisticsQuery, WeeklyProfileCell},
+};
 
 #[server]
-pub async fn list_nuisance_families() -> Result<Vec<NuisanceFamily>, ServerFnError> {
+pub async fn list_nuisance_families(
+    page: PageRequest,
+) -> Result<Page<NuisanceFamily>, ServerFnError> {
     use actix_web::web::Data;
     use leptos_actix::extract;
     use signuis_core::{services::reporting::ListNuisanceFamilies, Signuis};
 
     let (sg,): (Data<Signuis>,) = extract().await?;
 
-    let nuisance_families = sg.service.send(ListNuisanceFamilies::all()).await??;
+    let nuisance_families = sg
+        .reporting
+        .execute(ListNuisanceFamilies { page })
+        .await
+        .map_err(super::server_error)?;
 
     Ok(nuisance_families)
 }
+
+#[server]
+pub async fn report_statistics(
+    query: ReportStatisticsQuery,
+) -> Result<Vec<ReportStatistic>, ServerFnError> {
+    use actix_web::web::Data;
+    use leptos_actix::extract;
+    use signuis_core::{services::reporting::GetReportStatistics, Signuis};
+
+    let (sg,): (Data<Signuis>,) = extract().await?;
+    let session = super::current_session().await?;
+
+    sg.reporting
+        .execute(GetReportStatistics { query, session })
+        .await
+        .map_err(super::server_error)
+}
+
+#[server]
+pub async fn weekly_profile(
+    query: ReportStatisticsQuery,
+) -> Result<Vec<WeeklyProfileCell>, ServerFnError> {
+    use actix_web::web::Data;
+    use leptos_actix::extract;
+    use signuis_core::{services::reporting::GetWeeklyProfile, Signuis};
+
+    let (sg,): (Data<Signuis>,) = extract().await?;
+    let session = super::current_session().await?;
+
+    sg.reporting
+        .execute(GetWeeklyProfile { query, session })
+        .await
+        .map_err(super::server_error)
+}
+
+#[server]
+pub async fn list_administrative_areas(
+    level: Option<AreaLevel>,
+    page: PageRequest,
+) -> Result<Page<AdministrativeArea>, ServerFnError> {
+    use actix_web::web::Data;
+    use leptos_actix::extract;
+    use signuis_core::{services::territory::ListAdministrativeAreas, Signuis};
+
+    let (sg,): (Data<Signuis>,) = extract().await?;
+
+    sg.territory
+        .execute(ListAdministrativeAreas { level, page })
+        .await
+        .map_err(super::server_error)
+}
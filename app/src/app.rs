@@ -15,6 +15,7 @@ pub fn App() -> impl IntoView {
         <Stylesheet href="https://unpkg.com/leaflet@1.9.3/dist/leaflet.css"/>
         <Script src="https://unpkg.com/leaflet@1.9.3/dist/leaflet.js"/>
         <Stylesheet id="leptos" href="/pkg/app.css"/>
+        <Script src="/assets/passkey.js"/>
 
         // sets the document title
         <Title text="Welcome to Leptos"/>
@@ -22,9 +23,15 @@ pub fn App() -> impl IntoView {
         // content for this welcome page
         <Router>
             <main class="bg-slate-300 min-h-screen w-full">
+                <pages::ImpersonationBanner/>
                 <Routes>
                     <Route path="/" view=pages::HomePage/>
                     <Route path="/login" view=pages::LoginPage />
+                    <Route path="/login/second-factor" view=pages::SecondFactorPage />
+                    <Route path="/dashboard" view=pages::DashboardPage />
+                    <Route path="/profile" view=pages::ProfilePage />
+                    <Route path="/reset-password" view=pages::ResetPasswordPage />
+                    <Route path="/admin" view=pages::AdminPage />
                     <Route path="/*any" view=NotFound/>
                 </Routes>
             </main>
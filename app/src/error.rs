@@ -1,13 +1,13 @@
 use actix::MailboxError;
 use actix_web::{http::StatusCode, HttpResponse, ResponseError};
-use signuis_core::error::ErrorKind;
+use signuis_core::{error::ErrorKind, tracing::error};
 
 #[derive(Debug)]
 pub struct ServerError(ErrorKind);
 
 impl std::fmt::Display for ServerError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        todo!()
+        write!(f, "{}", self.0)
     }
 }
 
@@ -18,6 +18,7 @@ impl ResponseError for ServerError {
             ErrorKind::DatabaseError => StatusCode::INTERNAL_SERVER_ERROR,
             ErrorKind::Invalid(_) => StatusCode::NOT_ACCEPTABLE,
             ErrorKind::Unauthorized => StatusCode::UNAUTHORIZED,
+            ErrorKind::NotFound => StatusCode::NOT_FOUND,
         }
     }
 
@@ -33,6 +34,9 @@ impl ResponseError for ServerError {
             ErrorKind::Unauthorized => HttpResponse::InternalServerError()
                 .append_header(("Location", "/401"))
                 .finish(),
+            ErrorKind::NotFound => HttpResponse::NotFound()
+                .append_header(("Location", "/404"))
+                .finish(),
         }
     }
 }
@@ -45,6 +49,15 @@ impl From<MailboxError> for ServerError {
 
 impl From<signuis_core::error::Error> for ServerError {
     fn from(value: signuis_core::error::Error) -> Self {
+        // seule la nature de l'erreur est renvoyée au client : ses causes sont
+        // journalisées ici, dans le span de la requête
+        if matches!(
+            value.kind,
+            ErrorKind::InternalError | ErrorKind::DatabaseError
+        ) {
+            error!(target: "signuis::app", "{}", value.chain());
+        }
+
         Self(value.kind)
     }
 }
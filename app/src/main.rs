@@ -17,6 +17,7 @@ async fn main() -> std::io::Result<()> {
     use app::App;
     use leptos::*;
     use leptos_actix::{generate_route_list, LeptosRoutes};
+    use signuis_core::config::{Config, Mode};
     use signuis_core::forms::authentication;
 
     let conf = get_configuration(None).await.unwrap();
@@ -25,11 +26,38 @@ async fn main() -> std::io::Result<()> {
     let routes = generate_route_list(App);
     println!("listening on http://{}", &addr);
 
-    let signuis = signuis_core::Signuis::new(signuis_core::SgSettings::default())
+    let config = Config::load(&Mode::from_env()).expect("invalid configuration");
+
+    signuis_core::telemetry::init(&config.log).expect("cannot setup logging");
+
+    let metrics_addr = config.metrics.bind.clone();
+    let settings = config.into_settings().expect("invalid configuration");
+
+    let signuis = signuis_core::Signuis::new(settings)
         .await
         .expect("cannot setup signuis");
 
-    HttpServer::new(move || {
+    // les métriques sont servies à part, sur une adresse réservée à la collecte
+    let metrics_server = match metrics_addr {
+        Some(metrics_addr) => {
+            let signuis = signuis.clone();
+            println!("serving metrics on http://{}/metrics", &metrics_addr);
+
+            Some(
+                HttpServer::new(move || {
+                    App::new()
+                        .service(actions::metrics)
+                        .app_data(web::Data::new(signuis.clone()))
+                })
+                .workers(1)
+                .bind(metrics_addr)?
+                .run(),
+            )
+        }
+        None => None,
+    };
+
+    let server = HttpServer::new(move || {
         let leptos_options = &conf.leptos_options;
         let site_root = &leptos_options.site_root;
 
@@ -41,14 +69,26 @@ async fn main() -> std::io::Result<()> {
             // serve the favicon from /favicon.ico
             .service(favicon)
             .service(actions::authenticate_with_credential)
+            .service(actions::verify_second_factor)
+            .service(actions::begin_passkey_authentication)
+            .service(actions::authenticate_with_passkey)
+            .service(actions::begin_passkey_registration)
+            .service(actions::finish_passkey_registration)
+            .service(actions::healthz)
+            .service(actions::readyz)
             .leptos_routes(leptos_options.to_owned(), routes.to_owned(), App)
             .app_data(web::Data::new(leptos_options.to_owned()))
             .wrap(crate::middleware::SessionMiddleware::new(signuis.clone()))
-            .app_data(signuis.clone())
+            .app_data(web::Data::new(signuis.clone()))
+            .wrap(crate::middleware::MetricsMiddleware)
     })
     .bind(&addr)?
-    .run()
-    .await
+    .run();
+
+    match metrics_server {
+        Some(metrics_server) => futures_util::try_join!(server, metrics_server).map(|_| ()),
+        None => server.await,
+    }
 }
 
 #[cfg(feature = "ssr")]
@@ -1,3 +1,5 @@
+mod metrics;
 mod session;
 
+pub use metrics::MetricsMiddleware;
 pub use session::SessionMiddleware;
@@ -1,18 +1,37 @@
 use actix_web::{
     dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    http::header::{HeaderName, HeaderValue},
     HttpMessage,
 };
 use futures_util::future::LocalBoxFuture;
 use signuis_core::{
-    models::session::Session, services::authentication::CheckUserSessionToken, Signuis,
+    models::session::Session,
+    services::authentication::CheckUserSessionToken,
+    tracing::{info_span, Instrument},
+    Signuis,
 };
 use std::{
     future::{ready, Ready},
     rc::Rc,
 };
+use uuid::Uuid;
 
 use crate::error::ServerError;
 
+/// En-tête portant l'identifiant de la requête, repris de l'amont s'il est fourni.
+const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Identifiant de la requête : celui fourni par l'amont (ex: proxy), ou un nouveau.
+fn request_id(req: &ServiceRequest) -> String {
+    req.headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| !id.is_empty() && id.len() <= 128)
+        .map(str::to_owned)
+        .unwrap_or_else(|| Uuid::new_v4().to_string())
+}
+
+/// Rattache la session à la requête, et trace la requête sous son identifiant.
 pub struct SessionMiddleware(signuis_core::Signuis);
 
 impl SessionMiddleware {
@@ -64,24 +83,41 @@ where
         let sg = self.signuis.clone();
         let service = self.service.clone();
 
-        Box::pin(async move {
-            let maybe_session_token = req.cookie("SIGNUIS_SESSION_TOKEN");
-
-            let session: Session = match maybe_session_token {
-                None => Session::Anonymous,
-                Some(token) => sg
-                    .service
-                    .send(CheckUserSessionToken::new(token.value()))
-                    .await
-                    .map_err(ServerError::from)?
-                    .map_err(ServerError::from)?
-                    .map(Session::User)
-                    .unwrap_or_else(|| Session::Anonymous),
-            };
-
-            req.extensions_mut().insert(session);
-
-            service.call(req).await
-        })
+        let request_id = request_id(&req);
+        let span = info_span!(
+            "http_request",
+            request_id = %request_id,
+            method = %req.method(),
+            path = %req.path(),
+        );
+
+        Box::pin(
+            async move {
+                let maybe_session_token = req.cookie("SIGNUIS_SESSION_TOKEN");
+
+                let session: Session = match maybe_session_token {
+                    None => Session::Anonymous,
+                    Some(token) => sg
+                        .auth
+                        .execute(CheckUserSessionToken::new(token.value()))
+                        .await
+                        .map_err(ServerError::from)?
+                        .map(Session::User)
+                        .unwrap_or_else(|| Session::Anonymous),
+                };
+
+                req.extensions_mut().insert(session);
+
+                let mut res = service.call(req).await?;
+
+                if let Ok(value) = HeaderValue::from_str(&request_id) {
+                    res.headers_mut()
+                        .insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+                }
+
+                Ok::<_, actix_web::Error>(res)
+            }
+            .instrument(span),
+        )
     }
 }
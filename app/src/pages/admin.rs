@@ -0,0 +1,198 @@
+use leptos::{
+    component, create_resource, create_server_action, create_signal, event_target_value, view,
+    CollectView, ErrorBoundary, IntoView, SignalGet as _, SignalSet as _, SignalUpdate as _,
+    Suspense,
+};
+use leptos_router::ActionForm;
+use signuis_core::models::{
+    pagination::PageRequest,
+    user::{UserAccount, UserRole, UserSearchQuery},
+};
+
+use crate::api::admin::{
+    ChangeUserRole, EndImpersonation, ForcePasswordReset, ImpersonateUser, SetUserActivation,
+};
+
+const INPUT_CLASS: &str = "shadow appearance-none border rounded py-1 px-2 text-gray-700 leading-tight focus:outline-none focus:shadow-outline";
+const BUTTON_CLASS: &str =
+    "bg-blue-500 hover:bg-blue-700 text-white text-xs font-bold py-1 px-2 rounded";
+const DANGER_BUTTON_CLASS: &str =
+    "bg-red-600 hover:bg-red-800 text-white text-xs font-bold py-1 px-2 rounded";
+
+const PER_PAGE: u32 = 50;
+
+#[component]
+fn UserAccountRow(account: UserAccount) -> impl IntoView {
+    let change_role = create_server_action::<ChangeUserRole>();
+    let set_activation = create_server_action::<SetUserActivation>();
+    let force_reset = create_server_action::<ForcePasswordReset>();
+    let impersonate = create_server_action::<ImpersonateUser>();
+
+    let reset_token = force_reset.value();
+    let user_id = account.id.to_string();
+    let is_admin = account.role == UserRole::Administrator;
+
+    view! {
+        <tr class="border-t align-top">
+            <td>
+                <div class="font-bold">{account.username}</div>
+                <div class="text-xs">{account.email}</div>
+            </td>
+            <td>{account.registered_at.format("%d/%m/%Y").to_string()}</td>
+            <td>
+                <ActionForm action=change_role class="flex gap-1">
+                    <input type="hidden" name="user_id" value=user_id.clone()/>
+                    <select class=INPUT_CLASS name="role">
+                        <option value="User" selected=!is_admin>"Utilisateur"</option>
+                        <option value="Administrator" selected=is_admin>"Administrateur"</option>
+                    </select>
+                    <input class=BUTTON_CLASS type="submit" value="Changer"/>
+                </ActionForm>
+            </td>
+            <td>
+                <ActionForm action=set_activation>
+                    <input type="hidden" name="user_id" value=user_id.clone()/>
+                    <input type="hidden" name="activated" value=(!account.activated).to_string()/>
+                    {if account.activated {
+                        view! { <input class=DANGER_BUTTON_CLASS type="submit" value="Désactiver"/> }
+                    } else {
+                        view! { <input class=BUTTON_CLASS type="submit" value="Réactiver"/> }
+                    }}
+                </ActionForm>
+            </td>
+            <td>
+                <ActionForm action=force_reset>
+                    <input type="hidden" name="user_id" value=user_id.clone()/>
+                    <input class=DANGER_BUTTON_CLASS type="submit" value="Imposer un nouveau mot de passe"/>
+                </ActionForm>
+                {account.password_reset_pending.then(|| view! {
+                    <p class="text-xs text-orange-600">"Changement de mot de passe en attente"</p>
+                })}
+                {move || reset_token.get().map(|result| match result {
+                    Ok(token) => view! {
+                        <p class="text-xs break-all">
+                            "Lien à transmettre : "
+                            <code>{format!("/reset-password?token={token}")}</code>
+                        </p>
+                    },
+                    Err(error) => view! { <p class="text-xs text-red-700">{error.to_string()}</p> },
+                })}
+            </td>
+            <td>
+                {(!is_admin && account.activated).then(|| view! {
+                    <ActionForm action=impersonate class="flex gap-1">
+                        <input type="hidden" name="user_id" value=user_id.clone()/>
+                        <input class=INPUT_CLASS type="text" name="reason" placeholder="Motif" required/>
+                        <input class=BUTTON_CLASS type="submit" value="Agir en son nom"/>
+                    </ActionForm>
+                })}
+            </td>
+        </tr>
+    }
+}
+
+#[component]
+pub fn AdminPage() -> impl IntoView {
+    let (search, set_search) = create_signal(String::new());
+    // curseurs des pages parcourues, celui de la page affichée en dernier
+    let (cursors, set_cursors) = create_signal(vec![None::<String>]);
+
+    let accounts = create_resource(
+        move || (search.get(), cursors.get().last().cloned().flatten()),
+        |(search, cursor)| {
+            crate::api::admin::list_user_accounts(
+                UserSearchQuery {
+                    search: Some(search).filter(|search| !search.is_empty()),
+                    ..UserSearchQuery::default()
+                },
+                PageRequest {
+                    cursor,
+                    limit: PER_PAGE,
+                },
+            )
+        },
+    );
+
+    view! {
+        <div class="p-4 flex flex-col gap-4">
+            <h1 class="text-xl font-bold">"Administration des comptes"</h1>
+            <p class="text-sm text-gray-600">
+                "Agir au nom d'un utilisateur remplace votre session ; terminez l'assistance puis reconnectez-vous pour la retrouver."
+            </p>
+            <input
+                class=INPUT_CLASS
+                type="search"
+                placeholder="Nom d'utilisateur ou adresse courriel"
+                on:change=move |ev| {
+                    set_cursors.set(vec![None]);
+                    set_search.set(event_target_value(&ev));
+                }
+            />
+            <div class="bg-white rounded shadow p-4">
+                <Suspense>
+                    <ErrorBoundary fallback=move |error| view! {{ format!("{:?}", error.get()) }}>
+                        {move || accounts.get().map(|accounts| accounts.map(|accounts| {
+                            let page = cursors.get().len();
+                            let pages = (accounts.total as u32).div_ceil(PER_PAGE).max(1);
+                            let next_cursor = accounts.accounts.next_cursor.clone();
+                            let last_page = next_cursor.is_none();
+
+                            view! {
+                                <table class="table-auto w-full text-sm">
+                                    <thead>
+                                        <tr>
+                                            <th class="text-left">"Utilisateur"</th>
+                                            <th class="text-left">"Inscription"</th>
+                                            <th class="text-left">"Rôle"</th>
+                                            <th class="text-left">"Compte"</th>
+                                            <th class="text-left">"Mot de passe"</th>
+                                            <th class="text-left">"Assistance"</th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>
+                                        {accounts
+                                            .accounts
+                                            .items
+                                            .into_iter()
+                                            .map(|account| view! { <UserAccountRow account/> })
+                                            .collect_view()
+                                        }
+                                    </tbody>
+                                </table>
+                                <div class="flex gap-2 items-center mt-2 text-sm">
+                                    <button disabled=page == 1 on:click=move |_| set_cursors.update(|cursors| { cursors.pop(); })>
+                                        "Précédente"
+                                    </button>
+                                    <span>{format!("Page {} / {} ({} comptes)", page, pages, accounts.total)}</span>
+                                    <button disabled=last_page on:click=move |_| set_cursors.update(|cursors| cursors.push(next_cursor.clone()))>
+                                        "Suivante"
+                                    </button>
+                                </div>
+                            }
+                        }))}
+                    </ErrorBoundary>
+                </Suspense>
+            </div>
+        </div>
+    }
+}
+
+/// Bandeau signalant une session d'assistance, avec de quoi y mettre fin.
+#[component]
+pub fn ImpersonationBanner() -> impl IntoView {
+    let end_impersonation = create_server_action::<EndImpersonation>();
+    let impersonating = create_resource(|| (), |_| crate::api::admin::is_impersonating());
+
+    view! {
+        <Suspense>
+            {move || impersonating.get().and_then(Result::ok).filter(|impersonating| *impersonating).map(|_| view! {
+                <div class="bg-amber-200 p-2 flex gap-2 items-center text-sm">
+                    <span>"Vous agissez au nom d'un utilisateur."</span>
+                    <ActionForm action=end_impersonation>
+                        <input class=BUTTON_CLASS type="submit" value="Terminer l'assistance"/>
+                    </ActionForm>
+                </div>
+            })}
+        </Suspense>
+    }
+}
@@ -12,6 +12,7 @@ pub fn LoginPage() -> impl IntoView {
                     class="shadow appearance-none border rounded w-full py-2 px-3 text-gray-700 leading-tight focus:outline-none focus:shadow-outline"
                     type="text"
                     id="username_or_email"
+                    name="username_or_email"
                 />
                 </div>
                 <div class="mb-4">
@@ -20,14 +21,21 @@ pub fn LoginPage() -> impl IntoView {
                     class="shadow appearance-none border rounded w-full py-2 px-3 text-gray-700 leading-tight focus:outline-none focus:shadow-outline"
                     type="password"
                     id="password"
+                    name="password"
                 />
                 </div>
                 <div class="flex items-center justify-between">
                     <input
                         class="bg-blue-500 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded focus:outline-none focus:shadow-outline"
-                        type="button" value="Se connecter"/>
+                        type="submit" value="Se connecter"/>
                     </div>
             </form>
+            <div class="bg-white shadow-md rounded px-8 py-4 mb-4 flex flex-col items-center gap-2">
+                <button
+                    class="bg-slate-700 hover:bg-slate-900 text-white font-bold py-2 px-4 rounded focus:outline-none focus:shadow-outline"
+                    type="button" data-passkey-login>"Se connecter avec une clé d'accès"</button>
+                <p class="text-red-700 text-sm" data-passkey-error></p>
+            </div>
             <p class="text-center text-gray-500 text-xs">
                 "© 2024 Gaël Pabois"
             </p>
@@ -35,3 +43,29 @@ pub fn LoginPage() -> impl IntoView {
         </main>
     }
 }
+
+#[component]
+pub fn SecondFactorPage() -> impl IntoView {
+    view! {
+        <main class="w-full flex items-center justify-center pt-8">
+            <form method="post" action="/login/second-factor" class="bg-white shadow-md rounded px-8 pt-6 pb-8 mb-4">
+                <div class="mb-4">
+                <label class="block text-gray-700 text-sm font-bold mb-2" for="code">"Code de l'application d'authentification ou code de récupération"</label>
+                <input
+                    class="shadow appearance-none border rounded w-full py-2 px-3 text-gray-700 leading-tight focus:outline-none focus:shadow-outline"
+                    type="text"
+                    id="code"
+                    name="code"
+                    autocomplete="one-time-code"
+                    autofocus
+                />
+                </div>
+                <div class="flex items-center justify-between">
+                    <input
+                        class="bg-blue-500 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded focus:outline-none focus:shadow-outline"
+                        type="submit" value="Vérifier"/>
+                </div>
+            </form>
+        </main>
+    }
+}
@@ -0,0 +1,218 @@
+use chrono::{Duration, Utc};
+use leptos::{
+    component, create_resource, create_signal, event_target_value, view, CollectView,
+    ErrorBoundary, IntoView, SignalGet as _, SignalSet as _, Suspense,
+};
+use signuis_core::models::{
+    administrative_area::AreaLevel,
+    pagination::PageRequest,
+    statistics::{
+        ReportStatistic, ReportStatisticsQuery, StatisticsGrouping, TimeBucket, WeeklyProfileCell,
+    },
+};
+
+use crate::api;
+
+const DAYS_OF_WEEK: [&str; 7] = ["Lun", "Mar", "Mer", "Jeu", "Ven", "Sam", "Dim"];
+
+/// Critères des statistiques sur les derniers jours, éventuellement restreints à un département.
+fn last_days(
+    days: i64,
+    bucket: TimeBucket,
+    group_by: StatisticsGrouping,
+    department: Option<String>,
+) -> ReportStatisticsQuery {
+    let until = Utc::now();
+
+    ReportStatisticsQuery {
+        bucket,
+        group_by,
+        from: until - Duration::days(days),
+        until,
+        family_id: None,
+        type_id: None,
+        area_code: department,
+        area_level: Some(AreaLevel::Department),
+    }
+}
+
+#[component]
+fn StatisticsTable(statistics: Vec<ReportStatistic>) -> impl IntoView {
+    view! {
+        <table class="table-auto w-full text-sm">
+            <thead>
+                <tr>
+                    <th class="text-left">"Période"</th>
+                    <th class="text-left">"Groupe"</th>
+                    <th class="text-right">"Signalements"</th>
+                    <th class="text-right">"Intensité moyenne"</th>
+                    <th class="text-right">"Intensité max."</th>
+                </tr>
+            </thead>
+            <tbody>
+                {statistics
+                    .into_iter()
+                    .map(|statistic| view! {
+                        <tr>
+                            <td>{statistic.bucket.format("%d/%m/%Y %Hh").to_string()}</td>
+                            <td>{statistic.group_label.unwrap_or_else(|| "Tous".to_owned())}</td>
+                            <td class="text-right">{statistic.count}</td>
+                            <td class="text-right">{format!("{:.1}", statistic.mean_intensity)}</td>
+                            <td class="text-right">{statistic.max_intensity}</td>
+                        </tr>
+                    })
+                    .collect_view()
+                }
+            </tbody>
+        </table>
+    }
+}
+
+#[component]
+fn WeeklyHeatmap(cells: Vec<WeeklyProfileCell>) -> impl IntoView {
+    let max_count = cells
+        .iter()
+        .map(|cell| cell.count)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    let count_at = move |day: i32, hour: i32| {
+        cells
+            .iter()
+            .find(|cell| cell.day_of_week == day && cell.hour == hour)
+            .map(|cell| cell.count)
+            .unwrap_or(0)
+    };
+
+    view! {
+        <div class="grid grid-cols-[3rem_repeat(24,minmax(0,1fr))] gap-px text-xs">
+            <div></div>
+            {(0..24).map(|hour| view! { <div class="text-center">{hour}</div> }).collect_view()}
+            {DAYS_OF_WEEK
+                .iter()
+                .enumerate()
+                .map(|(index, day)| {
+                    let day_of_week = index as i32 + 1;
+                    view! {
+                        <div>{*day}</div>
+                        {(0..24)
+                            .map(|hour| {
+                                let count = count_at(day_of_week, hour);
+                                let opacity = count as f64 / max_count as f64;
+                                view! {
+                                    <div
+                                        class="h-4 bg-red-600"
+                                        style=format!("opacity: {:.2}", opacity.max(0.05))
+                                        title=format!("{count} signalement(s)")
+                                    ></div>
+                                }
+                            })
+                            .collect_view()
+                        }
+                    }
+                })
+                .collect_view()
+            }
+        </div>
+    }
+}
+
+#[component]
+pub fn DashboardPage() -> impl IntoView {
+    let (bucket, set_bucket) = create_signal(TimeBucket::Day);
+    let (group_by, set_group_by) = create_signal(StatisticsGrouping::Family);
+    let (department, set_department) = create_signal(None::<String>);
+
+    let departments = create_resource(
+        || (),
+        |_| async move {
+            api::reporting::list_administrative_areas(
+                Some(AreaLevel::Department),
+                PageRequest::first(PageRequest::MAX_LIMIT),
+            )
+            .await
+            .map(|page| page.items)
+        },
+    );
+
+    let statistics = create_resource(
+        move || (bucket.get(), group_by.get(), department.get()),
+        |(bucket, group_by, department)| async move {
+            let days = if bucket == TimeBucket::Hour { 2 } else { 30 };
+            api::reporting::report_statistics(last_days(days, bucket, group_by, department)).await
+        },
+    );
+
+    let weekly_profile = create_resource(
+        move || department.get(),
+        |department| async move {
+            api::reporting::weekly_profile(last_days(
+                90,
+                TimeBucket::Hour,
+                StatisticsGrouping::None,
+                department,
+            ))
+            .await
+        },
+    );
+
+    view! {
+        <div class="p-4 flex flex-col gap-4">
+            <h1 class="text-xl font-bold">"Tableau de bord"</h1>
+            <div class="flex gap-2">
+                <select on:change=move |ev| set_bucket.set(match event_target_value(&ev).as_str() {
+                    "hour" => TimeBucket::Hour,
+                    "week" => TimeBucket::Week,
+                    _ => TimeBucket::Day,
+                })>
+                    <option value="hour">"Par heure"</option>
+                    <option value="day" selected>"Par jour"</option>
+                    <option value="week">"Par semaine"</option>
+                </select>
+                <select on:change=move |ev| set_group_by.set(match event_target_value(&ev).as_str() {
+                    "none" => StatisticsGrouping::None,
+                    "type" => StatisticsGrouping::Type,
+                    "area" => StatisticsGrouping::Area,
+                    _ => StatisticsGrouping::Family,
+                })>
+                    <option value="none">"Sans regroupement"</option>
+                    <option value="family" selected>"Par famille"</option>
+                    <option value="type">"Par type"</option>
+                    <option value="area">"Par département"</option>
+                </select>
+                <select on:change=move |ev| set_department.set(Some(event_target_value(&ev)).filter(|code| !code.is_empty()))>
+                    <option value="" selected>"Tous les départements"</option>
+                    <Suspense>
+                        {move || departments.get().and_then(Result::ok).map(|departments| departments
+                            .into_iter()
+                            .map(|area| view! {
+                                <option value=area.code.clone()>{format!("{} - {}", area.code, area.name)}</option>
+                            })
+                            .collect_view()
+                        )}
+                    </Suspense>
+                </select>
+            </div>
+            <div class="bg-white rounded shadow p-4">
+                <Suspense>
+                    <ErrorBoundary fallback=move |error| view! {{ format!("{:?}", error.get()) }}>
+                        {move || statistics.get().map(|statistics| statistics.map(|statistics| view! {
+                            <StatisticsTable statistics/>
+                        }))}
+                    </ErrorBoundary>
+                </Suspense>
+            </div>
+            <div class="bg-white rounded shadow p-4">
+                <h2 class="font-bold mb-2">"Répartition hebdomadaire (90 derniers jours)"</h2>
+                <Suspense>
+                    <ErrorBoundary fallback=move |error| view! {{ format!("{:?}", error.get()) }}>
+                        {move || weekly_profile.get().map(|cells| cells.map(|cells| view! {
+                            <WeeklyHeatmap cells/>
+                        }))}
+                    </ErrorBoundary>
+                </Suspense>
+            </div>
+        </div>
+    }
+}
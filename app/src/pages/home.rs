@@ -6,6 +6,7 @@ use leptos_leaflet::*;
 
 use crate::api;
 use signuis_core::forms::reporting::CreateNuisanceReportForm;
+use signuis_core::models::pagination::PageRequest;
 
 #[component]
 pub fn NuisanceReportForm() -> impl IntoView {
@@ -56,7 +57,11 @@ pub fn NuisanceReportForm() -> impl IntoView {
 
     let nuisance_families = create_resource(
         || (),
-        |_| async move { api::reporting::list_nuisance_families().await },
+        |_| async move {
+            api::reporting::list_nuisance_families(PageRequest::first(PageRequest::MAX_LIMIT))
+                .await
+                .map(|page| page.items)
+        },
     );
     view! {
         {move || {
@@ -1,5 +1,13 @@
+mod admin;
 mod auth;
+mod dashboard;
 mod home;
+mod profile;
+mod reset_password;
 
-pub use auth::LoginPage;
+pub use admin::{AdminPage, ImpersonationBanner};
+pub use auth::{LoginPage, SecondFactorPage};
+pub use dashboard::DashboardPage;
 pub use home::HomePage;
+pub use profile::ProfilePage;
+pub use reset_password::ResetPasswordPage;
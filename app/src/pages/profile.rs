@@ -0,0 +1,248 @@
+use leptos::{
+    component, create_resource, create_server_action, view, CollectView, ErrorBoundary, IntoView,
+    SignalGet as _, Suspense,
+};
+use leptos_router::ActionForm;
+use signuis_core::models::{pagination::PageRequest, user::UserProfile};
+
+use crate::api::account::{
+    BeginTotpEnrolment, ChangePassword, ConfirmTotpEnrolment, DeleteMyAccount, DeleteMyPasskey,
+    DisableSecondFactor, RegenerateRecoveryCodes, UpdateProfile,
+};
+
+const INPUT_CLASS: &str = "shadow appearance-none border rounded w-full py-2 px-3 text-gray-700 leading-tight focus:outline-none focus:shadow-outline";
+const LABEL_CLASS: &str = "block text-gray-700 text-sm font-bold mb-2";
+const BUTTON_CLASS: &str = "bg-blue-500 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded focus:outline-none focus:shadow-outline";
+
+#[component]
+fn ProfileForm(profile: UserProfile) -> impl IntoView {
+    let update_profile = create_server_action::<UpdateProfile>();
+    let result = update_profile.value();
+
+    view! {
+        <ActionForm action=update_profile class="flex flex-col gap-4">
+            <div>
+                <label class=LABEL_CLASS for="username">"Nom d'utilisateur"</label>
+                <input class=INPUT_CLASS type="text" id="username" name="username" value=profile.username/>
+            </div>
+            <div>
+                <label class=LABEL_CLASS for="email">"Adresse courriel"</label>
+                <input class=INPUT_CLASS type="email" id="email" name="email" value=profile.email/>
+                {profile.email_verified_at.is_none().then(|| view! {
+                    <p class="text-xs text-orange-600">"Adresse en attente de vérification"</p>
+                })}
+            </div>
+            <div>
+                <label class=LABEL_CLASS for="locale">"Langue"</label>
+                <select class=INPUT_CLASS id="locale" name="locale">
+                    <option value="fr" selected=profile.locale.as_deref() != Some("en")>"Français"</option>
+                    <option value="en" selected=profile.locale.as_deref() == Some("en")>"English"</option>
+                </select>
+            </div>
+            <input class=BUTTON_CLASS type="submit" value="Enregistrer"/>
+            {move || result.get().map(|result| match result {
+                Ok(_) => view! { <p class="text-green-700">"Profil mis à jour"</p> },
+                Err(error) => view! { <p class="text-red-700">{error.to_string()}</p> },
+            })}
+        </ActionForm>
+    }
+}
+
+#[component]
+fn PasswordForm() -> impl IntoView {
+    let change_password = create_server_action::<ChangePassword>();
+    let result = change_password.value();
+
+    view! {
+        <ActionForm action=change_password class="flex flex-col gap-4">
+            <div>
+                <label class=LABEL_CLASS for="current_password">"Mot de passe actuel"</label>
+                <input class=INPUT_CLASS type="password" id="current_password" name="current_password"/>
+            </div>
+            <div>
+                <label class=LABEL_CLASS for="new_password">"Nouveau mot de passe"</label>
+                <input class=INPUT_CLASS type="password" id="new_password" name="new_password"/>
+            </div>
+            <div>
+                <label class=LABEL_CLASS for="confirm_password">"Confirmation"</label>
+                <input class=INPUT_CLASS type="password" id="confirm_password" name="confirm_password"/>
+            </div>
+            <input class=BUTTON_CLASS type="submit" value="Changer le mot de passe"/>
+            {move || result.get().map(|result| match result {
+                Ok(_) => view! { <p class="text-green-700">"Mot de passe changé"</p> },
+                Err(error) => view! { <p class="text-red-700">{error.to_string()}</p> },
+            })}
+        </ActionForm>
+    }
+}
+
+#[component]
+fn RecoveryCodes(codes: Vec<String>) -> impl IntoView {
+    view! {
+        <div class="bg-yellow-50 border rounded p-2 text-sm">
+            <p>"Conservez ces codes de récupération en lieu sûr ; chacun ne sert qu'une fois."</p>
+            <ul class="font-mono grid grid-cols-2">
+                {codes.into_iter().map(|code| view! { <li>{code}</li> }).collect_view()}
+            </ul>
+        </div>
+    }
+}
+
+#[component]
+fn SecondFactorForm(enabled: bool) -> impl IntoView {
+    let begin_enrolment = create_server_action::<BeginTotpEnrolment>();
+    let confirm_enrolment = create_server_action::<ConfirmTotpEnrolment>();
+    let regenerate_codes = create_server_action::<RegenerateRecoveryCodes>();
+    let disable = create_server_action::<DisableSecondFactor>();
+
+    let enrolment = begin_enrolment.value();
+    let confirmed = confirm_enrolment.value();
+    let regenerated = regenerate_codes.value();
+    let disabled = disable.value();
+
+    let recovery_codes = move || {
+        confirmed
+            .get()
+            .or_else(|| regenerated.get())
+            .map(|result| match result {
+                Ok(codes) => view! { <RecoveryCodes codes/> }.into_view(),
+                Err(error) => view! { <p class="text-red-700">{error.to_string()}</p> }.into_view(),
+            })
+    };
+
+    if enabled {
+        return view! {
+            <div class="flex flex-col gap-4">
+                <p>"L'authentification à deux facteurs est activée."</p>
+                <ActionForm action=regenerate_codes class="flex gap-2">
+                    <input class=INPUT_CLASS type="text" name="code" inputmode="numeric" autocomplete="one-time-code" placeholder="Code"/>
+                    <input class=BUTTON_CLASS type="submit" value="Nouveaux codes de récupération"/>
+                </ActionForm>
+                <ActionForm action=disable class="flex gap-2">
+                    <input class=INPUT_CLASS type="text" name="code" inputmode="numeric" autocomplete="one-time-code" placeholder="Code"/>
+                    <input class="bg-red-600 hover:bg-red-800 text-white font-bold py-2 px-4 rounded" type="submit" value="Désactiver"/>
+                </ActionForm>
+                {recovery_codes}
+                {move || disabled.get().map(|result| match result {
+                    Ok(_) => view! { <p class="text-green-700">"Authentification à deux facteurs désactivée"</p> },
+                    Err(error) => view! { <p class="text-red-700">{error.to_string()}</p> },
+                })}
+            </div>
+        }
+        .into_view();
+    }
+
+    view! {
+        <div class="flex flex-col gap-4">
+            <ActionForm action=begin_enrolment>
+                <input class=BUTTON_CLASS type="submit" value="Activer l'authentification à deux facteurs"/>
+            </ActionForm>
+            {move || enrolment.get().map(|result| match result {
+                Ok(enrolment) => view! {
+                    <div class="flex flex-col gap-2">
+                        <p>"Scannez ce QR code avec votre application d'authentification, puis saisissez le code affiché."</p>
+                        <img class="w-48 h-48" src=format!("data:image/png;base64,{}", enrolment.qr_code) alt="QR code"/>
+                        <p class="text-xs">"Clé : " <code>{enrolment.secret}</code></p>
+                        <ActionForm action=confirm_enrolment class="flex gap-2">
+                            <input class=INPUT_CLASS type="text" name="code" inputmode="numeric" autocomplete="one-time-code" placeholder="123456"/>
+                            <input class=BUTTON_CLASS type="submit" value="Confirmer"/>
+                        </ActionForm>
+                    </div>
+                }.into_view(),
+                Err(error) => view! { <p class="text-red-700">{error.to_string()}</p> }.into_view(),
+            })}
+            {recovery_codes}
+        </div>
+    }
+    .into_view()
+}
+
+/// Clés d'accès de l'utilisateur ; l'enregistrement passe par `passkey.js`.
+#[component]
+fn Passkeys() -> impl IntoView {
+    let delete_passkey = create_server_action::<DeleteMyPasskey>();
+    let passkeys = create_resource(
+        move || delete_passkey.version().get(),
+        |_| async move {
+            crate::api::account::list_my_passkeys(PageRequest::first(PageRequest::MAX_LIMIT))
+                .await
+                .map(|page| page.items)
+        },
+    );
+
+    view! {
+        <div class="flex flex-col gap-4">
+            <Suspense>
+                {move || passkeys.get().and_then(Result::ok).map(|passkeys| view! {
+                    <ul class="flex flex-col gap-2">
+                        {passkeys.into_iter().map(|passkey| view! {
+                            <li class="flex items-center justify-between">
+                                <span>
+                                    {passkey.name}
+                                    <span class="text-xs text-gray-500">
+                                        {passkey.last_used_at.map(|at| format!(" — utilisée le {}", at.format("%d/%m/%Y")))}
+                                    </span>
+                                </span>
+                                <ActionForm action=delete_passkey>
+                                    <input type="hidden" name="id" value=passkey.id.to_string()/>
+                                    <input class="text-red-700 underline" type="submit" value="Supprimer"/>
+                                </ActionForm>
+                            </li>
+                        }).collect_view()}
+                    </ul>
+                })}
+            </Suspense>
+            <form class="flex gap-2" data-passkey-register>
+                <input class=INPUT_CLASS type="text" name="name" placeholder="Nom de la clé, ex: Téléphone"/>
+                <input class=BUTTON_CLASS type="submit" value="Ajouter une clé d'accès"/>
+                <p class="text-red-700 text-sm" data-passkey-error></p>
+            </form>
+        </div>
+    }
+}
+
+#[component]
+pub fn ProfilePage() -> impl IntoView {
+    let profile = create_resource(|| (), |_| crate::api::account::get_my_profile());
+    let delete_account = create_server_action::<DeleteMyAccount>();
+
+    view! {
+        <div class="p-4 flex flex-col gap-4 max-w-xl mx-auto">
+            <h1 class="text-xl font-bold">"Mon profil"</h1>
+            <div class="bg-white rounded shadow p-4">
+                <Suspense>
+                    <ErrorBoundary fallback=move |error| view! {{ format!("{:?}", error.get()) }}>
+                        {move || profile.get().map(|profile| profile.map(|profile| view! {
+                            <ProfileForm profile/>
+                        }))}
+                    </ErrorBoundary>
+                </Suspense>
+            </div>
+            <div class="bg-white rounded shadow p-4">
+                <h2 class="font-bold mb-2">"Mot de passe"</h2>
+                <PasswordForm/>
+            </div>
+            <div class="bg-white rounded shadow p-4">
+                <h2 class="font-bold mb-2">"Authentification à deux facteurs"</h2>
+                <Suspense>
+                    {move || profile.get().and_then(Result::ok).map(|profile| view! {
+                        <SecondFactorForm enabled=profile.second_factor_enabled/>
+                    })}
+                </Suspense>
+            </div>
+            <div class="bg-white rounded shadow p-4">
+                <h2 class="font-bold mb-2">"Clés d'accès"</h2>
+                <Passkeys/>
+            </div>
+            <div class="bg-white rounded shadow p-4 flex flex-col gap-2">
+                <h2 class="font-bold">"Mes données"</h2>
+                <a class="underline" href="/api/export_my_data" download="signuis.json">"Télécharger mes données"</a>
+                <ActionForm action=delete_account>
+                    <input
+                        class="bg-red-600 hover:bg-red-800 text-white font-bold py-2 px-4 rounded"
+                        type="submit" value="Supprimer mon compte"/>
+                </ActionForm>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,38 @@
+use leptos::{component, create_server_action, view, IntoView, SignalGet as _, SignalWith as _};
+use leptos_router::{use_query_map, ActionForm};
+
+use crate::api::account::ResetPassword;
+
+const INPUT_CLASS: &str = "shadow appearance-none border rounded w-full py-2 px-3 text-gray-700 leading-tight focus:outline-none focus:shadow-outline";
+const LABEL_CLASS: &str = "block text-gray-700 text-sm font-bold mb-2";
+const BUTTON_CLASS: &str = "bg-blue-500 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded focus:outline-none focus:shadow-outline";
+
+#[component]
+pub fn ResetPasswordPage() -> impl IntoView {
+    let query = use_query_map();
+    let token = move || query.with(|query| query.get("token").cloned().unwrap_or_default());
+
+    let reset_password = create_server_action::<ResetPassword>();
+    let result = reset_password.value();
+
+    view! {
+        <main class="w-full flex items-center justify-center pt-8">
+            <ActionForm action=reset_password class="bg-white shadow-md rounded px-8 pt-6 pb-8 mb-4 flex flex-col gap-4">
+                <h1 class="text-xl font-bold">"Nouveau mot de passe"</h1>
+                <input type="hidden" name="token" value=token/>
+                <div>
+                    <label class=LABEL_CLASS for="new_password">"Nouveau mot de passe"</label>
+                    <input class=INPUT_CLASS type="password" id="new_password" name="new_password"/>
+                </div>
+                <div>
+                    <label class=LABEL_CLASS for="confirm_password">"Confirmation"</label>
+                    <input class=INPUT_CLASS type="password" id="confirm_password" name="confirm_password"/>
+                </div>
+                <input class=BUTTON_CLASS type="submit" value="Changer le mot de passe"/>
+                {move || result.get().and_then(Result::err).map(|error| view! {
+                    <p class="text-red-700">{error.to_string()}</p>
+                })}
+            </ActionForm>
+        </main>
+    }
+}
@@ -1,17 +1,60 @@
-use signuis_core::{services::{ServicePool, database::{DatabasePool, DatabasePoolArgs}}, config::Config, Error, fixtures::{self, nuisance_types::NuisanceTypeFixture, rel::ForeignKeyFixture, nuisance_reports::NuisanceReportFixture}};
+use std::sync::Arc;
+
+use signuis_core::{config::Config, error::Error, fixtures::{self, nuisance_families::NuisanceFamilyFixture, nuisance_types::NuisanceTypeFixture, rel::ForeignKeyFixture, nuisance_reports::NuisanceReportFixture, spatial::ClusteredPoints, users::UserFixture}};
+use signuis_core::models::{nuisance_family::NuisanceFamilyId, nuisance_type::NuisanceTypeId, user::UserId};
 use signuis_core::log::{info, error};
-use futures::stream::StreamExt;
-use rand::seq::SliceRandom;
+use signuis_core::config::{load_env_files, Mode};
+use signuis_core::error::ErrorKind;
+use signuis_core::telemetry::{self, LogSettings};
+use signuis_core::{Signuis, SgSettings, forms::administrative_area::ImportAdministrativeAreaForm, models::administrative_area::AreaLevel, services::territory::ImportAdministrativeArea};
+use futures::TryStreamExt;
+use rand::Rng;
 
-#[tokio::main]
+#[actix::main]
 async fn main() -> Result<(), Error> {
+    // la variable RUST_LOG permet d'affiner les journaux
+    telemetry::init(&LogSettings::default())?;
+
     let cmd = clap::Command::new("signuis-cli")
         .bin_name("signuis-cli")
         .subcommand_required(true)
-        .subcommand(clap::Command::new("db:migrate"))
-        .subcommand(clap::Command::new("db:revert"))
+        .subcommand(clap::Command::new("db:migrate").about("Applique les migrations en attente"))
+        .subcommand(
+            clap::Command::new("db:revert")
+                .about("Annule les dernières migrations appliquées")
+                .arg(
+                    clap::Arg::new("steps")
+                        .long("steps")
+                        .value_parser(clap::value_parser!(usize))
+                        .default_value("1"),
+                ),
+        )
+        .subcommand(clap::Command::new("db:status").about("Affiche l'état des migrations"))
         .subcommand(clap::Command::new("dev:reset"))
-        .subcommand(clap::Command::new("dev:gen:fixtures"));
+        .subcommand(clap::Command::new("dev:gen:fixtures"))
+        .subcommand(
+            clap::Command::new("config:check")
+                .about("Vérifie la configuration du service")
+                .arg(clap::Arg::new("file").long("file"))
+                .arg(
+                    clap::Arg::new("mode")
+                        .long("mode")
+                        .value_parser(["test", "dev", "prod"]),
+                ),
+        )
+        .subcommand(
+            clap::Command::new("areas:import")
+                .about("Importe des zones administratives depuis une FeatureCollection GeoJSON")
+                .arg(clap::Arg::new("file").required(true))
+                .arg(
+                    clap::Arg::new("level")
+                        .long("level")
+                        .value_parser(["commune", "department"])
+                        .default_value("commune"),
+                )
+                .arg(clap::Arg::new("code-property").long("code-property").default_value("code"))
+                .arg(clap::Arg::new("name-property").long("name-property").default_value("nom")),
+        );
 
     let matches = cmd.get_matches();
 
@@ -19,8 +62,11 @@ async fn main() -> Result<(), Error> {
         Some(("db:migrate", _)) => {
             migrate_database().await
         },
-        Some(("db:revert", _)) => {
-            revert_database().await
+        Some(("db:revert", args)) => {
+            revert_database(*args.get_one::<usize>("steps").unwrap()).await
+        },
+        Some(("db:status", _)) => {
+            database_status().await
         },
         Some(("dev:reset", _)) => {
             reset().await
@@ -28,86 +74,205 @@ async fn main() -> Result<(), Error> {
         Some(("dev:gen:fixtures", _)) => {
             generate_fixtures().await
         },
+        Some(("config:check", args)) => {
+            check_config(
+                args.get_one::<String>("file"),
+                args.get_one::<String>("mode"),
+            )
+        },
+        Some(("areas:import", args)) => {
+            import_areas(
+                args.get_one::<String>("file").unwrap(),
+                args.get_one::<String>("level").unwrap().parse().unwrap(),
+                args.get_one::<String>("code-property").unwrap(),
+                args.get_one::<String>("name-property").unwrap(),
+            ).await
+        },
         _ => unreachable!("invalid command")
     };
 
-    if result.is_err() {
-        error!(target: "signuis::cli", "{:?}", result.unwrap_err());
+    if let Err(err) = result {
+        error!(target: "signuis::cli", "{}", err.chain());
+        std::process::exit(1);
     }
 
     Result::Ok(())
 }
 
+/// Charge les paramètres de Signuis depuis la configuration.
+fn load_settings() -> Result<SgSettings, Error> {
+    Config::load(&Mode::from_env())?.into_settings()
+}
+
 /// Migrate the database
 async fn migrate_database() -> Result<(), Error> {
-    Config::init()?;
-    let database_url = Config::try_get_database_url()?;
+    let sg = Signuis::new(load_settings()?).await?;
     info!(target: "signuis::cli", "Migrating...");
-    let db = DatabasePool::new(DatabasePoolArgs::new(database_url.as_str())).await?;
-    let hub = ServicePool::new(db);
-    hub.migrate_database().await?;
+    sg.migrate().await?;
     info!(target: "signuis::cli", "done !");
     Result::Ok(())
 }
 
-/// Revert the database
-async fn revert_database() -> Result<(), Error> {
-    Config::init()?;
-    let database_url = Config::try_get_database_url()?;
-    info!(target: "signuis::cli", "Reverting...");
-    let db = DatabasePool::new(DatabasePoolArgs::new(database_url.as_str())).await?;
-    let hub = ServicePool::new(db);
-    hub.revert_database().await?;
+/// Revert the last `steps` applied migrations
+async fn revert_database(steps: usize) -> Result<(), Error> {
+    let sg = Signuis::new(load_settings()?).await?;
+
+    let applied: Vec<i64> = sg.migration_status().await?
+        .into_iter()
+        .filter(|migration| migration.applied)
+        .map(|migration| migration.version)
+        .rev()
+        .collect();
+
+    // dernière version conservée, 0 si toutes les migrations sont annulées
+    let target = applied.get(steps).copied().unwrap_or(0);
+
+    info!(target: "signuis::cli", "Reverting to {}...", target);
+    sg.revert(target).await?;
     info!(target: "signuis::cli", "done !");
     Result::Ok(())
 }
 
+/// Affiche les migrations, appliquées ou en attente.
+async fn database_status() -> Result<(), Error> {
+    let sg = Signuis::new(load_settings()?).await?;
+
+    for migration in sg.migration_status().await? {
+        let state = if migration.applied { "appliquée" } else { "en attente" };
+        println!("{} {:<40} {}", migration.version, migration.description, state);
+    }
+
+    Result::Ok(())
+}
+
 async fn reset() -> Result<(), Error> {
-    revert_database().await?;
+    revert_database(usize::MAX).await?;
     migrate_database().await?;
     generate_fixtures().await
 }
 
 async fn generate_fixtures() -> Result<(), Error> {
-    Config::init()?;
-    let pool = ServicePool::from_config().await?;
+    let sg = Signuis::new(load_settings()?).await?;
+    let repos = &sg.repos;
     info!(target: "signuis::cli", "Generating fixtures...");
-    pool.with::<(), Error, _>(|tx| {
-        Box::pin(async {
-            let users = fixtures::users::new_multi(tx)
-            .map(Result::unwrap)
-            .take(100)
-            .collect::<Vec<_>>()
-            .await;
-
-            let families = fixtures::nuisance_families::new_multi(tx)
-            .map(Result::unwrap)
-            .take(10)
-            .collect::<Vec<_>>()
-            .await;
-
-            let types = fixtures::nuisance_types::new_multi_with(tx, || {
-                let family = families.choose(&mut rand::thread_rng()).unwrap();
-                NuisanceTypeFixture::new()
-                .with_family(ForeignKeyFixture::ID(family.id))
-                .to_owned()
-            }).map(Result::unwrap).take(100).collect::<Vec<_>>().await;
-            
-            fixtures::nuisance_reports::new_multi_with(tx, || {
-                let typ = types.choose(&mut rand::thread_rng()).unwrap();
-                let user = users.choose(&mut rand::thread_rng()).unwrap();
-
-                NuisanceReportFixture::new()
-                .with_type(ForeignKeyFixture::ID(typ.id))
-                .with_user(ForeignKeyFixture::ID(user.id))
-                .to_owned()
-            }).map(Result::unwrap).take(1_000_000).collect::<Vec<_>>().await;
 
+    let users: Arc<[UserId]> = fixtures::generate::<UserFixture>(repos, 100)
+        .try_concat()
+        .await?
+        .into();
+
+    let families: Arc<[NuisanceFamilyId]> = fixtures::generate::<NuisanceFamilyFixture>(repos, 10)
+        .try_concat()
+        .await?
+        .into();
+
+    let types: Arc<[NuisanceTypeId]> = fixtures::generate_with(repos, 100, || {
+        NuisanceTypeFixture::new().with_family(ForeignKeyFixture::OneOf(families.clone()))
+    })
+    .try_concat()
+    .await?
+    .into();
+
+    let points = ClusteredPoints::default();
+    let mut rng = rand::thread_rng();
+    let mut inserted = 0;
+
+    fixtures::generate_with(repos, 1_000_000, || {
+        let report = NuisanceReportFixture::new()
+            .with_type(ForeignKeyFixture::OneOf(types.clone()))
+            .at(points.sample(&mut rng));
+
+        // un signalement sur cinq est anonyme
+        if rng.gen_bool(0.2) {
+            report.anonymous()
+        } else {
+            report.with_user(ForeignKeyFixture::OneOf(users.clone()))
+        }
+    })
+    .try_for_each(|batch| {
+        inserted += batch.len();
+        info!(target: "signuis::cli", "{} reports inserted", inserted);
+        futures::future::ready(Ok(()))
+    })
+    .await?;
+
+    info!(target: "signuis::cli", "done !");
+
+    Ok(())
+}
+
+/// Vérifie la configuration et affiche les problèmes relevés.
+///
+/// Le fichier donné remplace `signuis.toml` ; les variables d'environnement
+/// sont prises en compte dans tous les cas.
+fn check_config(file: Option<&String>, mode: Option<&String>) -> Result<(), Error> {
+    let mode = mode.map(|mode| Mode::from(mode.to_owned())).unwrap_or_else(Mode::from_env);
+
+    let config = match file {
+        Some(file) => {
+            load_env_files(&mode);
+            Config::load_from(file, true).map(|config| Config { mode, ..config })
+        },
+        None => Config::load(&mode),
+    };
+
+    match config.and_then(|config| config.validate().map(|_| config)) {
+        Ok(config) => {
+            println!("configuration valide (mode {})", mode);
+            println!("  base de données : {} connexion(s)", config.database.max_connections);
+            println!("  session : {} h", config.session.lifetime_hours);
             Ok(())
-        })
-    }).await?;
-    
+        },
+        Err(Error { kind: ErrorKind::Invalid(issues), .. }) => {
+            for issue in issues.iter() {
+                println!("{} : {} ({})", issue.path.join("."), issue.message, issue.code);
+            }
+            std::process::exit(1)
+        },
+        Err(err) => Err(err),
+    }
+}
+
+/// Importe les zones administratives d'une FeatureCollection GeoJSON.
+///
+/// Le code et le nom de chaque zone sont lus dans les propriétés des features.
+async fn import_areas(file: &str, level: AreaLevel, code_property: &str, name_property: &str) -> Result<(), Error> {
+    let content = std::fs::read(file).map_err(Error::internal_error_with_source)?;
+    let collection: serde_json::Value = serde_json::from_slice(&content).map_err(Error::internal_error_with_source)?;
+
+    let features = collection
+        .get("features")
+        .and_then(serde_json::Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+
+    let sg = Signuis::new(load_settings()?).await?;
+    info!(target: "signuis::cli", "Importing {} areas...", features.len());
+
+    let property = |feature: &serde_json::Value, name: &str| {
+        match feature.get("properties").and_then(|properties| properties.get(name)) {
+            Some(serde_json::Value::String(value)) => value.to_owned(),
+            Some(serde_json::Value::Number(value)) => value.to_string(),
+            _ => String::default(),
+        }
+    };
+
+    for feature in features {
+        let form = ImportAdministrativeAreaForm {
+            code: property(&feature, code_property),
+            name: property(&feature, name_property),
+            level,
+            geometry: feature.get("geometry").cloned().unwrap_or_default(),
+        };
+
+        let code = form.code.clone();
+
+        if let Err(err) = sg.territory.execute(ImportAdministrativeArea { form }).await {
+            error!(target: "signuis::cli", "cannot import area {}: {}", code, err.chain());
+        }
+    }
+
     info!(target: "signuis::cli", "done !");
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -15,7 +15,7 @@ create table users (
     locale varchar(10) default 'fr'
 );
 
-create unique index users_unique_name on users (name);
+create unique index users_unique_name on users (username);
 create unique index users_unique_email on users (email);
 
 create table sessions (
@@ -28,5 +28,4 @@ create table sessions (
     constraint fk_users foreign key(user_id) references users(id) on delete cascade
 );
 
-create unique index sessions_unique_token on sessions (token) include (user_id, client_ip);
-create index sessions_ip on sessions (client_ip);
\ No newline at end of file
+create unique index sessions_unique_token on sessions (token) include (user_id);
\ No newline at end of file
@@ -0,0 +1,8 @@
+-- Add down migration script here
+ALTER INDEX nuisance_reports_types RENAME TO reports_types;
+ALTER INDEX nuisance_reports_creation_dates RENAME TO reports_creation_dates;
+ALTER INDEX nuisance_reports_locations RENAME TO reports_locations;
+ALTER TABLE nuisance_reports RENAME TO reports;
+
+ALTER INDEX user_sessions_unique_token RENAME TO sessions_unique_token;
+ALTER TABLE user_sessions RENAME TO sessions;
@@ -0,0 +1,9 @@
+-- Add up migration script here
+-- Noms des tables attendus par les répertoires --
+alter table sessions rename to user_sessions;
+alter index sessions_unique_token rename to user_sessions_unique_token;
+
+alter table reports rename to nuisance_reports;
+alter index reports_locations rename to nuisance_reports_locations;
+alter index reports_creation_dates rename to nuisance_reports_creation_dates;
+alter index reports_types rename to nuisance_reports_types;
@@ -0,0 +1,6 @@
+-- Add down migration script here
+drop index nuisance_reports_users;
+
+alter table nuisance_reports
+    drop column updated_at,
+    drop column deleted_at;
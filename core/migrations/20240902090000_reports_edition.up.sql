@@ -0,0 +1,6 @@
+-- Add up migration script here
+alter table nuisance_reports
+    add column updated_at timestamp with time zone,
+    add column deleted_at timestamp with time zone;
+
+create index nuisance_reports_users on nuisance_reports(user_id) where deleted_at is null;
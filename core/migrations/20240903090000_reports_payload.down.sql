@@ -0,0 +1,8 @@
+-- Add down migration script here
+DROP TABLE nuisance_report_photos;
+
+alter table nuisance_reports
+    drop column description,
+    drop column observed_from,
+    drop column observed_until,
+    drop column perceived_duration;
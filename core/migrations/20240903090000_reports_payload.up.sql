@@ -0,0 +1,19 @@
+-- Add up migration script here
+alter table nuisance_reports
+    add column description        text,
+    add column observed_from      timestamp with time zone,
+    add column observed_until     timestamp with time zone,
+    add column perceived_duration integer;
+
+create table nuisance_report_photos (
+    id           uuid primary key not null default uuid_generate_v4(),
+    report_id    uuid not null,
+    storage_key  varchar(255) not null,
+    content_type varchar(50) not null,
+    size         integer not null,
+    created_at   timestamp with time zone default now(),
+    -- constraints --
+    constraint fk_report foreign key(report_id) references nuisance_reports(id) on delete cascade
+);
+
+create index nuisance_report_photos_reports on nuisance_report_photos(report_id);
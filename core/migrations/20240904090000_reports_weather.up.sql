@@ -0,0 +1,12 @@
+-- Add up migration script here
+create table nuisance_report_weather (
+    report_id       uuid primary key not null,
+    station         varchar(255),
+    observed_at     timestamp with time zone not null,
+    wind_direction  double precision not null,
+    wind_speed      double precision not null,
+    temperature     double precision not null,
+    humidity        double precision not null,
+    -- constraints --
+    constraint fk_report foreign key(report_id) references nuisance_reports(id) on delete cascade
+);
@@ -0,0 +1,3 @@
+-- Add down migration script here
+DROP TABLE emitter_nuisance_families;
+DROP TABLE emitters;
@@ -0,0 +1,20 @@
+-- Add up migration script here
+create table emitters (
+    id          uuid primary key not null default uuid_generate_v4(),
+    name        varchar(255) not null,
+    kind        varchar(50) not null,
+    description text,
+    location    geometry not null,
+    created_at  timestamp with time zone default now()
+);
+
+create index emitters_locations on emitters using GIST(location);
+
+create table emitter_nuisance_families (
+    emitter_id  uuid not null,
+    family_id   uuid not null,
+    -- constraints --
+    primary key (emitter_id, family_id),
+    constraint fk_emitter foreign key(emitter_id) references emitters(id) on delete cascade,
+    constraint fk_family  foreign key(family_id) references nuisance_families(id) on delete cascade
+);
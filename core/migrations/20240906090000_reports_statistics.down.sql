@@ -0,0 +1,2 @@
+-- Add down migration script here
+DROP MATERIALIZED VIEW nuisance_report_hourly_statistics;
@@ -0,0 +1,16 @@
+-- Add up migration script here
+create materialized view nuisance_report_hourly_statistics as
+    select
+        date_trunc('hour', coalesce(report.observed_from, report.created_at)) as bucket,
+        report.type_id,
+        kind.family_id,
+        count(*) as count,
+        sum(report.intensity::integer) as total_intensity,
+        max(report.intensity::integer) as max_intensity
+    from nuisance_reports as report
+    inner join nuisance_types as kind on kind.id = report.type_id
+    where report.deleted_at is null
+    group by 1, 2, 3;
+
+create unique index nuisance_report_hourly_statistics_keys
+    on nuisance_report_hourly_statistics (bucket, type_id);
@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP MATERIALIZED VIEW nuisance_report_area_hourly_statistics;
+DROP TABLE nuisance_report_areas;
+DROP TABLE administrative_areas;
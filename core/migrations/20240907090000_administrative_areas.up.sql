@@ -0,0 +1,42 @@
+-- Add up migration script here
+create table administrative_areas (
+    id          uuid primary key not null default uuid_generate_v4(),
+    code        varchar(20) not null,
+    name        varchar(255) not null,
+    level       varchar(50) not null,
+    geometry    geometry(MultiPolygon, 4326) not null,
+    created_at  timestamp with time zone default now(),
+    -- constraints --
+    constraint administrative_areas_codes unique (level, code)
+);
+
+create index administrative_areas_geometries on administrative_areas using GIST(geometry);
+
+create table nuisance_report_areas (
+    report_id   uuid not null,
+    area_id     uuid not null,
+    -- constraints --
+    primary key (report_id, area_id),
+    constraint fk_report foreign key(report_id) references nuisance_reports(id) on delete cascade,
+    constraint fk_area   foreign key(area_id) references administrative_areas(id) on delete cascade
+);
+
+create index nuisance_report_areas_areas on nuisance_report_areas (area_id);
+
+create materialized view nuisance_report_area_hourly_statistics as
+    select
+        date_trunc('hour', coalesce(report.observed_from, report.created_at)) as bucket,
+        report.type_id,
+        kind.family_id,
+        link.area_id,
+        count(*) as count,
+        sum(report.intensity::integer) as total_intensity,
+        max(report.intensity::integer) as max_intensity
+    from nuisance_reports as report
+    inner join nuisance_types as kind on kind.id = report.type_id
+    inner join nuisance_report_areas as link on link.report_id = report.id
+    where report.deleted_at is null
+    group by 1, 2, 3, 4;
+
+create unique index nuisance_report_area_hourly_statistics_keys
+    on nuisance_report_area_hourly_statistics (bucket, type_id, area_id);
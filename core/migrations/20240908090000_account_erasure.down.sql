@@ -0,0 +1,6 @@
+-- Add down migration script here
+DROP TABLE audit_entries;
+DROP INDEX nuisance_reports_anonymous;
+ALTER TABLE nuisance_reports
+    DROP CONSTRAINT fk_user,
+    ADD CONSTRAINT fk_user FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE;
@@ -0,0 +1,18 @@
+-- Add up migration script here
+alter table nuisance_reports
+    drop constraint fk_user,
+    add constraint fk_user foreign key(user_id) references users(id) on delete set null;
+
+create index nuisance_reports_anonymous on nuisance_reports(created_at) where user_id is null;
+
+create table audit_entries (
+    id          uuid primary key not null default uuid_generate_v4(),
+    action      varchar(50) not null,
+    -- pas de clé étrangère : l'entrée survit à la suppression des comptes --
+    actor_id    uuid,
+    subject_id  uuid,
+    details     text,
+    at          timestamp with time zone default now()
+);
+
+create index audit_entries_subjects on audit_entries(subject_id);
@@ -0,0 +1,5 @@
+-- Add down migration script here
+DROP INDEX users_password_reset_token;
+ALTER TABLE users
+    DROP COLUMN password_reset_token,
+    DROP COLUMN password_reset_expires_at;
@@ -0,0 +1,6 @@
+-- Add up migration script here
+alter table users
+    add column password_reset_token varchar(255),
+    add column password_reset_expires_at timestamp with time zone;
+
+create unique index users_password_reset_token on users(password_reset_token) where password_reset_token is not null;
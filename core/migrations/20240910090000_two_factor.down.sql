@@ -0,0 +1,7 @@
+-- Add down migration script here
+DROP TABLE pending_authentications;
+DROP TABLE recovery_codes;
+ALTER TABLE user_sessions DROP COLUMN second_factor_verified;
+ALTER TABLE users
+    DROP COLUMN totp_secret,
+    DROP COLUMN totp_enabled_at;
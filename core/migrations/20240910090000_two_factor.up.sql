@@ -0,0 +1,29 @@
+-- Add up migration script here
+alter table users
+    add column totp_secret varchar(64),
+    add column totp_enabled_at timestamp with time zone;
+
+alter table user_sessions
+    add column second_factor_verified boolean not null default false;
+
+create table recovery_codes (
+    id          uuid primary key not null default uuid_generate_v4(),
+    user_id     uuid not null,
+    code_hash   varchar(64) not null,
+    used_at     timestamp with time zone,
+    constraint fk_user foreign key(user_id) references users(id) on delete cascade
+);
+
+create unique index recovery_codes_unique_code on recovery_codes(user_id, code_hash);
+
+-- Authentifications en attente du second facteur --
+create table pending_authentications (
+    id          uuid primary key not null default uuid_generate_v4(),
+    token_hash  varchar(64) not null,
+    user_id     uuid not null,
+    attempts    smallint not null default 0,
+    expires_at  timestamp with time zone not null,
+    constraint fk_user foreign key(user_id) references users(id) on delete cascade
+);
+
+create unique index pending_authentications_unique_token on pending_authentications(token_hash);
@@ -0,0 +1,28 @@
+-- Add up migration script here
+create table passkeys (
+    id              uuid primary key not null default uuid_generate_v4(),
+    user_id         uuid not null,
+    credential_id   bytea not null,
+    -- clé publique au format COSE --
+    public_key      bytea not null,
+    sign_count      bigint not null default 0,
+    name            varchar(100) not null,
+    created_at      timestamp with time zone not null default now(),
+    last_used_at    timestamp with time zone,
+    constraint fk_user foreign key(user_id) references users(id) on delete cascade
+);
+
+create unique index passkeys_unique_credential on passkeys(credential_id);
+create index passkeys_users on passkeys(user_id);
+
+-- Défis des cérémonies WebAuthn en cours --
+create table webauthn_challenges (
+    id          uuid primary key not null default uuid_generate_v4(),
+    challenge   varchar(64) not null,
+    purpose     varchar(20) not null,
+    user_id     uuid,
+    expires_at  timestamp with time zone not null,
+    constraint fk_user foreign key(user_id) references users(id) on delete cascade
+);
+
+create unique index webauthn_challenges_unique_challenge on webauthn_challenges(challenge);
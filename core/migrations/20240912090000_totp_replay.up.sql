@@ -0,0 +1,4 @@
+-- Add up migration script here
+-- Dernier pas de temps TOTP accepté, pour refuser le rejeu d'un code --
+alter table users
+    add column totp_last_step bigint;
@@ -0,0 +1,4 @@
+-- Add up migration script here
+-- Administrateur agissant au nom de l'utilisateur, pour les sessions d'assistance --
+alter table user_sessions
+    add column impersonator_id uuid references users(id) on delete cascade;
@@ -0,0 +1,299 @@
+//! Cache des résultats des opérations du répertoire.
+//!
+//! Les résultats sont sérialisés, afin de pouvoir être conservés hors du
+//! processus par un autre [CacheBackend]. Chaque entrée appartient à un espace
+//! de noms, invalidé d'un bloc lorsque les données sous-jacentes changent
+//! (ex: création d'une famille de nuisance).
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use futures::future::LocalBoxFuture;
+use log::warn;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::error::Error;
+use crate::repositories::{Repository, RepositoryOp};
+
+/// Espace de noms de la taxonomie (familles et types de nuisance).
+pub const TAXONOMY: &str = "taxonomy";
+
+/// Espace de noms des agrégats des signalements (cartes, statistiques).
+pub const STATISTICS: &str = "statistics";
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+/// Paramètres du cache.
+pub struct CacheSettings {
+    pub enabled: bool,
+    /// Nombre maximal d'entrées conservées.
+    pub capacity: usize,
+    /// Durée de vie des entrées de la taxonomie, en secondes.
+    pub taxonomy_ttl_seconds: u64,
+    /// Durée de vie des agrégats des signalements, en secondes.
+    pub statistics_ttl_seconds: u64,
+}
+
+impl Default for CacheSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            capacity: 1000,
+            taxonomy_ttl_seconds: 60 * 60,
+            statistics_ttl_seconds: 30,
+        }
+    }
+}
+
+impl CacheSettings {
+    pub fn taxonomy_ttl(&self) -> Duration {
+        Duration::from_secs(self.taxonomy_ttl_seconds)
+    }
+
+    pub fn statistics_ttl(&self) -> Duration {
+        Duration::from_secs(self.statistics_ttl_seconds)
+    }
+}
+
+/// Stockage des entrées du cache.
+///
+/// Une défaillance du stockage ne doit pas faire échouer l'opération :
+/// l'entrée est alors réputée absente.
+pub trait CacheBackend: Send + Sync {
+    fn get<'a>(&'a self, key: &'a str) -> LocalBoxFuture<'a, Option<Vec<u8>>>;
+
+    fn put<'a>(&'a self, key: String, value: Vec<u8>, ttl: Duration) -> LocalBoxFuture<'a, ()>;
+
+    /// Retire toutes les entrées de l'espace de noms.
+    fn invalidate<'a>(&'a self, namespace: &'a str) -> LocalBoxFuture<'a, ()>;
+}
+
+/// Opération du répertoire dont le résultat peut être conservé en cache.
+pub trait CachedOp: RepositoryOp + 'static
+where
+    Self::Return: Serialize + DeserializeOwned,
+{
+    /// Espace de noms du résultat.
+    const NAMESPACE: &'static str;
+
+    /// Paramètres distinguant le résultat de l'opération.
+    fn cache_key(&self) -> Result<String, Error>;
+}
+
+struct LruEntry {
+    value: Vec<u8>,
+    expires_at: Instant,
+    used_at: u64,
+}
+
+#[derive(Default)]
+struct LruState {
+    entries: HashMap<String, LruEntry>,
+    /// Horloge logique des accès.
+    clock: u64,
+}
+
+impl LruState {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    /// Libère une place : d'abord les entrées expirées, sinon la moins récemment utilisée.
+    fn evict(&mut self, now: Instant) {
+        self.entries.retain(|_, entry| entry.expires_at > now);
+
+        if let Some(key) = self
+            .entries
+            .iter()
+            .min_by_key(|(_, entry)| entry.used_at)
+            .map(|(key, _)| key.clone())
+        {
+            self.entries.remove(&key);
+        }
+    }
+}
+
+/// Cache propre au processus, évinçant les entrées les moins récemment utilisées.
+pub struct LruCache {
+    capacity: usize,
+    state: Mutex<LruState>,
+}
+
+impl LruCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::default(),
+        }
+    }
+
+    /// Nombre d'entrées conservées, expirées comprises.
+    pub fn len(&self) -> usize {
+        self.state().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, LruState> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl CacheBackend for LruCache {
+    fn get<'a>(&'a self, key: &'a str) -> LocalBoxFuture<'a, Option<Vec<u8>>> {
+        let mut state = self.state();
+        let used_at = state.tick();
+
+        let value = match state.entries.get_mut(key) {
+            Some(entry) if entry.expires_at > Instant::now() => {
+                entry.used_at = used_at;
+                Some(entry.value.clone())
+            }
+            _ => None,
+        };
+
+        if value.is_none() {
+            state.entries.remove(key);
+        }
+
+        Box::pin(futures::future::ready(value))
+    }
+
+    fn put<'a>(&'a self, key: String, value: Vec<u8>, ttl: Duration) -> LocalBoxFuture<'a, ()> {
+        if self.capacity > 0 {
+            let now = Instant::now();
+            let mut state = self.state();
+
+            if state.entries.len() >= self.capacity && !state.entries.contains_key(&key) {
+                state.evict(now);
+            }
+
+            let used_at = state.tick();
+            state.entries.insert(
+                key,
+                LruEntry {
+                    value,
+                    expires_at: now + ttl,
+                    used_at,
+                },
+            );
+        }
+
+        Box::pin(futures::future::ready(()))
+    }
+
+    fn invalidate<'a>(&'a self, namespace: &'a str) -> LocalBoxFuture<'a, ()> {
+        let prefix = format!("{namespace}:");
+        self.state()
+            .entries
+            .retain(|key, _| !key.starts_with(&prefix));
+
+        Box::pin(futures::future::ready(()))
+    }
+}
+
+#[derive(Clone)]
+/// Cache des résultats des opérations du répertoire.
+pub struct QueryCache {
+    backend: Option<Arc<dyn CacheBackend>>,
+    /// Génération de chaque espace de noms, incrémentée à chaque invalidation.
+    ///
+    /// Elle fait partie de la clé des entrées : un résultat lu avant une
+    /// invalidation, puis rangé après elle, n'est jamais servi.
+    generations: Arc<Mutex<HashMap<String, u64>>>,
+}
+
+impl QueryCache {
+    pub fn new(settings: &CacheSettings) -> Self {
+        if settings.enabled {
+            Self::with_backend(LruCache::new(settings.capacity))
+        } else {
+            Self::disabled()
+        }
+    }
+
+    /// Cache conservant ses entrées dans le stockage donné.
+    pub fn with_backend<B: CacheBackend + 'static>(backend: B) -> Self {
+        Self {
+            backend: Some(Arc::new(backend)),
+            generations: Arc::default(),
+        }
+    }
+
+    /// Cache ne conservant rien : chaque opération est exécutée.
+    pub fn disabled() -> Self {
+        Self {
+            backend: None,
+            generations: Arc::default(),
+        }
+    }
+
+    fn generation(&self, namespace: &str) -> u64 {
+        let generations = self
+            .generations
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        generations.get(namespace).copied().unwrap_or_default()
+    }
+
+    /// Exécute l'opération, sauf si son résultat est déjà en cache.
+    pub async fn execute<O>(
+        &self,
+        repos: &Repository,
+        op: O,
+        ttl: Duration,
+    ) -> Result<O::Return, Error>
+    where
+        O: CachedOp,
+        O::Return: Serialize + DeserializeOwned,
+    {
+        let Some(backend) = &self.backend else {
+            return repos.execute(op).await;
+        };
+
+        let generation = self.generation(O::NAMESPACE);
+        let key = format!("{}:{}:{}", O::NAMESPACE, generation, op.cache_key()?);
+
+        if let Some(cached) = backend.get(&key).await {
+            match serde_json::from_slice(&cached) {
+                Ok(value) => return Ok(value),
+                Err(error) => {
+                    warn!(target: "signuis::cache", "entrée {key} illisible: {error}");
+                }
+            }
+        }
+
+        let value = repos.execute(op).await?;
+
+        // invalidé pendant la lecture : le résultat est peut-être déjà périmé
+        if self.generation(O::NAMESPACE) != generation {
+            return Ok(value);
+        }
+
+        match serde_json::to_vec(&value) {
+            Ok(serialized) => backend.put(key, serialized, ttl).await,
+            Err(error) => {
+                warn!(target: "signuis::cache", "entrée {key} non sérialisable: {error}");
+            }
+        }
+
+        Ok(value)
+    }
+
+    /// Retire toutes les entrées de l'espace de noms.
+    pub async fn invalidate(&self, namespace: &str) {
+        *self
+            .generations
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .entry(namespace.to_owned())
+            .or_default() += 1;
+
+        if let Some(backend) = &self.backend {
+            backend.invalidate(namespace).await
+        }
+    }
+}
@@ -1,136 +1,468 @@
-use crate::Error;
+//! Configuration du service.
+//!
+//! Les paramètres sont fusionnés, du moins au plus prioritaire : valeurs par
+//! défaut, fichier `signuis.toml` (ou celui désigné par `SIGNUIS_CONFIG`),
+//! fichier `signuis.<mode>.toml`, puis variables d'environnement préfixées par
+//! `SIGNUIS_`, les niveaux étant séparés par `__`
+//! (ex: `SIGNUIS_DATABASE__MAX_CONNECTIONS=10`).
+//!
+//! Les fichiers d'environnement (`test.env`, `dev.env`, `.env`) sont chargés au
+//! préalable, sans écraser les variables déjà définies.
+//!
+//! ```no_run
+//! use signuis_core::config::{Config, Mode};
+//!
+//! let settings = Config::load(&Mode::from_env())?.into_settings()?;
+//! # Ok::<(), signuis_core::error::Error>(())
+//! ```
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+
+use ::config::{Environment, File, FileFormat};
+use chrono::Duration;
 use log::{info, warn};
-use log4rs::append::console::ConsoleAppender;
+use serde::Deserialize;
 
-/// Système gérant la configuration du service.
-/// 
-/// ```
-/// let args = ConfigArgs::default();
-/// Config::init(&args);
-/// ```
-pub struct Config;
+use crate::cache::CacheSettings;
+use crate::error::Error;
+use crate::issues::{Issue, Issues};
+use crate::services::RateLimitSettings;
+use crate::storage::{S3Settings, StorageSettings};
+use crate::telemetry::LogSettings;
+use crate::validation::{Validation, Validator};
+use crate::SgSettings;
 
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+/// Environnement d'exécution du service.
 pub enum Mode {
     Test,
+    #[default]
     Development,
-    Production
+    Production,
 }
 
 impl From<String> for Mode {
-    fn from(val: std::string::String) -> Self {
+    fn from(val: String) -> Self {
         match val.as_str() {
             "test" => Mode::Test,
             "dev" => Mode::Development,
             "prod" => Mode::Production,
-            _ => Mode::Development
+            _ => Mode::Development,
         }
     }
 }
 
 impl std::fmt::Display for Mode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let str: String = self.into();
-        write!(f, "{}", str)
+        write!(f, "{}", self.as_str())
     }
 }
 
-impl Into<String> for &Mode {
-    fn into(self) -> String {
+impl Mode {
+    pub fn as_str(&self) -> &'static str {
         match self {
-            Mode::Test => "test".into(),
-            Mode::Production => "prod".into(),
-            Mode::Development => "dev".into()
+            Mode::Test => "test",
+            Mode::Development => "dev",
+            Mode::Production => "prod",
         }
     }
+
+    /// Retourne le mode sollicité par la variable d'environnement `MODE`,
+    /// ou le mode production par défaut.
+    pub fn from_env() -> Self {
+        std::env::var("MODE")
+            .map(Mode::from)
+            .unwrap_or(Mode::Production)
+    }
 }
 
-#[derive(Default)]
-pub struct ConfigArgs {
-    mode: Option<Mode>
+/// Charge les variables des fichiers d'environnement propres au mode, puis
+/// celles de `.env`.
+///
+/// Les variables déjà définies ne sont pas écrasées.
+pub fn load_env_files(mode: &Mode) {
+    let env_files: &[&str] = match mode {
+        Mode::Test => &["test.env", ".env"],
+        Mode::Development => &["dev.env", ".env"],
+        Mode::Production => &[".env"],
+    };
+
+    for env_file in env_files {
+        if Path::new(env_file).is_file() {
+            info!(target: "signuis::config", "Charge les variables depuis \"{}\".", env_file);
+            dotenv::from_filename(env_file).ok();
+        } else {
+            warn!(target: "signuis::config", "Aucun fichier \"{}\".", env_file);
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+/// Paramètres de la base de données.
+pub struct DatabaseConfig {
+    /// Adresse de la base ; à défaut, celle de `DATABASE_URL`.
+    pub url: String,
+    pub max_connections: u32,
+    /// Adresses des réplicas en lecture.
+    pub read_replicas: Vec<String>,
 }
 
-impl ConfigArgs {
-    pub fn set_mode(mut self, mode: Mode) -> Self {
-        self.mode = Option::Some(mode);
-        self
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            url: String::default(),
+            max_connections: 5,
+            read_replicas: Vec::default(),
+        }
     }
 }
 
-impl Config {
-    pub fn init() -> Result<(), Error> {
-        Self::init_with_args(Default::default())
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+/// Durées de vie liées aux sessions.
+pub struct SessionConfig {
+    /// Durée de validité d'une session utilisateur, en heures.
+    pub lifetime_hours: i64,
+    /// Délai pendant lequel un signalement peut être modifié, en minutes.
+    pub report_edition_grace_minutes: i64,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            lifetime_hours: 8,
+            report_edition_grace_minutes: 60,
+        }
     }
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+/// Pagination des listes.
+pub struct PaginationConfig {
+    /// Clé de signature des curseurs, commune à toutes les instances ;
+    /// à défaut, une clé aléatoire propre au processus.
+    pub cursor_secret: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+/// Protection de la vie privée des déclarants.
+pub struct PrivacyConfig {
+    /// Clé du déplacement des coordonnées, commune à toutes les instances ;
+    /// à défaut, une clé aléatoire propre au processus.
+    pub jitter_secret: Option<String>,
+}
 
-    /// Initialise la configuration du service en fonction d'arguments généralement passés depuis une commande.
-    pub fn init_with_args(args: ConfigArgs) -> Result<(), Error> {
-        let _ = Self::init_logging();
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+/// Exposition des métriques.
+pub struct MetricsConfig {
+    /// Adresse d'écoute (ex: `127.0.0.1:9100`) du point de collecte `/metrics`,
+    /// distincte de celle du site ; à défaut, les métriques ne sont pas exposées.
+    pub bind: Option<String>,
+}
 
-        if let Some(mode) = &args.mode {
-            let str_mode: String = mode.into();
-            std::env::set_var("MODE", str_mode);
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "backend", rename_all = "snake_case")]
+/// Espace de stockage des fichiers.
+pub enum StorageConfig {
+    Local {
+        root: PathBuf,
+    },
+    S3 {
+        endpoint: String,
+        #[serde(default)]
+        region: String,
+        bucket: String,
+        access_key: String,
+        secret_key: String,
+    },
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self::Local {
+            root: PathBuf::from("storage"),
         }
+    }
+}
 
-        // Get the current mode.
-        let mode = Self::get_mode();
-        
-        info!(target: "signuis::config", "Execute le service depuis {:?}.", std::env::current_dir().unwrap().as_os_str().to_os_string());
-        info!(target: "signuis::config", "Configure pour environnement de type {:?}...", mode);
+impl From<StorageConfig> for StorageSettings {
+    fn from(value: StorageConfig) -> Self {
+        match value {
+            StorageConfig::Local { root } => Self::Local { root },
+            StorageConfig::S3 {
+                endpoint,
+                region,
+                bucket,
+                access_key,
+                secret_key,
+            } => Self::S3(S3Settings {
+                endpoint,
+                region,
+                bucket,
+                access_key,
+                secret_key,
+            }),
+        }
+    }
+}
 
-        let env_file = match mode {
-            Mode::Test => "test.env",
-            Mode::Development => "dev.env",
-            Mode::Production => ".env"
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+/// Configuration du service, telle que lue depuis ses différentes sources.
+pub struct Config {
+    pub database: DatabaseConfig,
+    pub session: SessionConfig,
+    pub pagination: PaginationConfig,
+    pub privacy: PrivacyConfig,
+    pub rate_limits: RateLimitSettings,
+    pub cache: CacheSettings,
+    pub storage: StorageConfig,
+    pub log: LogSettings,
+    pub metrics: MetricsConfig,
+    /// Mode pour lequel la configuration est chargée, qui en durcit la validation.
+    #[serde(skip)]
+    pub mode: Mode,
+}
+
+impl Config {
+    /// Charge la configuration du mode donné.
+    pub fn load(mode: &Mode) -> Result<Self, Error> {
+        load_env_files(mode);
+
+        let main = match std::env::var("SIGNUIS_CONFIG") {
+            Ok(path) => File::from(PathBuf::from(path).as_path()).required(true),
+            Err(_) => File::from(Path::new("signuis.toml")).required(false),
         };
 
-        // Load file
-        if !Self::load_env_file(env_file) && env_file != ".env" {
-            warn!(target: "signuis::config", "Falling back to .env...");
-            Self::load_env_file(".env");
-        }
+        let overlay = format!("signuis.{}.toml", mode);
+
+        let mut config = Self::build(
+            [
+                main,
+                File::from(Path::new(overlay.as_str())).required(false),
+            ],
+            None,
+        )?;
 
-        Ok(())
+        config.mode = *mode;
+
+        Ok(config)
     }
 
-    /// Initialise le service de journalisation
-    fn init_logging() -> Result<(), Error> {
-        let stdout = ConsoleAppender::builder().build();
-    
-        let config = log4rs::config::Config::builder()
-            .appender(log4rs::config::Appender::builder().build("stdout", Box::new(stdout)))
-            .build(log4rs::config::Root::builder().appender("stdout").build(log::LevelFilter::Info))
-            .unwrap();
-    
-        log4rs::init_config(config)?;
-        Ok(())
+    /// Charge la configuration depuis un fichier TOML donné, complétée par les
+    /// variables d'environnement.
+    pub fn load_from<P: AsRef<Path>>(path: P, required: bool) -> Result<Self, Error> {
+        Self::build([File::from(path.as_ref()).required(required)], None)
     }
 
-    fn load_env_file(env_file: &str) -> bool {
-        match std::fs::metadata(env_file) {
-            Ok(_) => {
-                info!(target: "signuis::config", "Charge les variables environnement depuis le fichier \"{}\".", env_file);
-                dotenv::from_filename(env_file).ok();
-                return true;
-            },
+    /// Charge la configuration depuis un fichier TOML donné, complétée par les
+    /// variables données plutôt que par celles du processus.
+    pub fn load_from_with_env<P: AsRef<Path>>(
+        path: P,
+        required: bool,
+        env: HashMap<String, String>,
+    ) -> Result<Self, Error> {
+        Self::build([File::from(path.as_ref()).required(required)], Some(env))
+    }
 
-            Err(_) => {
-                warn!(target: "signuis::config", "Aucun fichier d'environnement n'existe à l'emplacement \"{}\".", env_file);
-                return false;
-            }
-        };     
+    fn build<I>(files: I, env: Option<HashMap<String, String>>) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = File<::config::FileSourceFile, FileFormat>>,
+    {
+        let builder = files
+            .into_iter()
+            .fold(::config::Config::builder(), |builder, file| {
+                builder.add_source(file.format(FileFormat::Toml))
+            })
+            .add_source(
+                Environment::with_prefix("SIGNUIS")
+                    .prefix_separator("_")
+                    .separator("__")
+                    .source(env.clone()),
+            );
+
+        let mut config: Self = builder
+            .build()
+            .and_then(|config| config.try_deserialize())
+            .map_err(|err| {
+                Issues::new()
+                    .add(Issue::new("invalid_config", err, ["config"]))
+                    .to_owned()
+                    .into_error()
+            })?;
+
+        if config.database.url.is_empty() {
+            config.database.url = match &env {
+                Some(env) => env.get("DATABASE_URL").cloned().unwrap_or_default(),
+                None => std::env::var("DATABASE_URL").unwrap_or_default(),
+            };
+        }
+
+        Ok(config)
     }
 
-    /// Retourne le mode sollicité (production, test ou debug)
-    /// 
-    /// La routine va vérifier si une variable d'environnement "MODE" a été définie.
-    /// La routine retourne le mode production par défaut.
-    pub fn get_mode() -> Mode {
-        if let Some(val) = std::env::var("MODE").ok() {
-            return val.into()
-        } 
-        return Mode::Production;
+    /// Vérifie la cohérence des paramètres.
+    pub fn validate(&self) -> Result<(), Error> {
+        let mut validator = Validator::default();
+        self.assert(&mut validator);
+        validator.check()
     }
 
-    pub fn try_get_database_url() -> Result<String, Error> {
-        std::env::var("DATABASE_URL").map_err(|_| Error::missing_env("DATABASE_URL"))
+    /// Convertit la configuration, après validation, en paramètres de Signuis.
+    pub fn into_settings(self) -> Result<SgSettings, Error> {
+        self.validate()?;
+
+        let mut settings = SgSettings::default();
+
+        settings
+            .set_database_url(self.database.url)
+            .set_max_connections(self.database.max_connections)
+            .set_read_replicas(self.database.read_replicas)
+            .set_user_session_lifetime(Duration::hours(self.session.lifetime_hours))
+            .set_report_edition_grace_period(Duration::minutes(
+                self.session.report_edition_grace_minutes,
+            ))
+            .set_cursor_secret(self.pagination.cursor_secret)
+            .set_jitter_secret(self.privacy.jitter_secret)
+            .set_rate_limits(self.rate_limits)
+            .set_cache(self.cache)
+            .set_storage(self.storage.into());
+
+        Ok(settings)
     }
-}
\ No newline at end of file
+}
+
+impl Validation for Config {
+    fn assert(&self, validator: &mut Validator) {
+        let url = &self.database.url;
+
+        validator.assert_not_empty(
+            url,
+            Some("the database url is required"),
+            ["database", "url"],
+        );
+
+        validator.assert_true(
+            url.is_empty() || url.starts_with("postgres://") || url.starts_with("postgresql://"),
+            Some("the database url must use the postgres scheme"),
+            ["database", "url"],
+        );
+
+        validator.assert_in_range_inclusive(
+            &self.database.max_connections,
+            1..=1000,
+            None,
+            ["database", "max_connections"],
+        );
+
+        for (index, replica) in self.database.read_replicas.iter().enumerate() {
+            validator.assert_true(
+                replica.starts_with("postgres://") || replica.starts_with("postgresql://"),
+                Some("the read replica url must use the postgres scheme"),
+                [
+                    "database".to_string(),
+                    "read_replicas".to_string(),
+                    index.to_string(),
+                ],
+            );
+        }
+
+        validator.assert_in_range_inclusive(
+            &self.session.lifetime_hours,
+            1..=720,
+            None,
+            ["session", "lifetime_hours"],
+        );
+
+        validator.assert_in_range_inclusive(
+            &self.session.report_edition_grace_minutes,
+            0..=7 * 24 * 60,
+            None,
+            ["session", "report_edition_grace_minutes"],
+        );
+
+        // une clé aléatoire par processus invaliderait les curseurs d'une instance à l'autre
+        validator.assert_true(
+            self.mode != Mode::Production || self.pagination.cursor_secret.is_some(),
+            Some("the cursor secret is required in production"),
+            ["pagination", "cursor_secret"],
+        );
+
+        if let Some(secret) = &self.pagination.cursor_secret {
+            validator.assert_min_length(
+                secret,
+                32,
+                Some("the cursor secret must be at least 32 characters long"),
+                ["pagination", "cursor_secret"],
+            );
+        }
+
+        if let Some(secret) = &self.privacy.jitter_secret {
+            validator.assert_min_length(
+                secret,
+                32,
+                Some("the jitter secret must be at least 32 characters long"),
+                ["privacy", "jitter_secret"],
+            );
+        }
+
+        if self.rate_limits.enabled {
+            validator.assert_true(
+                self.rate_limits.login_attempts_per_minute > 0,
+                Some("the limit must be positive"),
+                ["rate_limits", "login_attempts_per_minute"],
+            );
+
+            validator.assert_true(
+                self.rate_limits.reports_per_hour > 0,
+                Some("the limit must be positive"),
+                ["rate_limits", "reports_per_hour"],
+            );
+        }
+
+        if self.cache.enabled {
+            validator.assert_true(
+                self.cache.capacity > 0,
+                Some("the capacity must be positive"),
+                ["cache", "capacity"],
+            );
+        }
+
+        validator.assert_true(
+            self.log.is_valid_filter(),
+            Some("the log filter is invalid"),
+            ["log", "filter"],
+        );
+
+        if let Some(bind) = &self.metrics.bind {
+            validator.assert_true(
+                bind.parse::<SocketAddr>().is_ok(),
+                Some("the metrics address must be an ip address and a port"),
+                ["metrics", "bind"],
+            );
+        }
+
+        if let StorageConfig::S3 {
+            endpoint,
+            bucket,
+            access_key,
+            secret_key,
+            ..
+        } = &self.storage
+        {
+            validator.assert_true(
+                endpoint.starts_with("http://") || endpoint.starts_with("https://"),
+                Some("the endpoint must be an http(s) url"),
+                ["storage", "endpoint"],
+            );
+            validator.assert_not_empty(bucket, None, ["storage", "bucket"]);
+            validator.assert_not_empty(access_key, None, ["storage", "access_key"]);
+            validator.assert_not_empty(secret_key, None, ["storage", "secret_key"]);
+        }
+    }
+}
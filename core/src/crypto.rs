@@ -1,5 +1,14 @@
+use argon2::{password_hash::PasswordVerifier, Argon2, Params};
 use base64::Engine;
 use rand::RngCore;
+use sha2::{Digest, Sha256};
+use totp_rs::{Algorithm, Secret, TOTP};
+
+use crate::error::Error;
+use crate::password_policy::PasswordHashing;
+
+/// Préfixes des empreintes bcrypt, importées d'un système antérieur.
+const BCRYPT_PREFIXES: [&str; 4] = ["$2a$", "$2b$", "$2x$", "$2y$"];
 
 /// Génère un jeton cryptographique suffisamment robuste pour
 /// être utilisé comme secret de session par exemple.
@@ -9,8 +18,156 @@ use rand::RngCore;
 /// let token = generate_token(16);
 /// ```
 pub fn generate_token(byte_size: usize) -> String {
-    let mut buf = Vec::<u8>::with_capacity(byte_size);
+    base64::engine::general_purpose::STANDARD.encode(random_bytes(byte_size))
+}
+
+/// Génère des octets aléatoires, ex: pour un défi WebAuthn.
+pub fn random_bytes(byte_size: usize) -> Vec<u8> {
+    let mut buf = vec![0u8; byte_size];
     rand::thread_rng().fill_bytes(&mut buf);
-    base64::engine::general_purpose::STANDARD.encode(&buf)
+    buf
+}
+
+/// Empreinte d'un jeton à usage unique, seule conservée en base de données.
+pub fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Instance Argon2id configurée selon les paramètres donnés.
+fn argon2(settings: &PasswordHashing) -> Result<Argon2<'static>, Error> {
+    let params = Params::new(
+        settings.memory_cost,
+        settings.time_cost,
+        settings.parallelism,
+        None,
+    )
+    .map_err(|_| Error::internal_error())?;
+
+    Ok(Argon2::new(
+        argon2::Algorithm::Argon2id,
+        argon2::Version::V0x13,
+        params,
+    ))
+}
+
+fn is_bcrypt_hash(hash: &str) -> bool {
+    BCRYPT_PREFIXES
+        .iter()
+        .any(|prefix| hash.starts_with(prefix))
 }
 
+/// Hache un mot de passe pour son stockage en base de données.
+pub fn hash_password(password: &str, settings: &PasswordHashing) -> Result<String, Error> {
+    let salt = password_hash::SaltString::generate(rand::thread_rng());
+
+    Ok(
+        password_hash::PasswordHash::generate(argon2(settings)?, password, &salt)
+            .map_err(|_| Error::internal_error())?
+            .to_string(),
+    )
+}
+
+/// Vérifie un mot de passe par rapport à son empreinte, Argon2 ou bcrypt.
+pub fn verify_password(hash: &str, password: &str) -> Result<bool, Error> {
+    if is_bcrypt_hash(hash) {
+        return bcrypt::verify(password, hash).map_err(|_| Error::internal_error());
+    }
+
+    let hash = password_hash::PasswordHash::new(hash).map_err(|_| Error::internal_error())?;
+
+    Ok(Argon2::default()
+        .verify_password(password.as_bytes(), &hash)
+        .is_ok())
+}
+
+/// Vérifie si l'empreinte doit être recalculée : empreinte bcrypt, autre variante
+/// qu'Argon2id, ou paramètres plus faibles que ceux donnés.
+pub fn password_needs_rehash(hash: &str, settings: &PasswordHashing) -> bool {
+    if is_bcrypt_hash(hash) {
+        return true;
+    }
+
+    let Ok(hash) = password_hash::PasswordHash::new(hash) else {
+        return true;
+    };
+
+    let Ok(params) = Params::try_from(&hash) else {
+        return true;
+    };
+
+    hash.algorithm != argon2::Algorithm::Argon2id.ident()
+        || params.m_cost() < settings.memory_cost
+        || params.t_cost() < settings.time_cost
+        || params.p_cost() < settings.parallelism
+}
+
+/// Vérifie si l'empreinte importée est dans un format pris en charge.
+pub fn is_supported_password_hash(hash: &str) -> bool {
+    if is_bcrypt_hash(hash) {
+        return hash.parse::<bcrypt::HashParts>().is_ok();
+    }
+
+    password_hash::PasswordHash::new(hash).is_ok_and(|hash| {
+        argon2::Algorithm::try_from(hash.algorithm).is_ok() && Params::try_from(&hash).is_ok()
+    })
+}
+
+/// Émetteur affiché par les applications d'authentification.
+const TOTP_ISSUER: &str = "Signuis";
+
+/// Génère un secret TOTP de 160 bits, encodé en base32.
+pub fn generate_totp_secret() -> String {
+    Secret::generate_secret().to_encoded().to_string()
+}
+
+/// Construit le générateur TOTP (RFC 6238, SHA-1, 6 chiffres, pas de 30 secondes)
+/// associé à un secret encodé en base32.
+///
+/// Un décalage d'un pas est toléré de part et d'autre.
+pub fn totp(secret: &str, account_name: &str) -> Result<TOTP, Error> {
+    let secret = Secret::Encoded(secret.to_owned())
+        .to_bytes()
+        .map_err(|_| Error::internal_error())?;
+
+    TOTP::new(
+        Algorithm::SHA1,
+        6,
+        1,
+        30,
+        secret,
+        Some(TOTP_ISSUER.to_owned()),
+        account_name.to_owned(),
+    )
+    .map_err(|_| Error::internal_error())
+}
+
+/// Retourne le pas de temps auquel correspond un code TOTP, parmi ceux tolérés
+/// autour de l'instant donné (en secondes depuis l'époque Unix).
+pub fn totp_step(totp: &TOTP, code: &str, time: u64) -> Option<u64> {
+    let current = time / totp.step;
+    let skew = u64::from(totp.skew);
+
+    (current.saturating_sub(skew)..=current + skew)
+        .find(|step| constant_time_eq(totp.generate(step * totp.step).as_bytes(), code.as_bytes()))
+}
+
+/// Compare deux valeurs en un temps qui ne dépend que de leur longueur.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Génère un code de récupération lisible, ex: `k3f9-x2m7-q8pd`.
+pub fn generate_recovery_code() -> String {
+    const ALPHABET: &[u8] = b"abcdefghjkmnpqrstuvwxyz23456789";
+
+    let mut rng = rand::thread_rng();
+
+    (0..3)
+        .map(|_| {
+            (0..4)
+                .map(|_| ALPHABET[(rng.next_u32() as usize) % ALPHABET.len()] as char)
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
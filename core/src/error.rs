@@ -6,6 +6,7 @@ pub enum ErrorKind {
     DatabaseError,
     Invalid(Issues),
     Unauthorized,
+    NotFound,
 }
 
 #[derive(Debug)]
@@ -34,9 +35,40 @@ impl From<::actix::MailboxError> for Error {
     }
 }
 
+impl std::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErrorKind::InternalError => write!(f, "internal error"),
+            ErrorKind::DatabaseError => write!(f, "database error"),
+            ErrorKind::Invalid(issues) => write!(f, "invalid: {issues}"),
+            ErrorKind::Unauthorized => write!(f, "unauthorized"),
+            ErrorKind::NotFound => write!(f, "not found"),
+        }
+    }
+}
+
+/// N'affiche que la nature de l'erreur : ses causes, accessibles par
+/// [std::error::Error::source], ne sont pas destinées aux utilisateurs.
 impl std::fmt::Display for Error {
-    fn fmt(&self, _f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        todo!()
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.kind)
+    }
+}
+
+/// Erreur affichée avec la chaîne de ses causes, pour les journaux.
+pub struct ErrorChain<'a>(&'a Error);
+
+impl std::fmt::Display for ErrorChain<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)?;
+
+        let mut source = std::error::Error::source(self.0);
+        while let Some(cause) = source {
+            write!(f, ": {cause}")?;
+            source = cause.source();
+        }
+
+        Ok(())
     }
 }
 
@@ -47,6 +79,11 @@ impl std::error::Error for Error {
 }
 
 impl Error {
+    /// Erreur suivie de la chaîne de ses causes.
+    pub fn chain(&self) -> ErrorChain<'_> {
+        ErrorChain(self)
+    }
+
     pub fn new_with_source(
         kind: ErrorKind,
         source: Option<Box<dyn std::error::Error + Sync + Send + 'static>>,
@@ -68,6 +105,18 @@ impl Error {
             source: None,
         }
     }
+    pub fn unauthorized() -> Self {
+        Self {
+            kind: ErrorKind::Unauthorized,
+            source: None,
+        }
+    }
+    pub fn not_found() -> Self {
+        Self {
+            kind: ErrorKind::NotFound,
+            source: None,
+        }
+    }
     pub fn invalid(issues: Issues) -> Self {
         Self {
             kind: ErrorKind::Invalid(issues),
@@ -19,7 +19,7 @@ macro_rules! impl_event {
 
             #[derive(actix::prelude::Message)]
             #[rtype(result = "()")]
-            pub struct [<On $event>](actix::Recipient<$event>);
+            pub struct [<On $event>](pub actix::Recipient<$event>);
 
             impl actix::Handler<[<On $event>]> for super::EventBusActor {
                 type Result = ();
@@ -33,9 +33,17 @@ macro_rules! impl_event {
 }
 
 mod authentication_failed;
+mod nuisance_report_changed;
+mod nuisance_reported;
+mod taxonomy_changed;
+mod user_email_changed;
 mod user_registered;
 
 pub use authentication_failed::*;
+pub use nuisance_report_changed::*;
+pub use nuisance_reported::*;
+pub use taxonomy_changed::*;
+pub use user_email_changed::*;
 pub use user_registered::*;
 
 #[derive(Default)]
@@ -43,6 +51,10 @@ pub use user_registered::*;
 pub struct EventBusActor {
     pub user_registered_subscribers: Vec<Recipient<UserRegistered>>,
     pub authentication_failed_subscribers: Vec<Recipient<AuthenticationFailed>>,
+    pub nuisance_reported_subscribers: Vec<Recipient<NuisanceReported>>,
+    pub user_email_changed_subscribers: Vec<Recipient<UserEmailChanged>>,
+    pub nuisance_report_changed_subscribers: Vec<Recipient<NuisanceReportChanged>>,
+    pub taxonomy_changed_subscribers: Vec<Recipient<TaxonomyChanged>>,
 }
 
 impl Actor for EventBusActor {
@@ -63,6 +75,27 @@ impl EventBus {
         E: Message + Sync + Send + 'static,
         E::Result: Sync + Send,
     {
+        crate::metrics::count_event(crate::metrics::type_label::<E>());
         self.0.do_send(event)
     }
+
+    /// Abonne un destinataire à un type d'évènement.
+    ///
+    /// # Exemple
+    /// ```
+    /// events.subscribe(OnNuisanceReported(addr.recipient()));
+    /// ```
+    pub fn subscribe<S>(&self, subscription: S)
+    where
+        EventBusActor: Handler<S>,
+        S: Message + Send + 'static,
+        S::Result: Send,
+    {
+        self.0.do_send(subscription)
+    }
+
+    /// Indique si le bus est toujours en vie.
+    pub fn connected(&self) -> bool {
+        self.0.connected()
+    }
 }
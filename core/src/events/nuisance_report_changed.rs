@@ -0,0 +1,7 @@
+use crate::models::nuisance_report::NuisanceReportId;
+
+#[derive(Clone, Copy)]
+/// Un signalement a été modifié ou retiré par son auteur.
+pub struct NuisanceReportChanged(pub NuisanceReportId);
+
+impl_event!(NuisanceReportChanged);
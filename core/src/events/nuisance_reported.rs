@@ -0,0 +1,6 @@
+use crate::models::nuisance_report::NuisanceReportId;
+
+#[derive(Clone, Copy)]
+pub struct NuisanceReported(pub NuisanceReportId);
+
+impl_event!(NuisanceReported);
@@ -0,0 +1,5 @@
+#[derive(Clone, Copy)]
+/// La taxonomie des nuisances (familles, types) a été modifiée.
+pub struct TaxonomyChanged;
+
+impl_event!(TaxonomyChanged);
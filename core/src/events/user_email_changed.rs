@@ -0,0 +1,7 @@
+use crate::models::user::UserId;
+
+#[derive(Clone, Copy)]
+/// L'adresse courriel d'un utilisateur a changé et doit être vérifiée à nouveau.
+pub struct UserEmailChanged(pub UserId);
+
+impl_event!(UserEmailChanged);
@@ -0,0 +1,89 @@
+//! Génération de jeux de données factices, pour le développement et les tests
+//! de charge.
+//!
+//! Chaque fixture produit des valeurs aléatoires via [`Dummy`], résout ses
+//! clés étrangères ([`rel::ForeignKeyFixture`]) puis s'insère dans le
+//! répertoire ; les générateurs insèrent les fixtures par lots, au fil de l'eau.
+//!
+//! ```no_run
+//! # async fn example(sg: signuis_core::Signuis) -> Result<(), signuis_core::error::Error> {
+//! use futures::TryStreamExt;
+//! use signuis_core::fixtures::{self, nuisance_families::NuisanceFamilyFixture};
+//!
+//! let families = fixtures::generate::<NuisanceFamilyFixture>(&sg.repos, 10)
+//!     .try_concat()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+use fake::{Dummy, Fake, Faker};
+use futures::future::LocalBoxFuture;
+use futures::stream::{self, Stream, StreamExt};
+
+use crate::error::Error;
+use crate::repositories::Repository;
+
+pub mod nuisance_families;
+pub mod nuisance_reports;
+pub mod nuisance_types;
+pub mod rel;
+pub mod spatial;
+pub mod users;
+
+/// Nombre de fixtures insérées par lot.
+pub const BATCH_SIZE: usize = 1_000;
+
+/// Données factices insérables dans le répertoire.
+pub trait Fixture: Dummy<Faker> + Sized + 'static {
+    type Id: Copy + 'static;
+
+    /// Insère la fixture, et ses dépendances, dans le répertoire.
+    fn insert(self, repos: &Repository) -> LocalBoxFuture<'_, Result<Self::Id, Error>>;
+
+    /// Insère un lot de fixtures ; par défaut, une à une.
+    fn insert_batch(
+        fixtures: Vec<Self>,
+        repos: &Repository,
+    ) -> LocalBoxFuture<'_, Result<Vec<Self::Id>, Error>> {
+        Box::pin(async move {
+            let mut ids = Vec::with_capacity(fixtures.len());
+
+            for fixture in fixtures {
+                ids.push(fixture.insert(repos).await?);
+            }
+
+            Ok(ids)
+        })
+    }
+}
+
+/// Insère `count` fixtures aléatoires.
+///
+/// Le flux produit les identifiants de chaque lot inséré.
+pub fn generate<F: Fixture>(
+    repos: &Repository,
+    count: usize,
+) -> impl Stream<Item = Result<Vec<F::Id>, Error>> + '_ {
+    generate_with(repos, count, || Faker.fake::<F>())
+}
+
+/// Insère `count` fixtures produites par `factory`.
+///
+/// Le flux produit les identifiants de chaque lot inséré ; les lots suivants ne
+/// sont générés qu'à sa consommation.
+pub fn generate_with<'a, F, G>(
+    repos: &'a Repository,
+    count: usize,
+    mut factory: G,
+) -> impl Stream<Item = Result<Vec<F::Id>, Error>> + 'a
+where
+    F: Fixture,
+    G: FnMut() -> F + 'a,
+{
+    let batches = (0..count).step_by(BATCH_SIZE).map(move |start| {
+        let end = (start + BATCH_SIZE).min(count);
+        (start..end).map(|_| factory()).collect::<Vec<F>>()
+    });
+
+    stream::iter(batches).then(move |batch| F::insert_batch(batch, repos))
+}
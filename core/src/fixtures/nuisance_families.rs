@@ -0,0 +1,57 @@
+//! Familles de nuisance factices.
+use fake::faker::lorem::fr_fr::{Sentence, Word};
+use fake::{Dummy, Fake, Faker};
+use futures::future::LocalBoxFuture;
+use rand::Rng;
+
+use crate::error::Error;
+use crate::models::nuisance_family::NuisanceFamilyId;
+use crate::repositories::nuisance_family::InsertNuisanceFamily;
+use crate::repositories::Repository;
+
+use super::Fixture;
+
+#[derive(Clone)]
+pub struct NuisanceFamilyFixture {
+    pub label: String,
+    pub description: String,
+}
+
+impl NuisanceFamilyFixture {
+    pub fn new() -> Self {
+        Faker.fake()
+    }
+}
+
+impl Default for NuisanceFamilyFixture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Fixture for NuisanceFamilyFixture {
+    type Id = NuisanceFamilyId;
+
+    fn insert(self, repos: &Repository) -> LocalBoxFuture<'_, Result<Self::Id, Error>> {
+        Box::pin(repos.execute(InsertNuisanceFamily {
+            label: self.label,
+            description: self.description,
+        }))
+    }
+}
+
+impl Dummy<Faker> for NuisanceFamilyFixture {
+    fn dummy_with_rng<R: Rng + ?Sized>(_: &Faker, rng: &mut R) -> Self {
+        // suffixe aléatoire, les libellés étant uniques
+        let label = format!(
+            "{} {:08x}",
+            Word().fake_with_rng::<String, _>(rng),
+            rng.gen::<u32>()
+        );
+
+        Self {
+            label,
+            description: Sentence(5..12).fake_with_rng(rng),
+        }
+    }
+}
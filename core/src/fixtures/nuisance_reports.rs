@@ -0,0 +1,124 @@
+//! Signalements de nuisance factices.
+use chrono::{DateTime, Duration, Utc};
+use fake::{Dummy, Fake, Faker};
+use futures::future::LocalBoxFuture;
+use rand::Rng;
+
+use crate::error::Error;
+use crate::models::nuisance_report::NuisanceReportId;
+use crate::repositories::nuisance_report::{BulkInsertNuisanceReports, NewNuisanceReport};
+use crate::repositories::Repository;
+
+use super::nuisance_types::NuisanceTypeFixture;
+use super::rel::ForeignKeyFixture;
+use super::spatial::ClusteredPoints;
+use super::users::UserFixture;
+use super::Fixture;
+
+#[derive(Clone)]
+pub struct NuisanceReportFixture {
+    pub nuisance_type: ForeignKeyFixture<NuisanceTypeFixture>,
+    /// Déclarant, `None` pour un signalement anonyme.
+    pub user: Option<ForeignKeyFixture<UserFixture>>,
+    pub longitude: f64,
+    pub latitude: f64,
+    pub intensity: i8,
+    pub created_at: DateTime<Utc>,
+}
+
+impl NuisanceReportFixture {
+    pub fn new() -> Self {
+        Faker.fake()
+    }
+
+    pub fn with_type(mut self, nuisance_type: ForeignKeyFixture<NuisanceTypeFixture>) -> Self {
+        self.nuisance_type = nuisance_type;
+        self
+    }
+
+    pub fn with_user(mut self, user: ForeignKeyFixture<UserFixture>) -> Self {
+        self.user = Some(user);
+        self
+    }
+
+    pub fn anonymous(mut self) -> Self {
+        self.user = None;
+        self
+    }
+
+    /// Place le signalement aux coordonnées `(longitude, latitude)` données.
+    pub fn at(mut self, (longitude, latitude): (f64, f64)) -> Self {
+        self.longitude = longitude;
+        self.latitude = latitude;
+        self
+    }
+
+    /// Résout les clés étrangères du signalement.
+    async fn resolve(self, repos: &Repository) -> Result<NewNuisanceReport, Error> {
+        let type_id = self.nuisance_type.resolve(repos).await?;
+
+        let user_id = match self.user {
+            Some(user) => Some(user.resolve(repos).await?),
+            None => None,
+        };
+
+        let mut report = NewNuisanceReport::new(
+            type_id,
+            user_id,
+            self.longitude,
+            self.latitude,
+            self.intensity,
+        );
+        report.created_at = Some(self.created_at);
+
+        Ok(report)
+    }
+}
+
+impl Default for NuisanceReportFixture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Fixture for NuisanceReportFixture {
+    type Id = NuisanceReportId;
+
+    fn insert(self, repos: &Repository) -> LocalBoxFuture<'_, Result<Self::Id, Error>> {
+        Box::pin(async move {
+            let mut ids = Self::insert_batch(vec![self], repos).await?;
+            ids.pop().ok_or_else(Error::internal_error)
+        })
+    }
+
+    /// Insère le lot par `COPY`.
+    fn insert_batch(
+        fixtures: Vec<Self>,
+        repos: &Repository,
+    ) -> LocalBoxFuture<'_, Result<Vec<Self::Id>, Error>> {
+        Box::pin(async move {
+            let mut reports = Vec::with_capacity(fixtures.len());
+
+            for fixture in fixtures {
+                reports.push(fixture.resolve(repos).await?);
+            }
+
+            repos.execute(BulkInsertNuisanceReports(reports)).await
+        })
+    }
+}
+
+impl Dummy<Faker> for NuisanceReportFixture {
+    fn dummy_with_rng<R: Rng + ?Sized>(config: &Faker, rng: &mut R) -> Self {
+        let (longitude, latitude) = ClusteredPoints::default().sample(rng);
+
+        Self {
+            nuisance_type: config.fake_with_rng(rng),
+            user: rng.gen_bool(0.8).then(|| config.fake_with_rng(rng)),
+            longitude,
+            latitude,
+            intensity: rng.gen_range(1..=5),
+            created_at: Utc::now() - Duration::seconds(rng.gen_range(0..365 * 24 * 60 * 60)),
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//! Types de nuisance factices.
+use fake::faker::lorem::fr_fr::{Sentence, Word};
+use fake::{Dummy, Fake, Faker};
+use futures::future::LocalBoxFuture;
+use rand::Rng;
+
+use crate::error::Error;
+use crate::models::nuisance_type::NuisanceTypeId;
+use crate::repositories::nuisance_type::InsertNuisanceType;
+use crate::repositories::Repository;
+
+use super::nuisance_families::NuisanceFamilyFixture;
+use super::rel::ForeignKeyFixture;
+use super::Fixture;
+
+#[derive(Clone)]
+pub struct NuisanceTypeFixture {
+    pub label: String,
+    pub description: String,
+    pub family: ForeignKeyFixture<NuisanceFamilyFixture>,
+}
+
+impl NuisanceTypeFixture {
+    pub fn new() -> Self {
+        Faker.fake()
+    }
+
+    pub fn with_family(mut self, family: ForeignKeyFixture<NuisanceFamilyFixture>) -> Self {
+        self.family = family;
+        self
+    }
+}
+
+impl Default for NuisanceTypeFixture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Fixture for NuisanceTypeFixture {
+    type Id = NuisanceTypeId;
+
+    fn insert(self, repos: &Repository) -> LocalBoxFuture<'_, Result<Self::Id, Error>> {
+        Box::pin(async move {
+            let family_id = self.family.resolve(repos).await?;
+
+            repos
+                .execute(InsertNuisanceType {
+                    label: self.label,
+                    description: self.description,
+                    family_id,
+                })
+                .await
+        })
+    }
+}
+
+impl Dummy<Faker> for NuisanceTypeFixture {
+    fn dummy_with_rng<R: Rng + ?Sized>(config: &Faker, rng: &mut R) -> Self {
+        // suffixe aléatoire, les libellés étant uniques au sein d'une famille
+        let label = format!(
+            "{} {:08x}",
+            Word().fake_with_rng::<String, _>(rng),
+            rng.gen::<u32>()
+        );
+
+        Self {
+            label,
+            description: Sentence(5..12).fake_with_rng(rng),
+            family: config.fake_with_rng(rng),
+        }
+    }
+}
@@ -0,0 +1,59 @@
+//! Relations entre fixtures.
+use std::sync::Arc;
+
+use fake::{Dummy, Fake, Faker};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::error::Error;
+use crate::repositories::Repository;
+
+use super::Fixture;
+
+/// Clé étrangère d'une fixture, résolue au moment de son insertion.
+pub enum ForeignKeyFixture<F: Fixture> {
+    /// Entité existante.
+    Id(F::Id),
+    /// Entité tirée au hasard parmi des entités existantes.
+    OneOf(Arc<[F::Id]>),
+    /// Entité à créer au préalable.
+    New(Box<F>),
+}
+
+impl<F: Fixture> Clone for ForeignKeyFixture<F>
+where
+    F: Clone,
+{
+    fn clone(&self) -> Self {
+        match self {
+            Self::Id(id) => Self::Id(*id),
+            Self::OneOf(ids) => Self::OneOf(ids.clone()),
+            Self::New(fixture) => Self::New(fixture.clone()),
+        }
+    }
+}
+
+impl<F: Fixture> ForeignKeyFixture<F> {
+    /// Tire une entité au hasard parmi celles données.
+    pub fn one_of<I: Into<Arc<[F::Id]>>>(ids: I) -> Self {
+        Self::OneOf(ids.into())
+    }
+
+    /// Retourne l'identifiant de l'entité, en la créant si nécessaire.
+    pub async fn resolve(self, repos: &Repository) -> Result<F::Id, Error> {
+        match self {
+            Self::Id(id) => Ok(id),
+            Self::OneOf(ids) => ids
+                .choose(&mut rand::thread_rng())
+                .copied()
+                .ok_or_else(Error::not_found),
+            Self::New(fixture) => fixture.insert(repos).await,
+        }
+    }
+}
+
+impl<F: Fixture> Dummy<Faker> for ForeignKeyFixture<F> {
+    fn dummy_with_rng<R: Rng + ?Sized>(config: &Faker, rng: &mut R) -> Self {
+        Self::New(Box::new(config.fake_with_rng(rng)))
+    }
+}
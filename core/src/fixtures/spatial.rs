@@ -0,0 +1,94 @@
+//! Distributions spatiales des signalements factices.
+use rand::Rng;
+
+use crate::geodesy::destination;
+
+#[derive(Clone, Debug)]
+/// Foyer de signalements, autour d'un centre.
+pub struct Cluster {
+    pub longitude: f64,
+    pub latitude: f64,
+    /// Écart type de la distance au centre, en kilomètres.
+    pub spread: f64,
+    /// Poids relatif du foyer.
+    pub weight: f64,
+}
+
+impl Cluster {
+    pub fn new(longitude: f64, latitude: f64, spread: f64, weight: f64) -> Self {
+        Self {
+            longitude,
+            latitude,
+            spread,
+            weight,
+        }
+    }
+
+    /// Tire un point `(longitude, latitude)` autour du centre, à une distance
+    /// de loi normale.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> (f64, f64) {
+        let distance = (standard_normal(rng) * self.spread).abs();
+        let bearing = rng.gen_range(0.0..360.0);
+
+        destination(self.longitude, self.latitude, bearing, distance)
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Distribution de points regroupés en foyers pondérés.
+pub struct ClusteredPoints {
+    clusters: Vec<Cluster>,
+    total_weight: f64,
+}
+
+impl Default for ClusteredPoints {
+    /// Grands bassins industriels et urbains de France métropolitaine.
+    fn default() -> Self {
+        Self::new(vec![
+            Cluster::new(2.35, 48.85, 15.0, 5.0),
+            Cluster::new(4.84, 45.76, 10.0, 2.0),
+            Cluster::new(5.37, 43.30, 10.0, 2.0),
+            Cluster::new(4.95, 43.43, 8.0, 1.5),
+            Cluster::new(2.36, 51.03, 8.0, 1.5),
+            Cluster::new(0.11, 49.49, 6.0, 1.0),
+            Cluster::new(7.75, 48.57, 6.0, 1.0),
+        ])
+    }
+}
+
+impl ClusteredPoints {
+    pub fn new(clusters: Vec<Cluster>) -> Self {
+        let total_weight = clusters.iter().map(|cluster| cluster.weight).sum();
+
+        Self {
+            clusters,
+            total_weight,
+        }
+    }
+
+    /// Tire un foyer selon les poids, puis un point `(longitude, latitude)`
+    /// autour de son centre.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> (f64, f64) {
+        let mut threshold = rng.gen_range(0.0..self.total_weight);
+
+        let cluster = self
+            .clusters
+            .iter()
+            .find(|cluster| {
+                threshold -= cluster.weight;
+                threshold < 0.0
+            })
+            .or(self.clusters.last())
+            .expect("at least one cluster");
+
+        cluster.sample(rng)
+    }
+}
+
+/// Tire une valeur de loi normale centrée réduite (méthode de Box-Muller).
+fn standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
@@ -0,0 +1,18 @@
+//! Utilisateurs factices.
+use futures::future::LocalBoxFuture;
+
+use crate::error::Error;
+use crate::models::user::UserId;
+use crate::repositories::Repository;
+
+use super::Fixture;
+
+pub use crate::repositories::user::fixtures::InsertUserFixture as UserFixture;
+
+impl Fixture for UserFixture {
+    type Id = UserId;
+
+    fn insert(self, repos: &Repository) -> LocalBoxFuture<'_, Result<Self::Id, Error>> {
+        Box::pin(self.execute(repos))
+    }
+}
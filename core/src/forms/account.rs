@@ -1,6 +1,16 @@
 use serde::{Deserialize, Serialize};
 
-use crate::validation::{Validation, Validator};
+use crate::{
+    forms::reporting::PhotoUpload,
+    media::{sniff_content_type, ACCEPTED_PHOTO_CONTENT_TYPES},
+    models::user::UserId,
+    validation::{Validation, Validator},
+};
+
+/// Langues proposées pour l'interface.
+pub const ACCEPTED_LOCALES: [&str; 2] = ["fr", "en"];
+/// Taille maximale d'un avatar, en octets.
+pub const MAX_AVATAR_SIZE: usize = 1024 * 1024;
 
 #[derive(Deserialize, Serialize)]
 /// Objet pour enregistrer un nouvel utilisateur.
@@ -36,3 +46,171 @@ impl Validation for RegisterUserForm {
         );
     }
 }
+
+#[derive(Deserialize, Serialize, Clone, Default)]
+/// Objet pour modifier son profil ; les champs absents sont inchangés.
+pub struct UpdateProfileForm {
+    pub username: Option<String>,
+    /// Nouvelle adresse courriel, à vérifier de nouveau.
+    pub email: Option<String>,
+    pub locale: Option<String>,
+    pub avatar: Option<PhotoUpload>,
+}
+
+impl Validation for UpdateProfileForm {
+    fn assert(&self, validator: &mut Validator) {
+        if let Some(username) = &self.username {
+            validator.assert_not_empty(
+                username,
+                Some("le nom d'utilisateur ne doit pas être vide"),
+                ["username"],
+            );
+            validator.assert_max_length(
+                username,
+                50,
+                Some("le nom d'utilisateur est trop long"),
+                ["username"],
+            );
+        }
+
+        if let Some(email) = &self.email {
+            validator.assert_valid_email(email, Some("l'adresse courriel est invalide"), ["email"]);
+        }
+
+        if let Some(locale) = &self.locale {
+            validator.assert_one_of(
+                &locale.as_str(),
+                &ACCEPTED_LOCALES,
+                Some("la langue n'est pas prise en charge"),
+                ["locale"],
+            );
+        }
+
+        if let Some(avatar) = &self.avatar {
+            validator.assert_one_of(
+                &avatar.content_type.as_str(),
+                &ACCEPTED_PHOTO_CONTENT_TYPES,
+                Some("l'avatar doit être une image JPEG ou PNG"),
+                ["avatar"],
+            );
+            validator.assert_eq(
+                sniff_content_type(&avatar.data),
+                Some(avatar.content_type.as_str()),
+                Some("le contenu de l'avatar ne correspond pas à son type"),
+                ["avatar"],
+            );
+            validator.assert_true(
+                avatar.data.len() <= MAX_AVATAR_SIZE,
+                Some("l'avatar est trop volumineux"),
+                ["avatar"],
+            );
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+/// Objet pour changer son mot de passe.
+pub struct ChangePasswordForm {
+    pub current_password: String,
+    pub new_password: String,
+    pub confirm_password: String,
+}
+
+impl Validation for ChangePasswordForm {
+    fn assert(&self, validator: &mut Validator) {
+        validator.assert_not_empty(
+            &self.new_password,
+            Some("le mot de passe ne doit pas être vide"),
+            ["new_password"],
+        );
+        validator.assert_eq(
+            &self.new_password,
+            &self.confirm_password,
+            Some("les mots de passe ne sont pas égaux"),
+            ["confirm_password"],
+        );
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+/// Objet pour choisir un nouveau mot de passe au moyen d'un jeton de réinitialisation.
+pub struct ResetPasswordForm {
+    pub token: String,
+    pub new_password: String,
+    pub confirm_password: String,
+}
+
+impl Validation for ResetPasswordForm {
+    fn assert(&self, validator: &mut Validator) {
+        validator.assert_not_empty(
+            &self.token,
+            Some("le jeton de réinitialisation est requis"),
+            ["token"],
+        );
+        validator.assert_not_empty(
+            &self.new_password,
+            Some("le mot de passe ne doit pas être vide"),
+            ["new_password"],
+        );
+        validator.assert_eq(
+            &self.new_password,
+            &self.confirm_password,
+            Some("les mots de passe ne sont pas égaux"),
+            ["confirm_password"],
+        );
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+/// Objet pour ouvrir une session au nom d'un utilisateur.
+pub struct ImpersonateUserForm {
+    pub user_id: UserId,
+    /// Motif de l'assistance, consigné dans le journal d'audit.
+    pub reason: String,
+}
+
+impl Validation for ImpersonateUserForm {
+    fn assert(&self, validator: &mut Validator) {
+        validator.assert_not_empty(
+            &self.reason,
+            Some("le motif de l'assistance est requis"),
+            ["reason"],
+        );
+        validator.assert_max_length(
+            &self.reason,
+            500,
+            Some("le motif de l'assistance est trop long"),
+            ["reason"],
+        );
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+/// Compte à importer depuis un système antérieur.
+pub struct ImportUserForm {
+    pub username: String,
+    pub email: String,
+    /// Empreinte du mot de passe, Argon2 (PHC) ou bcrypt.
+    pub password_hash: String,
+}
+
+impl Validation for ImportUserForm {
+    fn assert(&self, validator: &mut Validator) {
+        validator.assert_not_empty(
+            &self.username,
+            Some("le nom d'utilisateur ne doit pas être vide"),
+            ["username"],
+        );
+        validator.assert_max_length(
+            &self.username,
+            50,
+            Some("le nom d'utilisateur est trop long"),
+            ["username"],
+        );
+        validator.assert_valid_email(
+            &self.email,
+            Some("l'adresse courriel est invalide"),
+            ["email"],
+        );
+    }
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    models::administrative_area::AreaLevel,
+    validation::{Validation, Validator},
+};
+
+/// Longueur maximale d'un code de zone administrative.
+pub const MAX_AREA_CODE_LENGTH: usize = 20;
+
+/// Types de géométrie GeoJSON acceptés pour une zone administrative.
+pub const ACCEPTED_AREA_GEOMETRY_TYPES: [&str; 2] = ["Polygon", "MultiPolygon"];
+
+#[derive(Serialize, Deserialize, Clone)]
+/// Objet pour importer une zone administrative.
+///
+/// Une zone déjà connue (même échelon, même code) est mise à jour.
+pub struct ImportAdministrativeAreaForm {
+    pub code: String,
+    pub name: String,
+    pub level: AreaLevel,
+    /// Contour de la zone, sous forme de géométrie GeoJSON en WGS84.
+    pub geometry: serde_json::Value,
+}
+
+impl Validation for ImportAdministrativeAreaForm {
+    fn assert(&self, validator: &mut Validator) {
+        validator.assert_not_empty(&self.code, Some("le code ne doit pas être vide"), ["code"]);
+        validator.assert_max_length(
+            &self.code,
+            MAX_AREA_CODE_LENGTH,
+            Some("le code est trop long"),
+            ["code"],
+        );
+        validator.assert_not_empty(&self.name, Some("le nom ne doit pas être vide"), ["name"]);
+
+        let geometry_type = self
+            .geometry
+            .get("type")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+
+        validator.assert_one_of(
+            &geometry_type,
+            &ACCEPTED_AREA_GEOMETRY_TYPES,
+            Some("le contour doit être un polygone ou un multipolygone"),
+            ["geometry"],
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::validation::{Validation, Validator};
+
 #[derive(Serialize, Deserialize)]
 pub struct CredentialForm {
     pub username_or_email: String,
@@ -14,3 +16,51 @@ impl CredentialForm {
         }
     }
 }
+
+#[derive(Serialize, Deserialize)]
+/// Objet pour présenter le second facteur d'une authentification en attente.
+pub struct SecondFactorForm {
+    /// Jeton de l'authentification en attente.
+    pub token: String,
+    /// Code TOTP à 6 chiffres, ou code de récupération.
+    pub code: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+/// Réponse d'un authentificateur à la création d'une clé d'accès.
+///
+/// Les valeurs binaires sont encodées en base64url.
+pub struct PasskeyRegistrationForm {
+    /// Nom donné à la clé par son titulaire, ex: « Téléphone ».
+    pub name: String,
+    pub credential_id: String,
+    pub client_data_json: String,
+    pub attestation_object: String,
+}
+
+impl Validation for PasskeyRegistrationForm {
+    fn assert(&self, validator: &mut Validator) {
+        validator.assert_not_empty(
+            &self.name,
+            Some("le nom de la clé ne doit pas être vide"),
+            ["name"],
+        );
+        validator.assert_max_length(
+            &self.name,
+            100,
+            Some("le nom de la clé est trop long"),
+            ["name"],
+        );
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+/// Assertion d'un authentificateur pour se connecter avec une clé d'accès.
+///
+/// Les valeurs binaires sont encodées en base64url.
+pub struct PasskeyAssertionForm {
+    pub credential_id: String,
+    pub client_data_json: String,
+    pub authenticator_data: String,
+    pub signature: String,
+}
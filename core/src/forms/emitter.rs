@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use sql_gis::geojson::GeoJsonPoint;
+
+use crate::{
+    models::{emitter::EmitterKind, nuisance_family::NuisanceFamilyId},
+    validation::{Validation, Validator},
+};
+
+#[derive(Serialize, Deserialize, Clone)]
+/// Objet pour enregistrer un émetteur potentiel de nuisances.
+pub struct CreateEmitterForm {
+    pub name: String,
+    pub kind: EmitterKind,
+    #[serde(default)]
+    pub description: String,
+    pub location: Option<GeoJsonPoint>,
+    #[serde(default)]
+    pub family_ids: Vec<NuisanceFamilyId>,
+}
+
+impl Validation for CreateEmitterForm {
+    fn assert(&self, validator: &mut Validator) {
+        validator.assert_not_empty(&self.name, Some("le nom ne doit pas être vide"), ["name"]);
+
+        validator.assert_is_some(
+            &self.location,
+            Some("une location doit être définie"),
+            ["location"],
+        );
+
+        validator.assert_true(
+            !self.family_ids.is_empty(),
+            Some("au moins une famille de nuisance doit être sélectionnée"),
+            ["family_ids"],
+        );
+    }
+}
@@ -1,3 +1,5 @@
 pub mod account;
+pub mod administrative_area;
 pub mod authentication;
+pub mod emitter;
 pub mod reporting;
@@ -1,8 +1,30 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sql_gis::geojson::GeoJsonPoint;
 use uuid::Uuid;
 
-use crate::{models::nuisance_type::NuisanceTypeId, validation::Validation};
+use crate::{
+    forms::administrative_area::MAX_AREA_CODE_LENGTH,
+    media::{sniff_content_type, ACCEPTED_PHOTO_CONTENT_TYPES},
+    models::{
+        nuisance_type::NuisanceTypeId,
+        statistics::{ReportStatisticsQuery, TimeBucket},
+    },
+    validation::Validation,
+};
+
+/// Longueur maximale de la description d'un signalement.
+pub const MAX_REPORT_DESCRIPTION_LENGTH: usize = 2000;
+/// Nombre maximal de photos jointes à un signalement.
+pub const MAX_REPORT_PHOTOS: usize = 5;
+/// Taille maximale d'une photo, en octets.
+pub const MAX_REPORT_PHOTO_SIZE: usize = 5 * 1024 * 1024;
+/// Durée perçue maximale d'une nuisance, en minutes.
+pub const MAX_PERCEIVED_DURATION: u32 = 7 * 24 * 60;
+/// Période maximale couverte par des statistiques horaires, en jours.
+pub const MAX_HOURLY_STATISTICS_DAYS: i64 = 31;
+/// Période maximale couverte par des statistiques, en jours.
+pub const MAX_STATISTICS_DAYS: i64 = 5 * 366;
 
 pub struct CreateNuisanceFamilyForm {
     pub label: String,
@@ -35,11 +57,33 @@ impl Validation for CreateNuisanceTypeForm {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Default)]
+/// Photo jointe à un signalement.
+pub struct PhotoUpload {
+    pub filename: String,
+    pub content_type: String,
+    pub data: Vec<u8>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Default)]
 pub struct CreateNuisanceReportForm {
     pub intensity: Option<u8>,
     pub type_id: Option<NuisanceTypeId>,
     pub location: Option<GeoJsonPoint>,
+    /// Description libre de la nuisance.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Début de l'observation de la nuisance.
+    #[serde(default)]
+    pub observed_from: Option<DateTime<Utc>>,
+    /// Fin de l'observation de la nuisance.
+    #[serde(default)]
+    pub observed_until: Option<DateTime<Utc>>,
+    /// Durée perçue de la nuisance, en minutes.
+    #[serde(default)]
+    pub perceived_duration: Option<u32>,
+    #[serde(default)]
+    pub photos: Vec<PhotoUpload>,
 }
 
 impl Validation for CreateNuisanceReportForm {
@@ -70,5 +114,129 @@ impl Validation for CreateNuisanceReportForm {
             Some("une location doit être définie"),
             ["location"],
         );
+
+        self.description.as_ref().inspect(|description| {
+            validator.assert_max_length(
+                description,
+                MAX_REPORT_DESCRIPTION_LENGTH,
+                Some("la description est trop longue"),
+                ["description"],
+            )
+        });
+
+        let now = Utc::now();
+
+        self.observed_from.inspect(|from| {
+            validator.assert_true(
+                *from <= now,
+                Some("le début de l'observation ne peut pas être dans le futur"),
+                ["observed_from"],
+            )
+        });
+
+        self.observed_until.inspect(|until| {
+            validator.assert_true(
+                *until <= now,
+                Some("la fin de l'observation ne peut pas être dans le futur"),
+                ["observed_until"],
+            )
+        });
+
+        if let (Some(from), Some(until)) = (self.observed_from, self.observed_until) {
+            validator.assert_true(
+                from <= until,
+                Some("la fin de l'observation doit être postérieure à son début"),
+                ["observed_until"],
+            );
+        }
+
+        self.perceived_duration.inspect(|duration| {
+            validator.assert_in_range_inclusive(
+                duration,
+                1..=MAX_PERCEIVED_DURATION,
+                Some("la durée perçue doit être comprise entre une minute et une semaine"),
+                ["perceived_duration"],
+            )
+        });
+
+        validator.assert_true(
+            self.photos.len() <= MAX_REPORT_PHOTOS,
+            Some("trop de photos ont été jointes"),
+            ["photos"],
+        );
+
+        for (index, photo) in self.photos.iter().enumerate() {
+            let index = index.to_string();
+
+            validator.assert_true(
+                photo.data.len() <= MAX_REPORT_PHOTO_SIZE,
+                Some("la photo est trop volumineuse"),
+                ["photos", index.as_str(), "data"],
+            );
+
+            validator.assert_one_of(
+                &photo.content_type.as_str(),
+                &ACCEPTED_PHOTO_CONTENT_TYPES,
+                Some("le format de la photo n'est pas accepté"),
+                ["photos", index.as_str(), "content_type"],
+            );
+
+            validator.assert_eq(
+                sniff_content_type(&photo.data),
+                Some(photo.content_type.as_str()),
+                Some("le contenu de la photo ne correspond pas à son format"),
+                ["photos", index.as_str(), "data"],
+            );
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+/// Objet pour modifier un signalement de nuisance existant.
+pub struct UpdateNuisanceReportForm {
+    pub intensity: Option<u8>,
+    pub type_id: Option<NuisanceTypeId>,
+}
+
+impl Validation for UpdateNuisanceReportForm {
+    fn assert(&self, validator: &mut crate::validation::Validator) {
+        self.intensity.inspect(|value| {
+            validator.assert_in_range_inclusive(
+                value,
+                1..=5,
+                Some("l'intensité doit être comprise entre 1 et 5"),
+                ["intensity"],
+            )
+        });
+    }
+}
+
+impl Validation for ReportStatisticsQuery {
+    fn assert(&self, validator: &mut crate::validation::Validator) {
+        validator.assert_true(
+            self.from < self.until,
+            Some("la fin de la période doit être postérieure à son début"),
+            ["until"],
+        );
+
+        let max_days = match self.bucket {
+            TimeBucket::Hour => MAX_HOURLY_STATISTICS_DAYS,
+            TimeBucket::Day | TimeBucket::Week => MAX_STATISTICS_DAYS,
+        };
+
+        validator.assert_true(
+            (self.until - self.from).num_days() <= max_days,
+            Some("la période demandée est trop longue"),
+            ["until"],
+        );
+
+        if let Some(area_code) = &self.area_code {
+            validator.assert_max_length(
+                area_code,
+                MAX_AREA_CODE_LENGTH,
+                Some("le code de zone est trop long"),
+                ["area_code"],
+            );
+        }
     }
 }
@@ -0,0 +1,48 @@
+//! Calculs géodésiques sur la sphère terrestre (coordonnées WGS84 en degrés).
+
+const EARTH_RADIUS: f64 = 6371.0;
+
+/// Distance orthodromique entre deux points, en kilomètres.
+pub fn haversine_distance(lon1: f64, lat1: f64, lon2: f64, lat2: f64) -> f64 {
+    let (lat1, lat2) = (lat1.to_radians(), lat2.to_radians());
+    let d_lat = lat2 - lat1;
+    let d_lon = (lon2 - lon1).to_radians();
+
+    let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+
+    2.0 * EARTH_RADIUS * a.sqrt().asin()
+}
+
+/// Cap initial pour aller du premier point vers le second, en degrés (0 = nord, 90 = est).
+pub fn initial_bearing(lon1: f64, lat1: f64, lon2: f64, lat2: f64) -> f64 {
+    let (lat1, lat2) = (lat1.to_radians(), lat2.to_radians());
+    let d_lon = (lon2 - lon1).to_radians();
+
+    let y = d_lon.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * d_lon.cos();
+
+    y.atan2(x).to_degrees().rem_euclid(360.0)
+}
+
+/// Écart angulaire entre deux directions, en degrés (entre 0 et 180).
+pub fn angular_difference(a: f64, b: f64) -> f64 {
+    let diff = (a - b).rem_euclid(360.0);
+    diff.min(360.0 - diff)
+}
+
+/// Point atteint en parcourant la distance donnée, en kilomètres, selon le cap
+/// initial donné, en degrés.
+pub fn destination(lon: f64, lat: f64, bearing: f64, distance: f64) -> (f64, f64) {
+    let (lat, lon) = (lat.to_radians(), lon.to_radians());
+    let bearing = bearing.to_radians();
+    let delta = distance / EARTH_RADIUS;
+
+    let lat2 = (lat.sin() * delta.cos() + lat.cos() * delta.sin() * bearing.cos()).asin();
+    let lon2 =
+        lon + (bearing.sin() * delta.sin() * lat.cos()).atan2(delta.cos() - lat.sin() * lat2.sin());
+
+    (
+        (lon2.to_degrees() + 540.0).rem_euclid(360.0) - 180.0,
+        lat2.to_degrees(),
+    )
+}
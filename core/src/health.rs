@@ -0,0 +1,168 @@
+//! Sondes de l'état du service.
+//!
+//! Un service est en vie tant que ses acteurs répondent ; il est prêt lorsque,
+//! en outre, la base de données est joignable, PostGIS installé et le schéma
+//! à jour.
+use std::future::Future;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::error::Error;
+use crate::repositories::health::{FetchPostgisVersion, Ping};
+use crate::repositories::Repository;
+
+/// Délai au-delà duquel une sonde est réputée en échec.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Up,
+    Down,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthProbe {
+    /// La base de données répond.
+    Database,
+    /// L'extension PostGIS est installée.
+    Postgis,
+    /// Toutes les migrations sont appliquées.
+    Migrations,
+    /// Les acteurs des services sont en vie.
+    Mailboxes,
+}
+
+#[derive(Clone, Debug, Serialize)]
+/// Résultat d'une sonde.
+pub struct HealthCheck {
+    pub probe: HealthProbe,
+    pub status: HealthStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl HealthCheck {
+    fn up(probe: HealthProbe, detail: Option<String>) -> Self {
+        Self {
+            probe,
+            status: HealthStatus::Up,
+            detail,
+        }
+    }
+
+    fn down(probe: HealthProbe, detail: String) -> Self {
+        Self {
+            probe,
+            status: HealthStatus::Down,
+            detail: Some(detail),
+        }
+    }
+
+    pub fn is_up(&self) -> bool {
+        self.status == HealthStatus::Up
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+/// Résultat de l'ensemble des sondes.
+pub struct HealthReport {
+    pub checks: Vec<HealthCheck>,
+}
+
+impl HealthReport {
+    /// Le service est en vie : ses acteurs répondent.
+    pub fn is_live(&self) -> bool {
+        self.checks
+            .iter()
+            .filter(|check| check.probe == HealthProbe::Mailboxes)
+            .all(HealthCheck::is_up)
+    }
+
+    /// Le service est prêt à traiter des requêtes : toutes les sondes réussissent.
+    pub fn is_ready(&self) -> bool {
+        self.checks.iter().all(HealthCheck::is_up)
+    }
+}
+
+/// Exécute une sonde, en échec si elle dépasse le délai imparti.
+async fn probe<F>(probe: HealthProbe, fut: F) -> HealthCheck
+where
+    F: Future<Output = Result<HealthCheck, Error>>,
+{
+    match tokio::time::timeout(PROBE_TIMEOUT, fut).await {
+        Ok(Ok(check)) => check,
+        Ok(Err(err)) => HealthCheck::down(probe, err.kind.to_string()),
+        Err(_) => HealthCheck::down(probe, "timeout".to_owned()),
+    }
+}
+
+/// Sonde les boîtes aux lettres des acteurs donnés.
+fn mailboxes_check(mailboxes: &[(&'static str, bool)]) -> HealthCheck {
+    let stopped = mailboxes
+        .iter()
+        .filter(|(_, connected)| !connected)
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>();
+
+    if stopped.is_empty() {
+        HealthCheck::up(HealthProbe::Mailboxes, None)
+    } else {
+        HealthCheck::down(
+            HealthProbe::Mailboxes,
+            format!("stopped: {}", stopped.join(", ")),
+        )
+    }
+}
+
+/// Sonde les seules boîtes aux lettres des acteurs donnés, sans solliciter la base.
+pub fn liveness(mailboxes: &[(&'static str, bool)]) -> HealthReport {
+    HealthReport {
+        checks: vec![mailboxes_check(mailboxes)],
+    }
+}
+
+/// Sonde le répertoire de données, et les boîtes aux lettres des acteurs donnés.
+///
+/// Les sondes du répertoire sont menées en parallèle : la réponse attend au
+/// plus le délai d'une sonde.
+pub async fn check(repos: &Repository, mailboxes: &[(&'static str, bool)]) -> HealthReport {
+    let database = probe(HealthProbe::Database, async {
+        repos.execute(Ping).await?;
+        Ok::<_, Error>(HealthCheck::up(HealthProbe::Database, None))
+    });
+
+    let postgis = probe(HealthProbe::Postgis, async {
+        // le répertoire en mémoire n'a pas besoin de PostGIS
+        if repos.pool_usage().await?.is_none() {
+            return Ok(HealthCheck::up(HealthProbe::Postgis, None));
+        }
+
+        Ok::<_, Error>(match repos.execute(FetchPostgisVersion).await? {
+            Some(version) => HealthCheck::up(HealthProbe::Postgis, Some(version)),
+            None => HealthCheck::down(HealthProbe::Postgis, "not installed".to_owned()),
+        })
+    });
+
+    let migrations = probe(HealthProbe::Migrations, async {
+        let pending = repos
+            .migration_status()
+            .await?
+            .iter()
+            .filter(|status| !status.applied)
+            .count();
+
+        Ok::<_, Error>(match pending {
+            0 => HealthCheck::up(HealthProbe::Migrations, None),
+            n => HealthCheck::down(HealthProbe::Migrations, format!("{n} pending")),
+        })
+    });
+
+    let (database, postgis, migrations) = futures::join!(database, postgis, migrations);
+
+    HealthReport {
+        checks: vec![database, postgis, migrations, mailboxes_check(mailboxes)],
+    }
+}
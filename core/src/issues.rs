@@ -1,5 +1,6 @@
 use std::ops::Deref;
 
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
 use crate::error::Error;
@@ -36,6 +37,16 @@ impl Issue {
     }
 }
 
+impl std::fmt::Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path.join("."), self.message)
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Issues {
     issues: Vec<Issue>,
@@ -49,6 +60,12 @@ impl Deref for Issues {
     }
 }
 
+impl std::fmt::Display for Issues {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.issues.iter().map(Issue::to_string).join("; "))
+    }
+}
+
 impl Issues {
     pub fn new() -> Self {
         Self {
@@ -2,7 +2,11 @@
 mod crypto;
 
 pub mod error;
+pub mod geodesy;
 pub mod issues;
+pub mod media;
+pub mod password_policy;
+pub mod privacy;
 pub mod validation;
 
 pub mod forms;
@@ -11,30 +15,83 @@ pub mod models;
 #[cfg(feature = "backend")]
 pub mod repositories;
 
-//pub mod config;
+#[cfg(feature = "backend")]
+pub mod cache;
+
+#[cfg(feature = "backend")]
+pub mod config;
 #[cfg(feature = "backend")]
 pub mod events;
 
+#[cfg(all(feature = "backend", feature = "fixture"))]
+pub mod fixtures;
+
+#[cfg(feature = "backend")]
+pub mod health;
+
+#[cfg(feature = "backend")]
+pub mod metrics;
+
+#[cfg(feature = "backend")]
+pub mod pagination;
+
+#[cfg(feature = "backend")]
+pub mod rate_limit;
+
 #[cfg(feature = "backend")]
 pub mod services;
 
-//pub use log;
+#[cfg(feature = "backend")]
+pub mod storage;
+
+#[cfg(feature = "backend")]
+pub mod telemetry;
+
+#[cfg(feature = "backend")]
+pub mod weather;
+
+#[cfg(feature = "backend")]
+pub mod webauthn;
+
+pub use log;
+
+#[cfg(feature = "backend")]
+pub use tracing;
 
 #[cfg(feature = "backend")]
 mod backend {
     use crate::events::EventBus;
+    use actix::Actor;
+
     use crate::services::account::Account;
+    use crate::services::attribution::Attribution;
     use crate::services::authentication::Authentication;
+    use crate::services::cache::CacheInvalidator;
+    use crate::services::enrichment::Enrichment;
     use crate::services::reporting::Reporting;
+    use crate::services::retention::RetentionJob;
+    use crate::services::statistics::StatisticsRefresher;
+    use crate::services::territory::Territory;
 
+    use crate::cache::{CacheSettings, QueryCache};
+    use crate::health::HealthReport;
+    use crate::pagination::CursorKey;
+    use crate::password_policy::{PasswordHashing, PasswordPolicy};
+    use crate::privacy::{JitterKey, PrivacySettings};
+    use crate::repositories::migration::MigrationStatus;
     use crate::repositories::{Repository, RepositorySettings};
-    use crate::services::ServiceSettings;
+    use crate::services::{RateLimitSettings, ServiceSettings};
+    use crate::storage::{Storage, StorageSettings};
+    use crate::weather::{Weather, WeatherSettings};
+    use crate::webauthn::WebAuthnSettings;
 
     #[derive(Default, Clone)]
     /// Paramètres de configuration pour Signuis.
     pub struct SgSettings {
         service: ServiceSettings,
         repos: RepositorySettings,
+        storage: StorageSettings,
+        weather: WeatherSettings,
     }
 
     impl SgSettings {
@@ -42,6 +99,129 @@ mod backend {
             self.repos.set_max_connections(value);
             self
         }
+
+        /// Définit l'adresse de la base de données.
+        pub fn set_database_url(&mut self, value: String) -> &mut Self {
+            self.repos.set_database_url(value);
+            self
+        }
+
+        /// Rattache toutes les opérations à une transaction jamais validée (tests).
+        pub fn set_rollback_only(&mut self, value: bool) -> &mut Self {
+            self.repos.set_rollback_only(value);
+            self
+        }
+
+        /// Définit les adresses des réplicas en lecture.
+        pub fn set_read_replicas(&mut self, value: Vec<String>) -> &mut Self {
+            self.repos.set_read_replicas(value);
+            self
+        }
+
+        /// Conserve les données en mémoire plutôt qu'en base de données (tests).
+        pub fn set_in_memory(&mut self, value: bool) -> &mut Self {
+            self.repos.set_in_memory(value);
+            self
+        }
+
+        /// Définit la durée de validité des sessions utilisateur.
+        pub fn set_user_session_lifetime(&mut self, value: chrono::Duration) -> &mut Self {
+            self.service.user_session_expiration_time = value;
+            self
+        }
+
+        /// Définit les limites de fréquence des requêtes.
+        pub fn set_rate_limits(&mut self, value: RateLimitSettings) -> &mut Self {
+            self.service.rate_limits = value;
+            self
+        }
+
+        /// Définit les paramètres du cache des résultats du répertoire.
+        pub fn set_cache(&mut self, value: CacheSettings) -> &mut Self {
+            self.service.cache = value;
+            self
+        }
+
+        /// Définit l'espace de stockage des fichiers (photos, etc.)
+        pub fn set_storage(&mut self, value: StorageSettings) -> &mut Self {
+            self.storage = value;
+            self
+        }
+
+        /// Définit le fournisseur de données météorologiques.
+        pub fn set_weather(&mut self, value: WeatherSettings) -> &mut Self {
+            self.weather = value;
+            self
+        }
+
+        /// Définit les règles de protection de la vie privée des déclarants.
+        pub fn set_privacy(&mut self, value: PrivacySettings) -> &mut Self {
+            self.service.privacy = value;
+            self
+        }
+
+        /// Définit l'intervalle de recalcul des statistiques agrégées.
+        pub fn set_statistics_refresh_interval(
+            &mut self,
+            value: Option<std::time::Duration>,
+        ) -> &mut Self {
+            self.service.statistics_refresh_interval = value;
+            self
+        }
+
+        /// Définit la durée de conservation des signalements anonymes,
+        /// au-delà de laquelle ils sont purgés.
+        pub fn set_anonymous_data_retention(
+            &mut self,
+            value: Option<chrono::Duration>,
+        ) -> &mut Self {
+            self.service.anonymous_data_retention = value;
+            self
+        }
+
+        /// Définit les règles imposées aux nouveaux mots de passe.
+        pub fn set_password_policy(&mut self, value: PasswordPolicy) -> &mut Self {
+            self.service.password_policy = value;
+            self
+        }
+
+        /// Définit les paramètres de hachage des mots de passe.
+        pub fn set_password_hashing(&mut self, value: PasswordHashing) -> &mut Self {
+            self.service.password_hashing = value;
+            self
+        }
+
+        /// Définit la partie de confiance à laquelle les clés d'accès sont rattachées.
+        pub fn set_webauthn(&mut self, value: WebAuthnSettings) -> &mut Self {
+            self.service.webauthn = value;
+            self
+        }
+
+        /// Définit la clé de signature des curseurs de pagination ; à défaut, une
+        /// clé aléatoire propre au processus.
+        pub fn set_cursor_secret(&mut self, value: Option<String>) -> &mut Self {
+            self.service.cursor_key = match value {
+                Some(secret) => CursorKey::new(secret.as_bytes()),
+                None => CursorKey::random(),
+            };
+            self
+        }
+
+        /// Définit la clé du déplacement des coordonnées des signalements ; à défaut,
+        /// une clé aléatoire propre au processus.
+        pub fn set_jitter_secret(&mut self, value: Option<String>) -> &mut Self {
+            self.service.privacy.jitter_key = match value {
+                Some(secret) => JitterKey::new(secret.as_bytes()),
+                None => JitterKey::random(),
+            };
+            self
+        }
+
+        /// Définit le délai pendant lequel un signalement peut être modifié ou retiré.
+        pub fn set_report_edition_grace_period(&mut self, value: chrono::Duration) -> &mut Self {
+            self.service.report_edition_grace_period = value;
+            self
+        }
     }
 
     #[cfg(feature = "backend")]
@@ -49,33 +229,131 @@ mod backend {
     /// Système principal de Signuis,
     pub struct Signuis {
         pub reporting: Reporting,
+        /// Service d'enrichissement des signalements
+        pub enrichment: Enrichment,
+        /// Service d'attribution des nuisances aux émetteurs
+        pub attribution: Attribution,
+        /// Service du découpage administratif
+        pub territory: Territory,
         /// Service de gestion de l'authentification
         pub auth: Authentication,
         /// Service de gestion des comptes utilisateurs
         pub account: Account,
         /// Répertoires de données
         pub repos: Repository,
+        /// Cache des résultats du répertoire
+        pub cache: QueryCache,
         /// Bus évènementiel
         pub events: EventBus,
+        /// Espace de stockage des fichiers
+        pub storage: Storage,
     }
 
     #[cfg(feature = "backend")]
     impl Signuis {
+        /// Applique les migrations en attente.
+        pub async fn migrate(&self) -> Result<(), crate::error::Error> {
+            self.repos.migrate().await
+        }
+
+        /// Annule les migrations postérieures à la version donnée ; `0` pour tout annuler.
+        pub async fn revert(&self, to: i64) -> Result<(), crate::error::Error> {
+            self.repos.revert(to).await
+        }
+
+        /// Retourne l'état des migrations, par version croissante.
+        pub async fn migration_status(&self) -> Result<Vec<MigrationStatus>, crate::error::Error> {
+            self.repos.migration_status().await
+        }
+
+        /// État des boîtes aux lettres des acteurs des services.
+        fn mailboxes(&self) -> [(&'static str, bool); 8] {
+            [
+                ("repository", self.repos.connected()),
+                ("events", self.events.connected()),
+                ("reporting", self.reporting.connected()),
+                ("enrichment", self.enrichment.connected()),
+                ("attribution", self.attribution.connected()),
+                ("territory", self.territory.connected()),
+                ("authentication", self.auth.connected()),
+                ("account", self.account.connected()),
+            ]
+        }
+
+        /// Sonde les seuls acteurs des services : une base indisponible ne rend
+        /// pas le service mort.
+        pub fn liveness(&self) -> HealthReport {
+            crate::health::liveness(&self.mailboxes())
+        }
+
+        /// Sonde la base de données, le schéma et les acteurs des services.
+        pub async fn health(&self) -> HealthReport {
+            crate::health::check(&self.repos, &self.mailboxes()).await
+        }
+
+        /// Restitue les métriques au format texte de Prometheus.
+        pub async fn metrics(&self) -> String {
+            let pool = self.repos.pool_usage().await.ok().flatten();
+            crate::metrics::render(pool.as_ref())
+        }
+
         pub async fn new(settings: SgSettings) -> Result<Self, crate::error::Error> {
             let events = EventBus::new();
             let repos = Repository::new(&settings.repos).await?;
-            let account = Account::new(repos.clone(), events.clone());
-            let auth = Authentication::new(repos.clone(), events.clone());
-            let reporting = Reporting::new(repos.clone(), events.clone());
+            let cache = QueryCache::new(&settings.service.cache);
+            let storage = Storage::new(&settings.storage)?;
+            let weather = Weather::new(&settings.weather)?;
+            let account = Account::new(
+                repos.clone(),
+                events.clone(),
+                storage.clone(),
+                settings.service.password_policy.clone(),
+                settings.service.password_hashing,
+                settings.service.cursor_key.clone(),
+            );
+            let auth = Authentication::new(repos.clone(), events.clone(), settings.service.clone());
+            let reporting = Reporting::new(
+                repos.clone(),
+                events.clone(),
+                storage.clone(),
+                cache.clone(),
+                settings.service.clone(),
+            );
+            let enrichment = Enrichment::new(repos.clone(), events.clone(), weather);
+            let attribution = Attribution::new(
+                repos.clone(),
+                settings.service.attribution.clone(),
+                settings.service.cursor_key.clone(),
+            );
+            let territory = Territory::new(repos.clone(), settings.service.cursor_key.clone());
 
-            let repos = Repository::new(&settings.repos).await?;
+            CacheInvalidator::new(cache.clone(), events.clone()).start();
+
+            if let Some(interval) = settings.service.statistics_refresh_interval {
+                StatisticsRefresher::new(repos.clone(), cache.clone(), interval).start();
+            }
+
+            if let Some(retention) = settings.service.anonymous_data_retention {
+                RetentionJob::new(
+                    repos.clone(),
+                    storage.clone(),
+                    retention,
+                    std::time::Duration::from_secs(24 * 60 * 60),
+                )
+                .start();
+            }
 
             Ok(Self {
                 reporting,
+                enrichment,
+                attribution,
+                territory,
                 auth,
                 account,
                 repos,
+                cache,
                 events,
+                storage,
             })
         }
     }
@@ -0,0 +1,190 @@
+//! Traitement des fichiers média joints aux signalements.
+
+/// Types MIME acceptés pour les photos.
+pub const ACCEPTED_PHOTO_CONTENT_TYPES: [&str; 2] = ["image/jpeg", "image/png"];
+
+const JPEG_MAGIC: [u8; 3] = [0xFF, 0xD8, 0xFF];
+const PNG_MAGIC: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+const EXIF_HEADER: &[u8] = b"Exif\0\0";
+const GPS_IFD_POINTER_TAG: u16 = 0x8825;
+
+/// Détermine le type MIME d'une image depuis sa signature.
+pub fn sniff_content_type(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(&JPEG_MAGIC) {
+        Some("image/jpeg")
+    } else if data.starts_with(&PNG_MAGIC) {
+        Some("image/png")
+    } else {
+        None
+    }
+}
+
+/// Retourne l'extension de fichier associée au type MIME.
+pub fn extension_of(content_type: &str) -> &'static str {
+    match content_type {
+        "image/jpeg" => "jpg",
+        "image/png" => "png",
+        _ => "bin",
+    }
+}
+
+/// Retire les coordonnées GPS des métadonnées EXIF d'une image.
+///
+/// Pour les JPEG, le répertoire GPS est vidé ; si le bloc EXIF ne peut pas
+/// être analysé, il est retiré en entier. Pour les PNG, le bloc `eXIf` est retiré.
+pub fn strip_gps_metadata(content_type: &str, data: Vec<u8>) -> Vec<u8> {
+    match content_type {
+        "image/jpeg" => strip_jpeg(data),
+        "image/png" => strip_png(data),
+        _ => data,
+    }
+}
+
+fn strip_jpeg(data: Vec<u8>) -> Vec<u8> {
+    if !data.starts_with(&JPEG_MAGIC) {
+        return data;
+    }
+
+    let mut out = Vec::with_capacity(data.len());
+    out.extend_from_slice(&data[..2]);
+
+    let mut pos = 2;
+
+    while pos + 4 <= data.len() && data[pos] == 0xFF {
+        let marker = data[pos + 1];
+
+        // Début des données compressées : le reste est copié tel quel.
+        if marker == 0xDA {
+            break;
+        }
+
+        let length = u16::from_be_bytes([data[pos + 2], data[pos + 3]]) as usize;
+        let end = pos + 2 + length;
+
+        if length < 2 || end > data.len() {
+            break;
+        }
+
+        let payload = &data[pos + 4..end];
+
+        if marker == 0xE1 && payload.starts_with(EXIF_HEADER) {
+            let mut tiff = payload[EXIF_HEADER.len()..].to_vec();
+
+            if clear_gps_ifd(&mut tiff).is_some() {
+                out.extend_from_slice(&data[pos..pos + 4]);
+                out.extend_from_slice(EXIF_HEADER);
+                out.extend_from_slice(&tiff);
+            }
+        } else {
+            out.extend_from_slice(&data[pos..end]);
+        }
+
+        pos = end;
+    }
+
+    out.extend_from_slice(&data[pos..]);
+    out
+}
+
+/// Vide le répertoire GPS d'un bloc TIFF.
+///
+/// Retourne `None` si le bloc est malformé.
+fn clear_gps_ifd(tiff: &mut [u8]) -> Option<()> {
+    let big_endian = match tiff.get(0..2)? {
+        b"MM" => true,
+        b"II" => false,
+        _ => return None,
+    };
+
+    let read_u16 = |buf: &[u8], at: usize| -> Option<u16> {
+        let bytes: [u8; 2] = buf.get(at..at + 2)?.try_into().ok()?;
+        Some(if big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        })
+    };
+
+    let read_u32 = |buf: &[u8], at: usize| -> Option<u32> {
+        let bytes: [u8; 4] = buf.get(at..at + 4)?.try_into().ok()?;
+        Some(if big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    };
+
+    let ifd0 = read_u32(tiff, 4)? as usize;
+    let count = read_u16(tiff, ifd0)? as usize;
+
+    let gps_ifd = (0..count)
+        .map(|i| ifd0 + 2 + i * 12)
+        .find(|&entry| read_u16(tiff, entry) == Some(GPS_IFD_POINTER_TAG))
+        .and_then(|entry| read_u32(tiff, entry + 8));
+
+    let Some(gps_ifd) = gps_ifd.map(|offset| offset as usize) else {
+        return Some(());
+    };
+
+    let gps_count = read_u16(tiff, gps_ifd)? as usize;
+
+    for i in 0..gps_count {
+        let entry = gps_ifd + 2 + i * 12;
+        let field_type = read_u16(tiff, entry + 2)?;
+        let values = read_u32(tiff, entry + 4)? as usize;
+        let size = type_size(field_type).saturating_mul(values);
+
+        if size > 4 {
+            let offset = read_u32(tiff, entry + 8)? as usize;
+            tiff.get_mut(offset..offset.checked_add(size)?)?.fill(0);
+        }
+
+        tiff.get_mut(entry..entry + 12)?.fill(0);
+    }
+
+    tiff.get_mut(gps_ifd..gps_ifd + 2)?.fill(0);
+
+    Some(())
+}
+
+/// Taille en octets d'une valeur TIFF selon son type.
+fn type_size(field_type: u16) -> usize {
+    match field_type {
+        1 | 2 | 6 | 7 => 1,
+        3 | 8 => 2,
+        4 | 9 | 11 => 4,
+        5 | 10 | 12 => 8,
+        _ => 0,
+    }
+}
+
+fn strip_png(data: Vec<u8>) -> Vec<u8> {
+    if !data.starts_with(&PNG_MAGIC) {
+        return data;
+    }
+
+    let mut out = Vec::with_capacity(data.len());
+    out.extend_from_slice(&PNG_MAGIC);
+
+    let mut pos = PNG_MAGIC.len();
+
+    while pos + 8 <= data.len() {
+        let length =
+            u32::from_be_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]) as usize;
+        let end = pos + 12 + length;
+
+        if end > data.len() {
+            break;
+        }
+
+        if &data[pos + 4..pos + 8] != b"eXIf" {
+            out.extend_from_slice(&data[pos..end]);
+        }
+
+        pos = end;
+    }
+
+    out.extend_from_slice(&data[pos..]);
+    out
+}
@@ -0,0 +1,176 @@
+//! Métriques du service, au format d'exposition de Prometheus.
+//!
+//! Les mesures sont propres au processus : elles sont cumulées dans un registre
+//! global, puis restituées par [render].
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::time::Duration;
+
+use crate::repositories::health::PoolUsage;
+
+/// Bornes des intervalles des histogrammes de durée, en secondes.
+const DURATION_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+#[derive(Clone, Default)]
+struct Histogram {
+    /// Nombre cumulé d'observations inférieures ou égales à chaque borne.
+    buckets: [u64; DURATION_BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+impl Histogram {
+    fn observe(&mut self, duration: Duration) {
+        let seconds = duration.as_secs_f64();
+
+        DURATION_BUCKETS
+            .iter()
+            .zip(self.buckets.iter_mut())
+            .filter(|(bound, _)| seconds <= **bound)
+            .for_each(|(_, bucket)| *bucket += 1);
+
+        self.count += 1;
+        self.sum += seconds;
+    }
+
+    fn render(&self, out: &mut String, name: &str, labels: &str) {
+        for (bound, count) in DURATION_BUCKETS.iter().zip(self.buckets) {
+            let _ = writeln!(out, "{name}_bucket{{{labels},le=\"{bound}\"}} {count}");
+        }
+
+        let _ = writeln!(out, "{name}_bucket{{{labels},le=\"+Inf\"}} {}", self.count);
+        let _ = writeln!(out, "{name}_sum{{{labels}}} {}", self.sum);
+        let _ = writeln!(out, "{name}_count{{{labels}}} {}", self.count);
+    }
+}
+
+#[derive(Default)]
+struct Registry {
+    /// Durées des requêtes HTTP, par méthode, route et statut.
+    http_requests: BTreeMap<(String, String, u16), Histogram>,
+    /// Durées des opérations du répertoire, par type d'opération.
+    repository_ops: BTreeMap<&'static str, Histogram>,
+    /// Évènements émis, par type d'évènement.
+    events: BTreeMap<&'static str, u64>,
+    reports_created: u64,
+}
+
+fn registry() -> MutexGuard<'static, Registry> {
+    static REGISTRY: OnceLock<Mutex<Registry>> = OnceLock::new();
+
+    REGISTRY
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+}
+
+/// Nom court d'un type, sans son chemin (ex: `InsertUser`).
+pub fn type_label<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Mesure la durée de traitement d'une requête HTTP.
+pub fn observe_http_request(method: &str, route: &str, status: u16, duration: Duration) {
+    registry()
+        .http_requests
+        .entry((method.to_owned(), route.to_owned(), status))
+        .or_default()
+        .observe(duration);
+}
+
+/// Mesure la durée d'exécution d'une opération du répertoire.
+pub fn observe_repository_op(op: &'static str, duration: Duration) {
+    registry()
+        .repository_ops
+        .entry(op)
+        .or_default()
+        .observe(duration);
+}
+
+/// Compte un évènement émis sur le bus.
+pub fn count_event(event: &'static str) {
+    *registry().events.entry(event).or_default() += 1;
+}
+
+/// Compte un signalement créé.
+pub fn count_report_created() {
+    registry().reports_created += 1;
+}
+
+/// Échappe la valeur d'une étiquette.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Restitue les métriques au format texte de Prometheus.
+///
+/// L'usage de la pool n'est restitué que s'il est connu (ex: pas en mémoire).
+pub fn render(pool: Option<&PoolUsage>) -> String {
+    let registry = registry();
+    let mut out = String::new();
+
+    out.push_str("# HELP signuis_http_request_duration_seconds Durée des requêtes HTTP.\n");
+    out.push_str("# TYPE signuis_http_request_duration_seconds histogram\n");
+    for ((method, route, status), histogram) in &registry.http_requests {
+        let labels = format!(
+            "method=\"{}\",route=\"{}\",status=\"{status}\"",
+            escape(method),
+            escape(route)
+        );
+        histogram.render(&mut out, "signuis_http_request_duration_seconds", &labels);
+    }
+
+    out.push_str("# HELP signuis_repository_op_duration_seconds Durée des opérations.\n");
+    out.push_str("# TYPE signuis_repository_op_duration_seconds histogram\n");
+    for (op, histogram) in &registry.repository_ops {
+        let labels = format!("op=\"{}\"", escape(op));
+        histogram.render(&mut out, "signuis_repository_op_duration_seconds", &labels);
+    }
+
+    if let Some(pool) = pool {
+        out.push_str("# HELP signuis_db_pool_connections Connexions de la pool, par état.\n");
+        out.push_str("# TYPE signuis_db_pool_connections gauge\n");
+        let _ = writeln!(
+            out,
+            "signuis_db_pool_connections{{state=\"idle\"}} {}",
+            pool.idle
+        );
+        let _ = writeln!(
+            out,
+            "signuis_db_pool_connections{{state=\"active\"}} {}",
+            pool.active()
+        );
+
+        out.push_str("# HELP signuis_db_pool_max_connections Taille maximale de la pool.\n");
+        out.push_str("# TYPE signuis_db_pool_max_connections gauge\n");
+        let _ = writeln!(out, "signuis_db_pool_max_connections {}", pool.max);
+    }
+
+    out.push_str("# HELP signuis_events_emitted_total Évènements émis sur le bus.\n");
+    out.push_str("# TYPE signuis_events_emitted_total counter\n");
+    for (event, count) in &registry.events {
+        let event = escape(event);
+        let _ = writeln!(
+            out,
+            "signuis_events_emitted_total{{event=\"{event}\"}} {count}"
+        );
+    }
+
+    out.push_str("# HELP signuis_reports_created_total Signalements créés.\n");
+    out.push_str("# TYPE signuis_reports_created_total counter\n");
+    let _ = writeln!(
+        out,
+        "signuis_reports_created_total {}",
+        registry.reports_created
+    );
+
+    out
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub type AdministrativeAreaId = Uuid;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "sqlx",
+    sqlx(type_name = "varchar", rename_all = "snake_case")
+)]
+/// Échelon d'un découpage administratif.
+pub enum AreaLevel {
+    #[default]
+    Commune,
+    Department,
+}
+
+impl AreaLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Commune => "commune",
+            Self::Department => "department",
+        }
+    }
+}
+
+impl std::str::FromStr for AreaLevel {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "commune" => Ok(Self::Commune),
+            "department" => Ok(Self::Department),
+            _ => Err(format!("échelon administratif inconnu : {value}")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+/// Une zone administrative (commune, département).
+pub struct AdministrativeArea {
+    pub id: AdministrativeAreaId,
+    /// Code officiel de la zone (code INSEE de la commune, numéro du département).
+    pub code: String,
+    pub name: String,
+    pub level: AreaLevel,
+}
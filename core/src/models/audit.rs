@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::user::UserId;
+
+pub type AuditEntryId = Uuid;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "sqlx",
+    sqlx(type_name = "varchar", rename_all = "snake_case")
+)]
+/// Action consignée dans le journal d'audit.
+pub enum AuditAction {
+    /// Export des données personnelles par leur titulaire.
+    PersonalDataExported,
+    /// Suppression d'un compte par son titulaire.
+    AccountDeleted,
+    /// Changement du rôle d'un utilisateur par un administrateur.
+    RoleChanged,
+    /// Désactivation d'un compte par un administrateur.
+    AccountDeactivated,
+    /// Réactivation d'un compte par un administrateur.
+    AccountReactivated,
+    /// Changement de mot de passe imposé par un administrateur.
+    PasswordResetForced,
+    /// Changement de mot de passe au moyen d'un jeton de réinitialisation.
+    PasswordReset,
+    /// Ouverture d'une session au nom d'un utilisateur, pour l'assister.
+    UserImpersonated,
+    /// Fin d'une session d'assistance, à l'initiative de l'administrateur.
+    ImpersonationEnded,
+    /// Import d'un compte depuis un système antérieur, par un administrateur.
+    AccountImported,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+/// Une entrée du journal d'audit.
+pub struct AuditEntry {
+    pub id: AuditEntryId,
+    pub action: AuditAction,
+    /// Utilisateur à l'origine de l'action.
+    pub actor_id: Option<UserId>,
+    /// Utilisateur concerné par l'action.
+    pub subject_id: Option<UserId>,
+    pub details: Option<String>,
+    pub at: DateTime<Utc>,
+}
@@ -1,24 +1,33 @@
-use argon2::PasswordHash;
 use uuid::Uuid;
 
+use crate::crypto::{password_needs_rehash, verify_password};
 use crate::error::Error;
+use crate::models::user::UserRole;
+use crate::password_policy::PasswordHashing;
 
 #[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
 /// Identifiants stockés en BDD.
 pub struct Credential {
     /// Identifiant du compte associé.
     pub id: Uuid,
-    /// Mot de passe hashé.
+    /// Mot de passe hashé, Argon2 ou bcrypt pour les comptes importés.
     pub password: String,
+    /// Un changement de mot de passe a été imposé par un administrateur.
+    pub password_reset_pending: bool,
+    /// L'authentification à deux facteurs est activée.
+    pub second_factor_enabled: bool,
+    /// Rôle du compte, dont les privilèges peuvent exiger un second facteur.
+    pub role: UserRole,
 }
 
 impl Credential {
     /// Vérifie les identifiants par rapport à une soumission.
     pub fn verify(&self, password: &str) -> Result<bool, Error> {
-        let pwd_hash = PasswordHash::new(&self.password).map_err(|_| Error::internal_error())?;
+        verify_password(&self.password, password)
+    }
 
-        Ok(pwd_hash
-            .verify_password(&[&argon2::Argon2::default()], password)
-            .is_ok())
+    /// Vérifie si le mot de passe doit être haché de nouveau avec les paramètres donnés.
+    pub fn needs_rehash(&self, settings: &PasswordHashing) -> bool {
+        password_needs_rehash(&self.password, settings)
     }
 }
@@ -0,0 +1,69 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{
+    nuisance_family::NuisanceFamilyId, nuisance_report::NuisanceReportId,
+    nuisance_type::NuisanceTypeId,
+};
+
+pub type EmitterId = Uuid;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "sqlx",
+    sqlx(type_name = "varchar", rename_all = "snake_case")
+)]
+/// Nature d'un émetteur potentiel de nuisances.
+pub enum EmitterKind {
+    /// Site industriel.
+    Industrial,
+    /// Station d'épuration des eaux usées.
+    WastewaterTreatment,
+    /// Exploitation agricole.
+    Farm,
+    Other,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+/// Un émetteur potentiel de nuisances (site industriel, station d'épuration, élevage...)
+pub struct Emitter {
+    pub id: EmitterId,
+    pub name: String,
+    pub kind: EmitterKind,
+    pub description: String,
+    pub longitude: f64,
+    pub latitude: f64,
+    /// Familles de nuisance susceptibles d'être émises.
+    pub family_ids: Vec<NuisanceFamilyId>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+/// Un émetteur classé comme source possible d'un ensemble de signalements.
+pub struct CandidateEmitter {
+    pub emitter: Emitter,
+    /// Score d'attribution, entre 0 et 1.
+    pub score: f64,
+    /// Nombre de signalements pour lesquels l'émetteur est sous le vent.
+    pub supporting_reports: usize,
+    /// Nombre de signalements analysés pour cet émetteur.
+    pub analysed_reports: usize,
+    /// Distance moyenne aux signalements, en kilomètres.
+    pub mean_distance: f64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+/// Ensemble de signalements soumis à l'analyse d'attribution.
+pub enum ReportSelection {
+    /// Une liste explicite de signalements.
+    Reports(Vec<NuisanceReportId>),
+    /// Un épisode : les signalements observés sur une période, éventuellement
+    /// restreints à une famille ou un type de nuisance.
+    Episode {
+        family_id: Option<NuisanceFamilyId>,
+        type_id: Option<NuisanceTypeId>,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    },
+}
@@ -1,8 +1,17 @@
 #[cfg(feature = "backend")]
 pub mod credential;
 
+pub mod administrative_area;
+pub mod audit;
+pub mod emitter;
 pub mod nuisance_family;
 pub mod nuisance_report;
 pub mod nuisance_type;
+pub mod pagination;
+pub mod passkey;
+pub mod personal_data;
+pub mod second_factor;
 pub mod session;
+pub mod statistics;
 pub mod user;
+pub mod weather;
@@ -2,17 +2,24 @@ use chrono::{DateTime, Utc};
 use sql_gis::types::Point;
 use uuid::Uuid;
 
-use super::nuisance_type::NuisanceType;
+use super::{nuisance_type::NuisanceType, weather::WeatherObservation};
 
 /// Identifier d'un signalemet de nuisance.
 pub type NuisanceReportId = Uuid;
 
+/// Identifiant d'une photo jointe à un signalement.
+pub type NuisanceReportPhotoId = Uuid;
+
 /// Objet pour créer un nouveau signalement de nuisance.
 pub struct CreateNuisanceReport {
     pub type_id: Uuid,
     pub user_id: Option<Uuid>,
     pub location: Point,
     pub intensity: i8,
+    pub description: Option<String>,
+    pub observed_from: Option<DateTime<Utc>>,
+    pub observed_until: Option<DateTime<Utc>>,
+    pub perceived_duration: Option<i32>,
 }
 
 /// Objet représentant un signalement de nuisance.
@@ -22,7 +29,54 @@ pub struct NuisanceReport {
     pub user: Option<ReportUser>,
     pub location: Point,
     pub intensity: i8,
+    /// Description libre de la nuisance.
+    pub description: Option<String>,
+    /// Début de l'observation, distinct de la date de création du signalement.
+    pub observed_from: Option<DateTime<Utc>>,
+    /// Fin de l'observation.
+    pub observed_until: Option<DateTime<Utc>>,
+    /// Durée perçue de la nuisance, en minutes.
+    pub perceived_duration: Option<i32>,
+    pub photos: Vec<NuisanceReportPhoto>,
+    /// Conditions météorologiques lors de l'observation, si elles ont pu être déterminées.
+    pub weather: Option<WeatherObservation>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+/// Position spatio-temporelle d'un signalement.
+pub struct NuisanceReportPosition {
+    pub longitude: f64,
+    pub latitude: f64,
+    /// Début de l'observation, ou à défaut date de création du signalement.
+    pub at: DateTime<Utc>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+/// Photo jointe à un signalement.
+pub struct NuisanceReportPhoto {
+    pub id: NuisanceReportPhotoId,
+    pub report_id: NuisanceReportId,
+    /// Clé du fichier dans l'espace de stockage.
+    pub storage_key: String,
+    pub content_type: String,
+    /// Taille du fichier, en octets.
+    pub size: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Vue à plat d'un signalement, utilisée pour la gestion de ses propres signalements.
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct NuisanceReportSummary {
+    pub id: Uuid,
+    pub type_id: Uuid,
+    pub user_id: Option<Uuid>,
+    #[cfg_attr(feature = "sqlx", sqlx(try_from = "sql_gis::sql_types::PgPoint"))]
+    pub location: Point,
+    pub intensity: i8,
     pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 pub struct NuisanceReportType {
@@ -35,6 +89,7 @@ pub struct NuisanceReportType {
 pub struct ReportUser {
     pub id: Uuid,
     pub name: String,
-    pub email: String,
+    /// Adresse électronique, restituée au seul déclarant et aux administrateurs.
+    pub email: Option<String>,
     pub avatar: Option<String>,
 }
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+/// Demande d'une page d'une liste.
+pub struct PageRequest {
+    /// Curseur de la page précédente, `None` pour la première page.
+    pub cursor: Option<String>,
+    pub limit: u32,
+}
+
+impl Default for PageRequest {
+    fn default() -> Self {
+        Self {
+            cursor: None,
+            limit: 50,
+        }
+    }
+}
+
+impl PageRequest {
+    /// Nombre maximal d'éléments par page.
+    pub const MAX_LIMIT: u32 = 200;
+
+    /// Première page, d'au plus `limit` éléments.
+    pub fn first(limit: u32) -> Self {
+        Self {
+            cursor: None,
+            limit,
+        }
+    }
+
+    /// Page suivant celle désignée par le curseur.
+    pub fn after(cursor: String, limit: u32) -> Self {
+        Self {
+            cursor: Some(cursor),
+            limit,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+/// Une page d'une liste.
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Curseur de la page suivante, `None` s'il s'agit de la dernière.
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    /// Page suivante, s'il en existe une.
+    pub fn next(&self, limit: u32) -> Option<PageRequest> {
+        self.next_cursor
+            .clone()
+            .map(|cursor| PageRequest::after(cursor, limit))
+    }
+}
@@ -0,0 +1,117 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::user::UserId;
+
+pub type PasskeyId = Uuid;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+/// Clé d'accès d'un utilisateur, telle que présentée à son titulaire.
+pub struct Passkey {
+    pub id: PasskeyId,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+/// Clé d'accès enregistrée, nécessaire à la vérification d'une assertion.
+pub struct StoredPasskey {
+    pub id: PasskeyId,
+    pub user_id: UserId,
+    /// Clé publique au format COSE.
+    pub public_key: Vec<u8>,
+    /// Dernier compteur de signatures connu, pour détecter les clés clonées.
+    pub sign_count: i64,
+    /// Un changement de mot de passe a été imposé par un administrateur.
+    pub password_reset_pending: bool,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "sqlx",
+    sqlx(type_name = "varchar", rename_all = "snake_case")
+)]
+/// Cérémonie à laquelle un défi WebAuthn est destiné.
+pub enum ChallengePurpose {
+    Registration,
+    Authentication,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// Partie de confiance, telle que présentée à l'authentificateur.
+pub struct RelyingParty {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// Utilisateur auquel rattacher la clé d'accès.
+pub struct PasskeyUser {
+    /// Identifiant opaque, encodé en base64url.
+    pub id: String,
+    pub name: String,
+    pub display_name: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// Algorithme de clé accepté.
+pub struct PublicKeyParameters {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub alg: i64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// Référence à une clé d'accès existante.
+pub struct PasskeyDescriptor {
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Identifiant d'authentificateur, encodé en base64url.
+    pub id: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// Exigences envers l'authentificateur.
+pub struct AuthenticatorSelection {
+    pub resident_key: String,
+    pub user_verification: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// Options de création d'une clé d'accès, au format JSON de
+/// `PublicKeyCredential.parseCreationOptionsFromJSON`.
+pub struct PasskeyCreationOptions {
+    /// Défi encodé en base64url.
+    pub challenge: String,
+    pub rp: RelyingParty,
+    pub user: PasskeyUser,
+    pub pub_key_cred_params: Vec<PublicKeyParameters>,
+    /// Délai de la cérémonie, en millisecondes.
+    pub timeout: u64,
+    /// Clés déjà enregistrées, que l'authentificateur ne doit pas recréer.
+    pub exclude_credentials: Vec<PasskeyDescriptor>,
+    pub authenticator_selection: AuthenticatorSelection,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// Options d'authentification par clé d'accès, au format JSON de
+/// `PublicKeyCredential.parseRequestOptionsFromJSON`.
+pub struct PasskeyRequestOptions {
+    /// Défi encodé en base64url.
+    pub challenge: String,
+    pub rp_id: String,
+    /// Délai de la cérémonie, en millisecondes.
+    pub timeout: u64,
+    pub user_verification: String,
+}
@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    nuisance_report::NuisanceReportId, nuisance_type::NuisanceTypeId, session::UserSessionId,
+    user::UserProfile,
+};
+
+#[derive(Clone, Serialize, Deserialize)]
+/// Archive des données personnelles d'un utilisateur, remise à sa demande.
+pub struct PersonalDataArchive {
+    pub exported_at: DateTime<Utc>,
+    pub profile: UserProfile,
+    pub sessions: Vec<PersonalSession>,
+    pub reports: Vec<PersonalReport>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+/// Une session de l'utilisateur, sans son jeton.
+pub struct PersonalSession {
+    pub id: UserSessionId,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+/// Un signalement de l'utilisateur, y compris s'il l'a retiré.
+pub struct PersonalReport {
+    pub id: NuisanceReportId,
+    pub type_id: NuisanceTypeId,
+    pub type_label: String,
+    pub longitude: f64,
+    pub latitude: f64,
+    pub intensity: i8,
+    pub description: Option<String>,
+    pub observed_from: Option<DateTime<Utc>>,
+    pub observed_until: Option<DateTime<Utc>>,
+    pub perceived_duration: Option<i32>,
+    /// Clés des photos jointes dans l'espace de stockage.
+    pub photo_keys: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::user::UserId;
+
+/// Nombre de codes de récupération délivrés à l'activation du second facteur.
+pub const RECOVERY_CODES_COUNT: usize = 10;
+
+#[derive(Clone, Serialize, Deserialize)]
+/// Inscription en cours à l'authentification à deux facteurs, à confirmer
+/// avec un premier code.
+pub struct TotpEnrolment {
+    /// Secret encodé en base32, pour une saisie manuelle.
+    pub secret: String,
+    /// URI `otpauth://` de provisionnement.
+    pub provisioning_uri: String,
+    /// Image PNG du QR code de provisionnement, encodée en base64.
+    pub qr_code: String,
+}
+
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+/// Secret TOTP d'un utilisateur.
+pub struct UserTotp {
+    pub secret: String,
+    /// L'inscription a été confirmée.
+    pub enabled: bool,
+}
+
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+/// Authentification en attente du second facteur, dont une tentative vient d'être consommée.
+pub struct ClaimedPendingAuthentication {
+    pub id: Uuid,
+    pub user_id: UserId,
+}
@@ -2,7 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::user::UserRole;
+use super::user::{UserId, UserRole};
 
 pub type UserSessionId = Uuid;
 
@@ -20,6 +20,47 @@ impl Session {
             _ => None,
         }
     }
+
+    /// Vérifie si la session est celle d'un administrateur.
+    ///
+    /// Les privilèges ne sont accordés qu'aux sessions ouvertes avec un second facteur.
+    pub fn is_admin(&self) -> bool {
+        match self {
+            Self::User(session) => {
+                session.user.role == UserRole::Administrator && session.second_factor_verified
+            }
+            _ => false,
+        }
+    }
+
+    /// Administrateur agissant au nom de l'utilisateur, pour une session d'assistance.
+    pub fn impersonator_id(&self) -> Option<UserId> {
+        match self {
+            Self::User(session) => session.impersonator_id,
+            _ => None,
+        }
+    }
+
+    /// Utilisateur à l'origine des actions menées au travers de la session.
+    ///
+    /// Pour une session d'assistance, il s'agit de l'administrateur, et non de
+    /// l'utilisateur assisté.
+    pub fn actor_id(&self) -> Option<UserId> {
+        self.impersonator_id().or(self.user().map(|user| user.id))
+    }
+
+    /// Vérifie que la session satisfait l'exigence de second facteur du rôle de
+    /// son utilisateur.
+    ///
+    /// Une session en attente d'inscription au second facteur ne la satisfait pas.
+    pub fn second_factor_satisfied(&self) -> bool {
+        match self {
+            Self::User(session) => {
+                !session.user.role.requires_second_factor() || session.second_factor_verified
+            }
+            _ => false,
+        }
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -32,6 +73,10 @@ pub struct UserSession {
     pub token: String,
     pub expires_at: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
+    /// La session a été ouverte après vérification d'un second facteur.
+    pub second_factor_verified: bool,
+    /// Administrateur agissant au nom de l'utilisateur, pour l'assister.
+    pub impersonator_id: Option<UserId>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
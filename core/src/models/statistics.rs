@@ -0,0 +1,95 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{
+    administrative_area::AreaLevel, nuisance_family::NuisanceFamilyId,
+    nuisance_type::NuisanceTypeId,
+};
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+/// Granularité temporelle des statistiques.
+pub enum TimeBucket {
+    Hour,
+    #[default]
+    Day,
+    Week,
+}
+
+impl TimeBucket {
+    /// Unité correspondante pour `date_trunc`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Hour => "hour",
+            Self::Day => "day",
+            Self::Week => "week",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+/// Regroupement des statistiques.
+pub enum StatisticsGrouping {
+    #[default]
+    None,
+    Family,
+    Type,
+    /// Par zone administrative de l'échelon demandé.
+    Area,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+/// Critères des statistiques sur les signalements.
+pub struct ReportStatisticsQuery {
+    #[serde(default)]
+    pub bucket: TimeBucket,
+    #[serde(default)]
+    pub group_by: StatisticsGrouping,
+    pub from: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    #[serde(default)]
+    pub family_id: Option<NuisanceFamilyId>,
+    #[serde(default)]
+    pub type_id: Option<NuisanceTypeId>,
+    /// Restreint aux signalements situés dans la zone administrative de ce code.
+    #[serde(default)]
+    pub area_code: Option<String>,
+    /// Échelon des zones administratives, pour le filtre par code et le regroupement
+    /// par zone ; par défaut, la commune.
+    #[serde(default)]
+    pub area_level: Option<AreaLevel>,
+}
+
+impl ReportStatisticsQuery {
+    /// Les statistiques portent-elles sur les zones administratives ?
+    pub fn by_area(&self) -> bool {
+        self.area_code.is_some() || self.group_by == StatisticsGrouping::Area
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+/// Statistiques des signalements pour un intervalle de temps et un groupe.
+pub struct ReportStatistic {
+    /// Début de l'intervalle.
+    pub bucket: DateTime<Utc>,
+    /// Identifiant du groupe (famille, type...), si regroupé.
+    pub group_id: Option<Uuid>,
+    /// Libellé du groupe, si regroupé.
+    pub group_label: Option<String>,
+    pub count: i64,
+    pub mean_intensity: f64,
+    pub max_intensity: i16,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+/// Cellule de la matrice jour de la semaine × heure de la journée.
+pub struct WeeklyProfileCell {
+    /// Jour de la semaine, de 1 (lundi) à 7 (dimanche).
+    pub day_of_week: i32,
+    /// Heure de la journée, de 0 à 23, en heure locale.
+    pub hour: i32,
+    pub count: i64,
+    pub mean_intensity: f64,
+}
@@ -1,6 +1,9 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::pagination::Page;
+
 pub type UserId = uuid::Uuid;
 
 pub struct User {
@@ -11,7 +14,55 @@ pub struct User {
     pub role: UserRole,
 }
 
-#[derive(Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+/// Profil d'un utilisateur, tel que connu de lui-même.
+pub struct UserProfile {
+    pub id: UserId,
+    pub username: String,
+    pub email: String,
+    pub avatar: Option<String>,
+    pub email_verified_at: Option<DateTime<Utc>>,
+    pub registered_at: DateTime<Utc>,
+    pub role: UserRole,
+    pub locale: Option<String>,
+    /// L'authentification à deux facteurs est activée.
+    pub second_factor_enabled: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+/// Compte d'un utilisateur, tel que vu par un administrateur.
+pub struct UserAccount {
+    pub id: UserId,
+    pub username: String,
+    pub email: String,
+    pub role: UserRole,
+    pub activated: bool,
+    pub email_verified_at: Option<DateTime<Utc>>,
+    pub registered_at: DateTime<Utc>,
+    /// Un changement de mot de passe a été imposé et n'a pas encore eu lieu.
+    pub password_reset_pending: bool,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+/// Critères de recherche des comptes utilisateurs.
+pub struct UserSearchQuery {
+    /// Fragment du nom d'utilisateur ou de l'adresse courriel.
+    pub search: Option<String>,
+    pub role: Option<UserRole>,
+    pub activated: Option<bool>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+/// Une page de comptes utilisateurs.
+pub struct UserAccountPage {
+    pub accounts: Page<UserAccount>,
+    /// Nombre total de comptes correspondant aux critères.
+    pub total: i64,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum UserRole {
     User,
     Administrator,
@@ -22,3 +73,17 @@ impl Default for UserRole {
         Self::User
     }
 }
+
+impl UserRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::User => "user",
+            Self::Administrator => "admin",
+        }
+    }
+
+    /// Les privilèges du rôle exigent une authentification à deux facteurs.
+    pub fn requires_second_factor(&self) -> bool {
+        matches!(self, Self::Administrator)
+    }
+}
@@ -0,0 +1,20 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+/// Conditions météorologiques au moment et à l'endroit d'un signalement.
+pub struct WeatherObservation {
+    /// Identifiant de la station ayant relevé les conditions, si connue.
+    pub station: Option<String>,
+    /// Date du relevé.
+    pub observed_at: DateTime<Utc>,
+    /// Direction d'où souffle le vent, en degrés (0 = nord, 90 = est).
+    pub wind_direction: f64,
+    /// Vitesse du vent, en m/s.
+    pub wind_speed: f64,
+    /// Température, en °C.
+    pub temperature: f64,
+    /// Humidité relative, en %.
+    pub humidity: f64,
+}
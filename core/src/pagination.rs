@@ -0,0 +1,168 @@
+//! Pagination par jeu de clés.
+//!
+//! Une page est désignée par la clé de tri du dernier élément de la page
+//! précédente. Cette clé est transmise aux clients sous forme d'un curseur
+//! opaque, signé, afin qu'un curseur altéré soit rejeté.
+use std::sync::Arc;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::Sha256;
+
+use crate::crypto::random_bytes;
+use crate::error::Error;
+use crate::issues::{Issue, Issues};
+use crate::models::pagination::{Page, PageRequest};
+
+#[derive(Clone)]
+/// Clé de signature des curseurs.
+///
+/// Elle doit être partagée par toutes les instances servant une même API.
+pub struct CursorKey(Arc<[u8]>);
+
+impl CursorKey {
+    pub fn new(secret: &[u8]) -> Self {
+        Self(secret.into())
+    }
+
+    /// Clé aléatoire, propre au processus.
+    pub fn random() -> Self {
+        Self(random_bytes(32).into())
+    }
+
+    /// Signe le contenu d'un curseur, pour l'espace de noms donné.
+    fn signature(&self, namespace: &str, payload: &[u8]) -> Hmac<Sha256> {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.0).expect("HMAC accepts keys of any size");
+        mac.update(namespace.as_bytes());
+        mac.update(&[0]);
+        mac.update(payload);
+        mac
+    }
+
+    /// Encode et signe une clé de tri.
+    ///
+    /// L'espace de noms, propre à l'opération, empêche de rejouer le curseur
+    /// d'une liste sur une autre.
+    pub fn encode<K: Serialize>(&self, namespace: &str, key: &K) -> Result<String, Error> {
+        let payload = serde_json::to_vec(key).map_err(Error::internal_error_with_source)?;
+        let signature = self.signature(namespace, &payload).finalize().into_bytes();
+
+        Ok(format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(&payload),
+            URL_SAFE_NO_PAD.encode(signature)
+        ))
+    }
+
+    /// Vérifie la signature d'un curseur et en extrait la clé de tri.
+    pub fn decode<K: DeserializeOwned>(&self, namespace: &str, cursor: &str) -> Result<K, Error> {
+        let (payload, signature_part) = cursor.split_once('.').ok_or_else(invalid_cursor)?;
+        let payload = URL_SAFE_NO_PAD
+            .decode(payload)
+            .map_err(|_| invalid_cursor())?;
+        let expected = URL_SAFE_NO_PAD
+            .decode(signature_part)
+            .map_err(|_| invalid_cursor())?;
+
+        self.signature(namespace, &payload)
+            .verify_slice(&expected)
+            .map_err(|_| invalid_cursor())?;
+
+        serde_json::from_slice(&payload).map_err(|_| invalid_cursor())
+    }
+}
+
+impl std::fmt::Debug for CursorKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("CursorKey(..)")
+    }
+}
+
+fn invalid_cursor() -> Error {
+    Issues::new()
+        .add(Issue::new(
+            "invalid_cursor",
+            "the cursor is invalid",
+            ["cursor"],
+        ))
+        .to_owned()
+        .into_error()
+}
+
+#[derive(Clone, Debug)]
+/// Page demandée à une opération du répertoire, triée selon la clé `K`.
+pub struct Keyset<K> {
+    /// Clé du dernier élément de la page précédente.
+    pub after: Option<K>,
+    pub limit: u32,
+    /// Clé de signature du curseur de la page suivante.
+    pub cursor_key: CursorKey,
+    /// Espace de noms des curseurs, propre à l'opération.
+    pub namespace: &'static str,
+}
+
+impl<K> Keyset<K> {
+    /// Première page, d'au plus `limit` éléments.
+    pub fn first(limit: u32, cursor_key: CursorKey, namespace: &'static str) -> Self {
+        Self {
+            after: None,
+            limit,
+            cursor_key,
+            namespace,
+        }
+    }
+
+    /// Page désignée par une requête, dont le curseur est vérifié.
+    pub fn from_request(
+        request: PageRequest,
+        cursor_key: CursorKey,
+        namespace: &'static str,
+    ) -> Result<Self, Error>
+    where
+        K: DeserializeOwned,
+    {
+        let after = request
+            .cursor
+            .as_deref()
+            .map(|cursor| cursor_key.decode(namespace, cursor))
+            .transpose()?;
+
+        Ok(Self {
+            after,
+            limit: request.limit,
+            cursor_key,
+            namespace,
+        })
+    }
+
+    /// Nombre de lignes à récupérer : une de plus que la page, pour savoir s'il en reste.
+    pub fn fetch_limit(&self) -> i64 {
+        self.limit.clamp(1, PageRequest::MAX_LIMIT) as i64 + 1
+    }
+
+    /// Forme la page à partir des lignes récupérées, avec le curseur de la page suivante.
+    pub fn into_page<T, F>(self, mut rows: Vec<T>, key: F) -> Result<Page<T>, Error>
+    where
+        K: Serialize,
+        F: Fn(&T) -> K,
+    {
+        let limit = (self.fetch_limit() - 1) as usize;
+
+        let next_cursor = if rows.len() > limit {
+            rows.truncate(limit);
+            rows.last()
+                .map(|last| self.cursor_key.encode(self.namespace, &key(last)))
+                .transpose()?
+        } else {
+            None
+        };
+
+        Ok(Page {
+            items: rows,
+            next_cursor,
+        })
+    }
+}
@@ -0,0 +1,168 @@
+//! Politique de choix des mots de passe.
+//!
+//! La robustesse est estimée hors ligne, et la recherche dans les fuites de
+//! données se fait contre une copie locale des listes par préfixe d'empreinte
+//! (k-anonymat), telle que produite par `haveibeenpwned-downloader` : le mot
+//! de passe ne quitte jamais le serveur.
+
+use std::path::PathBuf;
+
+#[derive(Clone, Debug)]
+/// Règles imposées aux nouveaux mots de passe.
+pub struct PasswordPolicy {
+    /// Nombre minimal de caractères.
+    pub min_length: usize,
+    /// Entropie minimale estimée, en bits.
+    pub min_entropy: f64,
+    /// Refuse les mots de passe contenant le nom d'utilisateur ou l'adresse courriel.
+    pub forbid_personal_data: bool,
+    /// Répertoire des listes de mots de passe compromis, un fichier `<PRÉFIXE>.txt`
+    /// par préfixe de 5 caractères de l'empreinte SHA-1 ; `None` pour ne pas vérifier.
+    pub breached_passwords: Option<PathBuf>,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 10,
+            min_entropy: 45.0,
+            forbid_personal_data: true,
+            breached_passwords: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// Paramètres de hachage des mots de passe (Argon2id).
+///
+/// Les empreintes produites avec des paramètres plus faibles sont recalculées
+/// à la connexion suivante de leur titulaire.
+pub struct PasswordHashing {
+    /// Mémoire utilisée, en Kio.
+    pub memory_cost: u32,
+    /// Nombre de passes.
+    pub time_cost: u32,
+    /// Degré de parallélisme.
+    pub parallelism: u32,
+}
+
+impl PasswordHashing {
+    /// Paramètres recommandés par l'OWASP, qui sont aussi ceux par défaut d'Argon2.
+    pub const DEFAULT: Self = Self {
+        memory_cost: 19 * 1024,
+        time_cost: 2,
+        parallelism: 1,
+    };
+}
+
+impl Default for PasswordHashing {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Estime l'entropie d'un mot de passe, en bits.
+///
+/// L'estimation repose sur la taille de l'alphabet employé ; les répétitions et
+/// les suites de caractères (`aaa`, `123`, `abc`) ne comptent que pour moitié.
+pub fn estimate_entropy(password: &str) -> f64 {
+    let chars: Vec<char> = password.chars().collect();
+
+    let alphabets: [(fn(&char) -> bool, u32); 5] = [
+        (char::is_ascii_lowercase, 26),
+        (char::is_ascii_uppercase, 26),
+        (char::is_ascii_digit, 10),
+        (|c| c.is_ascii_punctuation() || *c == ' ', 33),
+        (|c| !c.is_ascii(), 100),
+    ];
+
+    let pool: u32 = alphabets
+        .iter()
+        .filter(|(belongs, _)| chars.iter().any(belongs))
+        .map(|(_, size)| size)
+        .sum();
+
+    if pool == 0 {
+        return 0.0;
+    }
+
+    let length: f64 = chars
+        .iter()
+        .enumerate()
+        .map(|(i, c)| match i.checked_sub(1).map(|prev| chars[prev]) {
+            Some(prev) if (*c as i64 - prev as i64).abs() <= 1 => 0.5,
+            _ => 1.0,
+        })
+        .sum();
+
+    length * f64::from(pool).log2()
+}
+
+/// Vérifie si le mot de passe contient l'une des données personnelles, sans
+/// tenir compte de la casse.
+///
+/// Pour une adresse courriel, sa partie locale est également recherchée ; les
+/// fragments de moins de 3 caractères sont ignorés.
+pub fn contains_personal_data(password: &str, personal_data: &[&str]) -> bool {
+    let password = password.to_lowercase();
+
+    personal_data
+        .iter()
+        .flat_map(|value| {
+            let local_part = value.split_once('@').map(|(local, _)| local);
+            std::iter::once(*value).chain(local_part)
+        })
+        .map(str::to_lowercase)
+        .filter(|fragment| fragment.chars().count() >= 3)
+        .any(|fragment| password.contains(&fragment))
+}
+
+#[cfg(feature = "backend")]
+pub use breached::BreachedPasswords;
+
+#[cfg(feature = "backend")]
+mod breached {
+    use std::io::ErrorKind;
+    use std::path::PathBuf;
+
+    use sha1::{Digest, Sha1};
+
+    use crate::error::Error;
+
+    /// Copie locale des listes de mots de passe compromis, par préfixe d'empreinte.
+    ///
+    /// Chaque fichier `<PRÉFIXE>.txt` contient une ligne `<SUFFIXE>:<OCCURRENCES>`
+    /// par empreinte SHA-1 commençant par ce préfixe.
+    pub struct BreachedPasswords {
+        directory: PathBuf,
+    }
+
+    impl BreachedPasswords {
+        pub fn new<P: Into<PathBuf>>(directory: P) -> Self {
+            Self {
+                directory: directory.into(),
+            }
+        }
+
+        /// Vérifie si le mot de passe figure dans les listes.
+        ///
+        /// Un préfixe absent de la copie locale est réputé sans mot de passe compromis.
+        pub async fn contains(&self, password: &str) -> Result<bool, Error> {
+            let hash = hex::encode_upper(Sha1::digest(password.as_bytes()));
+            let (prefix, suffix) = hash.split_at(5);
+
+            let path = self.directory.join(format!("{prefix}.txt"));
+
+            let range = match tokio::fs::read_to_string(path).await {
+                Ok(range) => range,
+                Err(err) if err.kind() == ErrorKind::NotFound => return Ok(false),
+                Err(_) => return Err(Error::internal_error()),
+            };
+
+            Ok(range.lines().any(|line| {
+                line.split_once(':')
+                    .is_some_and(|(candidate, _)| candidate.trim().eq_ignore_ascii_case(suffix))
+            }))
+        }
+    }
+}
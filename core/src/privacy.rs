@@ -0,0 +1,196 @@
+//! Protection de la vie privée des déclarants dans les données restituées.
+//!
+//! Les règles sont portées par une [PrivacyPolicy], déterminée une fois pour
+//! toutes à partir de la session, puis exigée par les opérations de lecture.
+
+use std::sync::Arc;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::crypto::random_bytes;
+use crate::models::session::Session;
+
+/// Longueur d'un degré de latitude, en mètres.
+const METERS_PER_DEGREE: f64 = 111_320.0;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+/// Dégradation appliquée aux coordonnées des signalements.
+pub enum LocationFuzzing {
+    /// Coordonnées exactes.
+    Exact,
+    /// Coordonnées ramenées au centre de leur maille.
+    #[default]
+    Snap,
+    /// Coordonnées déplacées aléatoirement dans leur maille.
+    ///
+    /// Le déplacement est dérivé de l'identifiant du signalement, afin que des
+    /// lectures répétées ne permettent pas d'en moyenner l'effet.
+    Jitter,
+}
+
+#[derive(Clone)]
+/// Clé secrète dont est dérivé le déplacement des coordonnées.
+///
+/// Sans elle, le déplacement se déduirait de l'identifiant du signalement ;
+/// elle doit être partagée par toutes les instances servant une même API.
+pub struct JitterKey(Arc<[u8]>);
+
+impl JitterKey {
+    pub fn new(secret: &[u8]) -> Self {
+        Self(secret.into())
+    }
+
+    /// Clé aléatoire, propre au processus.
+    pub fn random() -> Self {
+        Self(random_bytes(32).into())
+    }
+
+    /// Position relative (entre 0 et 1 sur chaque axe) d'un signalement dans sa maille.
+    pub fn offset(&self, report_id: Uuid) -> (f64, f64) {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.0).expect("HMAC accepts keys of any size");
+        mac.update(report_id.as_bytes());
+        let digest = mac.finalize().into_bytes();
+
+        // 53 bits, la précision d'un f64, pour une répartition uniforme sur [0, 1[
+        let unit = |bytes: &[u8]| {
+            let value = u64::from_be_bytes(bytes.try_into().expect("8 bytes"));
+            (value >> 11) as f64 / (1u64 << 53) as f64
+        };
+
+        (unit(&digest[0..8]), unit(&digest[8..16]))
+    }
+}
+
+impl std::fmt::Debug for JitterKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("JitterKey(..)")
+    }
+}
+
+#[derive(Clone)]
+/// Paramètres de protection de la vie privée.
+pub struct PrivacySettings {
+    /// Dégradation des coordonnées pour les sessions non privilégiées.
+    pub location_fuzzing: LocationFuzzing,
+    /// Taille de la maille, en mètres.
+    pub grid_size: f64,
+    /// Nombre minimal de signalements pour qu'un agrégat soit restitué (k-anonymat).
+    pub min_aggregate_size: i64,
+    /// Clé du déplacement des coordonnées.
+    pub jitter_key: JitterKey,
+}
+
+impl Default for PrivacySettings {
+    fn default() -> Self {
+        Self {
+            location_fuzzing: LocationFuzzing::default(),
+            grid_size: 250.0,
+            min_aggregate_size: 5,
+            jitter_key: JitterKey::random(),
+        }
+    }
+}
+
+#[derive(Clone)]
+/// Règles de confidentialité applicables à une session.
+///
+/// Les administrateurs voient les données complètes ; un déclarant voit
+/// ses propres signalements sans dégradation.
+pub struct PrivacyPolicy {
+    location_fuzzing: LocationFuzzing,
+    grid_size: f64,
+    min_aggregate_size: i64,
+    jitter_key: JitterKey,
+    viewer_id: Option<Uuid>,
+    privileged: bool,
+}
+
+impl PrivacyPolicy {
+    pub fn new(settings: &PrivacySettings, session: &Session) -> Self {
+        Self {
+            location_fuzzing: settings.location_fuzzing,
+            grid_size: settings.grid_size,
+            min_aggregate_size: settings.min_aggregate_size,
+            jitter_key: settings.jitter_key.clone(),
+            viewer_id: session.user().map(|user| user.id),
+            privileged: session.is_admin(),
+        }
+    }
+
+    /// Règles sans aucune restriction, pour les traitements internes.
+    pub fn unrestricted() -> Self {
+        Self {
+            location_fuzzing: LocationFuzzing::Exact,
+            grid_size: 0.0,
+            min_aggregate_size: 0,
+            jitter_key: JitterKey::new(&[]),
+            viewer_id: None,
+            privileged: true,
+        }
+    }
+
+    /// La session a-t-elle accès aux données complètes du propriétaire ?
+    fn is_trusted_for(&self, owner_id: Option<Uuid>) -> bool {
+        self.privileged || (owner_id.is_some() && owner_id == self.viewer_id)
+    }
+
+    /// Coordonnées (longitude, latitude) d'un signalement telles que restituées.
+    pub fn location(
+        &self,
+        report_id: Uuid,
+        owner_id: Option<Uuid>,
+        longitude: f64,
+        latitude: f64,
+    ) -> (f64, f64) {
+        if self.is_trusted_for(owner_id) || self.grid_size <= 0.0 {
+            return (longitude, latitude);
+        }
+
+        match self.location_fuzzing {
+            LocationFuzzing::Exact => (longitude, latitude),
+            LocationFuzzing::Snap => fuzz_location(longitude, latitude, self.grid_size, (0.5, 0.5)),
+            LocationFuzzing::Jitter => {
+                let offset = self.jitter_key.offset(report_id);
+                fuzz_location(longitude, latitude, self.grid_size, offset)
+            }
+        }
+    }
+
+    /// Adresse électronique du déclarant, si la session peut la voir.
+    pub fn email(&self, owner_id: Option<Uuid>, email: Option<String>) -> Option<String> {
+        email.filter(|_| self.is_trusted_for(owner_id))
+    }
+
+    /// Nombre minimal de signalements pour qu'un agrégat soit restitué.
+    pub fn min_aggregate_size(&self) -> i64 {
+        if self.privileged {
+            0
+        } else {
+            self.min_aggregate_size
+        }
+    }
+}
+
+/// Place un point dans sa maille de `grid_size` mètres, à la position relative
+/// `offset` (entre 0 et 1 sur chaque axe ; `(0.5, 0.5)` correspond au centre).
+pub fn fuzz_location(
+    longitude: f64,
+    latitude: f64,
+    grid_size: f64,
+    offset: (f64, f64),
+) -> (f64, f64) {
+    let lat_step = grid_size / METERS_PER_DEGREE;
+    let lat_cell = (latitude / lat_step).floor();
+    let fuzzed_latitude = ((lat_cell + offset.1) * lat_step).clamp(-90.0, 90.0);
+
+    // La maille en longitude est élargie avec la latitude pour conserver sa taille au sol.
+    let center_latitude = (lat_cell + 0.5) * lat_step;
+    let lon_step = lat_step / center_latitude.to_radians().cos().max(0.01);
+    let lon_cell = (longitude / lon_step).floor();
+    let fuzzed_longitude = (lon_cell + offset.0) * lon_step;
+
+    (fuzzed_longitude, fuzzed_latitude)
+}
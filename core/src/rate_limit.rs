@@ -0,0 +1,64 @@
+//! Limitation de la fréquence des requêtes.
+//!
+//! Les compteurs sont tenus en mémoire par l'acteur du service concerné ; ils ne
+//! sont donc pas partagés entre plusieurs instances du service.
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::error::Error;
+use crate::issues::{Issue, Issues};
+
+/// Limite le nombre d'actions par clé sur une fenêtre de temps fixe.
+pub struct RateLimiter<K> {
+    /// Nombre d'actions permises par fenêtre, `None` si la limitation est désactivée.
+    limit: Option<u32>,
+    window: Duration,
+    /// Début de la fenêtre en cours, et nombre d'actions comptées, par clé.
+    windows: HashMap<K, (DateTime<Utc>, u32)>,
+}
+
+impl<K: Eq + Hash> RateLimiter<K> {
+    pub fn new(limit: Option<u32>, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            windows: HashMap::default(),
+        }
+    }
+
+    /// Compte une action pour la clé.
+    ///
+    /// Retourne `false` si la limite de la fenêtre en cours est déjà atteinte.
+    pub fn acquire(&mut self, key: K, now: DateTime<Utc>) -> bool {
+        let Some(limit) = self.limit else {
+            return true;
+        };
+
+        // les fenêtres échues sont oubliées, pour borner la mémoire occupée
+        let window = self.window;
+        self.windows.retain(|_, (start, _)| now - *start < window);
+
+        let (_, count) = self.windows.entry(key).or_insert((now, 0));
+
+        if *count >= limit {
+            return false;
+        }
+
+        *count += 1;
+        true
+    }
+}
+
+/// Erreur renvoyée lorsque la limite est atteinte.
+pub fn rate_limited() -> Error {
+    Issues::new()
+        .add(Issue::new(
+            "rate_limited",
+            "Trop de tentatives, réessayez plus tard",
+            Vec::<String>::default(),
+        ))
+        .to_owned()
+        .into_error()
+}
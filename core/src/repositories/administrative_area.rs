@@ -0,0 +1,246 @@
+use crate::{
+    error::Error,
+    models::{
+        administrative_area::{AdministrativeArea, AdministrativeAreaId, AreaLevel},
+        nuisance_report::NuisanceReportId,
+        pagination::Page,
+    },
+    pagination::Keyset,
+};
+
+use super::memory::{self, AdministrativeAreaRecord, MemoryTables};
+use super::RepositoryOp;
+
+/// Importe (ou met à jour) une zone, puis recalcule les zones des signalements
+/// qu'elle contient.
+const UPSERT_ADMINISTRATIVE_AREA_QUERY: &str = r#"
+    WITH area AS (
+        INSERT INTO administrative_areas (code, name, level, geometry)
+            VALUES ($1, $2, $3, ST_Multi(ST_SetSRID(ST_Force2D(ST_GeomFromGeoJSON($4)), 4326)))
+        ON CONFLICT (level, code) DO UPDATE
+            SET name = EXCLUDED.name, geometry = EXCLUDED.geometry
+        RETURNING id, geometry
+    ), unlinked AS (
+        DELETE FROM nuisance_report_areas AS link
+            USING area, nuisance_reports AS report
+            WHERE link.area_id = area.id
+                AND report.id = link.report_id
+                AND NOT ST_Covers(area.geometry, ST_SetSRID(report.location, 4326))
+    ), linked AS (
+        INSERT INTO nuisance_report_areas (report_id, area_id)
+            SELECT report.id, area.id
+            FROM area
+            INNER JOIN nuisance_reports AS report
+                ON ST_Covers(area.geometry, ST_SetSRID(report.location, 4326))
+        ON CONFLICT DO NOTHING
+    )
+    SELECT id FROM area
+"#;
+
+const LINK_NUISANCE_REPORT_AREAS_QUERY: &str = r#"
+    INSERT INTO nuisance_report_areas (report_id, area_id)
+        SELECT report.id, area.id
+        FROM nuisance_reports AS report
+        INNER JOIN administrative_areas AS area
+            ON ST_Covers(area.geometry, ST_SetSRID(report.location, 4326))
+        WHERE report.id = $1
+    ON CONFLICT DO NOTHING
+"#;
+
+const FETCH_ADMINISTRATIVE_AREAS_QUERY: &str = r#"
+    SELECT id, code, name, level
+    FROM administrative_areas
+    WHERE ($1::varchar IS NULL OR level = $1)
+        AND ($2::varchar IS NULL OR (level, code) > ($2, $3))
+    ORDER BY level, code
+    LIMIT $4
+"#;
+
+/// Insère une zone administrative, ou la met à jour si son code est déjà connu
+/// pour cet échelon.
+pub struct UpsertAdministrativeArea {
+    pub code: String,
+    pub name: String,
+    pub level: AreaLevel,
+    /// Géométrie GeoJSON sérialisée.
+    pub geometry: String,
+}
+
+impl RepositoryOp for UpsertAdministrativeArea {
+    type Return = AdministrativeAreaId;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let (id,): (AdministrativeAreaId,) = sqlx::query_as(UPSERT_ADMINISTRATIVE_AREA_QUERY)
+                .bind(self.code)
+                .bind(self.name)
+                .bind(self.level)
+                .bind(self.geometry)
+                .fetch_one(executor)
+                .await?;
+
+            Ok(id)
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        let polygons = memory::parse_polygons(&self.geometry)?;
+
+        let existing = tables
+            .administrative_areas
+            .iter_mut()
+            .find(|record| record.area.level == self.level && record.area.code == self.code);
+
+        let record = match existing {
+            Some(record) => {
+                record.area.name = self.name;
+                record.polygons = polygons;
+                record.clone()
+            }
+            None => {
+                let record = AdministrativeAreaRecord {
+                    area: AdministrativeArea {
+                        id: uuid::Uuid::new_v4(),
+                        code: self.code,
+                        name: self.name,
+                        level: self.level,
+                    },
+                    polygons,
+                };
+                tables.administrative_areas.push(record.clone());
+                record
+            }
+        };
+
+        let id = record.area.id;
+        let covered: Vec<NuisanceReportId> = tables
+            .nuisance_reports
+            .iter()
+            .filter(|report| record.covers(report.longitude, report.latitude))
+            .map(|report| report.id)
+            .collect();
+
+        tables
+            .nuisance_report_areas
+            .retain(|(_, area_id)| *area_id != id);
+        tables
+            .nuisance_report_areas
+            .extend(covered.into_iter().map(|report_id| (report_id, id)));
+
+        Ok(id)
+    }
+}
+
+/// Rattache un signalement aux zones administratives qui le contiennent.
+pub struct LinkNuisanceReportAreas(pub NuisanceReportId);
+
+impl RepositoryOp for LinkNuisanceReportAreas {
+    type Return = ();
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            sqlx::query(LINK_NUISANCE_REPORT_AREAS_QUERY)
+                .bind(self.0)
+                .execute(executor)
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        let Some(report) = tables
+            .nuisance_reports
+            .iter()
+            .find(|report| report.id == self.0)
+        else {
+            return Ok(());
+        };
+
+        let covering: Vec<AdministrativeAreaId> = tables
+            .administrative_areas
+            .iter()
+            .filter(|record| record.covers(report.longitude, report.latitude))
+            .map(|record| record.area.id)
+            .filter(|area_id| !tables.nuisance_report_areas.contains(&(self.0, *area_id)))
+            .collect();
+
+        tables
+            .nuisance_report_areas
+            .extend(covering.into_iter().map(|area_id| (self.0, area_id)));
+
+        Ok(())
+    }
+}
+
+/// Récupère une page des zones administratives, éventuellement d'un seul échelon.
+pub struct FetchAdministrativeAreas {
+    pub level: Option<AreaLevel>,
+    pub page: Keyset<(AreaLevel, String)>,
+}
+
+impl FetchAdministrativeAreas {
+    /// Espace de noms des curseurs de la liste.
+    pub const CURSOR_NAMESPACE: &'static str = "administrative_areas";
+}
+
+impl RepositoryOp for FetchAdministrativeAreas {
+    type Return = Page<AdministrativeArea>;
+
+    const READ_ONLY: bool = true;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let (level, code) = self.page.after.clone().unzip();
+
+            let areas: Vec<AdministrativeArea> = sqlx::query_as(FETCH_ADMINISTRATIVE_AREAS_QUERY)
+                .bind(self.level)
+                .bind(level)
+                .bind(code)
+                .bind(self.page.fetch_limit())
+                .fetch_all(executor)
+                .await?;
+
+            self.page
+                .into_page(areas, |area| (area.level, area.code.clone()))
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        let mut areas: Vec<AdministrativeArea> = tables
+            .administrative_areas
+            .iter()
+            .filter(|record| self.level.map_or(true, |level| record.area.level == level))
+            .filter(|record| {
+                self.page.after.as_ref().map_or(true, |after| {
+                    (record.area.level.as_str(), &record.area.code) > (after.0.as_str(), &after.1)
+                })
+            })
+            .map(|record| record.area.clone())
+            .collect();
+
+        areas.sort_by(|a, b| (a.level.as_str(), &a.code).cmp(&(b.level.as_str(), &b.code)));
+        areas.truncate(self.page.fetch_limit() as usize);
+
+        self.page
+            .into_page(areas, |area| (area.level, area.code.clone()))
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{
+    error::Error,
+    models::{
+        audit::{AuditAction, AuditEntry, AuditEntryId},
+        user::UserId,
+    },
+};
+
+use super::memory::MemoryTables;
+use super::RepositoryOp;
+
+const INSERT_AUDIT_ENTRY_QUERY: &str = r#"
+    INSERT INTO audit_entries (action, actor_id, subject_id, details)
+        VALUES ($1, $2, $3, $4)
+    RETURNING id
+"#;
+
+/// Consigne une action dans le journal d'audit.
+pub struct InsertAuditEntry {
+    pub action: AuditAction,
+    pub actor_id: Option<UserId>,
+    pub subject_id: Option<UserId>,
+    pub details: Option<String>,
+}
+
+impl RepositoryOp for InsertAuditEntry {
+    type Return = AuditEntryId;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let (id,): (AuditEntryId,) = sqlx::query_as(INSERT_AUDIT_ENTRY_QUERY)
+                .bind(self.action)
+                .bind(self.actor_id)
+                .bind(self.subject_id)
+                .bind(self.details)
+                .fetch_one(executor)
+                .await?;
+
+            Ok(id)
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        let id = uuid::Uuid::new_v4();
+
+        tables.audit_entries.push(AuditEntry {
+            id,
+            action: self.action,
+            actor_id: self.actor_id,
+            subject_id: self.subject_id,
+            details: self.details,
+            at: chrono::Utc::now(),
+        });
+
+        Ok(id)
+    }
+}
@@ -1,11 +1,21 @@
-use sql_builder::helpers::QuerySpecification;
-use sql_builder::{bind, eq, id, or, prelude::*, select, select_columns};
-
 use crate::error::Error;
 use crate::models::credential::Credential;
+use crate::models::user::UserId;
 
+use super::memory::{MemoryTables, UserRecord};
 use super::RepositoryOp;
 
+const CREDENTIAL_BY_NAME_OR_EMAIL_QUERY: &str = r#"
+    SELECT id, password,
+        password_reset_token IS NOT NULL AS password_reset_pending,
+        totp_enabled_at IS NOT NULL AS second_factor_enabled,
+        role
+    FROM users
+    WHERE (username = $1 OR email = $1) AND activated AND password IS NOT NULL
+"#;
+
+/// Récupère les identifiants d'un compte actif à partir de son nom d'utilisateur
+/// ou de son adresse courriel.
 pub struct MaybeFindOneCredentialByNameOrEmail(pub String);
 
 impl RepositoryOp for MaybeFindOneCredentialByNameOrEmail {
@@ -19,21 +29,54 @@ impl RepositoryOp for MaybeFindOneCredentialByNameOrEmail {
         E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
     {
         Box::pin(async move {
-            let (sql, args) = select(select_columns!(id!(id), id!(password)))
-                .from(TABLE)
-                .r#where(or(
-                    eq(id!(username), bind!(&self.0)),
-                    eq(id!(email), bind!(&self.0)),
-                ))
-                .build::<sqlx::Postgres>();
-
-            let credential: Option<Credential> = sqlx::query_as_with(&sql, args)
+            let credential: Option<Credential> = sqlx::query_as(CREDENTIAL_BY_NAME_OR_EMAIL_QUERY)
+                .bind(self.0)
                 .fetch_optional(executor)
                 .await?;
 
             Ok(credential)
         })
     }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        Ok(tables
+            .users
+            .iter()
+            .find(|user| (user.username == self.0 || user.email == self.0) && user.activated)
+            .and_then(UserRecord::credential))
+    }
 }
 
-const TABLE: sql_builder::identifier::IdentifierRef<'static> = id!(users);
+/// Récupère les identifiants d'un utilisateur.
+pub struct MaybeFindOneCredentialById(pub UserId);
+
+impl RepositoryOp for MaybeFindOneCredentialById {
+    type Return = Option<Credential>;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let credential: Option<Credential> = sqlx::query_as(
+                "SELECT id, password,
+                    password_reset_token IS NOT NULL AS password_reset_pending,
+                    totp_enabled_at IS NOT NULL AS second_factor_enabled,
+                    role
+                FROM users WHERE id = $1 AND password IS NOT NULL",
+            )
+            .bind(self.0)
+            .fetch_optional(executor)
+            .await?;
+
+            Ok(credential)
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        Ok(tables.user(self.0).and_then(UserRecord::credential))
+    }
+}
@@ -0,0 +1,220 @@
+use sql_gis::sql_types::PgPoint;
+use uuid::Uuid;
+
+use crate::{
+    error::Error,
+    models::{
+        emitter::{Emitter, EmitterId, EmitterKind, ReportSelection},
+        nuisance_family::NuisanceFamilyId,
+        nuisance_report::NuisanceReportId,
+        pagination::Page,
+    },
+    pagination::Keyset,
+};
+
+use super::RepositoryOp;
+
+const INSERT_EMITTER_QUERY: &str = r#"
+    WITH emitter AS (
+        INSERT INTO emitters (name, kind, description, location)
+            VALUES ($1, $2, $3, $4)
+        RETURNING id
+    ), families AS (
+        INSERT INTO emitter_nuisance_families (emitter_id, family_id)
+            SELECT emitter.id, family_id FROM emitter, unnest($5::uuid[]) AS family_id
+    )
+    SELECT id FROM emitter
+"#;
+
+const FETCH_EMITTERS_QUERY: &str = r#"
+    SELECT
+        emitter.id, emitter.name, emitter.kind, emitter.description,
+        ST_X(emitter.location) AS longitude, ST_Y(emitter.location) AS latitude,
+        COALESCE(
+            array_agg(family.family_id) FILTER (WHERE family.family_id IS NOT NULL),
+            '{}'
+        ) AS family_ids
+    FROM emitters AS emitter
+    LEFT JOIN emitter_nuisance_families AS family ON family.emitter_id = emitter.id
+    WHERE $2::varchar IS NULL OR (emitter.name, emitter.id) > ($2, $3)
+    GROUP BY emitter.id
+    HAVING $1::uuid[] IS NULL OR array_agg(family.family_id) && $1::uuid[]
+    ORDER BY emitter.name, emitter.id
+    LIMIT $4
+"#;
+
+const FETCH_ATTRIBUTION_OBSERVATIONS_QUERY: &str = r#"
+    SELECT
+        report.id AS report_id, kind.family_id,
+        ST_X(report.location) AS longitude, ST_Y(report.location) AS latitude,
+        report.intensity, weather.wind_direction, weather.wind_speed
+    FROM nuisance_reports AS report
+    INNER JOIN nuisance_types AS kind ON kind.id = report.type_id
+    LEFT JOIN nuisance_report_weather AS weather ON weather.report_id = report.id
+    WHERE report.deleted_at IS NULL
+        AND ($1::uuid[] IS NULL OR report.id = ANY($1))
+        AND ($2::uuid IS NULL OR kind.family_id = $2)
+        AND ($3::uuid IS NULL OR report.type_id = $3)
+        AND ($4::timestamptz IS NULL OR COALESCE(report.observed_from, report.created_at) >= $4)
+        AND ($5::timestamptz IS NULL OR COALESCE(report.observed_from, report.created_at) <= $5)
+"#;
+
+/// Enregistre un émetteur potentiel dans le registre.
+pub struct InsertEmitter {
+    pub name: String,
+    pub kind: EmitterKind,
+    pub description: String,
+    pub location: PgPoint,
+    pub family_ids: Vec<NuisanceFamilyId>,
+}
+
+impl RepositoryOp for InsertEmitter {
+    type Return = EmitterId;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let (id,): (EmitterId,) = sqlx::query_as(INSERT_EMITTER_QUERY)
+                .bind(self.name)
+                .bind(self.kind)
+                .bind(self.description)
+                .bind(self.location)
+                .bind(self.family_ids)
+                .fetch_one(executor)
+                .await?;
+
+            Ok(id)
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct EmitterRow {
+    id: EmitterId,
+    name: String,
+    kind: EmitterKind,
+    description: Option<String>,
+    longitude: f64,
+    latitude: f64,
+    family_ids: Vec<Uuid>,
+}
+
+impl From<EmitterRow> for Emitter {
+    fn from(row: EmitterRow) -> Self {
+        Self {
+            id: row.id,
+            name: row.name,
+            kind: row.kind,
+            description: row.description.unwrap_or_default(),
+            longitude: row.longitude,
+            latitude: row.latitude,
+            family_ids: row.family_ids,
+        }
+    }
+}
+
+/// Récupère une page des émetteurs du registre, par ordre alphabétique.
+pub struct FetchEmitters {
+    /// Restreint aux émetteurs associés à l'une de ces familles de nuisance.
+    pub family_ids: Option<Vec<NuisanceFamilyId>>,
+    pub page: Keyset<(String, EmitterId)>,
+}
+
+impl FetchEmitters {
+    /// Espace de noms des curseurs de la liste.
+    pub const CURSOR_NAMESPACE: &'static str = "emitters";
+}
+
+impl RepositoryOp for FetchEmitters {
+    type Return = Page<Emitter>;
+
+    const READ_ONLY: bool = true;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let (name, id) = self.page.after.clone().unzip();
+
+            let rows: Vec<EmitterRow> = sqlx::query_as(FETCH_EMITTERS_QUERY)
+                .bind(self.family_ids)
+                .bind(name)
+                .bind(id)
+                .bind(self.page.fetch_limit())
+                .fetch_all(executor)
+                .await?;
+
+            let emitters = rows.into_iter().map(Emitter::from).collect();
+
+            self.page
+                .into_page(emitters, |emitter| (emitter.name.clone(), emitter.id))
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+/// Données d'un signalement nécessaires à l'analyse d'attribution.
+pub struct AttributionObservation {
+    pub report_id: NuisanceReportId,
+    pub family_id: NuisanceFamilyId,
+    pub longitude: f64,
+    pub latitude: f64,
+    pub intensity: i8,
+    /// Direction d'où souffle le vent, en degrés, si la météo est connue.
+    pub wind_direction: Option<f64>,
+    pub wind_speed: Option<f64>,
+}
+
+/// Récupère les signalements sélectionnés pour l'analyse d'attribution.
+pub struct FetchAttributionObservations(pub ReportSelection);
+
+impl RepositoryOp for FetchAttributionObservations {
+    type Return = Vec<AttributionObservation>;
+
+    const READ_ONLY: bool = true;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let query = sqlx::query_as(FETCH_ATTRIBUTION_OBSERVATIONS_QUERY);
+
+            let query = match self.0 {
+                ReportSelection::Reports(ids) => query
+                    .bind(Some(ids))
+                    .bind(None::<Uuid>)
+                    .bind(None::<Uuid>)
+                    .bind(None::<chrono::DateTime<chrono::Utc>>)
+                    .bind(None::<chrono::DateTime<chrono::Utc>>),
+                ReportSelection::Episode {
+                    family_id,
+                    type_id,
+                    from,
+                    until,
+                } => query
+                    .bind(None::<Vec<Uuid>>)
+                    .bind(family_id)
+                    .bind(type_id)
+                    .bind(Some(from))
+                    .bind(Some(until)),
+            };
+
+            let observations: Vec<AttributionObservation> = query.fetch_all(executor).await?;
+
+            Ok(observations)
+        })
+    }
+}
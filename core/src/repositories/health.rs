@@ -0,0 +1,95 @@
+//! Sondes de l'état de la base de données.
+use actix::{Handler, Message, MessageResult};
+
+use crate::error::Error;
+
+use super::memory::MemoryTables;
+use super::{RepositoryActor, RepositoryOp};
+
+const POSTGIS_VERSION_QUERY: &str = r#"
+    SELECT extversion FROM pg_extension WHERE extname = 'postgis'
+"#;
+
+/// Vérifie que la base de données répond.
+pub struct Ping;
+
+impl RepositoryOp for Ping {
+    type Return = ();
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            sqlx::query("SELECT 1").execute(executor).await?;
+            Ok(())
+        })
+    }
+
+    fn execute_in_memory(self, _tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        Ok(())
+    }
+}
+
+/// Récupère la version de l'extension PostGIS, `None` si elle n'est pas installée.
+pub struct FetchPostgisVersion;
+
+impl RepositoryOp for FetchPostgisVersion {
+    type Return = Option<String>;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let version: Option<(String,)> = sqlx::query_as(POSTGIS_VERSION_QUERY)
+                .fetch_optional(executor)
+                .await?;
+
+            Ok(version.map(|(version,)| version))
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+/// Occupation de la pool de connexions.
+pub struct PoolUsage {
+    /// Connexions ouvertes.
+    pub size: u32,
+    /// Connexions ouvertes et inutilisées.
+    pub idle: u32,
+    /// Nombre maximal de connexions.
+    pub max: u32,
+}
+
+impl PoolUsage {
+    /// Connexions en cours d'utilisation.
+    pub fn active(&self) -> u32 {
+        self.size.saturating_sub(self.idle)
+    }
+}
+
+/// Message sollicitant l'occupation de la pool de connexions.
+pub struct FetchPoolUsage;
+
+impl Message for FetchPoolUsage {
+    type Result = PoolUsage;
+}
+
+impl Handler<FetchPoolUsage> for RepositoryActor {
+    type Result = MessageResult<FetchPoolUsage>;
+
+    fn handle(&mut self, _msg: FetchPoolUsage, _ctx: &mut Self::Context) -> Self::Result {
+        MessageResult(PoolUsage {
+            size: self.pool.size(),
+            idle: self.pool.num_idle() as u32,
+            max: self.pool.options().get_max_connections(),
+        })
+    }
+}
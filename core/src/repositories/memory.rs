@@ -0,0 +1,448 @@
+//! Répertoire de données en mémoire.
+//!
+//! Il permet d'éprouver la logique des services sans base de données. Seules
+//! les opérations qui redéfinissent [RepositoryOp::execute_in_memory] y sont
+//! prises en charge ; les autres échouent.
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sql_gis::{sql_types::PgPoint, types::Point};
+
+use crate::error::Error;
+use crate::models::{
+    administrative_area::{AdministrativeArea, AdministrativeAreaId},
+    audit::AuditEntry,
+    credential::Credential,
+    nuisance_family::{NuisanceFamily, NuisanceFamilyId},
+    nuisance_report::{
+        NuisanceReportId, NuisanceReportPhoto, NuisanceReportPosition, NuisanceReportSummary,
+    },
+    nuisance_type::NuisanceTypeId,
+    session::{SessionUser, UserSession, UserSessionId},
+    user::{UserAccount, UserId, UserProfile, UserRole},
+    weather::WeatherObservation,
+};
+
+use super::RepositoryOp;
+
+#[derive(Clone, Default)]
+/// Répertoire en mémoire, partagé entre ses clones.
+pub struct MemoryStore(Arc<Mutex<MemoryState>>);
+
+#[derive(Default)]
+struct MemoryState {
+    /// Version des tables, incrémentée par chaque écriture ou transaction validée.
+    version: u64,
+    tables: MemoryTables,
+}
+
+impl MemoryStore {
+    fn state(&self) -> MutexGuard<'_, MemoryState> {
+        self.0.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Execute une opération sur les tables.
+    ///
+    /// Seules les opérations qui ne sont pas en lecture seule changent la version.
+    pub fn execute<O: RepositoryOp>(&self, op: O) -> Result<O::Return, Error> {
+        let mut state = self.state();
+        let value = state.tables.apply(op)?;
+
+        if !O::READ_ONLY {
+            state.version += 1;
+        }
+
+        Ok(value)
+    }
+
+    /// Copie des tables, sur laquelle opère une transaction, et sa version.
+    pub(super) fn snapshot(&self) -> (u64, MemoryTables) {
+        let state = self.state();
+        (state.version, state.tables.clone())
+    }
+
+    /// Remplace les tables par celles d'une transaction validée.
+    ///
+    /// La validation échoue si les tables ont été modifiées depuis la copie, à la
+    /// manière d'une transaction sérialisable : les écritures concurrentes ne sont
+    /// jamais écrasées.
+    pub(super) fn commit(&self, version: u64, tables: MemoryTables) -> Result<(), Error> {
+        let mut state = self.state();
+
+        if state.version != version {
+            return Err(Error::internal_error_with_source(SerializationFailure));
+        }
+
+        state.version += 1;
+        state.tables = tables;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Default)]
+/// Tables du répertoire en mémoire.
+pub struct MemoryTables {
+    pub(crate) users: Vec<UserRecord>,
+    pub(crate) user_sessions: Vec<UserSessionRecord>,
+    pub(crate) audit_entries: Vec<AuditEntry>,
+    pub(crate) nuisance_families: Vec<NuisanceFamily>,
+    pub(crate) nuisance_types: Vec<NuisanceTypeRecord>,
+    pub(crate) nuisance_reports: Vec<NuisanceReportRecord>,
+    pub(crate) nuisance_report_photos: Vec<NuisanceReportPhoto>,
+    pub(crate) nuisance_report_weather: Vec<(NuisanceReportId, WeatherObservation)>,
+    pub(crate) administrative_areas: Vec<AdministrativeAreaRecord>,
+    pub(crate) nuisance_report_areas: Vec<(NuisanceReportId, AdministrativeAreaId)>,
+}
+
+impl MemoryTables {
+    /// Exécute une opération sur les tables.
+    ///
+    /// Une écriture opère sur une copie, reportée seulement si elle réussit : une
+    /// opération échouant en cours de route ne laisse pas de modification partielle.
+    pub(crate) fn apply<O: RepositoryOp>(&mut self, op: O) -> Result<O::Return, Error> {
+        if O::READ_ONLY {
+            return op.execute_in_memory(self);
+        }
+
+        let mut tables = self.clone();
+        let value = op.execute_in_memory(&mut tables)?;
+        *self = tables;
+
+        Ok(value)
+    }
+
+    pub(crate) fn user(&self, id: UserId) -> Option<&UserRecord> {
+        self.users.iter().find(|user| user.id == id)
+    }
+
+    pub(crate) fn user_mut(&mut self, id: UserId) -> Option<&mut UserRecord> {
+        self.users.iter_mut().find(|user| user.id == id)
+    }
+
+    pub(crate) fn nuisance_type(&self, id: NuisanceTypeId) -> Option<&NuisanceTypeRecord> {
+        self.nuisance_types.iter().find(|kind| kind.id == id)
+    }
+
+    /// Signalement non retiré.
+    pub(crate) fn nuisance_report(&self, id: NuisanceReportId) -> Option<&NuisanceReportRecord> {
+        self.nuisance_reports
+            .iter()
+            .find(|report| report.id == id && report.deleted_at.is_none())
+    }
+
+    pub(crate) fn nuisance_report_mut(
+        &mut self,
+        id: NuisanceReportId,
+    ) -> Option<&mut NuisanceReportRecord> {
+        self.nuisance_reports
+            .iter_mut()
+            .find(|report| report.id == id && report.deleted_at.is_none())
+    }
+
+    /// Vérifie l'unicité d'une valeur, à la manière d'un index unique.
+    pub(crate) fn assert_unique(&self, conflict: bool, constraint: &str) -> Result<(), Error> {
+        if conflict {
+            return Err(Error::internal_error_with_source(UniqueViolation(
+                constraint.to_owned(),
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct UserRecord {
+    pub id: UserId,
+    pub username: String,
+    pub email: String,
+    /// Empreinte du mot de passe.
+    pub password: Option<String>,
+    pub role: UserRole,
+    pub activated: bool,
+    pub avatar: Option<String>,
+    pub locale: Option<String>,
+    pub email_verified_at: Option<DateTime<Utc>>,
+    pub registered_at: DateTime<Utc>,
+    pub password_reset_token: Option<String>,
+    pub password_reset_expires_at: Option<DateTime<Utc>>,
+    pub totp_enabled_at: Option<DateTime<Utc>>,
+}
+
+impl UserRecord {
+    pub fn new(username: String, email: String, password: Option<String>, role: UserRole) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4(),
+            username,
+            email,
+            password,
+            role,
+            activated: true,
+            avatar: None,
+            locale: None,
+            email_verified_at: None,
+            registered_at: Utc::now(),
+            password_reset_token: None,
+            password_reset_expires_at: None,
+            totp_enabled_at: None,
+        }
+    }
+
+    pub fn profile(&self) -> UserProfile {
+        UserProfile {
+            id: self.id,
+            username: self.username.clone(),
+            email: self.email.clone(),
+            avatar: self.avatar.clone(),
+            email_verified_at: self.email_verified_at,
+            registered_at: self.registered_at,
+            role: self.role,
+            locale: self.locale.clone(),
+            second_factor_enabled: self.totp_enabled_at.is_some(),
+        }
+    }
+
+    pub fn account(&self) -> UserAccount {
+        UserAccount {
+            id: self.id,
+            username: self.username.clone(),
+            email: self.email.clone(),
+            role: self.role,
+            activated: self.activated,
+            email_verified_at: self.email_verified_at,
+            registered_at: self.registered_at,
+            password_reset_pending: self.password_reset_token.is_some(),
+        }
+    }
+
+    pub fn credential(&self) -> Option<Credential> {
+        self.password.clone().map(|password| Credential {
+            id: self.id,
+            password,
+            password_reset_pending: self.password_reset_token.is_some(),
+            second_factor_enabled: self.totp_enabled_at.is_some(),
+            role: self.role,
+        })
+    }
+
+    /// Le jeton de réinitialisation est celui-ci, et n'a pas expiré.
+    pub fn has_reset_token(&self, token_hash: &str) -> bool {
+        self.password_reset_token.as_deref() == Some(token_hash)
+            && self
+                .password_reset_expires_at
+                .is_some_and(|expires_at| expires_at > Utc::now())
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct UserSessionRecord {
+    pub id: UserSessionId,
+    pub token: String,
+    pub user_id: UserId,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub second_factor_verified: bool,
+    pub impersonator_id: Option<UserId>,
+}
+
+impl UserSessionRecord {
+    pub fn with_user(&self, user: &UserRecord) -> UserSession {
+        UserSession {
+            id: self.id,
+            user: SessionUser {
+                id: user.id,
+                username: user.username.clone(),
+                email: user.email.clone(),
+                avatar: user.avatar.clone(),
+                role: user.role,
+            },
+            token: self.token.clone(),
+            expires_at: self.expires_at,
+            created_at: self.created_at,
+            second_factor_verified: self.second_factor_verified,
+            impersonator_id: self.impersonator_id,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct NuisanceTypeRecord {
+    pub id: NuisanceTypeId,
+    pub label: String,
+    pub description: String,
+    pub family_id: NuisanceFamilyId,
+}
+
+#[derive(Clone)]
+pub(crate) struct NuisanceReportRecord {
+    pub id: NuisanceReportId,
+    pub type_id: NuisanceTypeId,
+    pub user_id: Option<UserId>,
+    pub longitude: f64,
+    pub latitude: f64,
+    pub intensity: i8,
+    pub description: Option<String>,
+    pub observed_from: Option<DateTime<Utc>>,
+    pub observed_until: Option<DateTime<Utc>>,
+    pub perceived_duration: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl NuisanceReportRecord {
+    pub fn summary(&self) -> NuisanceReportSummary {
+        NuisanceReportSummary {
+            id: self.id,
+            type_id: self.type_id,
+            user_id: self.user_id,
+            location: Point::new(self.longitude, self.latitude),
+            intensity: self.intensity,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+
+    pub fn position(&self) -> NuisanceReportPosition {
+        NuisanceReportPosition {
+            longitude: self.longitude,
+            latitude: self.latitude,
+            at: self.observed_from.unwrap_or(self.created_at),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct AdministrativeAreaRecord {
+    pub area: AdministrativeArea,
+    /// Polygones de la zone, chacun formé d'un contour extérieur et de ses trous.
+    pub polygons: Vec<Vec<Vec<(f64, f64)>>>,
+}
+
+impl AdministrativeAreaRecord {
+    /// La zone contient le point, trous exclus.
+    pub fn covers(&self, longitude: f64, latitude: f64) -> bool {
+        self.polygons.iter().any(|rings| {
+            let mut rings = rings.iter();
+
+            rings
+                .next()
+                .is_some_and(|exterior| ring_contains(exterior, longitude, latitude))
+                && rings.all(|hole| !ring_contains(hole, longitude, latitude))
+        })
+    }
+}
+
+/// Extrait les polygones d'une géométrie GeoJSON `Polygon` ou `MultiPolygon`.
+pub(crate) fn parse_polygons(geometry: &str) -> Result<Vec<Vec<Vec<(f64, f64)>>>, Error> {
+    let geometry: Value =
+        serde_json::from_str(geometry).map_err(Error::internal_error_with_source)?;
+
+    let polygons = match geometry["type"].as_str() {
+        Some("Polygon") => vec![&geometry["coordinates"]],
+        Some("MultiPolygon") => geometry["coordinates"]
+            .as_array()
+            .map(|polygons| polygons.iter().collect())
+            .unwrap_or_default(),
+        _ => return Err(Error::internal_error()),
+    };
+
+    polygons
+        .into_iter()
+        .map(|rings| {
+            rings
+                .as_array()
+                .ok_or_else(Error::internal_error)?
+                .iter()
+                .map(parse_ring)
+                .collect()
+        })
+        .collect()
+}
+
+fn parse_ring(ring: &Value) -> Result<Vec<(f64, f64)>, Error> {
+    ring.as_array()
+        .ok_or_else(Error::internal_error)?
+        .iter()
+        .map(
+            |position| match (position[0].as_f64(), position[1].as_f64()) {
+                (Some(longitude), Some(latitude)) => Ok((longitude, latitude)),
+                _ => Err(Error::internal_error()),
+            },
+        )
+        .collect()
+}
+
+/// Test du point dans le polygone par lancer de rayon ; le contour est inclus.
+fn ring_contains(ring: &[(f64, f64)], longitude: f64, latitude: f64) -> bool {
+    let mut inside = false;
+
+    for (&(x1, y1), &(x2, y2)) in ring.iter().zip(ring.iter().cycle().skip(1)) {
+        let on_segment = (longitude - x1) * (y2 - y1) == (latitude - y1) * (x2 - x1)
+            && longitude >= x1.min(x2)
+            && longitude <= x1.max(x2)
+            && latitude >= y1.min(y2)
+            && latitude <= y1.max(y2);
+
+        if on_segment {
+            return true;
+        }
+
+        if (y1 > latitude) != (y2 > latitude)
+            && longitude < (x2 - x1) * (latitude - y1) / (y2 - y1) + x1
+        {
+            inside = !inside;
+        }
+    }
+
+    inside
+}
+
+/// Coordonnées (longitude, latitude) d'un point.
+pub(crate) fn coordinates(location: PgPoint) -> Result<(f64, f64), Error> {
+    let point = Point::try_from(location).map_err(|_| Error::internal_error())?;
+    Ok((point.x(), point.y()))
+}
+
+/// Erreur d'une opération non prise en charge par le répertoire en mémoire.
+pub(crate) fn unsupported<O>() -> Error {
+    Error::internal_error_with_source(Unsupported(std::any::type_name::<O>()))
+}
+
+#[derive(Debug)]
+struct Unsupported(&'static str);
+
+impl std::fmt::Display for Unsupported {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} n'est pas pris en charge par le répertoire en mémoire",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for Unsupported {}
+
+#[derive(Debug)]
+struct UniqueViolation(String);
+
+impl std::fmt::Display for UniqueViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "violation de la contrainte d'unicité {}", self.0)
+    }
+}
+
+impl std::error::Error for UniqueViolation {}
+
+#[derive(Debug)]
+struct SerializationFailure;
+
+impl std::fmt::Display for SerializationFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "les tables ont été modifiées pendant la transaction")
+    }
+}
+
+impl std::error::Error for SerializationFailure {}
@@ -0,0 +1,106 @@
+//! Migrations du schéma de la base de données.
+//!
+//! Les scripts de `core/migrations` sont embarqués dans la bibliothèque à la
+//! compilation.
+use actix::{Handler, Message, ResponseFuture};
+use sqlx::migrate::{Migrate, Migrator};
+
+use crate::error::Error;
+
+use super::RepositoryActor;
+
+/// Migrations embarquées.
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+#[derive(Clone, Debug)]
+/// État d'une migration.
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    pub applied: bool,
+}
+
+/// Message sollicitant l'application des migrations en attente.
+pub struct RunMigrations;
+
+impl Message for RunMigrations {
+    type Result = Result<(), Error>;
+}
+
+impl Handler<RunMigrations> for RepositoryActor {
+    type Result = ResponseFuture<Result<(), Error>>;
+
+    fn handle(&mut self, _msg: RunMigrations, _ctx: &mut Self::Context) -> Self::Result {
+        let pool = self.pool.clone();
+
+        Box::pin(async move {
+            MIGRATOR
+                .run(&pool)
+                .await
+                .map_err(Error::internal_error_with_source)
+        })
+    }
+}
+
+/// Message sollicitant l'annulation des migrations postérieures à la version donnée.
+pub struct RevertMigrations {
+    /// Dernière version conservée ; `0` pour tout annuler.
+    pub target: i64,
+}
+
+impl Message for RevertMigrations {
+    type Result = Result<(), Error>;
+}
+
+impl Handler<RevertMigrations> for RepositoryActor {
+    type Result = ResponseFuture<Result<(), Error>>;
+
+    fn handle(&mut self, msg: RevertMigrations, _ctx: &mut Self::Context) -> Self::Result {
+        let pool = self.pool.clone();
+
+        Box::pin(async move {
+            MIGRATOR
+                .undo(&pool, msg.target)
+                .await
+                .map_err(Error::internal_error_with_source)
+        })
+    }
+}
+
+/// Message sollicitant l'état des migrations, par version croissante.
+pub struct FetchMigrationStatus;
+
+impl Message for FetchMigrationStatus {
+    type Result = Result<Vec<MigrationStatus>, Error>;
+}
+
+impl Handler<FetchMigrationStatus> for RepositoryActor {
+    type Result = ResponseFuture<Result<Vec<MigrationStatus>, Error>>;
+
+    fn handle(&mut self, _msg: FetchMigrationStatus, _ctx: &mut Self::Context) -> Self::Result {
+        let pool = self.pool.clone();
+
+        Box::pin(async move {
+            let mut conn = pool.acquire().await?;
+
+            conn.ensure_migrations_table()
+                .await
+                .map_err(Error::internal_error_with_source)?;
+
+            let applied = conn
+                .list_applied_migrations()
+                .await
+                .map_err(Error::internal_error_with_source)?;
+
+            Ok(MIGRATOR
+                .iter()
+                .filter(|migration| migration.migration_type.is_up_migration())
+                .map(|migration| MigrationStatus {
+                    version: migration.version,
+                    description: migration.description.to_string(),
+                    applied: applied.iter().any(|a| a.version == migration.version),
+                })
+                .collect())
+        })
+    }
+}
@@ -1,20 +1,51 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
+
 use crate::error::Error;
-use actix::{Actor, Addr, Context, Handler, Message, ResponseFuture};
+use crate::metrics;
+use actix::{Actor, Addr, AsyncContext, Context, Handler, Message, ResponseFuture};
 use futures::future::LocalBoxFuture;
+use futures::lock::Mutex;
 use sqlx::{Executor, PgPool, Postgres};
 use sqlx_postgres::PgPoolOptions;
+use tracing::{debug, debug_span, Instrument, Span};
+use uuid::Uuid;
+
+use self::health::{FetchPoolUsage, PoolUsage};
+use self::memory::{MemoryStore, MemoryTables};
+use self::migration::{FetchMigrationStatus, MigrationStatus, RevertMigrations, RunMigrations};
+use self::replica::{Replicas, HEALTH_CHECK_INTERVAL};
 
+pub mod administrative_area;
+pub mod audit;
 pub mod credential;
+pub mod emitter;
+pub mod health;
+pub mod memory;
+pub mod migration;
 pub mod nuisance_family;
 pub mod nuisance_report;
 pub mod nuisance_type;
+pub mod passkey;
+mod replica;
+pub mod second_factor;
+pub mod statistics;
 pub mod user;
 pub mod user_session;
+pub mod weather;
 
 #[derive(Clone)]
 pub struct RepositorySettings {
     pub max_connections: u32,
     pub database_url: String,
+    /// Exécute toutes les opérations dans une transaction jamais validée,
+    /// annulée à l'arrêt du répertoire (tests).
+    pub rollback_only: bool,
+    /// Adresses des réplicas en lecture, sollicités par les opérations en lecture seule.
+    pub read_replicas: Vec<String>,
+    /// Conserve les données en mémoire plutôt qu'en base de données (tests).
+    pub in_memory: bool,
 }
 
 impl Default for RepositorySettings {
@@ -22,6 +53,9 @@ impl Default for RepositorySettings {
         Self {
             max_connections: 5,
             database_url: std::env::var("DATABASE_URL").unwrap_or_default(),
+            rollback_only: false,
+            read_replicas: Vec::default(),
+            in_memory: false,
         }
     }
 }
@@ -32,30 +66,267 @@ impl RepositorySettings {
         self.max_connections = value;
         self
     }
+
+    /// Définit l'adresse de la base de données.
+    pub fn set_database_url(&mut self, value: String) -> &mut Self {
+        self.database_url = value;
+        self
+    }
+
+    /// Rattache toutes les opérations à une transaction jamais validée.
+    pub fn set_rollback_only(&mut self, value: bool) -> &mut Self {
+        self.rollback_only = value;
+        self
+    }
+
+    /// Définit les adresses des réplicas en lecture.
+    pub fn set_read_replicas(&mut self, value: Vec<String>) -> &mut Self {
+        self.read_replicas = value;
+        self
+    }
+
+    /// Conserve les données en mémoire plutôt qu'en base de données.
+    pub fn set_in_memory(&mut self, value: bool) -> &mut Self {
+        self.in_memory = value;
+        self
+    }
 }
 
 /// Un repertoire de données.
 #[derive(Clone)]
-pub struct Repository(Addr<RepositoryActor>);
+pub struct Repository(Backend);
+
+#[derive(Clone)]
+enum Backend {
+    Postgres(Addr<RepositoryActor>),
+    Memory(MemoryStore),
+}
 
 impl Repository {
     /// Crée une nouvelle connection au répertoire de données.
     pub async fn new(settings: &RepositorySettings) -> Result<Self, Error> {
+        if settings.in_memory {
+            return Ok(Self::in_memory());
+        }
+
         RepositoryActor::new(settings)
             .await
             .map(Actor::start)
+            .map(Backend::Postgres)
             .map(Self)
     }
 
+    /// Crée un répertoire vide, conservé en mémoire.
+    pub fn in_memory() -> Self {
+        Self(Backend::Memory(MemoryStore::default()))
+    }
+
     /// Execute une opération sur le répertoire de données.
     pub async fn execute<O: RepositoryOp + 'static>(&self, op: O) -> Result<O::Return, Error> {
-        self.0.send(ExecRepositoryOp::from(op)).await?
+        traced::<O, _>(async move {
+            match &self.0 {
+                Backend::Postgres(actor) => actor.send(ExecRepositoryOp::from(op)).await?,
+                Backend::Memory(store) => store.execute(op),
+            }
+        })
+        .await
+    }
+
+    /// Exécute plusieurs opérations au sein d'une même transaction.
+    ///
+    /// La transaction est validée si la fonction réussit, et annulée sinon.
+    /// Les opérations doivent passer par `tx`, et non par le répertoire, pour
+    /// y être rattachées.
+    ///
+    /// ```ignore
+    /// let user_id = repos
+    ///     .transaction(|tx| {
+    ///         Box::pin(async move {
+    ///             let exists = tx.execute(UserWithUsernameOrEmailExists { .. }).await?;
+    ///             tx.execute(InsertUser { .. }).await
+    ///         })
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn transaction<T, F>(&self, f: F) -> Result<T, Error>
+    where
+        F: for<'t, 'c> FnOnce(&'t mut Transaction<'c>) -> LocalBoxFuture<'t, Result<T, Error>>,
+    {
+        let actor = match &self.0 {
+            Backend::Postgres(actor) => actor,
+            Backend::Memory(store) => {
+                let (version, tables) = store.snapshot();
+                let tx = TransactionBackend::Memory(store.clone(), version, tables);
+                return Transaction(tx).run(f).await;
+            }
+        };
+
+        match actor.send(BeginTransaction).await?? {
+            TransactionSource::Pool(tx) => {
+                Transaction(TransactionBackend::Postgres(tx)).run(f).await
+            }
+            TransactionSource::Pinned(pinned) => {
+                // point de sauvegarde dans la transaction du répertoire, que les
+                // opérations ne verrouillent que le temps de leur exécution
+                let savepoint = format!("signuis_{}", Uuid::new_v4().simple());
+
+                sqlx::raw_sql(&format!("SAVEPOINT {savepoint}"))
+                    .execute(&mut **pinned.lock().await)
+                    .await?;
+
+                Transaction(TransactionBackend::Pinned(pinned, savepoint))
+                    .run(f)
+                    .await
+            }
+        }
+    }
+
+    /// Applique les migrations en attente.
+    pub async fn migrate(&self) -> Result<(), Error> {
+        match &self.0 {
+            Backend::Postgres(actor) => actor.send(RunMigrations).await?,
+            Backend::Memory(_) => Ok(()),
+        }
+    }
+
+    /// Annule les migrations postérieures à la version donnée.
+    pub async fn revert(&self, target: i64) -> Result<(), Error> {
+        match &self.0 {
+            Backend::Postgres(actor) => actor.send(RevertMigrations { target }).await?,
+            Backend::Memory(_) => Ok(()),
+        }
+    }
+
+    /// Retourne l'état des migrations, par version croissante.
+    ///
+    /// Le répertoire en mémoire n'a pas de migrations.
+    pub async fn migration_status(&self) -> Result<Vec<MigrationStatus>, Error> {
+        match &self.0 {
+            Backend::Postgres(actor) => actor.send(FetchMigrationStatus).await?,
+            Backend::Memory(_) => Ok(Vec::default()),
+        }
+    }
+
+    /// Retourne l'occupation de la pool de connexions.
+    ///
+    /// Le répertoire en mémoire n'a pas de pool.
+    pub async fn pool_usage(&self) -> Result<Option<PoolUsage>, Error> {
+        match &self.0 {
+            Backend::Postgres(actor) => Ok(Some(actor.send(FetchPoolUsage).await?)),
+            Backend::Memory(_) => Ok(None),
+        }
+    }
+
+    /// Indique si l'acteur du répertoire est toujours en vie.
+    pub fn connected(&self) -> bool {
+        match &self.0 {
+            Backend::Postgres(actor) => actor.connected(),
+            Backend::Memory(_) => true,
+        }
+    }
+}
+
+/// Exécute une opération du répertoire dans un span `repository_op`, en mesurant sa durée.
+///
+/// Les requêtes SQL émises par l'opération sont journalisées par sqlx au sein de ce span.
+async fn traced<O, F>(fut: F) -> Result<O::Return, Error>
+where
+    O: RepositoryOp,
+    F: Future<Output = Result<O::Return, Error>>,
+{
+    let op = metrics::type_label::<O>();
+
+    async move {
+        let started_at = Instant::now();
+        let result = fut.await;
+        let elapsed = started_at.elapsed();
+
+        metrics::observe_repository_op(op, elapsed);
+        debug!(
+            duration_ms = elapsed.as_millis() as u64,
+            failed = result.is_err(),
+            "opération exécutée"
+        );
+
+        result
     }
+    .instrument(debug_span!("repository_op", op))
+    .await
 }
 
+/// Transaction ouverte sur le répertoire de données.
+pub struct Transaction<'c>(TransactionBackend<'c>);
+
+enum TransactionBackend<'c> {
+    Postgres(sqlx::Transaction<'c, Postgres>),
+    /// Point de sauvegarde dans la transaction du répertoire, en mode `rollback_only`.
+    ///
+    /// La transaction n'est pas verrouillée entre les opérations : celles passant
+    /// par le répertoire plutôt que par `tx` ne sont pas bloquées.
+    Pinned(PinnedTransaction, String),
+    /// Copie des tables et sa version, reportée dans le répertoire à la validation.
+    Memory(MemoryStore, u64, MemoryTables),
+}
+
+impl Transaction<'_> {
+    /// Execute une opération au sein de la transaction.
+    pub async fn execute<O: RepositoryOp>(&mut self, op: O) -> Result<O::Return, Error> {
+        traced::<O, _>(async move {
+            match &mut self.0 {
+                TransactionBackend::Postgres(tx) => op.execute(&mut **tx).await,
+                TransactionBackend::Pinned(pinned, _) => {
+                    op.execute(&mut **pinned.lock().await).await
+                }
+                TransactionBackend::Memory(_, _, tables) => tables.apply(op),
+            }
+        })
+        .await
+    }
+
+    async fn run<T, F>(mut self, f: F) -> Result<T, Error>
+    where
+        F: for<'t, 'a> FnOnce(&'t mut Transaction<'a>) -> LocalBoxFuture<'t, Result<T, Error>>,
+    {
+        let result = f(&mut self).await;
+
+        match (self.0, result) {
+            (TransactionBackend::Postgres(tx), Ok(value)) => {
+                tx.commit().await?;
+                Ok(value)
+            }
+            (TransactionBackend::Postgres(tx), Err(err)) => {
+                tx.rollback().await?;
+                Err(err)
+            }
+            (TransactionBackend::Pinned(pinned, savepoint), Ok(value)) => {
+                sqlx::raw_sql(&format!("RELEASE SAVEPOINT {savepoint}"))
+                    .execute(&mut **pinned.lock().await)
+                    .await?;
+                Ok(value)
+            }
+            (TransactionBackend::Pinned(pinned, savepoint), Err(err)) => {
+                sqlx::raw_sql(&format!("ROLLBACK TO SAVEPOINT {savepoint}"))
+                    .execute(&mut **pinned.lock().await)
+                    .await?;
+                Err(err)
+            }
+            (TransactionBackend::Memory(store, version, tables), Ok(value)) => {
+                store.commit(version, tables)?;
+                Ok(value)
+            }
+            (TransactionBackend::Memory(..), Err(err)) => Err(err),
+        }
+    }
+}
+
+/// Transaction à laquelle sont rattachées toutes les opérations, en mode `rollback_only`.
+type PinnedTransaction = Arc<Mutex<sqlx::Transaction<'static, Postgres>>>;
+
 #[derive(Clone)]
 pub struct RepositoryActor {
     pool: PgPool,
+    pinned: Option<PinnedTransaction>,
+    replicas: Replicas,
 }
 
 impl RepositoryActor {
@@ -65,12 +336,38 @@ impl RepositoryActor {
             .connect(&settings.database_url)
             .await?;
 
-        Ok(Self { pool })
+        let pinned = if settings.rollback_only {
+            Some(Arc::new(Mutex::new(pool.begin().await?)))
+        } else {
+            None
+        };
+
+        let replicas = Replicas::new(&settings.read_replicas, settings.max_connections)?;
+
+        Ok(Self {
+            pool,
+            pinned,
+            replicas,
+        })
     }
 }
 
 impl Actor for RepositoryActor {
     type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        if self.replicas.is_empty() {
+            return;
+        }
+
+        let replicas = self.replicas.clone();
+        actix::spawn(async move { replicas.check().await });
+
+        ctx.run_interval(HEALTH_CHECK_INTERVAL, |actor, _ctx| {
+            let replicas = actor.replicas.clone();
+            actix::spawn(async move { replicas.check().await });
+        });
+    }
 }
 
 impl<T> Handler<ExecRepositoryOp<T>> for RepositoryActor
@@ -80,30 +377,63 @@ where
     type Result = ResponseFuture<Result<<T as RepositoryOp>::Return, Error>>;
 
     fn handle(&mut self, msg: ExecRepositoryOp<T>, _ctx: &mut Self::Context) -> Self::Result {
-        let pool = self.pool.clone();
-        Box::pin(async move { msg.0.execute(&pool).await })
+        let pinned = self.pinned.clone();
+
+        // les lectures tolérant le retard de réplication sont déportées sur un réplica sain
+        let pool = if T::READ_ONLY {
+            self.replicas.pick().unwrap_or(&self.pool).clone()
+        } else {
+            self.pool.clone()
+        };
+
+        let ExecRepositoryOp(op, span) = msg;
+
+        Box::pin(
+            async move {
+                match pinned {
+                    Some(pinned) => op.execute(&mut **pinned.lock().await).await,
+                    None => op.execute_on_pool(&pool).await,
+                }
+            }
+            .instrument(span),
+        )
     }
 }
 
-pub struct BeginTx {}
+/// Origine d'une nouvelle transaction.
+enum TransactionSource {
+    Pool(sqlx::Transaction<'static, Postgres>),
+    Pinned(PinnedTransaction),
+}
 
-impl RepositoryOp for BeginTx {
-    type Return = ();
+/// Message sollicitant l'ouverture d'une transaction.
+struct BeginTransaction;
 
-    fn execute<'c, E>(self, executor: E) -> LocalBoxFuture<'c, Result<Self::Return, Error>>
-    where
-        E: Executor<'c, Database = Postgres> + 'c,
-    {
-        Box::pin(async {
-            sqlx::query("BEGIN").execute(executor).await?;
-            Ok(())
+impl Message for BeginTransaction {
+    type Result = Result<TransactionSource, Error>;
+}
+
+impl Handler<BeginTransaction> for RepositoryActor {
+    type Result = ResponseFuture<Result<TransactionSource, Error>>;
+
+    fn handle(&mut self, _msg: BeginTransaction, _ctx: &mut Self::Context) -> Self::Result {
+        let pool = self.pool.clone();
+        let pinned = self.pinned.clone();
+
+        Box::pin(async move {
+            match pinned {
+                Some(pinned) => Ok(TransactionSource::Pinned(pinned)),
+                None => Ok(TransactionSource::Pool(pool.begin().await?)),
+            }
         })
     }
 }
 
 /// Message sollicitant l'exécution d'une opération sur le répertoire de données.
 ///
-pub struct ExecRepositoryOp<T>(T)
+/// Le span courant de l'émetteur est transmis, afin que les requêtes exécutées
+/// par l'acteur y soient rattachées.
+pub struct ExecRepositoryOp<T>(T, Span)
 where
     T: RepositoryOp;
 
@@ -112,7 +442,7 @@ where
     T: RepositoryOp,
 {
     fn from(value: T) -> Self {
-        Self(value)
+        Self(value, Span::current())
     }
 }
 
@@ -126,7 +456,32 @@ where
 pub trait RepositoryOp: Sync + Send {
     type Return: Sync + Send + 'static;
 
+    /// L'opération ne fait que lire, et tolère le retard de réplication.
+    ///
+    /// Elle est alors exécutée sur un réplica en lecture, s'il en existe un sain.
+    const READ_ONLY: bool = false;
+
     fn execute<'c, E>(self, executor: E) -> LocalBoxFuture<'c, Result<Self::Return, Error>>
     where
         E: Executor<'c, Database = Postgres> + 'c;
+
+    /// Exécute l'opération sur la pool du répertoire.
+    ///
+    /// Les opérations ayant besoin d'une connexion dédiée, comme `COPY`, la redéfinissent.
+    fn execute_on_pool(self, pool: &PgPool) -> LocalBoxFuture<'_, Result<Self::Return, Error>>
+    where
+        Self: Sized,
+    {
+        self.execute(pool)
+    }
+
+    /// Exécute l'opération sur les tables du répertoire en mémoire.
+    ///
+    /// Par défaut, l'opération n'y est pas prise en charge.
+    fn execute_in_memory(self, _tables: &mut MemoryTables) -> Result<Self::Return, Error>
+    where
+        Self: Sized,
+    {
+        Err(memory::unsupported::<Self>())
+    }
 }
@@ -1,13 +1,25 @@
+use super::memory::MemoryTables;
 use super::RepositoryOp;
 use crate::{
+    cache::{CachedOp, TAXONOMY},
     error::Error,
     models::nuisance_family::{NuisanceFamily, NuisanceFamilyId},
+    models::pagination::Page,
+    pagination::Keyset,
 };
 
-use sql_builder::{bind, columns, id, insert, prelude::*, row_value, select, select_columns};
+use sql_builder::{bind, columns, id, insert, prelude::*, row_value};
 
 const TABLE: sql_builder::identifier::IdentifierRef<'static> = id!(nuisance_families);
 
+const NUISANCE_FAMILIES_PAGE_QUERY: &str = r#"
+    SELECT id, label, description
+    FROM nuisance_families
+    WHERE $1::varchar IS NULL OR (label, id) > ($1, $2)
+    ORDER BY label, id
+    LIMIT $3
+"#;
+
 pub struct InsertNuisanceFamily {
     pub label: String,
     pub description: String,
@@ -29,13 +41,31 @@ impl RepositoryOp for InsertNuisanceFamily {
                 .values(row_value!(bind!(&self.label), bind!(&self.description)))
                 .build::<::sqlx::Postgres>();
 
-            ::sqlx::query_as_with(&sql, args)
+            let (id,): (NuisanceFamilyId,) = ::sqlx::query_as_with(&sql, args)
                 .fetch_one(executor)
                 .await?;
 
             Ok(id)
         })
     }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        let label_taken = tables
+            .nuisance_families
+            .iter()
+            .any(|family| family.label == self.label);
+        tables.assert_unique(label_taken, "nuisance_families_labels")?;
+
+        let id = uuid::Uuid::new_v4();
+
+        tables.nuisance_families.push(NuisanceFamily {
+            id,
+            label: self.label,
+            description: self.description,
+        });
+
+        Ok(id)
+    }
 }
 pub struct NuisanceFamilyExists(pub NuisanceFamilyId);
 
@@ -59,19 +89,27 @@ impl RepositoryOp for NuisanceFamilyExists {
             Ok(exists)
         })
     }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        Ok(tables
+            .nuisance_families
+            .iter()
+            .any(|family| family.id == self.0))
+    }
 }
 
-/// Récupère des familles de nuisance depuis le répertoire.
-pub struct FetchNuisanceFamilies {}
+/// Récupère une page de familles de nuisance, par ordre alphabétique.
+pub struct FetchNuisanceFamilies {
+    pub page: Keyset<(String, NuisanceFamilyId)>,
+}
 
 impl FetchNuisanceFamilies {
-    pub fn all() -> Self {
-        Self {}
-    }
+    /// Espace de noms des curseurs de la liste.
+    pub const CURSOR_NAMESPACE: &'static str = "nuisance_families";
 }
 
 impl RepositoryOp for FetchNuisanceFamilies {
-    type Return = Vec<NuisanceFamily>;
+    type Return = Page<NuisanceFamily>;
 
     fn execute<'c, E>(
         self,
@@ -81,16 +119,48 @@ impl RepositoryOp for FetchNuisanceFamilies {
         E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
     {
         Box::pin(async move {
-            let (sql, _) = select(select_columns!(id!(id), id!(label), id!(description)))
-                .from(TABLE)
-                .build::<sqlx::Postgres>();
+            let (label, id) = self.page.after.clone().unzip();
 
-            let nuisance_families =
-                sqlx::query_as("SELECT id, label, description FROM nuisance_families")
+            let nuisance_families: Vec<NuisanceFamily> =
+                sqlx::query_as(NUISANCE_FAMILIES_PAGE_QUERY)
+                    .bind(label)
+                    .bind(id)
+                    .bind(self.page.fetch_limit())
                     .fetch_all(executor)
                     .await?;
 
-            Ok(nuisance_families)
+            self.page.into_page(nuisance_families, |family| {
+                (family.label.clone(), family.id)
+            })
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        let mut nuisance_families: Vec<NuisanceFamily> = tables
+            .nuisance_families
+            .iter()
+            .filter(|family| {
+                self.page.after.as_ref().map_or(true, |after| {
+                    (&family.label, &family.id) > (&after.0, &after.1)
+                })
+            })
+            .cloned()
+            .collect();
+
+        nuisance_families.sort_by(|a, b| (&a.label, a.id).cmp(&(&b.label, b.id)));
+        nuisance_families.truncate(self.page.fetch_limit() as usize);
+
+        self.page.into_page(nuisance_families, |family| {
+            (family.label.clone(), family.id)
         })
     }
 }
+
+impl CachedOp for FetchNuisanceFamilies {
+    const NAMESPACE: &'static str = TAXONOMY;
+
+    fn cache_key(&self) -> Result<String, Error> {
+        serde_json::to_string(&(&self.page.after, self.page.limit))
+            .map_err(Error::internal_error_with_source)
+    }
+}
@@ -1,9 +1,131 @@
+use chrono::{DateTime, Duration, Utc};
+use futures::future::LocalBoxFuture;
 use sql_builder::{bind, columns, id, insert, prelude::*, row_value};
-use sql_gis::sql_types::PgPoint;
+use sql_gis::{sql_types::PgPoint, types::Point};
+use sqlx::PgPool;
 use uuid::Uuid;
 
+use super::memory::{self, MemoryTables, NuisanceReportRecord};
 use super::RepositoryOp;
-use crate::{error::Error, models::nuisance_report::NuisanceReportId};
+use crate::{
+    error::Error,
+    models::{
+        administrative_area::AdministrativeAreaId,
+        nuisance_report::{
+            NuisanceReport, NuisanceReportId, NuisanceReportPhoto, NuisanceReportPhotoId,
+            NuisanceReportPosition, NuisanceReportSummary, NuisanceReportType, ReportUser,
+        },
+        nuisance_type::NuisanceType,
+        pagination::Page,
+        personal_data::PersonalReport,
+        user::UserId,
+    },
+    pagination::Keyset,
+    privacy::PrivacyPolicy,
+};
+
+const NUISANCE_REPORT_SUMMARY_BY_ID_QUERY: &str = r#"
+    SELECT id, type_id, user_id, location, intensity, created_at, updated_at
+    FROM nuisance_reports
+    WHERE id = $1 AND deleted_at IS NULL
+"#;
+
+const NUISANCE_REPORT_SUMMARIES_BY_USER_QUERY: &str = r#"
+    SELECT id, type_id, user_id, location, intensity, created_at, updated_at
+    FROM nuisance_reports
+    WHERE user_id = $1 AND deleted_at IS NULL
+        AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3))
+    ORDER BY created_at DESC, id DESC
+    LIMIT $4
+"#;
+
+const NUISANCE_REPORT_QUERY: &str = r#"
+    SELECT
+        report.id, ST_X(report.location) AS longitude, ST_Y(report.location) AS latitude,
+        report.intensity, report.description,
+        report.observed_from, report.observed_until, report.perceived_duration, report.created_at,
+        kind.id AS type_id, kind.label AS type_label,
+        kind.description AS type_description, kind.family_id AS type_family_id,
+        reporter.id AS user_id, reporter.username AS user_name,
+        reporter.email AS user_email, reporter.avatar AS user_avatar
+    FROM nuisance_reports AS report
+    INNER JOIN nuisance_types AS kind ON kind.id = report.type_id
+    LEFT JOIN users AS reporter ON reporter.id = report.user_id
+    WHERE report.id = $1 AND report.deleted_at IS NULL
+"#;
+
+const NUISANCE_REPORT_PHOTOS_QUERY: &str = r#"
+    SELECT id, report_id, storage_key, content_type, size, created_at
+    FROM nuisance_report_photos
+    WHERE report_id = $1
+    ORDER BY created_at
+"#;
+
+const NUISANCE_REPORT_POSITION_QUERY: &str = r#"
+    SELECT
+        ST_X(location) AS longitude,
+        ST_Y(location) AS latitude,
+        COALESCE(observed_from, created_at) AS at
+    FROM nuisance_reports
+    WHERE id = $1
+"#;
+
+const PATCH_NUISANCE_REPORT_QUERY: &str = r#"
+    UPDATE nuisance_reports
+    SET type_id = COALESCE($2, type_id),
+        intensity = COALESCE($3, intensity),
+        updated_at = now()
+    WHERE id = $1 AND deleted_at IS NULL AND created_at > now() - $4::interval
+"#;
+
+const SOFT_DELETE_NUISANCE_REPORT_QUERY: &str = r#"
+    UPDATE nuisance_reports
+    SET deleted_at = now()
+    WHERE id = $1 AND deleted_at IS NULL AND created_at > now() - $2::interval
+"#;
+
+const PERSONAL_REPORTS_QUERY: &str = r#"
+    SELECT
+        report.id, report.type_id, kind.label AS type_label,
+        ST_X(report.location) AS longitude, ST_Y(report.location) AS latitude,
+        report.intensity, report.description,
+        report.observed_from, report.observed_until, report.perceived_duration,
+        COALESCE(
+            array_agg(photo.storage_key) FILTER (WHERE photo.id IS NOT NULL),
+            '{}'
+        ) AS photo_keys,
+        report.created_at, report.updated_at, report.deleted_at
+    FROM nuisance_reports AS report
+    INNER JOIN nuisance_types AS kind ON kind.id = report.type_id
+    LEFT JOIN nuisance_report_photos AS photo ON photo.report_id = report.id
+    WHERE report.user_id = $1
+    GROUP BY report.id, kind.label
+    ORDER BY report.created_at
+"#;
+
+const ANONYMIZE_NUISANCE_REPORTS_BY_USER_QUERY: &str = r#"
+    UPDATE nuisance_reports
+    SET user_id = NULL
+    WHERE user_id = $1
+"#;
+
+const DELETE_NUISANCE_REPORT_PHOTOS_BY_USER_QUERY: &str = r#"
+    DELETE FROM nuisance_report_photos
+    WHERE report_id IN (SELECT id FROM nuisance_reports WHERE user_id = $1)
+    RETURNING storage_key
+"#;
+
+const ANONYMOUS_NUISANCE_REPORT_PHOTO_KEYS_QUERY: &str = r#"
+    SELECT photo.storage_key
+    FROM nuisance_report_photos AS photo
+    INNER JOIN nuisance_reports AS report ON report.id = photo.report_id
+    WHERE report.user_id IS NULL AND report.created_at < $1
+"#;
+
+const PURGE_ANONYMOUS_NUISANCE_REPORTS_QUERY: &str = r#"
+    DELETE FROM nuisance_reports
+    WHERE user_id IS NULL AND created_at < $1
+"#;
 
 /// Objet pour insérer un signalement de nuisance.
 pub struct InsertNuisanceReport {
@@ -11,6 +133,25 @@ pub struct InsertNuisanceReport {
     pub user_id: Option<Uuid>,
     pub location: PgPoint,
     pub intensity: i8,
+    pub description: Option<String>,
+    pub observed_from: Option<DateTime<Utc>>,
+    pub observed_until: Option<DateTime<Utc>>,
+    pub perceived_duration: Option<i32>,
+}
+
+impl InsertNuisanceReport {
+    pub fn new(type_id: Uuid, user_id: Option<Uuid>, location: PgPoint, intensity: i8) -> Self {
+        Self {
+            type_id,
+            user_id,
+            location,
+            intensity,
+            description: None,
+            observed_from: None,
+            observed_until: None,
+            perceived_duration: None,
+        }
+    }
 }
 
 impl RepositoryOp for InsertNuisanceReport {
@@ -29,13 +170,21 @@ impl RepositoryOp for InsertNuisanceReport {
                     id!(type_id),
                     id!(location),
                     id!(intensity),
-                    id!(user_id)
+                    id!(user_id),
+                    id!(description),
+                    id!(observed_from),
+                    id!(observed_until),
+                    id!(perceived_duration)
                 ))
                 .values(row_value!(
                     bind!(self.type_id),
                     bind!(self.location),
                     bind!(self.intensity),
-                    bind!(self.user_id)
+                    bind!(self.user_id),
+                    bind!(self.description),
+                    bind!(self.observed_from),
+                    bind!(self.observed_until),
+                    bind!(self.perceived_duration)
                 ))
                 .build::<::sqlx::Postgres>();
 
@@ -44,6 +193,1012 @@ impl RepositoryOp for InsertNuisanceReport {
             Ok(id)
         })
     }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        let (longitude, latitude) = memory::coordinates(self.location)?;
+        let id = Uuid::new_v4();
+
+        tables.nuisance_reports.push(NuisanceReportRecord {
+            id,
+            type_id: self.type_id,
+            user_id: self.user_id,
+            longitude,
+            latitude,
+            intensity: self.intensity,
+            description: self.description,
+            observed_from: self.observed_from,
+            observed_until: self.observed_until,
+            perceived_duration: self.perceived_duration,
+            created_at: Utc::now(),
+            updated_at: None,
+            deleted_at: None,
+        });
+
+        Ok(id)
+    }
+}
+
+/// Signalement à insérer en masse.
+pub struct NewNuisanceReport {
+    pub type_id: Uuid,
+    pub user_id: Option<Uuid>,
+    pub longitude: f64,
+    pub latitude: f64,
+    pub intensity: i8,
+    pub description: Option<String>,
+    pub observed_from: Option<DateTime<Utc>>,
+    pub observed_until: Option<DateTime<Utc>>,
+    pub perceived_duration: Option<i32>,
+    /// Date du signalement ; à défaut, celle de l'insertion.
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl NewNuisanceReport {
+    pub fn new(
+        type_id: Uuid,
+        user_id: Option<Uuid>,
+        longitude: f64,
+        latitude: f64,
+        intensity: i8,
+    ) -> Self {
+        Self {
+            type_id,
+            user_id,
+            longitude,
+            latitude,
+            intensity,
+            description: None,
+            observed_from: None,
+            observed_until: None,
+            perceived_duration: None,
+            created_at: None,
+        }
+    }
+}
+
+const COPY_NUISANCE_REPORTS_QUERY: &str = r#"
+    COPY nuisance_reports (
+        id, type_id, user_id, location, intensity, description,
+        observed_from, observed_until, perceived_duration, created_at
+    ) FROM STDIN WITH (FORMAT binary)
+"#;
+
+const BULK_INSERT_NUISANCE_REPORTS_QUERY: &str = r#"
+    WITH report AS (
+        INSERT INTO nuisance_reports (
+            id, type_id, user_id, location, intensity, description,
+            observed_from, observed_until, perceived_duration, created_at
+        )
+        SELECT
+            input.id, input.type_id, input.user_id,
+            ST_MakePoint(input.longitude, input.latitude), input.intensity, input.description,
+            input.observed_from, input.observed_until, input.perceived_duration, input.created_at
+        FROM UNNEST(
+            $1::uuid[], $2::uuid[], $3::uuid[], $4::float8[], $5::float8[], $6::"char"[],
+            $7::text[], $8::timestamptz[], $9::timestamptz[], $10::int4[], $11::timestamptz[]
+        ) AS input(
+            id, type_id, user_id, longitude, latitude, intensity, description,
+            observed_from, observed_until, perceived_duration, created_at
+        )
+        RETURNING id, location
+    )
+    INSERT INTO nuisance_report_areas (report_id, area_id)
+        SELECT report.id, area.id
+        FROM report
+        INNER JOIN administrative_areas AS area
+            ON ST_Covers(area.geometry, ST_SetSRID(report.location, 4326))
+    ON CONFLICT DO NOTHING
+"#;
+
+const LINK_BULK_NUISANCE_REPORT_AREAS_QUERY: &str = r#"
+    INSERT INTO nuisance_report_areas (report_id, area_id)
+        SELECT report.id, area.id
+        FROM nuisance_reports AS report
+        INNER JOIN administrative_areas AS area
+            ON ST_Covers(area.geometry, ST_SetSRID(report.location, 4326))
+        WHERE report.id = ANY($1)
+    ON CONFLICT DO NOTHING
+"#;
+
+/// Nombre de signalements encodés par message `COPY`.
+const COPY_CHUNK_SIZE: usize = 10_000;
+
+/// Insère des signalements en masse, par `COPY ... FROM STDIN` au format binaire.
+///
+/// Les identifiants sont générés à l'avance et retournés dans l'ordre des
+/// signalements. Hors de la pool du répertoire (ex: au sein d'une transaction),
+/// l'insertion se replie sur une seule requête `INSERT ... SELECT FROM UNNEST`.
+///
+/// Les signalements sont ensuite rattachés, en une seule requête, aux zones
+/// administratives qui les contiennent.
+pub struct BulkInsertNuisanceReports(pub Vec<NewNuisanceReport>);
+
+/// Signalement prêt à l'insertion, avec son identifiant et sa date.
+struct PreparedNuisanceReport {
+    id: NuisanceReportId,
+    created_at: DateTime<Utc>,
+    report: NewNuisanceReport,
+}
+
+impl BulkInsertNuisanceReports {
+    fn prepare(self) -> Vec<PreparedNuisanceReport> {
+        let now = Utc::now();
+
+        self.0
+            .into_iter()
+            .map(|report| PreparedNuisanceReport {
+                id: Uuid::new_v4(),
+                created_at: report.created_at.unwrap_or(now),
+                report,
+            })
+            .collect()
+    }
+}
+
+impl RepositoryOp for BulkInsertNuisanceReports {
+    type Return = Vec<NuisanceReportId>;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let rows = self.prepare();
+            let ids: Vec<NuisanceReportId> = rows.iter().map(|row| row.id).collect();
+
+            sqlx::query(BULK_INSERT_NUISANCE_REPORTS_QUERY)
+                .bind(ids.clone())
+                .bind(
+                    rows.iter()
+                        .map(|row| row.report.type_id)
+                        .collect::<Vec<_>>(),
+                )
+                .bind(
+                    rows.iter()
+                        .map(|row| row.report.user_id)
+                        .collect::<Vec<_>>(),
+                )
+                .bind(
+                    rows.iter()
+                        .map(|row| row.report.longitude)
+                        .collect::<Vec<_>>(),
+                )
+                .bind(
+                    rows.iter()
+                        .map(|row| row.report.latitude)
+                        .collect::<Vec<_>>(),
+                )
+                .bind(
+                    rows.iter()
+                        .map(|row| row.report.intensity)
+                        .collect::<Vec<_>>(),
+                )
+                .bind(
+                    rows.iter()
+                        .map(|row| row.report.description.clone())
+                        .collect::<Vec<_>>(),
+                )
+                .bind(
+                    rows.iter()
+                        .map(|row| row.report.observed_from)
+                        .collect::<Vec<_>>(),
+                )
+                .bind(
+                    rows.iter()
+                        .map(|row| row.report.observed_until)
+                        .collect::<Vec<_>>(),
+                )
+                .bind(
+                    rows.iter()
+                        .map(|row| row.report.perceived_duration)
+                        .collect::<Vec<_>>(),
+                )
+                .bind(rows.iter().map(|row| row.created_at).collect::<Vec<_>>())
+                .execute(executor)
+                .await?;
+
+            Ok(ids)
+        })
+    }
+
+    fn execute_on_pool(self, pool: &PgPool) -> LocalBoxFuture<'_, Result<Self::Return, Error>> {
+        Box::pin(async move {
+            let rows = self.prepare();
+
+            // la copie et le rattachement aux zones partagent la même transaction
+            let mut tx = pool.begin().await?;
+            let mut copy = tx.copy_in_raw(COPY_NUISANCE_REPORTS_QUERY).await?;
+            copy.send(COPY_BINARY_HEADER).await?;
+
+            for chunk in rows.chunks(COPY_CHUNK_SIZE) {
+                let mut buffer = Vec::with_capacity(chunk.len() * 160);
+                chunk
+                    .iter()
+                    .for_each(|row| row.encode_copy_binary(&mut buffer));
+                copy.send(buffer).await?;
+            }
+
+            copy.send(COPY_BINARY_TRAILER.as_slice()).await?;
+            copy.finish().await?;
+
+            let ids: Vec<NuisanceReportId> = rows.into_iter().map(|row| row.id).collect();
+
+            sqlx::query(LINK_BULK_NUISANCE_REPORT_AREAS_QUERY)
+                .bind(&ids)
+                .execute(&mut *tx)
+                .await?;
+
+            tx.commit().await?;
+
+            Ok(ids)
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        let rows = self.prepare();
+        let ids: Vec<NuisanceReportId> = rows.iter().map(|row| row.id).collect();
+        let inserted_from = tables.nuisance_reports.len();
+
+        tables
+            .nuisance_reports
+            .extend(rows.into_iter().map(|row| NuisanceReportRecord {
+                id: row.id,
+                type_id: row.report.type_id,
+                user_id: row.report.user_id,
+                longitude: row.report.longitude,
+                latitude: row.report.latitude,
+                intensity: row.report.intensity,
+                description: row.report.description,
+                observed_from: row.report.observed_from,
+                observed_until: row.report.observed_until,
+                perceived_duration: row.report.perceived_duration,
+                created_at: row.created_at,
+                updated_at: None,
+                deleted_at: None,
+            }));
+
+        let links: Vec<(NuisanceReportId, AdministrativeAreaId)> = tables.nuisance_reports
+            [inserted_from..]
+            .iter()
+            .flat_map(|report| {
+                tables
+                    .administrative_areas
+                    .iter()
+                    .filter(|area| area.covers(report.longitude, report.latitude))
+                    .map(|area| (report.id, area.area.id))
+            })
+            .collect();
+
+        tables.nuisance_report_areas.extend(links);
+
+        Ok(ids)
+    }
+}
+
+/// En-tête du format binaire de `COPY` : signature, options et extension vides.
+const COPY_BINARY_HEADER: &[u8] = b"PGCOPY\n\xff\r\n\0\0\0\0\0\0\0\0\0";
+
+/// Fin du format binaire de `COPY`.
+const COPY_BINARY_TRAILER: [u8; 2] = (-1i16).to_be_bytes();
+
+/// Origine des dates de PostgreSQL (2000-01-01), en microsecondes depuis l'époque Unix.
+const POSTGRES_EPOCH_MICROS: i64 = 946_684_800_000_000;
+
+impl PreparedNuisanceReport {
+    /// Encode la ligne au format binaire de `COPY`.
+    fn encode_copy_binary(&self, buffer: &mut Vec<u8>) {
+        let report = &self.report;
+
+        buffer.extend_from_slice(&10i16.to_be_bytes());
+        put_copy_field(buffer, Some(self.id.as_bytes()));
+        put_copy_field(buffer, Some(report.type_id.as_bytes()));
+        put_copy_field(buffer, report.user_id.as_ref().map(Uuid::as_bytes));
+        put_copy_field(buffer, Some(point_wkb(report.longitude, report.latitude)));
+        put_copy_field(buffer, Some(report.intensity.to_be_bytes()));
+        put_copy_field(buffer, report.description.as_deref());
+        put_copy_field(buffer, report.observed_from.as_ref().map(timestamp_binary));
+        put_copy_field(buffer, report.observed_until.as_ref().map(timestamp_binary));
+        put_copy_field(buffer, report.perceived_duration.map(i32::to_be_bytes));
+        put_copy_field(buffer, Some(timestamp_binary(&self.created_at)));
+    }
+}
+
+/// Écrit un champ, précédé de sa longueur (`-1` pour `NULL`).
+fn put_copy_field<B: AsRef<[u8]>>(buffer: &mut Vec<u8>, value: Option<B>) {
+    match value {
+        Some(value) => {
+            let value = value.as_ref();
+            buffer.extend_from_slice(&(value.len() as i32).to_be_bytes());
+            buffer.extend_from_slice(value);
+        }
+        None => buffer.extend_from_slice(&(-1i32).to_be_bytes()),
+    }
+}
+
+/// Encode un point en WKB petit-boutiste, tel qu'accepté par PostGIS.
+fn point_wkb(longitude: f64, latitude: f64) -> [u8; 21] {
+    let mut wkb = [0u8; 21];
+    wkb[0] = 1;
+    wkb[1..5].copy_from_slice(&1u32.to_le_bytes());
+    wkb[5..13].copy_from_slice(&longitude.to_le_bytes());
+    wkb[13..21].copy_from_slice(&latitude.to_le_bytes());
+    wkb
+}
+
+/// Encode une date au format binaire de `timestamptz`.
+fn timestamp_binary(at: &DateTime<Utc>) -> [u8; 8] {
+    (at.timestamp_micros() - POSTGRES_EPOCH_MICROS).to_be_bytes()
+}
+
+/// Objet pour insérer la référence d'une photo jointe à un signalement.
+pub struct InsertNuisanceReportPhoto {
+    pub id: NuisanceReportPhotoId,
+    pub report_id: NuisanceReportId,
+    pub storage_key: String,
+    pub content_type: String,
+    pub size: i32,
+}
+
+const INSERT_NUISANCE_REPORT_PHOTO_QUERY: &str = r#"
+    INSERT INTO nuisance_report_photos (id, report_id, storage_key, content_type, size)
+        VALUES ($1, $2, $3, $4, $5)
+"#;
+
+impl RepositoryOp for InsertNuisanceReportPhoto {
+    type Return = NuisanceReportPhotoId;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            sqlx::query(INSERT_NUISANCE_REPORT_PHOTO_QUERY)
+                .bind(self.id)
+                .bind(self.report_id)
+                .bind(self.storage_key)
+                .bind(self.content_type)
+                .bind(self.size)
+                .execute(executor)
+                .await?;
+
+            Ok(self.id)
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        tables.nuisance_report_photos.push(NuisanceReportPhoto {
+            id: self.id,
+            report_id: self.report_id,
+            storage_key: self.storage_key,
+            content_type: self.content_type,
+            size: self.size,
+            created_at: Utc::now(),
+        });
+
+        Ok(self.id)
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct NuisanceReportRow {
+    id: NuisanceReportId,
+    longitude: f64,
+    latitude: f64,
+    intensity: i8,
+    description: Option<String>,
+    observed_from: Option<DateTime<Utc>>,
+    observed_until: Option<DateTime<Utc>>,
+    perceived_duration: Option<i32>,
+    created_at: DateTime<Utc>,
+    type_id: Uuid,
+    type_label: String,
+    type_description: Option<String>,
+    type_family_id: Uuid,
+    user_id: Option<Uuid>,
+    user_name: Option<String>,
+    user_email: Option<String>,
+    user_avatar: Option<String>,
+}
+
+impl NuisanceReportRow {
+    /// Construit le signalement en appliquant les règles de confidentialité.
+    fn into_report(self, policy: &PrivacyPolicy) -> NuisanceReport {
+        let description = self.type_description.unwrap_or_default();
+
+        let (longitude, latitude) =
+            policy.location(self.id, self.user_id, self.longitude, self.latitude);
+
+        let user = match (self.user_id, self.user_name) {
+            (Some(id), Some(name)) => Some(ReportUser {
+                id,
+                name,
+                email: policy.email(Some(id), self.user_email),
+                avatar: self.user_avatar,
+            }),
+            _ => None,
+        };
+
+        NuisanceReport {
+            id: self.id,
+            r#type: NuisanceReportType {
+                id: self.type_id,
+                label: self.type_label.clone(),
+                description: description.clone(),
+                kind: NuisanceType {
+                    id: self.type_id,
+                    label: self.type_label,
+                    description,
+                    family_id: self.type_family_id,
+                },
+            },
+            user,
+            location: Point::new(longitude, latitude),
+            intensity: self.intensity,
+            description: self.description,
+            observed_from: self.observed_from,
+            observed_until: self.observed_until,
+            perceived_duration: self.perceived_duration,
+            photos: Vec::default(),
+            weather: None,
+            created_at: self.created_at,
+        }
+    }
+}
+
+/// Récupère un signalement non retiré, avec son type et son déclarant.
+///
+/// Les photos et la météo sont récupérées séparément.
+pub struct MaybeFindOneNuisanceReport {
+    pub id: NuisanceReportId,
+    /// Règles de confidentialité appliquées à la position et au déclarant.
+    pub policy: PrivacyPolicy,
+}
+
+impl RepositoryOp for MaybeFindOneNuisanceReport {
+    type Return = Option<NuisanceReport>;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let row: Option<NuisanceReportRow> = sqlx::query_as(NUISANCE_REPORT_QUERY)
+                .bind(self.id)
+                .fetch_optional(executor)
+                .await?;
+
+            Ok(row.map(|row| row.into_report(&self.policy)))
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        let Some(report) = tables.nuisance_report(self.id) else {
+            return Ok(None);
+        };
+
+        let Some(kind) = tables.nuisance_type(report.type_id) else {
+            return Ok(None);
+        };
+
+        let reporter = report.user_id.and_then(|user_id| tables.user(user_id));
+
+        let row = NuisanceReportRow {
+            id: report.id,
+            longitude: report.longitude,
+            latitude: report.latitude,
+            intensity: report.intensity,
+            description: report.description.clone(),
+            observed_from: report.observed_from,
+            observed_until: report.observed_until,
+            perceived_duration: report.perceived_duration,
+            created_at: report.created_at,
+            type_id: kind.id,
+            type_label: kind.label.clone(),
+            type_description: Some(kind.description.clone()),
+            type_family_id: kind.family_id,
+            user_id: reporter.map(|user| user.id),
+            user_name: reporter.map(|user| user.username.clone()),
+            user_email: reporter.map(|user| user.email.clone()),
+            user_avatar: reporter.and_then(|user| user.avatar.clone()),
+        };
+
+        Ok(Some(row.into_report(&self.policy)))
+    }
+}
+
+/// Récupère les photos jointes à un signalement.
+pub struct FetchNuisanceReportPhotos(pub NuisanceReportId);
+
+impl RepositoryOp for FetchNuisanceReportPhotos {
+    type Return = Vec<NuisanceReportPhoto>;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let photos: Vec<NuisanceReportPhoto> = sqlx::query_as(NUISANCE_REPORT_PHOTOS_QUERY)
+                .bind(self.0)
+                .fetch_all(executor)
+                .await?;
+
+            Ok(photos)
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        let mut photos: Vec<NuisanceReportPhoto> = tables
+            .nuisance_report_photos
+            .iter()
+            .filter(|photo| photo.report_id == self.0)
+            .cloned()
+            .collect();
+
+        photos.sort_by_key(|photo| photo.created_at);
+
+        Ok(photos)
+    }
+}
+
+/// Récupère la position spatio-temporelle d'un signalement.
+pub struct MaybeFindOneNuisanceReportPosition(pub NuisanceReportId);
+
+impl RepositoryOp for MaybeFindOneNuisanceReportPosition {
+    type Return = Option<NuisanceReportPosition>;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let position: Option<NuisanceReportPosition> =
+                sqlx::query_as(NUISANCE_REPORT_POSITION_QUERY)
+                    .bind(self.0)
+                    .fetch_optional(executor)
+                    .await?;
+
+            Ok(position)
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        Ok(tables
+            .nuisance_reports
+            .iter()
+            .find(|report| report.id == self.0)
+            .map(NuisanceReportRecord::position))
+    }
+}
+
+/// Récupère un signalement non retiré depuis son identifiant.
+pub struct MaybeFindOneNuisanceReportById(pub NuisanceReportId);
+
+impl RepositoryOp for MaybeFindOneNuisanceReportById {
+    type Return = Option<NuisanceReportSummary>;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let report: Option<NuisanceReportSummary> =
+                sqlx::query_as(NUISANCE_REPORT_SUMMARY_BY_ID_QUERY)
+                    .bind(self.0)
+                    .fetch_optional(executor)
+                    .await?;
+
+            Ok(report)
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        Ok(tables
+            .nuisance_report(self.0)
+            .map(NuisanceReportRecord::summary))
+    }
+}
+
+/// Récupère une page des signalements non retirés d'un utilisateur,
+/// du plus récent au plus ancien.
+pub struct FetchNuisanceReportsByUser {
+    pub user_id: UserId,
+    pub page: Keyset<(DateTime<Utc>, NuisanceReportId)>,
+}
+
+impl FetchNuisanceReportsByUser {
+    /// Espace de noms des curseurs de la liste.
+    pub const CURSOR_NAMESPACE: &'static str = "nuisance_reports_by_user";
+}
+
+impl RepositoryOp for FetchNuisanceReportsByUser {
+    type Return = Page<NuisanceReportSummary>;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let (created_at, id) = self.page.after.clone().unzip();
+
+            let reports: Vec<NuisanceReportSummary> =
+                sqlx::query_as(NUISANCE_REPORT_SUMMARIES_BY_USER_QUERY)
+                    .bind(self.user_id)
+                    .bind(created_at)
+                    .bind(id)
+                    .bind(self.page.fetch_limit())
+                    .fetch_all(executor)
+                    .await?;
+
+            self.page
+                .into_page(reports, |report| (report.created_at, report.id))
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        let mut reports: Vec<&NuisanceReportRecord> = tables
+            .nuisance_reports
+            .iter()
+            .filter(|report| report.user_id == Some(self.user_id) && report.deleted_at.is_none())
+            .filter(|report| {
+                self.page
+                    .after
+                    .map_or(true, |after| (report.created_at, report.id) < after)
+            })
+            .collect();
+
+        reports.sort_by(|a, b| (b.created_at, b.id).cmp(&(a.created_at, a.id)));
+
+        let reports = reports
+            .into_iter()
+            .take(self.page.fetch_limit() as usize)
+            .map(NuisanceReportRecord::summary)
+            .collect();
+
+        self.page
+            .into_page(reports, |report| (report.created_at, report.id))
+    }
+}
+
+/// Modifie le type et/ou l'intensité d'un signalement encore modifiable.
+///
+/// Les champs à `None` sont laissés inchangés. Retourne `false` si le signalement
+/// n'existe pas, a été retiré ou a été créé il y a plus de `grace_period`.
+pub struct PatchNuisanceReport {
+    pub id: NuisanceReportId,
+    pub type_id: Option<Uuid>,
+    pub intensity: Option<i8>,
+    /// Délai de modification, vérifié lors de l'écriture.
+    pub grace_period: Duration,
+}
+
+impl RepositoryOp for PatchNuisanceReport {
+    type Return = bool;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let result = sqlx::query(PATCH_NUISANCE_REPORT_QUERY)
+                .bind(self.id)
+                .bind(self.type_id)
+                .bind(self.intensity)
+                .bind(self.grace_period)
+                .execute(executor)
+                .await?;
+
+            Ok(result.rows_affected() == 1)
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        let editable_since = Utc::now() - self.grace_period;
+
+        match tables.nuisance_report_mut(self.id) {
+            Some(report) if report.created_at > editable_since => {
+                report.type_id = self.type_id.unwrap_or(report.type_id);
+                report.intensity = self.intensity.unwrap_or(report.intensity);
+                report.updated_at = Some(Utc::now());
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+/// Retire un signalement encore modifiable sans le supprimer physiquement, afin de
+/// conserver l'historique des analyses.
+///
+/// Retourne `false` si le signalement n'existe pas, a déjà été retiré ou a été
+/// créé il y a plus de `grace_period`.
+pub struct SoftDeleteNuisanceReport {
+    pub id: NuisanceReportId,
+    /// Délai de modification, vérifié lors de l'écriture.
+    pub grace_period: Duration,
+}
+
+impl RepositoryOp for SoftDeleteNuisanceReport {
+    type Return = bool;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let result = sqlx::query(SOFT_DELETE_NUISANCE_REPORT_QUERY)
+                .bind(self.id)
+                .bind(self.grace_period)
+                .execute(executor)
+                .await?;
+
+            Ok(result.rows_affected() == 1)
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        let editable_since = Utc::now() - self.grace_period;
+
+        match tables.nuisance_report_mut(self.id) {
+            Some(report) if report.created_at > editable_since => {
+                report.deleted_at = Some(Utc::now());
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+/// Récupère tous les signalements d'un utilisateur, y compris ceux qu'il a retirés.
+pub struct FetchPersonalReports(pub UserId);
+
+impl RepositoryOp for FetchPersonalReports {
+    type Return = Vec<PersonalReport>;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let reports: Vec<PersonalReport> = sqlx::query_as(PERSONAL_REPORTS_QUERY)
+                .bind(self.0)
+                .fetch_all(executor)
+                .await?;
+
+            Ok(reports)
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        let mut reports: Vec<PersonalReport> = tables
+            .nuisance_reports
+            .iter()
+            .filter(|report| report.user_id == Some(self.0))
+            .filter_map(|report| {
+                let kind = tables.nuisance_type(report.type_id)?;
+
+                let photo_keys = tables
+                    .nuisance_report_photos
+                    .iter()
+                    .filter(|photo| photo.report_id == report.id)
+                    .map(|photo| photo.storage_key.clone())
+                    .collect();
+
+                Some(PersonalReport {
+                    id: report.id,
+                    type_id: report.type_id,
+                    type_label: kind.label.clone(),
+                    longitude: report.longitude,
+                    latitude: report.latitude,
+                    intensity: report.intensity,
+                    description: report.description.clone(),
+                    observed_from: report.observed_from,
+                    observed_until: report.observed_until,
+                    perceived_duration: report.perceived_duration,
+                    photo_keys,
+                    created_at: report.created_at,
+                    updated_at: report.updated_at,
+                    deleted_at: report.deleted_at,
+                })
+            })
+            .collect();
+
+        reports.sort_by_key(|report| report.created_at);
+
+        Ok(reports)
+    }
+}
+
+/// Détache les signalements d'un utilisateur, qui sont conservés sans auteur.
+pub struct AnonymizeNuisanceReportsByUser(pub UserId);
+
+impl RepositoryOp for AnonymizeNuisanceReportsByUser {
+    type Return = ();
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            sqlx::query(ANONYMIZE_NUISANCE_REPORTS_BY_USER_QUERY)
+                .bind(self.0)
+                .execute(executor)
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        tables
+            .nuisance_reports
+            .iter_mut()
+            .filter(|report| report.user_id == Some(self.0))
+            .for_each(|report| report.user_id = None);
+
+        Ok(())
+    }
+}
+
+/// Supprime les photos jointes aux signalements d'un utilisateur.
+///
+/// Retourne les clés des photos, à supprimer de l'espace de stockage.
+pub struct DeleteNuisanceReportPhotosByUser(pub UserId);
+
+impl RepositoryOp for DeleteNuisanceReportPhotosByUser {
+    type Return = Vec<String>;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let keys: Vec<(String,)> = sqlx::query_as(DELETE_NUISANCE_REPORT_PHOTOS_BY_USER_QUERY)
+                .bind(self.0)
+                .fetch_all(executor)
+                .await?;
+
+            Ok(keys.into_iter().map(|(key,)| key).collect())
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        let reports: Vec<NuisanceReportId> = tables
+            .nuisance_reports
+            .iter()
+            .filter(|report| report.user_id == Some(self.0))
+            .map(|report| report.id)
+            .collect();
+
+        let mut keys = Vec::default();
+
+        tables.nuisance_report_photos.retain(|photo| {
+            let owned = reports.contains(&photo.report_id);
+            if owned {
+                keys.push(photo.storage_key.clone());
+            }
+            !owned
+        });
+
+        Ok(keys)
+    }
+}
+
+/// Récupère les clés des photos jointes aux signalements anonymes créés avant la date.
+pub struct FetchAnonymousNuisanceReportPhotoKeys {
+    pub before: DateTime<Utc>,
+}
+
+impl RepositoryOp for FetchAnonymousNuisanceReportPhotoKeys {
+    type Return = Vec<String>;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let keys: Vec<(String,)> = sqlx::query_as(ANONYMOUS_NUISANCE_REPORT_PHOTO_KEYS_QUERY)
+                .bind(self.before)
+                .fetch_all(executor)
+                .await?;
+
+            Ok(keys.into_iter().map(|(key,)| key).collect())
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        Ok(tables
+            .nuisance_report_photos
+            .iter()
+            .filter(|photo| {
+                tables.nuisance_reports.iter().any(|report| {
+                    report.id == photo.report_id
+                        && report.user_id.is_none()
+                        && report.created_at < self.before
+                })
+            })
+            .map(|photo| photo.storage_key.clone())
+            .collect())
+    }
+}
+
+/// Supprime physiquement les signalements anonymes créés avant la date.
+///
+/// Retourne le nombre de signalements supprimés.
+pub struct PurgeAnonymousNuisanceReports {
+    pub before: DateTime<Utc>,
+}
+
+impl RepositoryOp for PurgeAnonymousNuisanceReports {
+    type Return = u64;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let result = sqlx::query(PURGE_ANONYMOUS_NUISANCE_REPORTS_QUERY)
+                .bind(self.before)
+                .execute(executor)
+                .await?;
+
+            Ok(result.rows_affected())
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        let (purged, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut tables.nuisance_reports)
+            .into_iter()
+            .partition(|report| report.user_id.is_none() && report.created_at < self.before);
+
+        tables.nuisance_reports = kept;
+        tables
+            .nuisance_report_photos
+            .retain(|photo| purged.iter().all(|report| report.id != photo.report_id));
+
+        Ok(purged.len() as u64)
+    }
 }
 
 const TABLE: sql_builder::identifier::IdentifierRef<'static> = id!(nuisance_reports);
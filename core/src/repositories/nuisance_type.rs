@@ -4,6 +4,7 @@ use uuid::Uuid;
 use crate::error::Error;
 use crate::models::nuisance_type::NuisanceTypeId;
 
+use super::memory::{MemoryTables, NuisanceTypeRecord};
 use super::RepositoryOp;
 
 const INSERT_NUISANCE_TYPE_QUERY: &str = r#"
@@ -52,6 +53,24 @@ impl RepositoryOp for InsertNuisanceType {
             Ok(id)
         })
     }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        let label_taken = tables.nuisance_types.iter().any(|nuisance_type| {
+            nuisance_type.family_id == self.family_id && nuisance_type.label == self.label
+        });
+        tables.assert_unique(label_taken, "nuisance_types_labels")?;
+
+        let id = Uuid::new_v4();
+
+        tables.nuisance_types.push(NuisanceTypeRecord {
+            id,
+            label: self.label,
+            description: self.description,
+            family_id: self.family_id,
+        });
+
+        Ok(id)
+    }
 }
 
 pub struct NuisanceTypeExists(pub NuisanceTypeId);
@@ -74,4 +93,8 @@ impl RepositoryOp for NuisanceTypeExists {
             Ok(exists)
         })
     }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        Ok(tables.nuisance_type(self.0).is_some())
+    }
 }
@@ -0,0 +1,296 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{
+    error::Error,
+    models::{
+        pagination::Page,
+        passkey::{ChallengePurpose, Passkey, PasskeyId, StoredPasskey},
+        user::UserId,
+    },
+    pagination::Keyset,
+};
+
+use super::RepositoryOp;
+
+const FETCH_PASSKEYS_QUERY: &str = r#"
+    SELECT id, name, created_at, last_used_at
+    FROM passkeys
+    WHERE user_id = $1
+        AND ($2::timestamptz IS NULL OR (created_at, id) > ($2, $3))
+    ORDER BY created_at, id
+    LIMIT $4
+"#;
+
+/// Enregistre le défi d'une cérémonie WebAuthn.
+pub struct InsertWebAuthnChallenge {
+    /// Défi encodé en base64url.
+    pub challenge: String,
+    pub purpose: ChallengePurpose,
+    /// Utilisateur à l'origine d'un enregistrement de clé.
+    pub user_id: Option<UserId>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl RepositoryOp for InsertWebAuthnChallenge {
+    type Return = ();
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            sqlx::query(
+                "INSERT INTO webauthn_challenges (challenge, purpose, user_id, expires_at) VALUES ($1, $2, $3, $4)",
+            )
+            .bind(self.challenge)
+            .bind(self.purpose)
+            .bind(self.user_id)
+            .bind(self.expires_at)
+            .execute(executor)
+            .await?;
+
+            Ok(())
+        })
+    }
+}
+
+const CONSUME_WEBAUTHN_CHALLENGE_QUERY: &str = r#"
+    DELETE FROM webauthn_challenges
+    WHERE challenge = $1 AND purpose = $2 AND expires_at > now()
+        AND user_id IS NOT DISTINCT FROM $3
+    RETURNING id
+"#;
+
+/// Consomme le défi d'une cérémonie encore valide.
+///
+/// Retourne `false` si le défi est inconnu, expiré ou destiné à un autre usage.
+pub struct ConsumeWebAuthnChallenge {
+    pub challenge: String,
+    pub purpose: ChallengePurpose,
+    pub user_id: Option<UserId>,
+}
+
+impl RepositoryOp for ConsumeWebAuthnChallenge {
+    type Return = bool;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let consumed: Option<(Uuid,)> = sqlx::query_as(CONSUME_WEBAUTHN_CHALLENGE_QUERY)
+                .bind(self.challenge)
+                .bind(self.purpose)
+                .bind(self.user_id)
+                .fetch_optional(executor)
+                .await?;
+
+            Ok(consumed.is_some())
+        })
+    }
+}
+
+/// Enregistre une clé d'accès.
+pub struct InsertPasskey {
+    pub user_id: UserId,
+    pub credential_id: Vec<u8>,
+    /// Clé publique au format COSE.
+    pub public_key: Vec<u8>,
+    pub sign_count: i64,
+    pub name: String,
+}
+
+impl RepositoryOp for InsertPasskey {
+    type Return = PasskeyId;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let (id,): (PasskeyId,) = sqlx::query_as(
+                "INSERT INTO passkeys (user_id, credential_id, public_key, sign_count, name) VALUES ($1, $2, $3, $4, $5) RETURNING id",
+            )
+            .bind(self.user_id)
+            .bind(self.credential_id)
+            .bind(self.public_key)
+            .bind(self.sign_count)
+            .bind(self.name)
+            .fetch_one(executor)
+            .await?;
+
+            Ok(id)
+        })
+    }
+}
+
+const STORED_PASSKEY_BY_CREDENTIAL_ID_QUERY: &str = r#"
+    SELECT passkey.id, passkey.user_id, passkey.public_key, passkey.sign_count,
+        account.password_reset_token IS NOT NULL AS password_reset_pending
+    FROM passkeys AS passkey
+    INNER JOIN users AS account ON account.id = passkey.user_id
+    WHERE passkey.credential_id = $1 AND account.activated
+"#;
+
+/// Récupère la clé d'accès d'un compte actif à partir de son identifiant d'authentificateur.
+pub struct MaybeFindOneStoredPasskey(pub Vec<u8>);
+
+impl RepositoryOp for MaybeFindOneStoredPasskey {
+    type Return = Option<StoredPasskey>;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let passkey: Option<StoredPasskey> =
+                sqlx::query_as(STORED_PASSKEY_BY_CREDENTIAL_ID_QUERY)
+                    .bind(self.0)
+                    .fetch_optional(executor)
+                    .await?;
+
+            Ok(passkey)
+        })
+    }
+}
+
+const TOUCH_PASSKEY_QUERY: &str = r#"
+    UPDATE passkeys SET sign_count = $2, last_used_at = now()
+    WHERE id = $1 AND (sign_count < $2 OR (sign_count = 0 AND $2 = 0))
+"#;
+
+/// Consigne l'utilisation d'une clé d'accès et son nouveau compteur de signatures.
+///
+/// Retourne `false` si le compteur n'a pas progressé entre-temps, ce qui trahit
+/// une assertion concurrente ou une clé clonée. Les authentificateurs sans
+/// compteur renvoient toujours zéro.
+pub struct TouchPasskey {
+    pub id: PasskeyId,
+    pub sign_count: i64,
+}
+
+impl RepositoryOp for TouchPasskey {
+    type Return = bool;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let result = sqlx::query(TOUCH_PASSKEY_QUERY)
+                .bind(self.id)
+                .bind(self.sign_count)
+                .execute(executor)
+                .await?;
+
+            Ok(result.rows_affected() == 1)
+        })
+    }
+}
+
+/// Récupère une page des clés d'accès d'un utilisateur, par date de création.
+pub struct FetchPasskeys {
+    pub user_id: UserId,
+    pub page: Keyset<(DateTime<Utc>, PasskeyId)>,
+}
+
+impl FetchPasskeys {
+    /// Espace de noms des curseurs de la liste.
+    pub const CURSOR_NAMESPACE: &'static str = "passkeys";
+}
+
+impl RepositoryOp for FetchPasskeys {
+    type Return = Page<Passkey>;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let (created_at, id) = self.page.after.clone().unzip();
+
+            let passkeys: Vec<Passkey> = sqlx::query_as(FETCH_PASSKEYS_QUERY)
+                .bind(self.user_id)
+                .bind(created_at)
+                .bind(id)
+                .bind(self.page.fetch_limit())
+                .fetch_all(executor)
+                .await?;
+
+            self.page
+                .into_page(passkeys, |passkey| (passkey.created_at, passkey.id))
+        })
+    }
+}
+
+/// Récupère les identifiants d'authentificateur des clés d'accès d'un utilisateur.
+pub struct FetchPasskeyCredentialIds(pub UserId);
+
+impl RepositoryOp for FetchPasskeyCredentialIds {
+    type Return = Vec<Vec<u8>>;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let ids: Vec<(Vec<u8>,)> =
+                sqlx::query_as("SELECT credential_id FROM passkeys WHERE user_id = $1")
+                    .bind(self.0)
+                    .fetch_all(executor)
+                    .await?;
+
+            Ok(ids.into_iter().map(|(id,)| id).collect())
+        })
+    }
+}
+
+/// Supprime une clé d'accès d'un utilisateur.
+pub struct DeletePasskey {
+    pub id: PasskeyId,
+    pub user_id: UserId,
+}
+
+impl RepositoryOp for DeletePasskey {
+    type Return = ();
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            sqlx::query("DELETE FROM passkeys WHERE id = $1 AND user_id = $2")
+                .bind(self.id)
+                .bind(self.user_id)
+                .execute(executor)
+                .await?;
+
+            Ok(())
+        })
+    }
+}
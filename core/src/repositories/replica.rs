@@ -0,0 +1,89 @@
+//! Réplicas en lecture de la base de données.
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use log::warn;
+use sqlx::PgPool;
+use sqlx_postgres::PgPoolOptions;
+
+use crate::error::Error;
+
+/// Délai entre deux vérifications de l'état des réplicas.
+pub(super) const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Clone)]
+struct Replica {
+    /// Hôte et port du réplica, sans les identifiants de connexion.
+    host: String,
+    pool: PgPool,
+    healthy: Arc<AtomicBool>,
+}
+
+#[derive(Clone, Default)]
+/// Réplicas en lecture, sollicités à tour de rôle.
+///
+/// Un réplica n'est sollicité qu'une fois déclaré sain par une vérification.
+pub(super) struct Replicas {
+    replicas: Vec<Replica>,
+    next: Arc<AtomicUsize>,
+}
+
+impl Replicas {
+    /// Prépare les pools des réplicas ; les connexions sont établies à la demande.
+    pub fn new(urls: &[String], max_connections: u32) -> Result<Self, Error> {
+        let replicas = urls
+            .iter()
+            .map(|url| {
+                let pool = PgPoolOptions::new()
+                    .max_connections(max_connections)
+                    .acquire_timeout(Duration::from_secs(2))
+                    .connect_lazy(url)?;
+
+                let options = pool.connect_options();
+                let host = format!("{}:{}", options.get_host(), options.get_port());
+
+                Ok(Replica {
+                    host,
+                    pool,
+                    healthy: Arc::default(),
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(Self {
+            replicas,
+            next: Arc::default(),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.replicas.is_empty()
+    }
+
+    /// Retourne la pool du prochain réplica sain, s'il en existe.
+    pub fn pick(&self) -> Option<&PgPool> {
+        let count = self.replicas.len();
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+
+        (0..count)
+            .map(|offset| &self.replicas[(start + offset) % count])
+            .find(|replica| replica.healthy.load(Ordering::Relaxed))
+            .map(|replica| &replica.pool)
+    }
+
+    /// Vérifie que chaque réplica répond.
+    pub async fn check(&self) {
+        for (index, replica) in self.replicas.iter().enumerate() {
+            let healthy = sqlx::query("SELECT 1").execute(&replica.pool).await.is_ok();
+            let was_healthy = replica.healthy.swap(healthy, Ordering::Relaxed);
+
+            if was_healthy && !healthy {
+                warn!(
+                    target: "signuis::repositories",
+                    "réplica {} indisponible: {}", index, replica.host
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,329 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{
+    error::Error,
+    models::{
+        second_factor::{ClaimedPendingAuthentication, UserTotp},
+        user::UserId,
+    },
+};
+
+use super::RepositoryOp;
+
+/// Enregistre un nouveau secret TOTP, en attente de confirmation.
+///
+/// Le second facteur précédent, s'il existait, est désactivé.
+pub struct SetTotpSecret {
+    pub user_id: UserId,
+    /// Secret encodé en base32.
+    pub secret: String,
+}
+
+impl RepositoryOp for SetTotpSecret {
+    type Return = ();
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            sqlx::query("UPDATE users SET totp_secret = $2, totp_enabled_at = NULL WHERE id = $1")
+                .bind(self.user_id)
+                .bind(self.secret)
+                .execute(executor)
+                .await?;
+
+            Ok(())
+        })
+    }
+}
+
+/// Récupère le secret TOTP d'un utilisateur.
+pub struct MaybeFindOneUserTotp(pub UserId);
+
+impl RepositoryOp for MaybeFindOneUserTotp {
+    type Return = Option<UserTotp>;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let totp: Option<UserTotp> = sqlx::query_as(
+                "SELECT totp_secret AS secret, totp_enabled_at IS NOT NULL AS enabled
+                FROM users WHERE id = $1 AND totp_secret IS NOT NULL",
+            )
+            .bind(self.0)
+            .fetch_optional(executor)
+            .await?;
+
+            Ok(totp)
+        })
+    }
+}
+
+/// Confirme l'inscription au second facteur.
+pub struct EnableTotp(pub UserId);
+
+impl RepositoryOp for EnableTotp {
+    type Return = ();
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            sqlx::query(
+                "UPDATE users SET totp_enabled_at = now() WHERE id = $1 AND totp_secret IS NOT NULL",
+            )
+            .bind(self.0)
+            .execute(executor)
+            .await?;
+
+            Ok(())
+        })
+    }
+}
+
+const DISABLE_TOTP_QUERY: &str = r#"
+    WITH deleted AS (
+        DELETE FROM recovery_codes WHERE user_id = $1
+    )
+    UPDATE users SET totp_secret = NULL, totp_enabled_at = NULL WHERE id = $1
+"#;
+
+/// Désactive le second facteur et supprime les codes de récupération.
+pub struct DisableTotp(pub UserId);
+
+impl RepositoryOp for DisableTotp {
+    type Return = ();
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            sqlx::query(DISABLE_TOTP_QUERY)
+                .bind(self.0)
+                .execute(executor)
+                .await?;
+
+            Ok(())
+        })
+    }
+}
+
+const REPLACE_RECOVERY_CODES_QUERY: &str = r#"
+    WITH deleted AS (
+        DELETE FROM recovery_codes WHERE user_id = $1
+    )
+    INSERT INTO recovery_codes (user_id, code_hash)
+        SELECT $1, code_hash FROM UNNEST($2::varchar[]) AS code_hash
+"#;
+
+/// Remplace les codes de récupération d'un utilisateur.
+pub struct ReplaceRecoveryCodes {
+    pub user_id: UserId,
+    /// Empreintes des codes.
+    pub code_hashes: Vec<String>,
+}
+
+impl RepositoryOp for ReplaceRecoveryCodes {
+    type Return = ();
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            sqlx::query(REPLACE_RECOVERY_CODES_QUERY)
+                .bind(self.user_id)
+                .bind(self.code_hashes)
+                .execute(executor)
+                .await?;
+
+            Ok(())
+        })
+    }
+}
+
+const CONSUME_RECOVERY_CODE_QUERY: &str = r#"
+    UPDATE recovery_codes SET used_at = now()
+    WHERE user_id = $1 AND code_hash = $2 AND used_at IS NULL
+    RETURNING id
+"#;
+
+/// Consomme un code de récupération inutilisé.
+///
+/// Retourne `false` si le code est inconnu ou a déjà servi.
+pub struct ConsumeRecoveryCode {
+    pub user_id: UserId,
+    pub code_hash: String,
+}
+
+impl RepositoryOp for ConsumeRecoveryCode {
+    type Return = bool;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let consumed: Option<(Uuid,)> = sqlx::query_as(CONSUME_RECOVERY_CODE_QUERY)
+                .bind(self.user_id)
+                .bind(self.code_hash)
+                .fetch_optional(executor)
+                .await?;
+
+            Ok(consumed.is_some())
+        })
+    }
+}
+
+const ACCEPT_TOTP_STEP_QUERY: &str = r#"
+    UPDATE users SET totp_last_step = $2
+    WHERE id = $1 AND (totp_last_step IS NULL OR totp_last_step < $2)
+"#;
+
+/// Retient le pas de temps d'un code TOTP accepté.
+///
+/// Retourne `false` si un code de ce pas, ou d'un pas ultérieur, a déjà été accepté.
+pub struct AcceptTotpStep {
+    pub user_id: UserId,
+    pub step: i64,
+}
+
+impl RepositoryOp for AcceptTotpStep {
+    type Return = bool;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let result = sqlx::query(ACCEPT_TOTP_STEP_QUERY)
+                .bind(self.user_id)
+                .bind(self.step)
+                .execute(executor)
+                .await?;
+
+            Ok(result.rows_affected() == 1)
+        })
+    }
+}
+
+/// Enregistre une authentification en attente du second facteur.
+pub struct InsertPendingAuthentication {
+    /// Empreinte du jeton remis au client.
+    pub token_hash: String,
+    pub user_id: UserId,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl RepositoryOp for InsertPendingAuthentication {
+    type Return = Uuid;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let (id,): (Uuid,) = sqlx::query_as(
+                "INSERT INTO pending_authentications (token_hash, user_id, expires_at) VALUES ($1, $2, $3) RETURNING id",
+            )
+            .bind(self.token_hash)
+            .bind(self.user_id)
+            .bind(self.expires_at)
+            .fetch_one(executor)
+            .await?;
+
+            Ok(id)
+        })
+    }
+}
+
+const CLAIM_PENDING_AUTHENTICATION_QUERY: &str = r#"
+    UPDATE pending_authentications SET attempts = attempts + 1
+    WHERE token_hash = $1 AND expires_at > now() AND attempts < $2
+    RETURNING id, user_id
+"#;
+
+/// Consomme une tentative d'une authentification en attente encore valide.
+///
+/// Retourne `None` si le jeton est inconnu, expiré ou si les tentatives sont épuisées.
+pub struct ClaimPendingAuthentication {
+    pub token_hash: String,
+    pub max_attempts: i16,
+}
+
+impl RepositoryOp for ClaimPendingAuthentication {
+    type Return = Option<ClaimedPendingAuthentication>;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let claimed: Option<ClaimedPendingAuthentication> =
+                sqlx::query_as(CLAIM_PENDING_AUTHENTICATION_QUERY)
+                    .bind(self.token_hash)
+                    .bind(self.max_attempts)
+                    .fetch_optional(executor)
+                    .await?;
+
+            Ok(claimed)
+        })
+    }
+}
+
+/// Supprime une authentification en attente, une fois aboutie.
+pub struct DeletePendingAuthentication(pub Uuid);
+
+impl RepositoryOp for DeletePendingAuthentication {
+    type Return = ();
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            sqlx::query("DELETE FROM pending_authentications WHERE id = $1")
+                .bind(self.0)
+                .execute(executor)
+                .await?;
+
+            Ok(())
+        })
+    }
+}
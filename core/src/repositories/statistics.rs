@@ -0,0 +1,236 @@
+use crate::{
+    cache::{CachedOp, STATISTICS},
+    error::Error,
+    models::statistics::{
+        ReportStatistic, ReportStatisticsQuery, StatisticsGrouping, WeeklyProfileCell,
+    },
+    privacy::PrivacyPolicy,
+};
+
+use super::memory::MemoryTables;
+use super::RepositoryOp;
+
+const REFRESH_REPORT_STATISTICS_QUERY: &str = r#"
+    REFRESH MATERIALIZED VIEW CONCURRENTLY nuisance_report_hourly_statistics;
+    REFRESH MATERIALIZED VIEW CONCURRENTLY nuisance_report_area_hourly_statistics;
+"#;
+
+const STATISTICS_FILTERS: &str = r#"
+    WHERE stats.bucket >= $2 AND stats.bucket < $3
+        AND ($4::uuid IS NULL OR stats.family_id = $4)
+        AND ($5::uuid IS NULL OR stats.type_id = $5)
+"#;
+
+const AREA_STATISTICS_FILTERS: &str = r#"
+        AND area.level = $6
+        AND ($7::varchar IS NULL OR area.code = $7)
+"#;
+
+/// Clauses `FROM` et filtres propres aux zones administratives.
+///
+/// Les statistiques portant sur les zones sont lues depuis la vue par zone,
+/// où un signalement compte une fois par zone qui le contient.
+fn statistics_source(query: &ReportStatisticsQuery) -> (&'static str, &'static str) {
+    if query.by_area() {
+        (
+            r#"
+            FROM nuisance_report_area_hourly_statistics AS stats
+            INNER JOIN administrative_areas AS area ON area.id = stats.area_id
+            "#,
+            AREA_STATISTICS_FILTERS,
+        )
+    } else {
+        ("FROM nuisance_report_hourly_statistics AS stats", "")
+    }
+}
+
+/// Recalcule les vues matérialisées des statistiques horaires des signalements.
+pub struct RefreshReportStatistics;
+
+impl RepositoryOp for RefreshReportStatistics {
+    type Return = ();
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            sqlx::raw_sql(REFRESH_REPORT_STATISTICS_QUERY)
+                .execute(executor)
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    /// Les statistiques ne sont pas matérialisées en mémoire : rien à recalculer.
+    fn execute_in_memory(self, _tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        Ok(())
+    }
+}
+
+/// Récupère le nombre de signalements et les statistiques d'intensité
+/// par intervalle de temps, éventuellement regroupés.
+///
+/// Les agrégats comptant moins de signalements que le seuil de la politique
+/// de confidentialité sont écartés.
+pub struct FetchReportStatistics {
+    pub query: ReportStatisticsQuery,
+    pub policy: PrivacyPolicy,
+}
+
+impl FetchReportStatistics {
+    fn sql(&self) -> String {
+        let (group_id, group_label, join) = match self.query.group_by {
+            StatisticsGrouping::None => ("NULL::uuid", "NULL::varchar", ""),
+            StatisticsGrouping::Family => (
+                "stats.family_id",
+                "family.label",
+                "INNER JOIN nuisance_families AS family ON family.id = stats.family_id",
+            ),
+            StatisticsGrouping::Type => (
+                "stats.type_id",
+                "kind.label",
+                "INNER JOIN nuisance_types AS kind ON kind.id = stats.type_id",
+            ),
+            StatisticsGrouping::Area => ("area.id", "area.name", ""),
+        };
+
+        let (source, area_filters) = statistics_source(&self.query);
+        let min_count = self.policy.min_aggregate_size();
+
+        format!(
+            r#"
+            SELECT
+                date_trunc($1, stats.bucket) AS bucket,
+                {group_id} AS group_id,
+                {group_label} AS group_label,
+                SUM(stats.count)::bigint AS count,
+                SUM(stats.total_intensity)::double precision / SUM(stats.count) AS mean_intensity,
+                MAX(stats.max_intensity)::smallint AS max_intensity
+            {source}
+            {join}
+            {STATISTICS_FILTERS}
+            {area_filters}
+            GROUP BY 1, 2, 3
+            HAVING SUM(stats.count) >= {min_count}
+            ORDER BY 1, 3
+            "#
+        )
+    }
+}
+
+impl RepositoryOp for FetchReportStatistics {
+    type Return = Vec<ReportStatistic>;
+
+    const READ_ONLY: bool = true;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let sql = self.sql();
+
+            let by_area = self.query.by_area();
+
+            let mut query = sqlx::query_as(&sql)
+                .bind(self.query.bucket.as_str())
+                .bind(self.query.from)
+                .bind(self.query.until)
+                .bind(self.query.family_id)
+                .bind(self.query.type_id);
+
+            if by_area {
+                query = query
+                    .bind(self.query.area_level.unwrap_or_default())
+                    .bind(self.query.area_code);
+            }
+
+            let statistics: Vec<ReportStatistic> = query.fetch_all(executor).await?;
+
+            Ok(statistics)
+        })
+    }
+}
+
+impl CachedOp for FetchReportStatistics {
+    const NAMESPACE: &'static str = STATISTICS;
+
+    /// Seul le seuil de la politique de confidentialité influe sur les agrégats.
+    fn cache_key(&self) -> Result<String, Error> {
+        serde_json::to_string(&(&self.query, self.policy.min_aggregate_size()))
+            .map_err(Error::internal_error_with_source)
+    }
+}
+
+/// Récupère la matrice jour de la semaine × heure de la journée des signalements.
+///
+/// Les cellules comptant moins de signalements que le seuil de la politique
+/// de confidentialité sont écartées.
+pub struct FetchWeeklyProfile {
+    pub query: ReportStatisticsQuery,
+    /// Fuseau horaire dans lequel exprimer les jours et heures, ex: `Europe/Paris`.
+    pub time_zone: String,
+    pub policy: PrivacyPolicy,
+}
+
+impl RepositoryOp for FetchWeeklyProfile {
+    type Return = Vec<WeeklyProfileCell>;
+
+    const READ_ONLY: bool = true;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let (source, area_filters) = statistics_source(&self.query);
+            let min_count = self.policy.min_aggregate_size();
+
+            let sql = format!(
+                r#"
+                SELECT
+                    EXTRACT(ISODOW FROM stats.bucket AT TIME ZONE $1)::integer AS day_of_week,
+                    EXTRACT(HOUR FROM stats.bucket AT TIME ZONE $1)::integer AS hour,
+                    SUM(stats.count)::bigint AS count,
+                    SUM(stats.total_intensity)::double precision / SUM(stats.count) AS mean_intensity
+                {source}
+                {STATISTICS_FILTERS}
+                {area_filters}
+                GROUP BY 1, 2
+                HAVING SUM(stats.count) >= {min_count}
+                ORDER BY 1, 2
+                "#
+            );
+
+            let by_area = self.query.by_area();
+
+            let mut query = sqlx::query_as(&sql)
+                .bind(self.time_zone)
+                .bind(self.query.from)
+                .bind(self.query.until)
+                .bind(self.query.family_id)
+                .bind(self.query.type_id);
+
+            if by_area {
+                query = query
+                    .bind(self.query.area_level.unwrap_or_default())
+                    .bind(self.query.area_code);
+            }
+
+            let cells: Vec<WeeklyProfileCell> = query.fetch_all(executor).await?;
+
+            Ok(cells)
+        })
+    }
+}
@@ -1,13 +1,20 @@
 use actix::Message;
-use argon2::Argon2;
+use chrono::{DateTime, Utc};
 use sql_builder::{bind, columns, id, insert, row_value, Symbol};
 use sqlx::{prelude::Type, Decode, Encode, Executor, Postgres};
 
 use crate::{
+    crypto::hash_password,
     error::Error,
-    models::user::{UserId, UserRole},
+    models::{
+        pagination::Page,
+        user::{UserAccount, UserId, UserProfile, UserRole, UserSearchQuery},
+    },
+    pagination::Keyset,
+    password_policy::PasswordHashing,
 };
 
+use super::memory::{MemoryTables, UserRecord};
 use super::RepositoryOp;
 
 impl Type<Postgres> for UserRole {
@@ -21,10 +28,7 @@ impl<'q> Encode<'q, Postgres> for UserRole {
         &self,
         buf: &mut <Postgres as sqlx::database::HasArguments<'q>>::ArgumentBuffer,
     ) -> sqlx::encode::IsNull {
-        match self {
-            UserRole::User => <&str as Encode<'q, Postgres>>::encode_by_ref(&"user", buf),
-            UserRole::Administrator => <&str as Encode<'q, Postgres>>::encode_by_ref(&"admin", buf),
-        }
+        <&str as Encode<'q, Postgres>>::encode_by_ref(&self.as_str(), buf)
     }
 }
 
@@ -83,6 +87,16 @@ impl RepositoryOp for UserWithUsernameOrEmailExists {
             })
         })
     }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        Ok(UserWithUsernameOrEmailExistsResult {
+            username_exists: tables
+                .users
+                .iter()
+                .any(|user| user.username == self.username),
+            email_exists: tables.users.iter().any(|user| user.email == self.email),
+        })
+    }
 }
 
 pub struct InsertUser {
@@ -90,6 +104,8 @@ pub struct InsertUser {
     pub email: String,
     pub password: Option<String>,
     pub role: UserRole,
+    /// Paramètres de hachage du mot de passe.
+    pub password_hashing: PasswordHashing,
 }
 
 impl RepositoryOp for InsertUser {
@@ -125,6 +141,58 @@ impl RepositoryOp for InsertUser {
             Ok(id)
         })
     }
+
+    fn execute_in_memory(mut self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        self.hash_password()?;
+        let user = UserRecord::new(self.username, self.email, self.password, self.role);
+        insert_user_in_memory(tables, user)
+    }
+}
+
+/// Enregistre un utilisateur importé d'un système antérieur, avec l'empreinte
+/// de son mot de passe telle quelle.
+pub struct InsertImportedUser {
+    pub username: String,
+    pub email: String,
+    /// Empreinte Argon2 ou bcrypt, recalculée à la première connexion.
+    pub password_hash: String,
+    pub role: UserRole,
+}
+
+impl RepositoryOp for InsertImportedUser {
+    type Return = UserId;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: Executor<'c, Database = Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let (id,): (UserId,) = sqlx::query_as(
+                "INSERT INTO users (username, email, password, role) VALUES ($1, $2, $3, $4) RETURNING id",
+            )
+            .bind(self.username)
+            .bind(self.email)
+            .bind(self.password_hash)
+            .bind(self.role)
+            .fetch_one(executor)
+            .await?;
+
+            Ok(id)
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        let user = UserRecord::new(
+            self.username,
+            self.email,
+            Some(self.password_hash),
+            self.role,
+        );
+        insert_user_in_memory(tables, user)
+    }
 }
 
 impl InsertUser {
@@ -134,29 +202,618 @@ impl InsertUser {
             email: email.into(),
             role: UserRole::default(),
             password: None,
+            password_hashing: PasswordHashing::default(),
         }
     }
     /// Hash the password
     pub fn hash_password(&mut self) -> Result<(), Error> {
-        if let Some(pwd) = self.password.clone() {
-            let salt = password_hash::SaltString::generate(rand::thread_rng());
-            self.password = Some(
-                password_hash::PasswordHash::generate(Argon2::default(), pwd, &salt)
-                    .map_err(|_| Error::internal_error())?
-                    .to_string(),
+        if let Some(pwd) = self.password.as_deref() {
+            self.password = Some(hash_password(pwd, &self.password_hashing)?);
+        }
+
+        Ok(())
+    }
+}
+
+const USER_PROFILE_QUERY: &str = r#"
+    SELECT id, username, email, avatar, email_verified_at, registered_at, role, locale,
+        totp_enabled_at IS NOT NULL AS second_factor_enabled
+    FROM users
+    WHERE id = $1
+"#;
+
+/// Récupère le profil d'un utilisateur.
+pub struct MaybeFindOneUserProfile(pub UserId);
+
+impl RepositoryOp for MaybeFindOneUserProfile {
+    type Return = Option<UserProfile>;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: Executor<'c, Database = Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let profile: Option<UserProfile> = sqlx::query_as(USER_PROFILE_QUERY)
+                .bind(self.0)
+                .fetch_optional(executor)
+                .await?;
+
+            Ok(profile)
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        Ok(tables.user(self.0).map(UserRecord::profile))
+    }
+}
+
+/// Supprime un utilisateur.
+///
+/// Ses sessions sont supprimées, ses signalements sont conservés sans auteur.
+pub struct DeleteUser(pub UserId);
+
+impl RepositoryOp for DeleteUser {
+    type Return = ();
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: Executor<'c, Database = Postgres> + 'c,
+    {
+        Box::pin(async move {
+            sqlx::query("DELETE FROM users WHERE id = $1")
+                .bind(self.0)
+                .execute(executor)
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        tables.users.retain(|user| user.id != self.0);
+        tables
+            .user_sessions
+            .retain(|session| session.user_id != self.0 && session.impersonator_id != Some(self.0));
+
+        tables
+            .nuisance_reports
+            .iter_mut()
+            .filter(|report| report.user_id == Some(self.0))
+            .for_each(|report| report.user_id = None);
+
+        Ok(())
+    }
+}
+
+const PATCH_USER_QUERY: &str = r#"
+    UPDATE users
+    SET username = COALESCE($2, username),
+        email_verified_at = CASE WHEN $3 IS NOT NULL AND $3 <> email THEN NULL ELSE email_verified_at END,
+        email = COALESCE($3, email),
+        avatar = COALESCE($4, avatar),
+        locale = COALESCE($5, locale)
+    WHERE id = $1
+"#;
+
+/// Modifie le profil d'un utilisateur ; les champs à `None` sont inchangés.
+///
+/// Un changement d'adresse courriel en annule la vérification.
+pub struct PatchUser {
+    pub id: UserId,
+    pub username: Option<String>,
+    pub email: Option<String>,
+    /// Clé de l'avatar dans l'espace de stockage.
+    pub avatar: Option<String>,
+    pub locale: Option<String>,
+}
+
+impl RepositoryOp for PatchUser {
+    type Return = ();
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: Executor<'c, Database = Postgres> + 'c,
+    {
+        Box::pin(async move {
+            sqlx::query(PATCH_USER_QUERY)
+                .bind(self.id)
+                .bind(self.username)
+                .bind(self.email)
+                .bind(self.avatar)
+                .bind(self.locale)
+                .execute(executor)
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        let Some(user) = tables.user_mut(self.id) else {
+            return Ok(());
+        };
+
+        if self
+            .email
+            .as_ref()
+            .is_some_and(|email| *email != user.email)
+        {
+            user.email_verified_at = None;
+        }
+
+        user.username = self.username.unwrap_or(user.username.clone());
+        user.email = self.email.unwrap_or(user.email.clone());
+        user.avatar = self.avatar.or(user.avatar.take());
+        user.locale = self.locale.or(user.locale.take());
+
+        Ok(())
+    }
+}
+
+/// Remplace le mot de passe d'un utilisateur.
+pub struct UpdateUserPassword {
+    pub id: UserId,
+    /// Mot de passe en clair, haché avant son stockage.
+    pub password: String,
+    pub password_hashing: PasswordHashing,
+}
+
+impl RepositoryOp for UpdateUserPassword {
+    type Return = ();
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: Executor<'c, Database = Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let password = hash_password(&self.password, &self.password_hashing)?;
+
+            sqlx::query("UPDATE users SET password = $2 WHERE id = $1")
+                .bind(self.id)
+                .bind(password)
+                .execute(executor)
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        let password = hash_password(&self.password, &self.password_hashing)?;
+
+        if let Some(user) = tables.user_mut(self.id) {
+            user.password = Some(password);
+        }
+
+        Ok(())
+    }
+}
+
+const USER_ACCOUNT_COLUMNS: &str = r#"
+    id, username, email, role, activated, email_verified_at, registered_at,
+    password_reset_token IS NOT NULL AS password_reset_pending
+"#;
+
+const USER_SEARCH_FILTERS: &str = r#"
+    WHERE ($1::varchar IS NULL OR username ILIKE $1 OR email ILIKE $1)
+        AND ($2::varchar IS NULL OR role = $2)
+        AND ($3::boolean IS NULL OR activated = $3)
+"#;
+
+/// Motif `ILIKE` recherchant le fragment n'importe où, ses jokers échappés.
+fn search_pattern(search: Option<String>) -> Option<String> {
+    search
+        .map(|search| search.trim().to_owned())
+        .filter(|search| !search.is_empty())
+        .map(|search| {
+            let escaped = search
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            format!("%{escaped}%")
+        })
+}
+
+/// Récupère le compte d'un utilisateur.
+pub struct MaybeFindOneUserAccount(pub UserId);
+
+impl RepositoryOp for MaybeFindOneUserAccount {
+    type Return = Option<UserAccount>;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: Executor<'c, Database = Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let sql = format!("SELECT {USER_ACCOUNT_COLUMNS} FROM users WHERE id = $1");
+
+            let account: Option<UserAccount> = sqlx::query_as(&sql)
+                .bind(self.0)
+                .fetch_optional(executor)
+                .await?;
+
+            Ok(account)
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        Ok(tables.user(self.0).map(UserRecord::account))
+    }
+}
+
+/// Récupère le compte derrière un jeton de réinitialisation de mot de passe encore valide.
+pub struct MaybeFindOneUserAccountByResetToken {
+    /// Empreinte du jeton de réinitialisation.
+    pub token_hash: String,
+}
+
+impl RepositoryOp for MaybeFindOneUserAccountByResetToken {
+    type Return = Option<UserAccount>;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: Executor<'c, Database = Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let sql = format!(
+                "SELECT {USER_ACCOUNT_COLUMNS} FROM users WHERE password_reset_token = $1 AND password_reset_expires_at > now()"
+            );
+
+            let account: Option<UserAccount> = sqlx::query_as(&sql)
+                .bind(self.token_hash)
+                .fetch_optional(executor)
+                .await?;
+
+            Ok(account)
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        Ok(tables
+            .users
+            .iter()
+            .find(|user| user.has_reset_token(&self.token_hash))
+            .map(UserRecord::account))
+    }
+}
+
+/// Récupère une page de comptes utilisateurs, par ordre alphabétique.
+pub struct FetchUserAccounts {
+    pub query: UserSearchQuery,
+    pub page: Keyset<(String, UserId)>,
+}
+
+impl FetchUserAccounts {
+    /// Espace de noms des curseurs de la liste.
+    pub const CURSOR_NAMESPACE: &'static str = "user_accounts";
+}
+
+impl RepositoryOp for FetchUserAccounts {
+    type Return = Page<UserAccount>;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: Executor<'c, Database = Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let sql = format!(
+                "SELECT {USER_ACCOUNT_COLUMNS} FROM users {USER_SEARCH_FILTERS} AND ($4::varchar IS NULL OR (username, id) > ($4, $5)) ORDER BY username, id LIMIT $6"
             );
+            let (username, id) = self.page.after.clone().unzip();
+
+            let accounts: Vec<UserAccount> = sqlx::query_as(&sql)
+                .bind(search_pattern(self.query.search))
+                .bind(self.query.role)
+                .bind(self.query.activated)
+                .bind(username)
+                .bind(id)
+                .bind(self.page.fetch_limit())
+                .fetch_all(executor)
+                .await?;
+
+            self.page
+                .into_page(accounts, |account| (account.username.clone(), account.id))
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        let mut users: Vec<&UserRecord> = tables
+            .users
+            .iter()
+            .filter(|user| self.query.matches(user))
+            .filter(|user| {
+                self.page.after.as_ref().map_or(true, |after| {
+                    (&user.username, &user.id) > (&after.0, &after.1)
+                })
+            })
+            .collect();
+
+        users.sort_by(|a, b| (&a.username, a.id).cmp(&(&b.username, b.id)));
+        users.truncate(self.page.fetch_limit() as usize);
+
+        let accounts = users.into_iter().map(UserRecord::account).collect();
+
+        self.page
+            .into_page(accounts, |account| (account.username.clone(), account.id))
+    }
+}
+
+/// Compte les utilisateurs correspondant aux critères de recherche, sans pagination.
+pub struct CountUserAccounts(pub UserSearchQuery);
+
+impl RepositoryOp for CountUserAccounts {
+    type Return = i64;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: Executor<'c, Database = Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let sql = format!("SELECT COUNT(*) FROM users {USER_SEARCH_FILTERS}");
+
+            let (total,): (i64,) = sqlx::query_as(&sql)
+                .bind(search_pattern(self.0.search))
+                .bind(self.0.role)
+                .bind(self.0.activated)
+                .fetch_one(executor)
+                .await?;
+
+            Ok(total)
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        Ok(tables
+            .users
+            .iter()
+            .filter(|user| self.0.matches(user))
+            .count() as i64)
+    }
+}
+
+/// Change le rôle d'un utilisateur.
+pub struct UpdateUserRole {
+    pub id: UserId,
+    pub role: UserRole,
+}
+
+impl RepositoryOp for UpdateUserRole {
+    type Return = ();
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: Executor<'c, Database = Postgres> + 'c,
+    {
+        Box::pin(async move {
+            sqlx::query("UPDATE users SET role = $2 WHERE id = $1")
+                .bind(self.id)
+                .bind(self.role)
+                .execute(executor)
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        if let Some(user) = tables.user_mut(self.id) {
+            user.role = self.role;
+        }
+
+        Ok(())
+    }
+}
+
+/// Active ou désactive le compte d'un utilisateur.
+pub struct SetUserActivated {
+    pub id: UserId,
+    pub activated: bool,
+}
+
+impl RepositoryOp for SetUserActivated {
+    type Return = ();
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: Executor<'c, Database = Postgres> + 'c,
+    {
+        Box::pin(async move {
+            sqlx::query("UPDATE users SET activated = $2 WHERE id = $1")
+                .bind(self.id)
+                .bind(self.activated)
+                .execute(executor)
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        if let Some(user) = tables.user_mut(self.id) {
+            user.activated = self.activated;
         }
 
         Ok(())
     }
 }
 
+/// Impose un changement de mot de passe, possible uniquement sur présentation du jeton.
+pub struct SetPasswordResetToken {
+    pub id: UserId,
+    /// Empreinte du jeton de réinitialisation.
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl RepositoryOp for SetPasswordResetToken {
+    type Return = ();
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: Executor<'c, Database = Postgres> + 'c,
+    {
+        Box::pin(async move {
+            sqlx::query(
+                "UPDATE users SET password_reset_token = $2, password_reset_expires_at = $3 WHERE id = $1",
+            )
+            .bind(self.id)
+            .bind(self.token_hash)
+            .bind(self.expires_at)
+            .execute(executor)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        if let Some(user) = tables.user_mut(self.id) {
+            user.password_reset_token = Some(self.token_hash);
+            user.password_reset_expires_at = Some(self.expires_at);
+        }
+
+        Ok(())
+    }
+}
+
+const RESET_USER_PASSWORD_QUERY: &str = r#"
+    UPDATE users
+    SET password = $2, password_reset_token = NULL, password_reset_expires_at = NULL
+    WHERE password_reset_token = $1 AND password_reset_expires_at > now()
+    RETURNING id
+"#;
+
+/// Remplace le mot de passe de l'utilisateur derrière un jeton de réinitialisation
+/// encore valide, et consomme ce dernier.
+///
+/// Retourne l'identifiant de l'utilisateur, ou `None` si le jeton est inconnu ou expiré.
+pub struct ResetUserPassword {
+    /// Empreinte du jeton de réinitialisation.
+    pub token_hash: String,
+    /// Mot de passe en clair, haché avant son stockage.
+    pub password: String,
+    pub password_hashing: PasswordHashing,
+}
+
+impl RepositoryOp for ResetUserPassword {
+    type Return = Option<UserId>;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: Executor<'c, Database = Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let password = hash_password(&self.password, &self.password_hashing)?;
+
+            let id: Option<(UserId,)> = sqlx::query_as(RESET_USER_PASSWORD_QUERY)
+                .bind(self.token_hash)
+                .bind(password)
+                .fetch_optional(executor)
+                .await?;
+
+            Ok(id.map(|(id,)| id))
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        let password = hash_password(&self.password, &self.password_hashing)?;
+
+        let Some(user) = tables
+            .users
+            .iter_mut()
+            .find(|user| user.has_reset_token(&self.token_hash))
+        else {
+            return Ok(None);
+        };
+
+        user.password = Some(password);
+        user.password_reset_token = None;
+        user.password_reset_expires_at = None;
+
+        Ok(Some(user.id))
+    }
+}
+
+/// Insère un utilisateur en mémoire, en respectant l'unicité des noms et des adresses.
+fn insert_user_in_memory(tables: &mut MemoryTables, user: UserRecord) -> Result<UserId, Error> {
+    let username_taken = tables
+        .users
+        .iter()
+        .any(|other| other.username == user.username);
+    tables.assert_unique(username_taken, "users_unique_name")?;
+
+    let email_taken = tables.users.iter().any(|other| other.email == user.email);
+    tables.assert_unique(email_taken, "users_unique_email")?;
+
+    let id = user.id;
+    tables.users.push(user);
+
+    Ok(id)
+}
+
+impl UserSearchQuery {
+    /// Équivalent en mémoire des filtres de recherche.
+    fn matches(&self, user: &UserRecord) -> bool {
+        let search = self
+            .search
+            .as_deref()
+            .map(|search| search.trim().to_lowercase())
+            .filter(|search| !search.is_empty());
+
+        search.map_or(true, |search| {
+            user.username.to_lowercase().contains(&search)
+                || user.email.to_lowercase().contains(&search)
+        }) && self.role.map_or(true, |role| user.role == role)
+            && self
+                .activated
+                .map_or(true, |activated| user.activated == activated)
+    }
+}
+
 const TABLE: sql_builder::identifier::IdentifierRef<'_> = id!(users);
 
 #[cfg(any(test, feature = "fixture"))]
 pub mod fixtures {
     use crate::error::Error;
     use crate::models::user::{UserId, UserRole};
+    use crate::password_policy::PasswordHashing;
+    use crate::repositories::memory::MemoryTables;
     use crate::repositories::{Repository, RepositoryOp};
 
     use super::InsertUser;
@@ -198,6 +855,10 @@ pub mod fixtures {
         {
             self.to_model().execute(executor)
         }
+
+        fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+            self.to_model().execute_in_memory(tables)
+        }
     }
 
     impl InsertUserFixture {
@@ -225,16 +886,23 @@ pub mod fixtures {
                 email,
                 password,
                 role,
+                password_hashing: PasswordHashing::default(),
             }
         }
     }
 
     impl Dummy<Faker> for InsertUserFixture {
         fn dummy_with_rng<R: Rng + ?Sized>(_: &Faker, rng: &mut R) -> Self {
+            // suffixe aléatoire, pour respecter l'unicité des noms et des adresses
+            let suffix = format!("{:08x}", rng.gen::<u32>());
+
             let username = Fake::fake_with_rng::<String, _>(&(Username()), rng);
             let email = Fake::fake_with_rng::<String, _>(&(SafeEmail()), rng);
             let password = Fake::fake_with_rng::<String, _>(&(Password(8..16)), rng);
 
+            let username = format!("{username}.{suffix}");
+            let email = format!("{suffix}.{email}");
+
             InsertUserFixture {
                 username,
                 email,
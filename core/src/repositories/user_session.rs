@@ -3,9 +3,14 @@ use uuid::Uuid;
 
 use crate::{
     error::Error,
-    models::session::{UserSession, UserSessionId},
+    models::{
+        personal_data::PersonalSession,
+        session::{UserSession, UserSessionId},
+        user::UserId,
+    },
 };
 
+use super::memory::{MemoryTables, UserSessionRecord};
 use super::RepositoryOp;
 
 /// Insére une nouvelle session utilisateur dans la base de données.
@@ -13,6 +18,10 @@ pub struct InsertUserSession {
     pub token: String,
     pub user_id: Uuid,
     pub expires_at: DateTime<Utc>,
+    /// La session est ouverte après vérification d'un second facteur.
+    pub second_factor_verified: bool,
+    /// Administrateur agissant au nom de l'utilisateur, pour une session d'assistance.
+    pub impersonator_id: Option<UserId>,
 }
 
 impl RepositoryOp for InsertUserSession {
@@ -27,20 +36,48 @@ impl RepositoryOp for InsertUserSession {
     {
         Box::pin(async move {
             let (id,): (UserSessionId,) = sqlx::query_as(
-                "INSERT INTO user_sessions (token, user_id, expires_at) VALUES ($1, $2, $3) RETURNING id",
+                "INSERT INTO user_sessions (token, user_id, expires_at, second_factor_verified, impersonator_id) VALUES ($1, $2, $3, $4, $5) RETURNING id",
             )
             .bind(self.token)
             .bind(self.user_id)
             .bind(self.expires_at)
+            .bind(self.second_factor_verified)
+            .bind(self.impersonator_id)
             .fetch_one(executor)
             .await?;
 
             Ok(id)
         })
     }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        let id = Uuid::new_v4();
+
+        tables.user_sessions.push(UserSessionRecord {
+            id,
+            token: self.token,
+            user_id: self.user_id,
+            expires_at: self.expires_at,
+            created_at: Utc::now(),
+            second_factor_verified: self.second_factor_verified,
+            impersonator_id: self.impersonator_id,
+        });
+
+        Ok(id)
+    }
 }
 
-/// Récupère une session utilisateur valide (ex: pas expirée) derrière le jeton.
+const VALID_USER_SESSION_BY_TOKEN_QUERY: &str = r#"
+    SELECT session.id, session.token, session.expires_at, session.created_at,
+        session.second_factor_verified, session.impersonator_id,
+        session.user_id, account.username AS user_username, account.email AS user_email,
+        account.avatar AS user_avatar, account.role AS user_role
+    FROM user_sessions AS session
+    INNER JOIN users AS account ON session.user_id = account.id
+    WHERE session.token = $1 AND session.expires_at > now() AND account.activated
+"#;
+
+/// Récupère une session utilisateur valide (ex: pas expirée, compte actif) derrière le jeton.
 pub struct MaybeFindOneValidUserSessionByToken(pub String);
 
 impl RepositoryOp for MaybeFindOneValidUserSessionByToken {
@@ -54,19 +91,128 @@ impl RepositoryOp for MaybeFindOneValidUserSessionByToken {
         E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
     {
         Box::pin(async move {
-            let session: Option<UserSession> = sqlx::query_as(
-                "SELECT id, token, expires_at, created_at, 
-                    user_id, user.username as user_name, user.email as user_email,
-                    user.avatar as user_avatar, user.role as user_role
-            FROM user_sessions
-            INNER JOIN users AS user ON user_sessions.user_id = user.id
-            WHERE token = $1",
+            let session: Option<UserSession> = sqlx::query_as(VALID_USER_SESSION_BY_TOKEN_QUERY)
+                .bind(self.0)
+                .fetch_optional(executor)
+                .await?;
+
+            Ok(session)
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        Ok(tables
+            .user_sessions
+            .iter()
+            .filter(|session| session.token == self.0 && session.expires_at > Utc::now())
+            .find_map(|session| {
+                tables
+                    .user(session.user_id)
+                    .filter(|user| user.activated)
+                    .map(|user| session.with_user(user))
+            }))
+    }
+}
+
+/// Récupère les sessions d'un utilisateur, sans leur jeton.
+pub struct FetchPersonalSessions(pub UserId);
+
+impl RepositoryOp for FetchPersonalSessions {
+    type Return = Vec<PersonalSession>;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let sessions: Vec<PersonalSession> = sqlx::query_as(
+                "SELECT id, created_at, expires_at FROM user_sessions WHERE user_id = $1 ORDER BY created_at",
             )
             .bind(self.0)
-            .fetch_optional(executor)
+            .fetch_all(executor)
             .await?;
 
-            Ok(session)
+            Ok(sessions)
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        let mut sessions: Vec<PersonalSession> = tables
+            .user_sessions
+            .iter()
+            .filter(|session| session.user_id == self.0)
+            .map(|session| PersonalSession {
+                id: session.id,
+                created_at: session.created_at,
+                expires_at: session.expires_at,
+            })
+            .collect();
+
+        sessions.sort_by_key(|session| session.created_at);
+
+        Ok(sessions)
+    }
+}
+
+/// Révoque toutes les sessions d'un utilisateur.
+pub struct DeleteUserSessionsByUser(pub UserId);
+
+impl RepositoryOp for DeleteUserSessionsByUser {
+    type Return = ();
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            sqlx::query("DELETE FROM user_sessions WHERE user_id = $1")
+                .bind(self.0)
+                .execute(executor)
+                .await?;
+
+            Ok(())
         })
     }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        tables
+            .user_sessions
+            .retain(|session| session.user_id != self.0);
+        Ok(())
+    }
+}
+
+/// Révoque une session.
+pub struct DeleteUserSession(pub UserSessionId);
+
+impl RepositoryOp for DeleteUserSession {
+    type Return = ();
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            sqlx::query("DELETE FROM user_sessions WHERE id = $1")
+                .bind(self.0)
+                .execute(executor)
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        tables.user_sessions.retain(|session| session.id != self.0);
+        Ok(())
+    }
 }
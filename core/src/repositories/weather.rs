@@ -0,0 +1,103 @@
+use crate::{
+    error::Error,
+    models::{nuisance_report::NuisanceReportId, weather::WeatherObservation},
+};
+
+use super::memory::MemoryTables;
+use super::RepositoryOp;
+
+const INSERT_NUISANCE_REPORT_WEATHER_QUERY: &str = r#"
+    INSERT INTO nuisance_report_weather
+        (report_id, station, observed_at, wind_direction, wind_speed, temperature, humidity)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+    ON CONFLICT (report_id) DO UPDATE SET
+        station = EXCLUDED.station,
+        observed_at = EXCLUDED.observed_at,
+        wind_direction = EXCLUDED.wind_direction,
+        wind_speed = EXCLUDED.wind_speed,
+        temperature = EXCLUDED.temperature,
+        humidity = EXCLUDED.humidity
+"#;
+
+const NUISANCE_REPORT_WEATHER_QUERY: &str = r#"
+    SELECT station, observed_at, wind_direction, wind_speed, temperature, humidity
+    FROM nuisance_report_weather
+    WHERE report_id = $1
+"#;
+
+/// Enregistre les conditions météorologiques associées à un signalement.
+pub struct InsertNuisanceReportWeather {
+    pub report_id: NuisanceReportId,
+    pub observation: WeatherObservation,
+}
+
+impl RepositoryOp for InsertNuisanceReportWeather {
+    type Return = ();
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            sqlx::query(INSERT_NUISANCE_REPORT_WEATHER_QUERY)
+                .bind(self.report_id)
+                .bind(self.observation.station)
+                .bind(self.observation.observed_at)
+                .bind(self.observation.wind_direction)
+                .bind(self.observation.wind_speed)
+                .bind(self.observation.temperature)
+                .bind(self.observation.humidity)
+                .execute(executor)
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        tables
+            .nuisance_report_weather
+            .retain(|(report_id, _)| *report_id != self.report_id);
+        tables
+            .nuisance_report_weather
+            .push((self.report_id, self.observation));
+
+        Ok(())
+    }
+}
+
+/// Récupère les conditions météorologiques associées à un signalement.
+pub struct MaybeFindOneNuisanceReportWeather(pub NuisanceReportId);
+
+impl RepositoryOp for MaybeFindOneNuisanceReportWeather {
+    type Return = Option<WeatherObservation>;
+
+    fn execute<'c, E>(
+        self,
+        executor: E,
+    ) -> futures::prelude::future::LocalBoxFuture<'c, Result<Self::Return, Error>>
+    where
+        E: sqlx::prelude::Executor<'c, Database = sqlx::Postgres> + 'c,
+    {
+        Box::pin(async move {
+            let observation: Option<WeatherObservation> =
+                sqlx::query_as(NUISANCE_REPORT_WEATHER_QUERY)
+                    .bind(self.0)
+                    .fetch_optional(executor)
+                    .await?;
+
+            Ok(observation)
+        })
+    }
+
+    fn execute_in_memory(self, tables: &mut MemoryTables) -> Result<Self::Return, Error> {
+        Ok(tables
+            .nuisance_report_weather
+            .iter()
+            .find(|(report_id, _)| *report_id == self.0)
+            .map(|(_, observation)| observation.clone()))
+    }
+}
@@ -1,15 +1,48 @@
 use actix::{Actor, Addr, Context, Handler, Message, ResponseFuture};
+use chrono::{DateTime, Duration, Utc};
 use futures::future::LocalBoxFuture;
+use log::warn;
+use tracing::{info_span, Instrument, Span};
+use uuid::Uuid;
 
 use crate::{
+    crypto::{generate_token, hash_token, is_supported_password_hash},
     error::Error,
-    events::{EventBus, UserRegistered},
-    forms::account::RegisterUserForm,
-    models::user::{UserId, UserRole},
+    events::{EventBus, UserEmailChanged, UserRegistered},
+    forms::account::{
+        ChangePasswordForm, ImpersonateUserForm, ImportUserForm, RegisterUserForm,
+        ResetPasswordForm, UpdateProfileForm,
+    },
+    media::{extension_of, strip_gps_metadata},
+    metrics,
+    models::{
+        audit::AuditAction,
+        pagination::PageRequest,
+        personal_data::PersonalDataArchive,
+        session::Session,
+        user::{UserAccountPage, UserId, UserProfile, UserRole, UserSearchQuery},
+    },
+    pagination::{CursorKey, Keyset},
+    password_policy::{BreachedPasswords, PasswordHashing, PasswordPolicy},
     repositories::{
-        user::{InsertUser, UserWithUsernameOrEmailExists},
+        audit::InsertAuditEntry,
+        credential::MaybeFindOneCredentialById,
+        nuisance_report::{
+            AnonymizeNuisanceReportsByUser, DeleteNuisanceReportPhotosByUser, FetchPersonalReports,
+        },
+        user::{
+            CountUserAccounts, DeleteUser, FetchUserAccounts, InsertImportedUser, InsertUser,
+            MaybeFindOneUserAccount, MaybeFindOneUserAccountByResetToken, MaybeFindOneUserProfile,
+            PatchUser, ResetUserPassword, SetPasswordResetToken, SetUserActivated,
+            UpdateUserPassword, UpdateUserRole, UserWithUsernameOrEmailExists,
+        },
+        user_session::{
+            DeleteUserSession, DeleteUserSessionsByUser, FetchPersonalSessions, InsertUserSession,
+        },
         Repository,
     },
+    services::authentication::CreatedUserSession,
+    storage::Storage,
     validation::{Validation, Validator},
 };
 
@@ -17,23 +50,64 @@ use crate::{
 pub struct Account(Addr<AccountActor>);
 
 impl Account {
-    pub fn new(repos: Repository, events: EventBus) -> Self {
-        Self(AccountActor::new(repos, events).start())
+    pub fn new(
+        repos: Repository,
+        events: EventBus,
+        storage: Storage,
+        password_policy: PasswordPolicy,
+        password_hashing: PasswordHashing,
+        cursor_key: CursorKey,
+    ) -> Self {
+        Self(
+            AccountActor::new(
+                repos,
+                events,
+                storage,
+                password_policy,
+                password_hashing,
+                cursor_key,
+            )
+            .start(),
+        )
     }
 
     pub async fn execute<O: AccountOp>(&self, op: O) -> Result<O::Return, Error> {
-        self.0.send(ExecuteAccountOp(op)).await?
+        let span = info_span!("account", op = metrics::type_label::<O>());
+        self.0.send(ExecuteAccountOp(op, span)).await?
+    }
+
+    /// Indique si l'acteur du service est toujours en vie.
+    pub fn connected(&self) -> bool {
+        self.0.connected()
     }
 }
 
 pub struct AccountActor {
     repos: Repository,
     events: EventBus,
+    storage: Storage,
+    password_policy: PasswordPolicy,
+    password_hashing: PasswordHashing,
+    cursor_key: CursorKey,
 }
 
 impl AccountActor {
-    pub fn new(repos: Repository, events: EventBus) -> Self {
-        Self { repos, events }
+    pub fn new(
+        repos: Repository,
+        events: EventBus,
+        storage: Storage,
+        password_policy: PasswordPolicy,
+        password_hashing: PasswordHashing,
+        cursor_key: CursorKey,
+    ) -> Self {
+        Self {
+            repos,
+            events,
+            storage,
+            password_policy,
+            password_hashing,
+            cursor_key,
+        }
     }
 }
 
@@ -48,9 +122,10 @@ where
     type Result = ResponseFuture<Result<O::Return, Error>>;
 
     fn handle(&mut self, msg: ExecuteAccountOp<O>, _ctx: &mut Self::Context) -> Self::Result {
-        let fut = msg.0.execute(self);
+        let ExecuteAccountOp(op, span) = msg;
+        let fut = span.in_scope(|| op.execute(self));
 
-        Box::pin(fut)
+        Box::pin(fut.instrument(span))
     }
 }
 
@@ -65,7 +140,7 @@ pub trait AccountOp: Sync + Send + 'static {
     ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>>;
 }
 
-pub struct ExecuteAccountOp<O>(O)
+pub struct ExecuteAccountOp<O>(O, Span)
 where
     O: AccountOp;
 
@@ -76,9 +151,28 @@ where
     type Result = Result<O::Return, Error>;
 }
 
+/// Vérifie qu'un nouveau mot de passe respecte la politique, y compris
+/// l'absence des listes de mots de passe compromis.
+async fn assert_password_policy(
+    validator: &mut Validator,
+    policy: &PasswordPolicy,
+    password: &str,
+    personal_data: &[&str],
+    field: &str,
+) -> Result<(), Error> {
+    validator.assert_password_policy(password, policy, personal_data, [field]);
+
+    if let Some(directory) = &policy.breached_passwords {
+        let breached = BreachedPasswords::new(directory).contains(password).await?;
+        validator.assert_not_breached(breached, [field]);
+    }
+
+    Ok(())
+}
+
 /// Enregistre un nouvel utilisateur.
 pub struct RegisterUser {
-    form: RegisterUserForm,
+    pub form: RegisterUserForm,
 }
 
 impl AccountOp for RegisterUser {
@@ -90,16 +184,242 @@ impl AccountOp for RegisterUser {
     ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
         let repos = accounts.repos.clone();
         let events = accounts.events.clone();
+        let password_policy = accounts.password_policy.clone();
+        let password_hashing = accounts.password_hashing;
 
         Box::pin(async move {
             let mut validator = Validator::default();
             self.form.assert(&mut validator);
             validator.check().inspect_err(|_| {})?;
 
+            assert_password_policy(
+                &mut validator,
+                &password_policy,
+                &self.form.password,
+                &[&self.form.username, &self.form.email],
+                "password",
+            )
+            .await?;
+
+            // la vérification et l'insertion forment une seule unité
+            let user_id = repos
+                .transaction(move |tx| {
+                    Box::pin(async move {
+                        let exists = tx
+                            .execute(UserWithUsernameOrEmailExists {
+                                username: self.form.username.clone(),
+                                email: self.form.email.clone(),
+                            })
+                            .await?;
+
+                        validator.assert_false(
+                            exists.username_exists,
+                            Some("le nom d'utilisateur est déjà pris"),
+                            ["username"],
+                        );
+
+                        validator.assert_false(
+                            exists.email_exists,
+                            Some("l'adresse courriel est déjà pris"),
+                            ["email"],
+                        );
+                        validator.check()?;
+
+                        tx.execute(InsertUser {
+                            username: self.form.username,
+                            email: self.form.email,
+                            password: Some(self.form.password),
+                            role: UserRole::default(),
+                            password_hashing,
+                        })
+                        .await
+                    })
+                })
+                .await?;
+
+            // notifie les autres systèmes
+            events.notify(UserRegistered(user_id));
+
+            Ok(user_id)
+        })
+    }
+}
+
+/// Exporte les données personnelles de l'utilisateur connecté :
+/// profil, sessions et signalements.
+pub struct ExportMyData {
+    pub session: Session,
+}
+
+impl AccountOp for ExportMyData {
+    type Return = PersonalDataArchive;
+
+    fn execute<'fut>(
+        self,
+        accounts: &mut AccountActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = accounts.repos.clone();
+
+        Box::pin(async move {
+            let user_id = self.session.user().ok_or_else(Error::unauthorized)?.id;
+
+            let profile = repos
+                .execute(MaybeFindOneUserProfile(user_id))
+                .await?
+                .ok_or_else(Error::not_found)?;
+
+            let sessions = repos.execute(FetchPersonalSessions(user_id)).await?;
+            let reports = repos.execute(FetchPersonalReports(user_id)).await?;
+
+            repos
+                .execute(InsertAuditEntry {
+                    action: AuditAction::PersonalDataExported,
+                    actor_id: self.session.actor_id(),
+                    subject_id: Some(user_id),
+                    details: None,
+                })
+                .await?;
+
+            Ok(PersonalDataArchive {
+                exported_at: Utc::now(),
+                profile,
+                sessions,
+                reports,
+            })
+        })
+    }
+}
+
+/// Supprime le compte de l'utilisateur connecté.
+///
+/// Ses signalements sont conservés sans auteur ni photos, ses sessions sont
+/// révoquées, son avatar supprimé, et la suppression est consignée dans le
+/// journal d'audit. L'effacement est atomique.
+pub struct DeleteMyAccount {
+    pub session: Session,
+}
+
+impl AccountOp for DeleteMyAccount {
+    type Return = ();
+
+    fn execute<'fut>(
+        self,
+        accounts: &mut AccountActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = accounts.repos.clone();
+        let storage = accounts.storage.clone();
+
+        Box::pin(async move {
+            let user_id = self.session.user().ok_or_else(Error::unauthorized)?.id;
+            let impersonator_id = self.session.impersonator_id();
+
+            // les clés d'accès et seconds facteurs sont supprimés avec l'utilisateur
+            let keys = repos
+                .transaction(move |tx| {
+                    Box::pin(async move {
+                        let avatar = tx
+                            .execute(MaybeFindOneUserProfile(user_id))
+                            .await?
+                            .and_then(|profile| profile.avatar);
+
+                        let mut keys = tx
+                            .execute(DeleteNuisanceReportPhotosByUser(user_id))
+                            .await?;
+                        keys.extend(avatar);
+
+                        tx.execute(AnonymizeNuisanceReportsByUser(user_id)).await?;
+                        tx.execute(DeleteUserSessionsByUser(user_id)).await?;
+                        tx.execute(DeleteUser(user_id)).await?;
+
+                        tx.execute(InsertAuditEntry {
+                            action: AuditAction::AccountDeleted,
+                            actor_id: impersonator_id,
+                            subject_id: Some(user_id),
+                            details: None,
+                        })
+                        .await?;
+
+                        Ok::<_, Error>(keys)
+                    })
+                })
+                .await?;
+
+            // les fichiers ne sont supprimés qu'une fois l'effacement validé
+            for key in keys {
+                if let Err(error) = storage.delete(&key).await {
+                    warn!(target: "signuis::account", "impossible de supprimer le fichier {key}: {}", error.chain());
+                }
+            }
+
+            Ok(())
+        })
+    }
+}
+
+/// Récupère le profil de l'utilisateur connecté.
+pub struct GetMyProfile {
+    pub session: Session,
+}
+
+impl AccountOp for GetMyProfile {
+    type Return = UserProfile;
+
+    fn execute<'fut>(
+        self,
+        accounts: &mut AccountActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = accounts.repos.clone();
+
+        Box::pin(async move {
+            let user_id = self.session.user().ok_or_else(Error::unauthorized)?.id;
+
+            repos
+                .execute(MaybeFindOneUserProfile(user_id))
+                .await?
+                .ok_or_else(Error::not_found)
+        })
+    }
+}
+
+/// Modifie le profil de l'utilisateur connecté.
+///
+/// Un changement d'adresse courriel en annule la vérification ; l'avatar
+/// est déposé dans l'espace de stockage, débarrassé de ses coordonnées GPS.
+pub struct UpdateProfile {
+    pub form: UpdateProfileForm,
+    pub session: Session,
+}
+
+impl AccountOp for UpdateProfile {
+    type Return = UserProfile;
+
+    fn execute<'fut>(
+        self,
+        accounts: &mut AccountActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = accounts.repos.clone();
+        let events = accounts.events.clone();
+        let storage = accounts.storage.clone();
+
+        Box::pin(async move {
+            let user_id = self.session.user().ok_or_else(Error::unauthorized)?.id;
+
+            let mut validator = Validator::default();
+            self.form.assert(&mut validator);
+            validator.check()?;
+
+            let profile = repos
+                .execute(MaybeFindOneUserProfile(user_id))
+                .await?
+                .ok_or_else(Error::not_found)?;
+
+            let username = self.form.username.filter(|u| *u != profile.username);
+            let email = self.form.email.filter(|e| *e != profile.email);
+
             let exists = repos
                 .execute(UserWithUsernameOrEmailExists {
-                    username: self.form.username.clone(),
-                    email: self.form.email.clone(),
+                    username: username.clone().unwrap_or_default(),
+                    email: email.clone().unwrap_or_default(),
                 })
                 .await?;
 
@@ -114,20 +434,676 @@ impl AccountOp for RegisterUser {
                 Some("l'adresse courriel est déjà pris"),
                 ["email"],
             );
+            validator.check()?;
+
+            let avatar = match self.form.avatar {
+                Some(avatar) => {
+                    let key = format!(
+                        "avatars/{user_id}/{}.{}",
+                        Uuid::new_v4(),
+                        extension_of(&avatar.content_type)
+                    );
+                    let data = strip_gps_metadata(&avatar.content_type, avatar.data);
+                    storage.put(&key, &avatar.content_type, data).await?;
+                    Some(key)
+                }
+                None => None,
+            };
+
+            let email_changed = email.is_some();
+
+            repos
+                .execute(PatchUser {
+                    id: user_id,
+                    username,
+                    email,
+                    avatar: avatar.clone(),
+                    locale: self.form.locale,
+                })
+                .await?;
+
+            // L'ancien avatar n'est plus référencé.
+            if let (Some(_), Some(previous)) = (&avatar, &profile.avatar) {
+                if let Err(error) = storage.delete(previous).await {
+                    warn!(target: "signuis::account", "impossible de supprimer l'avatar {previous}: {}", error.chain());
+                }
+            }
+
+            if email_changed {
+                events.notify(UserEmailChanged(user_id));
+            }
+
+            repos
+                .execute(MaybeFindOneUserProfile(user_id))
+                .await?
+                .ok_or_else(Error::not_found)
+        })
+    }
+}
+
+/// Change le mot de passe de l'utilisateur connecté, sur présentation du mot de passe actuel.
+pub struct ChangePassword {
+    pub form: ChangePasswordForm,
+    pub session: Session,
+}
+
+impl AccountOp for ChangePassword {
+    type Return = ();
+
+    fn execute<'fut>(
+        self,
+        accounts: &mut AccountActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = accounts.repos.clone();
+        let password_policy = accounts.password_policy.clone();
+        let password_hashing = accounts.password_hashing;
+
+        Box::pin(async move {
+            let user_id = self.session.user().ok_or_else(Error::unauthorized)?.id;
+
+            let mut validator = Validator::default();
+            self.form.assert(&mut validator);
+            validator.check()?;
+
+            let account = repos
+                .execute(MaybeFindOneUserAccount(user_id))
+                .await?
+                .ok_or_else(Error::unauthorized)?;
+
+            assert_password_policy(
+                &mut validator,
+                &password_policy,
+                &self.form.new_password,
+                &[&account.username, &account.email],
+                "new_password",
+            )
+            .await?;
+            validator.check()?;
+
+            let credential = repos
+                .execute(MaybeFindOneCredentialById(user_id))
+                .await?
+                .ok_or_else(Error::unauthorized)?;
+
+            validator.assert_true(
+                credential.verify(&self.form.current_password)?,
+                Some("le mot de passe actuel est incorrect"),
+                ["current_password"],
+            );
+            validator.check()?;
+
+            repos
+                .execute(UpdateUserPassword {
+                    id: user_id,
+                    password: self.form.new_password,
+                    password_hashing,
+                })
+                .await
+        })
+    }
+}
+
+/// Durée de validité d'un jeton de réinitialisation de mot de passe, en jours.
+const PASSWORD_RESET_LIFETIME_DAYS: i64 = 3;
+/// Durée de validité d'une session ouverte au nom d'un utilisateur, en minutes.
+const IMPERSONATION_LIFETIME_MINUTES: i64 = 60;
+
+/// Vérifie que la session est celle d'un administrateur, et en retourne l'utilisateur.
+fn administrator_of(session: &Session) -> Result<UserId, Error> {
+    if !session.is_admin() {
+        return Err(Error::unauthorized());
+    }
+
+    session
+        .user()
+        .map(|user| user.id)
+        .ok_or_else(Error::unauthorized)
+}
+
+/// Un administrateur ne peut pas agir sur son propre compte, au risque de s'en exclure.
+fn assert_not_oneself(validator: &mut Validator, admin_id: UserId, user_id: UserId) {
+    validator.assert_false(
+        admin_id == user_id,
+        Some("cette action ne peut pas porter sur votre propre compte"),
+        ["user_id"],
+    );
+}
+
+/// Recherche les comptes utilisateurs (réservé aux administrateurs).
+pub struct ListUserAccounts {
+    pub query: UserSearchQuery,
+    pub page: PageRequest,
+    pub session: Session,
+}
+
+impl AccountOp for ListUserAccounts {
+    type Return = UserAccountPage;
+
+    fn execute<'fut>(
+        self,
+        accounts: &mut AccountActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = accounts.repos.clone();
+        let cursor_key = accounts.cursor_key.clone();
+
+        Box::pin(async move {
+            administrator_of(&self.session)?;
+
+            let total = repos.execute(CountUserAccounts(self.query.clone())).await?;
+            let accounts = repos
+                .execute(FetchUserAccounts {
+                    query: self.query,
+                    page: Keyset::from_request(
+                        self.page,
+                        cursor_key,
+                        FetchUserAccounts::CURSOR_NAMESPACE,
+                    )?,
+                })
+                .await?;
+
+            Ok(UserAccountPage { accounts, total })
+        })
+    }
+}
+
+/// Change le rôle d'un utilisateur (réservé aux administrateurs).
+pub struct ChangeUserRole {
+    pub user_id: UserId,
+    pub role: UserRole,
+    pub session: Session,
+}
+
+impl AccountOp for ChangeUserRole {
+    type Return = ();
+
+    fn execute<'fut>(
+        self,
+        accounts: &mut AccountActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = accounts.repos.clone();
+
+        Box::pin(async move {
+            let admin_id = administrator_of(&self.session)?;
+
+            let mut validator = Validator::default();
+            assert_not_oneself(&mut validator, admin_id, self.user_id);
+            validator.check()?;
+
+            let account = repos
+                .execute(MaybeFindOneUserAccount(self.user_id))
+                .await?
+                .ok_or_else(Error::not_found)?;
+
+            if account.role == self.role {
+                return Ok(());
+            }
+
+            repos
+                .transaction(move |tx| {
+                    Box::pin(async move {
+                        tx.execute(UpdateUserRole {
+                            id: self.user_id,
+                            role: self.role,
+                        })
+                        .await?;
+
+                        tx.execute(InsertAuditEntry {
+                            action: AuditAction::RoleChanged,
+                            actor_id: Some(admin_id),
+                            subject_id: Some(self.user_id),
+                            details: Some(format!(
+                                "{} -> {}",
+                                account.role.as_str(),
+                                self.role.as_str()
+                            )),
+                        })
+                        .await?;
+
+                        Ok(())
+                    })
+                })
+                .await
+        })
+    }
+}
+
+/// Désactive ou réactive le compte d'un utilisateur (réservé aux administrateurs).
+///
+/// Un compte désactivé ne peut plus s'authentifier et ses sessions sont révoquées.
+pub struct SetUserActivation {
+    pub user_id: UserId,
+    pub activated: bool,
+    pub session: Session,
+}
+
+impl AccountOp for SetUserActivation {
+    type Return = ();
+
+    fn execute<'fut>(
+        self,
+        accounts: &mut AccountActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = accounts.repos.clone();
+
+        Box::pin(async move {
+            let admin_id = administrator_of(&self.session)?;
+
+            let mut validator = Validator::default();
+            assert_not_oneself(&mut validator, admin_id, self.user_id);
+            validator.check()?;
+
+            let account = repos
+                .execute(MaybeFindOneUserAccount(self.user_id))
+                .await?
+                .ok_or_else(Error::not_found)?;
+
+            if account.activated == self.activated {
+                return Ok(());
+            }
+
+            repos
+                .transaction(move |tx| {
+                    Box::pin(async move {
+                        tx.execute(SetUserActivated {
+                            id: self.user_id,
+                            activated: self.activated,
+                        })
+                        .await?;
+
+                        if !self.activated {
+                            tx.execute(DeleteUserSessionsByUser(self.user_id)).await?;
+                        }
+
+                        tx.execute(InsertAuditEntry {
+                            action: if self.activated {
+                                AuditAction::AccountReactivated
+                            } else {
+                                AuditAction::AccountDeactivated
+                            },
+                            actor_id: Some(admin_id),
+                            subject_id: Some(self.user_id),
+                            details: None,
+                        })
+                        .await?;
+
+                        Ok(())
+                    })
+                })
+                .await
+        })
+    }
+}
+
+/// Jeton de réinitialisation de mot de passe, à transmettre à l'utilisateur.
+pub struct PasswordResetTicket {
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Impose un changement de mot de passe à un utilisateur (réservé aux administrateurs).
+///
+/// Ses sessions sont révoquées et il ne peut plus s'authentifier tant qu'il n'a pas
+/// choisi un nouveau mot de passe au moyen du jeton retourné.
+pub struct ForcePasswordReset {
+    pub user_id: UserId,
+    pub session: Session,
+}
+
+impl AccountOp for ForcePasswordReset {
+    type Return = PasswordResetTicket;
+
+    fn execute<'fut>(
+        self,
+        accounts: &mut AccountActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = accounts.repos.clone();
+
+        Box::pin(async move {
+            let admin_id = administrator_of(&self.session)?;
+
+            repos
+                .execute(MaybeFindOneUserAccount(self.user_id))
+                .await?
+                .ok_or_else(Error::not_found)?;
+
+            let token = generate_token(32);
+            let token_hash = hash_token(&token);
+            let expires_at = Utc::now() + Duration::days(PASSWORD_RESET_LIFETIME_DAYS);
+
+            repos
+                .transaction(move |tx| {
+                    Box::pin(async move {
+                        tx.execute(SetPasswordResetToken {
+                            id: self.user_id,
+                            token_hash,
+                            expires_at,
+                        })
+                        .await?;
+
+                        tx.execute(DeleteUserSessionsByUser(self.user_id)).await?;
+
+                        tx.execute(InsertAuditEntry {
+                            action: AuditAction::PasswordResetForced,
+                            actor_id: Some(admin_id),
+                            subject_id: Some(self.user_id),
+                            details: None,
+                        })
+                        .await?;
+
+                        Ok(())
+                    })
+                })
+                .await?;
+
+            Ok(PasswordResetTicket { token, expires_at })
+        })
+    }
+}
+
+/// Choisit un nouveau mot de passe au moyen d'un jeton de réinitialisation.
+pub struct ResetPassword {
+    pub form: ResetPasswordForm,
+}
+
+impl AccountOp for ResetPassword {
+    type Return = ();
+
+    fn execute<'fut>(
+        self,
+        accounts: &mut AccountActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = accounts.repos.clone();
+        let password_policy = accounts.password_policy.clone();
+        let password_hashing = accounts.password_hashing;
+
+        Box::pin(async move {
+            let mut validator = Validator::default();
+            self.form.assert(&mut validator);
+            validator.check()?;
+
+            let token_hash = hash_token(&self.form.token);
+
+            let account = repos
+                .execute(MaybeFindOneUserAccountByResetToken {
+                    token_hash: token_hash.clone(),
+                })
+                .await?;
+
+            validator.assert_is_some(
+                &account,
+                Some("le jeton de réinitialisation est invalide ou expiré"),
+                ["token"],
+            );
+            validator.check()?;
+
+            let account = account.ok_or_else(Error::not_found)?;
+
+            assert_password_policy(
+                &mut validator,
+                &password_policy,
+                &self.form.new_password,
+                &[&account.username, &account.email],
+                "new_password",
+            )
+            .await?;
+            validator.check()?;
 
             let user_id = repos
-                .execute(InsertUser {
-                    username: self.form.username,
-                    email: self.form.email,
-                    password: Some(self.form.password),
-                    role: UserRole::default(),
+                .execute(ResetUserPassword {
+                    token_hash,
+                    password: self.form.new_password,
+                    password_hashing,
                 })
                 .await?;
 
-            // notifie les autres systèmes
-            events.notify(UserRegistered(user_id));
+            validator.assert_is_some(
+                &user_id,
+                Some("le jeton de réinitialisation est invalide ou expiré"),
+                ["token"],
+            );
+            validator.check()?;
 
-            Ok(user_id)
+            repos
+                .execute(InsertAuditEntry {
+                    action: AuditAction::PasswordReset,
+                    actor_id: user_id,
+                    subject_id: user_id,
+                    details: None,
+                })
+                .await?;
+
+            Ok(())
+        })
+    }
+}
+
+/// Ouvre une session au nom d'un utilisateur pour l'assister (réservé aux administrateurs).
+///
+/// La session est de courte durée, ne peut viser un autre administrateur
+/// ni un compte désactivé, et son ouverture est consignée avec son motif.
+pub struct ImpersonateUser {
+    pub form: ImpersonateUserForm,
+    pub session: Session,
+}
+
+impl AccountOp for ImpersonateUser {
+    type Return = CreatedUserSession;
+
+    fn execute<'fut>(
+        self,
+        accounts: &mut AccountActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = accounts.repos.clone();
+
+        Box::pin(async move {
+            let admin_id = administrator_of(&self.session)?;
+
+            let mut validator = Validator::default();
+            self.form.assert(&mut validator);
+            assert_not_oneself(&mut validator, admin_id, self.form.user_id);
+            validator.check()?;
+
+            let account = repos
+                .execute(MaybeFindOneUserAccount(self.form.user_id))
+                .await?
+                .ok_or_else(Error::not_found)?;
+
+            validator.assert_false(
+                account.role == UserRole::Administrator,
+                Some("impossible d'agir au nom d'un autre administrateur"),
+                ["user_id"],
+            );
+            validator.assert_true(
+                account.activated,
+                Some("le compte est désactivé"),
+                ["user_id"],
+            );
+            validator.check()?;
+
+            let token = generate_token(16);
+            let expires_at = Utc::now() + Duration::minutes(IMPERSONATION_LIFETIME_MINUTES);
+            let session_token = token.clone();
+            let user_id = account.id;
+
+            // la session porte l'administrateur, à qui sont imputées les actions menées
+            repos
+                .transaction(move |tx| {
+                    Box::pin(async move {
+                        tx.execute(InsertUserSession {
+                            token: session_token,
+                            user_id,
+                            expires_at,
+                            second_factor_verified: false,
+                            impersonator_id: Some(admin_id),
+                        })
+                        .await?;
+
+                        tx.execute(InsertAuditEntry {
+                            action: AuditAction::UserImpersonated,
+                            actor_id: Some(admin_id),
+                            subject_id: Some(user_id),
+                            details: Some(self.form.reason),
+                        })
+                        .await?;
+
+                        Ok(())
+                    })
+                })
+                .await?;
+
+            Ok(CreatedUserSession {
+                user_id,
+                token,
+                expires_at,
+            })
+        })
+    }
+}
+
+/// Met fin à une session d'assistance ouverte au nom d'un utilisateur.
+///
+/// La session est révoquée et la fin de l'assistance est consignée ;
+/// l'administrateur doit ensuite rouvrir sa propre session.
+pub struct EndImpersonation {
+    pub session: Session,
+}
+
+impl AccountOp for EndImpersonation {
+    type Return = ();
+
+    fn execute<'fut>(
+        self,
+        accounts: &mut AccountActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = accounts.repos.clone();
+
+        Box::pin(async move {
+            let Session::User(session) = self.session else {
+                return Err(Error::unauthorized());
+            };
+            let admin_id = session.impersonator_id.ok_or_else(Error::unauthorized)?;
+
+            repos
+                .transaction(move |tx| {
+                    Box::pin(async move {
+                        tx.execute(DeleteUserSession(session.id)).await?;
+
+                        tx.execute(InsertAuditEntry {
+                            action: AuditAction::ImpersonationEnded,
+                            actor_id: Some(admin_id),
+                            subject_id: Some(session.user.id),
+                            details: None,
+                        })
+                        .await?;
+
+                        Ok(())
+                    })
+                })
+                .await
+        })
+    }
+}
+
+/// Importe des comptes depuis un système antérieur (réservé aux administrateurs).
+///
+/// Les empreintes de mots de passe sont reprises telles quelles, y compris bcrypt,
+/// puis recalculées avec Argon2id à la première connexion de chaque utilisateur.
+/// Aucun compte n'est importé si l'un d'eux est invalide ou déjà pris.
+pub struct ImportUserAccounts {
+    pub accounts: Vec<ImportUserForm>,
+    pub session: Session,
+}
+
+impl AccountOp for ImportUserAccounts {
+    type Return = Vec<UserId>;
+
+    fn execute<'fut>(
+        self,
+        accounts: &mut AccountActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = accounts.repos.clone();
+
+        Box::pin(async move {
+            let admin_id = administrator_of(&self.session)?;
+
+            let mut validator = Validator::default();
+
+            for (i, account) in self.accounts.iter().enumerate() {
+                let mut account_validator = Validator::default();
+                account.assert(&mut account_validator);
+                account_validator.assert_true(
+                    is_supported_password_hash(&account.password_hash),
+                    Some("l'empreinte du mot de passe n'est ni Argon2 ni bcrypt"),
+                    ["password_hash"],
+                );
+
+                let exists = repos
+                    .execute(UserWithUsernameOrEmailExists {
+                        username: account.username.clone(),
+                        email: account.email.clone(),
+                    })
+                    .await?;
+
+                account_validator.assert_false(
+                    exists.username_exists,
+                    Some("le nom d'utilisateur est déjà pris"),
+                    ["username"],
+                );
+                account_validator.assert_false(
+                    exists.email_exists,
+                    Some("l'adresse courriel est déjà prise"),
+                    ["email"],
+                );
+                account_validator.assert_false(
+                    self.accounts[..i].iter().any(|other| {
+                        other.username == account.username || other.email == account.email
+                    }),
+                    Some("le compte figure plusieurs fois dans l'import"),
+                    ["username"],
+                );
+
+                for issue in account_validator.issues.iter() {
+                    let mut issue = issue.clone();
+                    issue
+                        .path
+                        .splice(0..0, ["accounts".to_owned(), i.to_string()]);
+                    validator.issues.add(issue);
+                }
+            }
+
+            validator.check()?;
+
+            // tous les comptes sont importés, ou aucun
+            repos
+                .transaction(move |tx| {
+                    Box::pin(async move {
+                        let mut user_ids = Vec::with_capacity(self.accounts.len());
+
+                        for account in self.accounts {
+                            let user_id = tx
+                                .execute(InsertImportedUser {
+                                    username: account.username,
+                                    email: account.email,
+                                    password_hash: account.password_hash,
+                                    role: UserRole::default(),
+                                })
+                                .await?;
+
+                            tx.execute(InsertAuditEntry {
+                                action: AuditAction::AccountImported,
+                                actor_id: Some(admin_id),
+                                subject_id: Some(user_id),
+                                details: None,
+                            })
+                            .await?;
+
+                            user_ids.push(user_id);
+                        }
+
+                        Ok(user_ids)
+                    })
+                })
+                .await
         })
     }
 }
@@ -0,0 +1,325 @@
+use actix::{Actor, Addr, Context, Handler, Message, ResponseFuture};
+use futures::future::LocalBoxFuture;
+use sql_gis::types::Point;
+use tracing::{info_span, Instrument, Span};
+
+use crate::error::Error;
+use crate::forms::emitter::CreateEmitterForm;
+use crate::geodesy::{angular_difference, haversine_distance, initial_bearing};
+use crate::metrics;
+use crate::models::emitter::{CandidateEmitter, Emitter, EmitterId, ReportSelection};
+use crate::models::pagination::{Page, PageRequest};
+use crate::models::session::Session;
+use crate::pagination::{CursorKey, Keyset};
+use crate::repositories::emitter::{
+    AttributionObservation, FetchAttributionObservations, FetchEmitters, InsertEmitter,
+};
+use crate::repositories::Repository;
+use crate::validation::{Validation, Validator};
+
+/// Vitesse de vent en dessous de laquelle sa direction n'est pas significative, en m/s.
+const CALM_WIND_SPEED: f64 = 0.5;
+
+#[derive(Clone)]
+/// Paramètres de l'analyse d'attribution.
+pub struct AttributionSettings {
+    /// Distance maximale entre un signalement et un émetteur candidat, en kilomètres.
+    pub radius: f64,
+    /// Distance caractéristique de décroissance du score avec l'éloignement, en kilomètres.
+    pub distance_decay: f64,
+    /// Écart angulaire maximal avec la direction du vent pour qu'un émetteur
+    /// soit considéré sous le vent, en degrés.
+    pub upwind_tolerance: f64,
+}
+
+impl Default for AttributionSettings {
+    fn default() -> Self {
+        Self {
+            radius: 20.0,
+            distance_decay: 5.0,
+            upwind_tolerance: 45.0,
+        }
+    }
+}
+
+/// Service d'attribution des nuisances à des émetteurs potentiels.
+#[derive(Clone)]
+pub struct Attribution(Addr<AttributionActor>);
+
+impl Attribution {
+    pub fn new(repos: Repository, settings: AttributionSettings, cursor_key: CursorKey) -> Self {
+        Self(AttributionActor::new(repos, settings, cursor_key).start())
+    }
+
+    pub async fn execute<O: AttributionOp>(&self, op: O) -> Result<O::Return, Error> {
+        let span = info_span!("attribution", op = metrics::type_label::<O>());
+        self.0.send(ExecuteAttributionOp(op, span)).await?
+    }
+
+    /// Indique si l'acteur du service est toujours en vie.
+    pub fn connected(&self) -> bool {
+        self.0.connected()
+    }
+}
+
+pub struct AttributionActor {
+    repos: Repository,
+    settings: AttributionSettings,
+    cursor_key: CursorKey,
+}
+
+impl AttributionActor {
+    pub fn new(repos: Repository, settings: AttributionSettings, cursor_key: CursorKey) -> Self {
+        Self {
+            repos,
+            settings,
+            cursor_key,
+        }
+    }
+}
+
+impl Actor for AttributionActor {
+    type Context = Context<Self>;
+}
+
+impl<O> Handler<ExecuteAttributionOp<O>> for AttributionActor
+where
+    O: AttributionOp,
+{
+    type Result = ResponseFuture<Result<O::Return, Error>>;
+
+    fn handle(&mut self, msg: ExecuteAttributionOp<O>, _ctx: &mut Self::Context) -> Self::Result {
+        let ExecuteAttributionOp(op, span) = msg;
+        let fut = span.in_scope(|| op.execute(self));
+
+        Box::pin(fut.instrument(span))
+    }
+}
+
+/// Une opération à executer auprès du service d'attribution.
+pub trait AttributionOp: Sync + Send + 'static {
+    type Return: Sync + Send;
+
+    fn execute<'fut>(
+        self,
+        attribution: &mut AttributionActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>>;
+}
+
+pub struct ExecuteAttributionOp<O>(O, Span)
+where
+    O: AttributionOp;
+
+impl<O> Message for ExecuteAttributionOp<O>
+where
+    O: AttributionOp,
+{
+    type Result = Result<O::Return, Error>;
+}
+
+/// Enregistre un émetteur potentiel dans le registre.
+pub struct RegisterEmitter {
+    pub form: CreateEmitterForm,
+    pub session: Session,
+}
+
+impl AttributionOp for RegisterEmitter {
+    type Return = EmitterId;
+
+    fn execute<'fut>(
+        self,
+        attribution: &mut AttributionActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = attribution.repos.clone();
+
+        Box::pin(async move {
+            if !self.session.is_admin() {
+                return Err(Error::unauthorized());
+            }
+
+            let mut validator = Validator::default();
+            self.form.assert(&mut validator);
+            validator.check()?;
+
+            let location = Point::from(self.form.location.unwrap());
+
+            repos
+                .execute(InsertEmitter {
+                    name: self.form.name,
+                    kind: self.form.kind,
+                    description: self.form.description,
+                    location: location.into(),
+                    family_ids: self.form.family_ids,
+                })
+                .await
+        })
+    }
+}
+
+/// Liste les émetteurs du registre, par ordre alphabétique.
+pub struct ListEmitters {
+    pub page: PageRequest,
+}
+
+impl AttributionOp for ListEmitters {
+    type Return = Page<Emitter>;
+
+    fn execute<'fut>(
+        self,
+        attribution: &mut AttributionActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = attribution.repos.clone();
+        let cursor_key = attribution.cursor_key.clone();
+
+        Box::pin(async move {
+            repos
+                .execute(FetchEmitters {
+                    family_ids: None,
+                    page: Keyset::from_request(
+                        self.page,
+                        cursor_key,
+                        FetchEmitters::CURSOR_NAMESPACE,
+                    )?,
+                })
+                .await
+        })
+    }
+}
+
+/// Classe les émetteurs candidats pour un ensemble de signalements,
+/// selon leur distance et leur alignement avec la direction d'où vient le vent.
+pub struct RankCandidateEmitters {
+    pub selection: ReportSelection,
+    pub session: Session,
+}
+
+impl AttributionOp for RankCandidateEmitters {
+    type Return = Vec<CandidateEmitter>;
+
+    fn execute<'fut>(
+        self,
+        attribution: &mut AttributionActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = attribution.repos.clone();
+        let settings = attribution.settings.clone();
+        let cursor_key = attribution.cursor_key.clone();
+
+        Box::pin(async move {
+            if !self.session.is_admin() {
+                return Err(Error::unauthorized());
+            }
+
+            let observations = repos
+                .execute(FetchAttributionObservations(self.selection))
+                .await?;
+
+            let mut family_ids: Vec<_> = observations.iter().map(|o| o.family_id).collect();
+            family_ids.sort();
+            family_ids.dedup();
+
+            // tous les émetteurs des familles concernées sont candidats
+            let mut emitters = Vec::default();
+            let mut request = Some(PageRequest::first(PageRequest::MAX_LIMIT));
+
+            while let Some(page) = request {
+                let page = repos
+                    .execute(FetchEmitters {
+                        family_ids: Some(family_ids.clone()),
+                        page: Keyset::from_request(
+                            page,
+                            cursor_key.clone(),
+                            FetchEmitters::CURSOR_NAMESPACE,
+                        )?,
+                    })
+                    .await?;
+
+                request = page.next(PageRequest::MAX_LIMIT);
+                emitters.extend(page.items);
+            }
+
+            Ok(rank_candidate_emitters(&observations, emitters, &settings))
+        })
+    }
+}
+
+/// Classe les émetteurs par score d'attribution décroissant.
+///
+/// Pour chaque signalement d'une famille émise par l'émetteur et situé dans le rayon,
+/// le score combine la proximité (décroissance exponentielle) et l'alignement entre
+/// le cap vers l'émetteur et la direction d'où vient le vent. Sans vent significatif,
+/// l'alignement est neutre (0,5). Les scores sont pondérés par l'intensité.
+pub fn rank_candidate_emitters(
+    observations: &[AttributionObservation],
+    emitters: Vec<Emitter>,
+    settings: &AttributionSettings,
+) -> Vec<CandidateEmitter> {
+    let mut candidates: Vec<CandidateEmitter> = emitters
+        .into_iter()
+        .filter_map(|emitter| {
+            let mut weighted_score = 0.0;
+            let mut total_weight = 0.0;
+            let mut total_distance = 0.0;
+            let mut analysed_reports = 0;
+            let mut supporting_reports = 0;
+
+            for observation in observations
+                .iter()
+                .filter(|o| emitter.family_ids.contains(&o.family_id))
+            {
+                let distance = haversine_distance(
+                    observation.longitude,
+                    observation.latitude,
+                    emitter.longitude,
+                    emitter.latitude,
+                );
+
+                if distance > settings.radius {
+                    continue;
+                }
+
+                let bearing = initial_bearing(
+                    observation.longitude,
+                    observation.latitude,
+                    emitter.longitude,
+                    emitter.latitude,
+                );
+
+                let wind_direction = observation
+                    .wind_direction
+                    .filter(|_| observation.wind_speed.unwrap_or(0.0) >= CALM_WIND_SPEED);
+
+                let alignment = match wind_direction {
+                    Some(wind_direction) => {
+                        let deviation = angular_difference(bearing, wind_direction);
+
+                        if deviation <= settings.upwind_tolerance {
+                            supporting_reports += 1;
+                        }
+
+                        deviation.to_radians().cos().max(0.0)
+                    }
+                    None => 0.5,
+                };
+
+                let proximity = (-distance / settings.distance_decay).exp();
+                let weight = f64::from(observation.intensity.max(1));
+
+                weighted_score += weight * proximity * alignment;
+                total_weight += weight;
+                total_distance += distance;
+                analysed_reports += 1;
+            }
+
+            (analysed_reports > 0).then(|| CandidateEmitter {
+                emitter,
+                score: weighted_score / total_weight,
+                supporting_reports,
+                analysed_reports,
+                mean_distance: total_distance / analysed_reports as f64,
+            })
+        })
+        .collect();
+
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+    candidates
+}
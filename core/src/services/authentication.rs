@@ -2,39 +2,105 @@ use actix::{Actor, Addr, Context, Handler, Message, ResponseFuture};
 use chrono::{Duration, Utc};
 use futures::future::LocalBoxFuture;
 use std::ops::Add;
+use tracing::{info_span, Instrument, Span};
 use uuid::Uuid;
 
-use crate::crypto::generate_token;
+use crate::crypto::{
+    generate_recovery_code, generate_token, generate_totp_secret, hash_token, random_bytes, totp,
+    totp_step,
+};
 use crate::error::Error;
 use crate::events::{AuthenticationFailed, EventBus};
-use crate::forms::authentication::CredentialForm;
+use crate::forms::authentication::{
+    CredentialForm, PasskeyAssertionForm, PasskeyRegistrationForm, SecondFactorForm,
+};
 use crate::issues::{Issue, Issues};
+use crate::metrics;
+use crate::models::pagination::{Page, PageRequest};
+use crate::models::passkey::{
+    AuthenticatorSelection, ChallengePurpose, Passkey, PasskeyCreationOptions, PasskeyDescriptor,
+    PasskeyId, PasskeyRequestOptions, PasskeyUser, PublicKeyParameters, RelyingParty,
+};
+use crate::models::second_factor::{TotpEnrolment, RECOVERY_CODES_COUNT};
 use crate::models::session::{Session, UserSession};
+use crate::pagination::{CursorKey, Keyset};
+use crate::password_policy::PasswordHashing;
+use crate::rate_limit::{rate_limited, RateLimiter};
 use crate::repositories::credential::MaybeFindOneCredentialByNameOrEmail;
+use crate::repositories::passkey::{
+    ConsumeWebAuthnChallenge, DeletePasskey, FetchPasskeyCredentialIds, FetchPasskeys,
+    InsertPasskey, InsertWebAuthnChallenge, MaybeFindOneStoredPasskey, TouchPasskey,
+};
+use crate::repositories::second_factor::{
+    AcceptTotpStep, ClaimPendingAuthentication, ConsumeRecoveryCode, DeletePendingAuthentication,
+    DisableTotp, EnableTotp, InsertPendingAuthentication, MaybeFindOneUserTotp,
+    ReplaceRecoveryCodes, SetTotpSecret,
+};
+use crate::repositories::user::{MaybeFindOneUserProfile, UpdateUserPassword};
 use crate::repositories::user_session::{InsertUserSession, MaybeFindOneValidUserSessionByToken};
 use crate::repositories::Repository;
+use crate::services::ServiceSettings;
+use crate::validation::{Validation, Validator};
+use crate::webauthn::{
+    check_public_key, decode_base64url, encode_base64url, invalid_passkey,
+    parse_attestation_object, parse_authenticator_data, verify_assertion_signature,
+    WebAuthnSettings, COSE_ALG_ES256,
+};
+
+/// Délai pour présenter le second facteur, en minutes.
+const PENDING_AUTHENTICATION_MINUTES: i64 = 5;
+/// Nombre de codes que l'on peut essayer pour une même authentification.
+const PENDING_AUTHENTICATION_ATTEMPTS: i16 = 5;
+/// Délai accordé à une cérémonie WebAuthn, en minutes.
+const WEBAUTHN_CHALLENGE_MINUTES: i64 = 5;
 
 #[derive(Clone)]
 pub struct Authentication(Addr<AuthenticationActor>);
 
 impl Authentication {
-    pub fn new(repos: Repository, events: EventBus) -> Self {
-        Self(AuthenticationActor::new(repos, events).start())
+    pub fn new(repos: Repository, events: EventBus, settings: ServiceSettings) -> Self {
+        Self(AuthenticationActor::new(repos, events, settings).start())
     }
 
     pub async fn execute<O: AuthenticationOp>(&self, op: O) -> Result<O::Return, Error> {
-        self.0.send(ExecuteAuthenticationOp(op)).await?
+        let span = info_span!("authentication", op = metrics::type_label::<O>());
+        self.0.send(ExecuteAuthenticationOp(op, span)).await?
+    }
+
+    /// Indique si l'acteur du service est toujours en vie.
+    pub fn connected(&self) -> bool {
+        self.0.connected()
     }
 }
 
 pub struct AuthenticationActor {
     repos: Repository,
     events: EventBus,
+    webauthn: WebAuthnSettings,
+    user_session_lifetime: Duration,
+    password_hashing: PasswordHashing,
+    /// Tentatives d'authentification par identifiant, par minute.
+    login_attempts: RateLimiter<String>,
+    cursor_key: CursorKey,
 }
 
 impl AuthenticationActor {
-    pub fn new(repos: Repository, events: EventBus) -> Self {
-        Self { repos, events }
+    pub fn new(repos: Repository, events: EventBus, settings: ServiceSettings) -> Self {
+        Self {
+            repos,
+            events,
+            webauthn: settings.webauthn,
+            user_session_lifetime: settings.user_session_expiration_time,
+            password_hashing: settings.password_hashing,
+            login_attempts: RateLimiter::new(
+                settings
+                    .rate_limits
+                    .enabled
+                    .then_some(settings.rate_limits.login_attempts_per_minute),
+                Duration::minutes(1),
+            ),
+            cursor_key: settings.cursor_key,
+        }
     }
 }
 
@@ -53,9 +119,10 @@ where
         msg: ExecuteAuthenticationOp<O>,
         _ctx: &mut Self::Context,
     ) -> Self::Result {
-        let fut = msg.0.execute(self);
+        let ExecuteAuthenticationOp(op, span) = msg;
+        let fut = span.in_scope(|| op.execute(self));
 
-        Box::pin(fut)
+        Box::pin(fut.instrument(span))
     }
 }
 
@@ -68,7 +135,7 @@ pub trait AuthenticationOp: Sync + Send + 'static {
     ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>>;
 }
 
-pub struct ExecuteAuthenticationOp<O>(O)
+pub struct ExecuteAuthenticationOp<O>(O, Span)
 where
     O: AuthenticationOp;
 
@@ -92,7 +159,7 @@ impl AuthenticateWithCredential {
 }
 
 impl AuthenticationOp for AuthenticateWithCredential {
-    type Return = CreatedUserSession;
+    type Return = AuthenticationOutcome;
 
     fn execute<'fut>(
         self,
@@ -100,7 +167,13 @@ impl AuthenticationOp for AuthenticateWithCredential {
     ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
         let repos = auth.repos.clone();
         let events = auth.events.clone();
-        let user_session_lifetime = Duration::hours(8);
+        let lifetime = auth.user_session_lifetime;
+        let password_hashing = auth.password_hashing;
+
+        let key = self.form.username_or_email.to_lowercase();
+        if !auth.login_attempts.acquire(key, Utc::now()) {
+            return Box::pin(async { Err(rate_limited()) });
+        }
 
         Box::pin(async move {
             let credential = repos
@@ -124,23 +197,50 @@ impl AuthenticationOp for AuthenticateWithCredential {
                     .into_error());
             }
 
-            let user_id = credential.id;
-            let token = generate_token(16);
-            let expires_at = Utc::now().add(user_session_lifetime);
+            // empreinte importée ou produite avec des paramètres dépassés
+            if credential.needs_rehash(&password_hashing) {
+                repos
+                    .execute(UpdateUserPassword {
+                        id: credential.id,
+                        password: self.form.password.clone(),
+                        password_hashing,
+                    })
+                    .await?;
+            }
 
-            repos
-                .execute(InsertUserSession {
-                    user_id,
-                    token: token.clone(),
-                    expires_at,
-                })
-                .await?;
+            if credential.password_reset_pending {
+                return Err(Issues::new()
+                    .add(password_reset_pending_issue())
+                    .to_owned()
+                    .into_error());
+            }
 
-            Ok(CreatedUserSession {
-                user_id,
-                token,
-                expires_at,
-            })
+            if credential.second_factor_enabled {
+                let token = generate_token(16);
+                let expires_at = Utc::now() + Duration::minutes(PENDING_AUTHENTICATION_MINUTES);
+
+                repos
+                    .execute(InsertPendingAuthentication {
+                        token_hash: hash_token(&token),
+                        user_id: credential.id,
+                        expires_at,
+                    })
+                    .await?;
+
+                return Ok(AuthenticationOutcome::SecondFactorRequired(
+                    PendingAuthentication { token, expires_at },
+                ));
+            }
+
+            let outcome = if credential.role.requires_second_factor() {
+                AuthenticationOutcome::SecondFactorEnrolmentRequired
+            } else {
+                AuthenticationOutcome::Authenticated
+            };
+
+            open_user_session(&repos, credential.id, false, lifetime)
+                .await
+                .map(outcome)
         })
     }
 }
@@ -151,6 +251,61 @@ pub struct CreatedUserSession {
     pub expires_at: chrono::DateTime<Utc>,
 }
 
+/// Authentification en attente du second facteur.
+pub struct PendingAuthentication {
+    /// Jeton à présenter avec le code du second facteur.
+    pub token: String,
+    pub expires_at: chrono::DateTime<Utc>,
+}
+
+/// Issue d'une authentification par identifiants.
+pub enum AuthenticationOutcome {
+    /// La session est ouverte.
+    Authenticated(CreatedUserSession),
+    /// L'utilisateur doit encore présenter un code de son second facteur.
+    SecondFactorRequired(PendingAuthentication),
+    /// Le rôle de l'utilisateur exige un second facteur auquel il ne s'est pas
+    /// encore inscrit : la session, sans privilèges, ne sert qu'à cette inscription.
+    SecondFactorEnrolmentRequired(CreatedUserSession),
+}
+
+impl AuthenticationOutcome {
+    /// Retourne la session ouverte, si l'authentification est complète.
+    pub fn session(self) -> Option<CreatedUserSession> {
+        match self {
+            Self::Authenticated(session) => Some(session),
+            Self::SecondFactorRequired(_) | Self::SecondFactorEnrolmentRequired(_) => None,
+        }
+    }
+}
+
+/// Ouvre une nouvelle session pour l'utilisateur.
+async fn open_user_session(
+    repos: &Repository,
+    user_id: Uuid,
+    second_factor_verified: bool,
+    lifetime: Duration,
+) -> Result<CreatedUserSession, Error> {
+    let token = generate_token(16);
+    let expires_at = Utc::now().add(lifetime);
+
+    repos
+        .execute(InsertUserSession {
+            user_id,
+            token: token.clone(),
+            expires_at,
+            second_factor_verified,
+            impersonator_id: None,
+        })
+        .await?;
+
+    Ok(CreatedUserSession {
+        user_id,
+        token,
+        expires_at,
+    })
+}
+
 /// Vérifie le jeton de la session utilisateur.
 pub struct CheckUserSessionToken {
     pub token: String,
@@ -189,3 +344,671 @@ fn invalid_credential_issue() -> Issue {
         Vec::<String>::default(),
     )
 }
+
+#[inline]
+fn password_reset_pending_issue() -> Issue {
+    Issue::new(
+        "password_reset_pending",
+        "Un changement de mot de passe est requis",
+        Vec::<String>::default(),
+    )
+}
+
+/// Vérifie un code TOTP, ou à défaut consomme un code de récupération.
+async fn verify_second_factor(
+    repos: &Repository,
+    user_id: Uuid,
+    account_name: &str,
+    secret: &str,
+    code: &str,
+) -> Result<bool, Error> {
+    let code = code.trim().to_lowercase();
+
+    if let Some(accepted) = accept_totp_code(repos, user_id, account_name, secret, &code).await? {
+        return Ok(accepted);
+    }
+
+    repos
+        .execute(ConsumeRecoveryCode {
+            user_id,
+            code_hash: hash_token(&code),
+        })
+        .await
+}
+
+/// Vérifie un code TOTP, qui n'est accepté qu'une fois : les codes d'un pas de
+/// temps antérieur ou égal au dernier accepté sont refusés.
+///
+/// Retourne `None` si le code ne correspond à aucun pas toléré.
+async fn accept_totp_code(
+    repos: &Repository,
+    user_id: Uuid,
+    account_name: &str,
+    secret: &str,
+    code: &str,
+) -> Result<Option<bool>, Error> {
+    let generator = totp(secret, account_name)?;
+    let now = u64::try_from(Utc::now().timestamp()).map_err(|_| Error::internal_error())?;
+
+    let Some(step) = totp_step(&generator, code, now) else {
+        return Ok(None);
+    };
+
+    repos
+        .execute(AcceptTotpStep {
+            user_id,
+            step: i64::try_from(step).map_err(|_| Error::internal_error())?,
+        })
+        .await
+        .map(Some)
+}
+
+/// Génère de nouveaux codes de récupération, dont seules les empreintes sont conservées.
+async fn renew_recovery_codes(repos: &Repository, user_id: Uuid) -> Result<Vec<String>, Error> {
+    let codes: Vec<String> = (0..RECOVERY_CODES_COUNT)
+        .map(|_| generate_recovery_code())
+        .collect();
+
+    repos
+        .execute(ReplaceRecoveryCodes {
+            user_id,
+            code_hashes: codes.iter().map(|code| hash_token(code)).collect(),
+        })
+        .await?;
+
+    Ok(codes)
+}
+
+/// Termine une authentification en attente avec un code TOTP ou de récupération.
+pub struct VerifySecondFactor {
+    pub form: SecondFactorForm,
+}
+
+impl AuthenticationOp for VerifySecondFactor {
+    type Return = CreatedUserSession;
+
+    fn execute<'fut>(
+        self,
+        auth: &mut AuthenticationActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = auth.repos.clone();
+        let events = auth.events.clone();
+        let lifetime = auth.user_session_lifetime;
+
+        Box::pin(async move {
+            let pending = repos
+                .execute(ClaimPendingAuthentication {
+                    token_hash: hash_token(&self.form.token),
+                    max_attempts: PENDING_AUTHENTICATION_ATTEMPTS,
+                })
+                .await?
+                .ok_or_else(|| {
+                    Issues::new()
+                        .add(expired_authentication_issue())
+                        .to_owned()
+                        .into_error()
+                })?;
+
+            let totp = repos
+                .execute(MaybeFindOneUserTotp(pending.user_id))
+                .await?
+                .filter(|totp| totp.enabled)
+                .ok_or_else(Error::unauthorized)?;
+
+            let profile = repos
+                .execute(MaybeFindOneUserProfile(pending.user_id))
+                .await?
+                .ok_or_else(Error::unauthorized)?;
+
+            if !verify_second_factor(
+                &repos,
+                pending.user_id,
+                &profile.username,
+                &totp.secret,
+                &self.form.code,
+            )
+            .await?
+            {
+                events.notify(AuthenticationFailed(pending.user_id));
+
+                return Err(Issues::new()
+                    .add(invalid_second_factor_issue())
+                    .to_owned()
+                    .into_error());
+            }
+
+            repos
+                .execute(DeletePendingAuthentication(pending.id))
+                .await?;
+
+            open_user_session(&repos, pending.user_id, true, lifetime).await
+        })
+    }
+}
+
+/// Démarre l'inscription de l'utilisateur connecté à l'authentification à deux facteurs.
+///
+/// Le secret n'est effectif qu'une fois confirmé par un premier code.
+pub struct BeginTotpEnrolment {
+    pub session: Session,
+}
+
+impl AuthenticationOp for BeginTotpEnrolment {
+    type Return = TotpEnrolment;
+
+    fn execute<'fut>(
+        self,
+        auth: &mut AuthenticationActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = auth.repos.clone();
+
+        Box::pin(async move {
+            let user = self.session.user().ok_or_else(Error::unauthorized)?;
+
+            let current = repos.execute(MaybeFindOneUserTotp(user.id)).await?;
+
+            let mut validator = Validator::default();
+            validator.assert_false(
+                current.is_some_and(|current| current.enabled),
+                Some("l'authentification à deux facteurs est déjà activée"),
+                ["code"],
+            );
+            validator.check()?;
+
+            let secret = generate_totp_secret();
+            let generator = totp(&secret, &user.username)?;
+
+            repos
+                .execute(SetTotpSecret {
+                    user_id: user.id,
+                    secret: secret.clone(),
+                })
+                .await?;
+
+            Ok(TotpEnrolment {
+                provisioning_uri: generator.get_url(),
+                qr_code: generator
+                    .get_qr_base64()
+                    .map_err(|_| Error::internal_error())?,
+                secret,
+            })
+        })
+    }
+}
+
+/// Confirme l'inscription au second facteur avec un premier code, et délivre
+/// les codes de récupération, présentés une seule fois.
+pub struct ConfirmTotpEnrolment {
+    pub code: String,
+    pub session: Session,
+}
+
+impl AuthenticationOp for ConfirmTotpEnrolment {
+    type Return = Vec<String>;
+
+    fn execute<'fut>(
+        self,
+        auth: &mut AuthenticationActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = auth.repos.clone();
+
+        Box::pin(async move {
+            let user = self.session.user().ok_or_else(Error::unauthorized)?;
+
+            let pending = repos
+                .execute(MaybeFindOneUserTotp(user.id))
+                .await?
+                .filter(|totp| !totp.enabled)
+                .ok_or_else(Error::not_found)?;
+
+            let accepted = accept_totp_code(
+                &repos,
+                user.id,
+                &user.username,
+                &pending.secret,
+                self.code.trim(),
+            )
+            .await?
+            .unwrap_or(false);
+
+            let mut validator = Validator::default();
+            validator.assert_true(accepted, Some("le code est incorrect"), ["code"]);
+            validator.check()?;
+
+            repos.execute(EnableTotp(user.id)).await?;
+
+            renew_recovery_codes(&repos, user.id).await
+        })
+    }
+}
+
+/// Remplace les codes de récupération de l'utilisateur connecté, sur présentation
+/// d'un code de son second facteur.
+pub struct RegenerateRecoveryCodes {
+    pub code: String,
+    pub session: Session,
+}
+
+impl AuthenticationOp for RegenerateRecoveryCodes {
+    type Return = Vec<String>;
+
+    fn execute<'fut>(
+        self,
+        auth: &mut AuthenticationActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = auth.repos.clone();
+
+        Box::pin(async move {
+            let user = self.session.user().ok_or_else(Error::unauthorized)?;
+
+            let totp = repos
+                .execute(MaybeFindOneUserTotp(user.id))
+                .await?
+                .filter(|totp| totp.enabled)
+                .ok_or_else(Error::not_found)?;
+
+            let mut validator = Validator::default();
+            validator.assert_true(
+                verify_second_factor(&repos, user.id, &user.username, &totp.secret, &self.code)
+                    .await?,
+                Some("le code est incorrect"),
+                ["code"],
+            );
+            validator.check()?;
+
+            renew_recovery_codes(&repos, user.id).await
+        })
+    }
+}
+
+/// Désactive l'authentification à deux facteurs de l'utilisateur connecté,
+/// sur présentation d'un code de son second facteur.
+///
+/// Les rôles qui l'exigent ne peuvent pas la désactiver.
+pub struct DisableSecondFactor {
+    pub code: String,
+    pub session: Session,
+}
+
+impl AuthenticationOp for DisableSecondFactor {
+    type Return = ();
+
+    fn execute<'fut>(
+        self,
+        auth: &mut AuthenticationActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = auth.repos.clone();
+
+        Box::pin(async move {
+            let user = self.session.user().ok_or_else(Error::unauthorized)?;
+
+            let totp = repos
+                .execute(MaybeFindOneUserTotp(user.id))
+                .await?
+                .filter(|totp| totp.enabled)
+                .ok_or_else(Error::not_found)?;
+
+            let mut validator = Validator::default();
+            validator.assert_false(
+                user.role.requires_second_factor(),
+                Some("votre rôle exige l'authentification à deux facteurs"),
+                ["code"],
+            );
+            validator.check()?;
+
+            validator.assert_true(
+                verify_second_factor(&repos, user.id, &user.username, &totp.secret, &self.code)
+                    .await?,
+                Some("le code est incorrect"),
+                ["code"],
+            );
+            validator.check()?;
+
+            repos.execute(DisableTotp(user.id)).await
+        })
+    }
+}
+
+#[inline]
+fn expired_authentication_issue() -> Issue {
+    Issue::new(
+        "expired_authentication",
+        "L'authentification a expiré, veuillez vous reconnecter",
+        Vec::<String>::default(),
+    )
+}
+
+#[inline]
+fn invalid_second_factor_issue() -> Issue {
+    Issue::new("invalid_form", "Le code est incorrect", ["code"])
+}
+
+/// Crée et enregistre le défi d'une cérémonie WebAuthn.
+async fn issue_webauthn_challenge(
+    repos: &Repository,
+    purpose: ChallengePurpose,
+    user_id: Option<Uuid>,
+) -> Result<String, Error> {
+    let challenge = encode_base64url(&random_bytes(32));
+
+    repos
+        .execute(InsertWebAuthnChallenge {
+            challenge: challenge.clone(),
+            purpose,
+            user_id,
+            expires_at: Utc::now() + Duration::minutes(WEBAUTHN_CHALLENGE_MINUTES),
+        })
+        .await?;
+
+    Ok(challenge)
+}
+
+/// Démarre l'enregistrement d'une clé d'accès pour l'utilisateur connecté.
+pub struct BeginPasskeyRegistration {
+    pub session: Session,
+}
+
+impl AuthenticationOp for BeginPasskeyRegistration {
+    type Return = PasskeyCreationOptions;
+
+    fn execute<'fut>(
+        self,
+        auth: &mut AuthenticationActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = auth.repos.clone();
+        let webauthn = auth.webauthn.clone();
+
+        Box::pin(async move {
+            let user = self.session.user().ok_or_else(Error::unauthorized)?;
+
+            // une clé vérifiant l'utilisateur vaut second facteur : elle ne peut
+            // pas remplacer l'inscription exigée par le rôle
+            if !self.session.second_factor_satisfied() {
+                return Err(Error::unauthorized());
+            }
+
+            let challenge =
+                issue_webauthn_challenge(&repos, ChallengePurpose::Registration, Some(user.id))
+                    .await?;
+
+            let exclude_credentials = repos
+                .execute(FetchPasskeyCredentialIds(user.id))
+                .await?
+                .iter()
+                .map(|id| PasskeyDescriptor {
+                    kind: "public-key".to_owned(),
+                    id: encode_base64url(id),
+                })
+                .collect();
+
+            Ok(PasskeyCreationOptions {
+                challenge,
+                rp: RelyingParty {
+                    id: webauthn.rp_id,
+                    name: webauthn.rp_name,
+                },
+                user: PasskeyUser {
+                    id: encode_base64url(user.id.as_bytes()),
+                    name: user.username.clone(),
+                    display_name: user.username.clone(),
+                },
+                pub_key_cred_params: vec![PublicKeyParameters {
+                    kind: "public-key".to_owned(),
+                    alg: COSE_ALG_ES256,
+                }],
+                timeout: (WEBAUTHN_CHALLENGE_MINUTES * 60_000) as u64,
+                exclude_credentials,
+                authenticator_selection: AuthenticatorSelection {
+                    resident_key: "required".to_owned(),
+                    user_verification: "preferred".to_owned(),
+                },
+            })
+        })
+    }
+}
+
+/// Enregistre la clé d'accès créée par l'authentificateur de l'utilisateur connecté.
+pub struct FinishPasskeyRegistration {
+    pub form: PasskeyRegistrationForm,
+    pub session: Session,
+}
+
+impl AuthenticationOp for FinishPasskeyRegistration {
+    type Return = PasskeyId;
+
+    fn execute<'fut>(
+        self,
+        auth: &mut AuthenticationActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = auth.repos.clone();
+        let webauthn = auth.webauthn.clone();
+
+        Box::pin(async move {
+            let user = self.session.user().ok_or_else(Error::unauthorized)?;
+
+            if !self.session.second_factor_satisfied() {
+                return Err(Error::unauthorized());
+            }
+
+            let mut validator = Validator::default();
+            self.form.assert(&mut validator);
+            validator.check()?;
+
+            let client_data_json = decode_base64url(&self.form.client_data_json)?;
+            let challenge = webauthn.verify_client_data(&client_data_json, "webauthn.create")?;
+
+            if !repos
+                .execute(ConsumeWebAuthnChallenge {
+                    challenge,
+                    purpose: ChallengePurpose::Registration,
+                    user_id: Some(user.id),
+                })
+                .await?
+            {
+                return Err(invalid_passkey());
+            }
+
+            let auth_data =
+                parse_attestation_object(&decode_base64url(&self.form.attestation_object)?)?;
+            webauthn.verify_authenticator_data(&auth_data)?;
+
+            let credential = auth_data
+                .attested_credential
+                .filter(|credential| {
+                    decode_base64url(&self.form.credential_id)
+                        .is_ok_and(|id| id == credential.credential_id)
+                })
+                .ok_or_else(invalid_passkey)?;
+
+            check_public_key(&credential.public_key)?;
+
+            repos
+                .execute(InsertPasskey {
+                    user_id: user.id,
+                    credential_id: credential.credential_id,
+                    public_key: credential.public_key,
+                    sign_count: auth_data.sign_count.into(),
+                    name: self.form.name.trim().to_owned(),
+                })
+                .await
+        })
+    }
+}
+
+/// Démarre une authentification par clé d'accès.
+///
+/// Aucun identifiant n'est demandé : l'authentificateur propose les clés
+/// dont il dispose pour la partie de confiance.
+pub struct BeginPasskeyAuthentication;
+
+impl AuthenticationOp for BeginPasskeyAuthentication {
+    type Return = PasskeyRequestOptions;
+
+    fn execute<'fut>(
+        self,
+        auth: &mut AuthenticationActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = auth.repos.clone();
+        let webauthn = auth.webauthn.clone();
+
+        Box::pin(async move {
+            let challenge =
+                issue_webauthn_challenge(&repos, ChallengePurpose::Authentication, None).await?;
+
+            Ok(PasskeyRequestOptions {
+                challenge,
+                rp_id: webauthn.rp_id,
+                timeout: (WEBAUTHN_CHALLENGE_MINUTES * 60_000) as u64,
+                user_verification: "preferred".to_owned(),
+            })
+        })
+    }
+}
+
+/// Authentifie avec une clé d'accès, sans mot de passe.
+///
+/// Une clé dont l'authentificateur a vérifié l'utilisateur vaut second facteur.
+pub struct AuthenticateWithPasskey {
+    pub form: PasskeyAssertionForm,
+}
+
+impl AuthenticationOp for AuthenticateWithPasskey {
+    type Return = CreatedUserSession;
+
+    fn execute<'fut>(
+        self,
+        auth: &mut AuthenticationActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = auth.repos.clone();
+        let events = auth.events.clone();
+        let lifetime = auth.user_session_lifetime;
+        let webauthn = auth.webauthn.clone();
+
+        Box::pin(async move {
+            let client_data_json = decode_base64url(&self.form.client_data_json)?;
+            let challenge = webauthn.verify_client_data(&client_data_json, "webauthn.get")?;
+
+            if !repos
+                .execute(ConsumeWebAuthnChallenge {
+                    challenge,
+                    purpose: ChallengePurpose::Authentication,
+                    user_id: None,
+                })
+                .await?
+            {
+                return Err(invalid_passkey());
+            }
+
+            let passkey = repos
+                .execute(MaybeFindOneStoredPasskey(decode_base64url(
+                    &self.form.credential_id,
+                )?))
+                .await?
+                .ok_or_else(invalid_passkey)?;
+
+            let authenticator_data = decode_base64url(&self.form.authenticator_data)?;
+            let auth_data = parse_authenticator_data(&authenticator_data)?;
+            webauthn.verify_authenticator_data(&auth_data)?;
+
+            if !verify_assertion_signature(
+                &passkey.public_key,
+                &authenticator_data,
+                &client_data_json,
+                &decode_base64url(&self.form.signature)?,
+            )? {
+                events.notify(AuthenticationFailed(passkey.user_id));
+                return Err(invalid_passkey());
+            }
+
+            // Un compteur qui ne progresse pas trahit une clé clonée ; les
+            // authentificateurs sans compteur renvoient toujours zéro.
+            let sign_count = i64::from(auth_data.sign_count);
+            if (sign_count != 0 || passkey.sign_count != 0) && sign_count <= passkey.sign_count {
+                events.notify(AuthenticationFailed(passkey.user_id));
+                return Err(invalid_passkey());
+            }
+
+            if !repos
+                .execute(TouchPasskey {
+                    id: passkey.id,
+                    sign_count,
+                })
+                .await?
+            {
+                events.notify(AuthenticationFailed(passkey.user_id));
+                return Err(invalid_passkey());
+            }
+
+            if passkey.password_reset_pending {
+                return Err(Issues::new()
+                    .add(password_reset_pending_issue())
+                    .to_owned()
+                    .into_error());
+            }
+
+            let verified = auth_data.user_verified();
+            open_user_session(&repos, passkey.user_id, verified, lifetime).await
+        })
+    }
+}
+
+/// Liste les clés d'accès de l'utilisateur connecté.
+pub struct ListMyPasskeys {
+    pub page: PageRequest,
+    pub session: Session,
+}
+
+impl AuthenticationOp for ListMyPasskeys {
+    type Return = Page<Passkey>;
+
+    fn execute<'fut>(
+        self,
+        auth: &mut AuthenticationActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = auth.repos.clone();
+        let cursor_key = auth.cursor_key.clone();
+
+        Box::pin(async move {
+            let user = self.session.user().ok_or_else(Error::unauthorized)?;
+
+            repos
+                .execute(FetchPasskeys {
+                    user_id: user.id,
+                    page: Keyset::from_request(
+                        self.page,
+                        cursor_key,
+                        FetchPasskeys::CURSOR_NAMESPACE,
+                    )?,
+                })
+                .await
+        })
+    }
+}
+
+/// Supprime une clé d'accès de l'utilisateur connecté.
+pub struct DeleteMyPasskey {
+    pub id: PasskeyId,
+    pub session: Session,
+}
+
+impl AuthenticationOp for DeleteMyPasskey {
+    type Return = ();
+
+    fn execute<'fut>(
+        self,
+        auth: &mut AuthenticationActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = auth.repos.clone();
+
+        Box::pin(async move {
+            let user = self.session.user().ok_or_else(Error::unauthorized)?;
+
+            repos
+                .execute(DeletePasskey {
+                    id: self.id,
+                    user_id: user.id,
+                })
+                .await
+        })
+    }
+}
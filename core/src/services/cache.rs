@@ -0,0 +1,65 @@
+use actix::{Actor, AsyncContext, Context, Handler, ResponseFuture};
+
+use crate::cache::{QueryCache, STATISTICS, TAXONOMY};
+use crate::events::{
+    EventBus, NuisanceReportChanged, NuisanceReported, OnNuisanceReportChanged, OnNuisanceReported,
+    OnTaxonomyChanged, TaxonomyChanged,
+};
+
+/// Invalide les entrées du cache à la modification des données sous-jacentes.
+///
+/// Les opérations du service de signalement invalident en outre le cache
+/// directement, afin que leur auteur relise aussitôt ses propres écritures.
+pub struct CacheInvalidator {
+    cache: QueryCache,
+    events: EventBus,
+}
+
+impl CacheInvalidator {
+    pub fn new(cache: QueryCache, events: EventBus) -> Self {
+        Self { cache, events }
+    }
+}
+
+impl Actor for CacheInvalidator {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        self.events
+            .subscribe(OnTaxonomyChanged(ctx.address().recipient()));
+        self.events
+            .subscribe(OnNuisanceReported(ctx.address().recipient()));
+        self.events
+            .subscribe(OnNuisanceReportChanged(ctx.address().recipient()));
+    }
+}
+
+impl Handler<TaxonomyChanged> for CacheInvalidator {
+    type Result = ResponseFuture<()>;
+
+    fn handle(&mut self, _msg: TaxonomyChanged, _ctx: &mut Self::Context) -> Self::Result {
+        let cache = self.cache.clone();
+
+        Box::pin(async move { cache.invalidate(TAXONOMY).await })
+    }
+}
+
+impl Handler<NuisanceReported> for CacheInvalidator {
+    type Result = ResponseFuture<()>;
+
+    fn handle(&mut self, _msg: NuisanceReported, _ctx: &mut Self::Context) -> Self::Result {
+        let cache = self.cache.clone();
+
+        Box::pin(async move { cache.invalidate(STATISTICS).await })
+    }
+}
+
+impl Handler<NuisanceReportChanged> for CacheInvalidator {
+    type Result = ResponseFuture<()>;
+
+    fn handle(&mut self, _msg: NuisanceReportChanged, _ctx: &mut Self::Context) -> Self::Result {
+        let cache = self.cache.clone();
+
+        Box::pin(async move { cache.invalidate(STATISTICS).await })
+    }
+}
@@ -0,0 +1,104 @@
+use actix::{Actor, Addr, AsyncContext, Context, Handler, ResponseFuture};
+use log::warn;
+use tracing::{info_span, Instrument};
+
+use crate::error::Error;
+use crate::events::{EventBus, NuisanceReported, OnNuisanceReported};
+use crate::models::nuisance_report::NuisanceReportId;
+use crate::repositories::nuisance_report::MaybeFindOneNuisanceReportPosition;
+use crate::repositories::weather::InsertNuisanceReportWeather;
+use crate::repositories::Repository;
+use crate::weather::Weather;
+
+/// Service d'enrichissement des signalements nouvellement créés
+/// (conditions météorologiques, etc.)
+#[derive(Clone)]
+pub struct Enrichment(Addr<EnrichmentActor>);
+
+impl Enrichment {
+    pub fn new(repos: Repository, events: EventBus, weather: Option<Weather>) -> Self {
+        Self(EnrichmentActor::new(repos, events, weather).start())
+    }
+
+    /// Indique si l'acteur du service est toujours en vie.
+    pub fn connected(&self) -> bool {
+        self.0.connected()
+    }
+}
+
+pub struct EnrichmentActor {
+    repos: Repository,
+    events: EventBus,
+    weather: Option<Weather>,
+}
+
+impl EnrichmentActor {
+    pub fn new(repos: Repository, events: EventBus, weather: Option<Weather>) -> Self {
+        Self {
+            repos,
+            events,
+            weather,
+        }
+    }
+}
+
+impl Actor for EnrichmentActor {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        self.events
+            .subscribe(OnNuisanceReported(ctx.address().recipient()));
+    }
+}
+
+impl Handler<NuisanceReported> for EnrichmentActor {
+    type Result = ResponseFuture<()>;
+
+    fn handle(&mut self, msg: NuisanceReported, _ctx: &mut Self::Context) -> Self::Result {
+        let repos = self.repos.clone();
+        let weather = self.weather.clone();
+
+        // l'enrichissement suit un évènement : il n'hérite pas du span de la requête
+        let span = info_span!("enrichment", report_id = %msg.0);
+
+        Box::pin(
+            async move {
+                if let Some(weather) = weather {
+                    if let Err(error) = enrich_with_weather(&repos, &weather, msg.0).await {
+                        warn!(target: "signuis::enrichment", "impossible d'enrichir le signalement {} avec la météo: {}", msg.0, error.chain());
+                    }
+                }
+            }
+            .instrument(span),
+        )
+    }
+}
+
+/// Associe au signalement les conditions météorologiques lors de son observation.
+async fn enrich_with_weather(
+    repos: &Repository,
+    weather: &Weather,
+    report_id: NuisanceReportId,
+) -> Result<(), Error> {
+    let Some(position) = repos
+        .execute(MaybeFindOneNuisanceReportPosition(report_id))
+        .await?
+    else {
+        return Ok(());
+    };
+
+    let observation = weather
+        .observe(position.longitude, position.latitude, position.at)
+        .await?;
+
+    if let Some(observation) = observation {
+        repos
+            .execute(InsertNuisanceReportWeather {
+                report_id,
+                observation,
+            })
+            .await?;
+    }
+
+    Ok(())
+}
@@ -1,18 +1,89 @@
 pub mod account;
+pub mod attribution;
 pub mod authentication;
+pub mod cache;
+pub mod enrichment;
 pub mod reporting;
+pub mod retention;
+pub mod statistics;
+pub mod territory;
 
 use chrono::Duration;
+use serde::Deserialize;
+
+use attribution::AttributionSettings;
+
+use crate::cache::CacheSettings;
+use crate::pagination::CursorKey;
+use crate::password_policy::{PasswordHashing, PasswordPolicy};
+use crate::privacy::PrivacySettings;
+use crate::webauthn::WebAuthnSettings;
 
 #[derive(Clone)]
 pub struct ServiceSettings {
     pub user_session_expiration_time: Duration,
+    /// Délai pendant lequel un signalement peut être modifié ou retiré par son auteur.
+    pub report_edition_grace_period: Duration,
+    pub attribution: AttributionSettings,
+    /// Protection de la vie privée des déclarants dans les données restituées.
+    pub privacy: PrivacySettings,
+    /// Fuseau horaire des statistiques par jour de la semaine et heure.
+    pub statistics_time_zone: String,
+    /// Intervalle de recalcul des statistiques agrégées, `None` pour le désactiver.
+    pub statistics_refresh_interval: Option<std::time::Duration>,
+    /// Durée de conservation des signalements anonymes, `None` pour les conserver indéfiniment.
+    pub anonymous_data_retention: Option<Duration>,
+    /// Partie de confiance des clés d'accès.
+    pub webauthn: WebAuthnSettings,
+    /// Règles imposées aux nouveaux mots de passe.
+    pub password_policy: PasswordPolicy,
+    /// Paramètres de hachage des mots de passe.
+    pub password_hashing: PasswordHashing,
+    /// Limites de fréquence des requêtes.
+    pub rate_limits: RateLimitSettings,
+    /// Clé de signature des curseurs de pagination.
+    pub cursor_key: CursorKey,
+    /// Cache des résultats du répertoire.
+    pub cache: CacheSettings,
 }
 
 impl Default for ServiceSettings {
     fn default() -> Self {
         Self {
             user_session_expiration_time: Duration::hours(8),
+            report_edition_grace_period: Duration::hours(1),
+            attribution: AttributionSettings::default(),
+            privacy: PrivacySettings::default(),
+            statistics_time_zone: "Europe/Paris".to_owned(),
+            statistics_refresh_interval: Some(std::time::Duration::from_secs(5 * 60)),
+            anonymous_data_retention: None,
+            webauthn: WebAuthnSettings::default(),
+            password_policy: PasswordPolicy::default(),
+            password_hashing: PasswordHashing::default(),
+            rate_limits: RateLimitSettings::default(),
+            cursor_key: CursorKey::random(),
+            cache: CacheSettings::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+/// Limites de fréquence des requêtes, par compte.
+pub struct RateLimitSettings {
+    pub enabled: bool,
+    /// Tentatives d'authentification par minute.
+    pub login_attempts_per_minute: u32,
+    /// Signalements par heure.
+    pub reports_per_hour: u32,
+}
+
+impl Default for RateLimitSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            login_attempts_per_minute: 10,
+            reports_per_hour: 30,
         }
     }
 }
@@ -1,46 +1,116 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
 use actix::prelude::*;
+use chrono::{Duration, Utc};
 use futures::future::LocalBoxFuture;
+use log::warn;
 use sql_gis::types::Point;
+use tracing::{info_span, Instrument, Span};
+use uuid::Uuid;
 
+use crate::cache::{QueryCache, STATISTICS, TAXONOMY};
 use crate::error::Error;
-use crate::events::EventBus;
+use crate::events::{EventBus, NuisanceReportChanged, NuisanceReported, TaxonomyChanged};
 use crate::forms::reporting::{
     CreateNuisanceFamilyForm, CreateNuisanceReportForm, CreateNuisanceTypeForm,
+    UpdateNuisanceReportForm,
 };
+use crate::media::{extension_of, strip_gps_metadata};
 use crate::models::nuisance_family::{NuisanceFamily, NuisanceFamilyId};
-use crate::models::nuisance_report::NuisanceReportId;
+use crate::models::nuisance_report::{NuisanceReport, NuisanceReportId, NuisanceReportSummary};
 use crate::models::nuisance_type::NuisanceTypeId;
-
-use crate::models::session::Session;
+use crate::models::pagination::{Page, PageRequest};
+use crate::pagination::Keyset;
+
+use crate::metrics;
+use crate::models::session::{Session, SessionUser};
+use crate::models::statistics::{ReportStatistic, ReportStatisticsQuery, WeeklyProfileCell};
+use crate::models::user::UserId;
+use crate::privacy::PrivacyPolicy;
+use crate::rate_limit::{rate_limited, RateLimiter};
+use crate::repositories::administrative_area::LinkNuisanceReportAreas;
 use crate::repositories::nuisance_family::{
     FetchNuisanceFamilies, InsertNuisanceFamily, NuisanceFamilyExists,
 };
-use crate::repositories::nuisance_report::InsertNuisanceReport;
+use crate::repositories::nuisance_report::{
+    FetchNuisanceReportPhotos, FetchNuisanceReportsByUser, InsertNuisanceReport,
+    InsertNuisanceReportPhoto, MaybeFindOneNuisanceReport, MaybeFindOneNuisanceReportById,
+    PatchNuisanceReport, SoftDeleteNuisanceReport,
+};
 use crate::repositories::nuisance_type::{InsertNuisanceType, NuisanceTypeExists};
+use crate::repositories::statistics::{FetchReportStatistics, FetchWeeklyProfile};
+use crate::repositories::weather::MaybeFindOneNuisanceReportWeather;
 use crate::repositories::Repository;
+use crate::storage::Storage;
 use crate::validation::{Validation, Validator};
 
+use super::ServiceSettings;
+
 #[derive(Clone)]
 pub struct Reporting(Addr<ReportingActor>);
 
 impl Reporting {
-    pub fn new(repos: Repository, events: EventBus) -> Self {
-        Self(ReportingActor::new(repos, events).start())
+    pub fn new(
+        repos: Repository,
+        events: EventBus,
+        storage: Storage,
+        cache: QueryCache,
+        settings: ServiceSettings,
+    ) -> Self {
+        Self(ReportingActor::new(repos, events, storage, cache, settings).start())
     }
 
     pub async fn execute<O: ReportingOp>(&self, op: O) -> Result<O::Return, Error> {
-        self.0.send(ExecuteReportingOp(op)).await?
+        let span = info_span!("reporting", op = metrics::type_label::<O>());
+        self.0.send(ExecuteReportingOp(op, span)).await?
+    }
+
+    /// Indique si l'acteur du service est toujours en vie.
+    pub fn connected(&self) -> bool {
+        self.0.connected()
     }
 }
 
 pub struct ReportingActor {
     repos: Repository,
     events: EventBus,
+    storage: Storage,
+    cache: QueryCache,
+    settings: ServiceSettings,
+    /// Signalements par compte, par heure.
+    reports: RateLimiter<UserId>,
 }
 
 impl ReportingActor {
-    pub fn new(repos: Repository, events: EventBus) -> Self {
-        Self { repos, events }
+    pub fn new(
+        repos: Repository,
+        events: EventBus,
+        storage: Storage,
+        cache: QueryCache,
+        settings: ServiceSettings,
+    ) -> Self {
+        let reports = RateLimiter::new(
+            settings
+                .rate_limits
+                .enabled
+                .then_some(settings.rate_limits.reports_per_hour),
+            Duration::hours(1),
+        );
+
+        Self {
+            repos,
+            events,
+            storage,
+            cache,
+            settings,
+            reports,
+        }
+    }
+
+    /// Règles de confidentialité des données restituées à la session.
+    pub fn privacy_policy(&self, session: &Session) -> PrivacyPolicy {
+        PrivacyPolicy::new(&self.settings.privacy, session)
     }
 }
 
@@ -55,9 +125,10 @@ where
     type Result = ResponseFuture<Result<O::Return, Error>>;
 
     fn handle(&mut self, msg: ExecuteReportingOp<O>, _ctx: &mut Self::Context) -> Self::Result {
-        let fut = msg.0.execute(self);
+        let ExecuteReportingOp(op, span) = msg;
+        let fut = span.in_scope(|| op.execute(self));
 
-        Box::pin(fut)
+        Box::pin(fut.instrument(span))
     }
 }
 
@@ -70,7 +141,7 @@ pub trait ReportingOp: Sync + Send + 'static {
     ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>>;
 }
 
-pub struct ExecuteReportingOp<O>(O)
+pub struct ExecuteReportingOp<O>(O, Span)
 where
     O: ReportingOp;
 
@@ -94,12 +165,25 @@ impl ReportingOp for CreateNuisanceReport {
         reporting: &mut ReportingActor,
     ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
         let repos = reporting.repos.clone();
+        let events = reporting.events.clone();
+        let storage = reporting.storage.clone();
+        let cache = reporting.cache.clone();
+
+        // un formulaire invalide ne consomme pas le quota de l'utilisateur
+        let mut validator = Validator::default();
+        self.form.assert(&mut validator);
+        if let Err(error) = validator.check() {
+            return Box::pin(async { Err(error) });
+        }
+
+        // les signalements anonymes ne sont rattachés à aucun compte
+        if let Some(user) = self.session.user() {
+            if !reporting.reports.acquire(user.id, Utc::now()) {
+                return Box::pin(async { Err(rate_limited()) });
+            }
+        }
 
         Box::pin(async move {
-            let mut validator = Validator::default();
-            self.form.assert(&mut validator);
-            validator.check()?;
-
             let user_id = self.session.user().map(|u| u.id);
             let type_id = self.form.type_id.unwrap();
 
@@ -119,18 +203,298 @@ impl ReportingOp for CreateNuisanceReport {
                 .try_into()
                 .map_err(Error::internal_error_with_source)?;
 
+            let perceived_duration = self
+                .form
+                .perceived_duration
+                .map(i32::try_from)
+                .transpose()
+                .map_err(Error::internal_error_with_source)?;
+
             let location = Point::from(self.form.location.unwrap());
 
-            let report_id = repos
-                .execute(InsertNuisanceReport {
-                    user_id,
-                    type_id,
+            // Les coordonnées GPS pourraient révéler le domicile du déclarant.
+            let photos = self
+                .form
+                .photos
+                .into_iter()
+                .map(|photo| {
+                    let data = strip_gps_metadata(&photo.content_type, photo.data);
+                    let size =
+                        i32::try_from(data.len()).map_err(Error::internal_error_with_source)?;
+                    Ok((photo.content_type, data, size))
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            // fichiers déposés, à supprimer si le signalement n'est pas enregistré
+            let uploaded = Rc::new(RefCell::new(Vec::<String>::new()));
+
+            let result = repos
+                .transaction({
+                    let storage = storage.clone();
+                    let uploaded = uploaded.clone();
+
+                    move |tx| {
+                        Box::pin(async move {
+                            let report_id = tx
+                                .execute(InsertNuisanceReport {
+                                    user_id,
+                                    type_id,
+                                    intensity,
+                                    location: location.into(),
+                                    description: self.form.description,
+                                    observed_from: self.form.observed_from,
+                                    observed_until: self.form.observed_until,
+                                    perceived_duration,
+                                })
+                                .await?;
+
+                            tx.execute(LinkNuisanceReportAreas(report_id)).await?;
+
+                            for (content_type, data, size) in photos {
+                                let photo_id = Uuid::new_v4();
+                                let storage_key = format!(
+                                    "reports/{report_id}/{photo_id}.{}",
+                                    extension_of(&content_type)
+                                );
+
+                                storage.put(&storage_key, &content_type, data).await?;
+                                uploaded.borrow_mut().push(storage_key.clone());
+
+                                tx.execute(InsertNuisanceReportPhoto {
+                                    id: photo_id,
+                                    report_id,
+                                    storage_key,
+                                    content_type,
+                                    size,
+                                })
+                                .await?;
+                            }
+
+                            Ok::<_, Error>(report_id)
+                        })
+                    }
+                })
+                .await;
+
+            let report_id = match result {
+                Ok(report_id) => report_id,
+                Err(err) => {
+                    for key in uploaded.take() {
+                        if let Err(error) = storage.delete(&key).await {
+                            warn!(target: "signuis::reporting", "impossible de supprimer la photo orpheline {key}: {}", error.chain());
+                        }
+                    }
+
+                    return Err(err);
+                }
+            };
+
+            metrics::count_report_created();
+            cache.invalidate(STATISTICS).await;
+
+            // notifie les autres systèmes (enrichissement, etc.)
+            events.notify(NuisanceReported(report_id));
+
+            Ok(report_id)
+        })
+    }
+}
+
+/// Récupère un signalement appartenant à l'utilisateur, et encore modifiable.
+///
+/// Le délai est vérifié à nouveau lors de l'écriture, qui échoue s'il a expiré entre-temps.
+async fn fetch_editable_report(
+    repos: &Repository,
+    id: NuisanceReportId,
+    user: &SessionUser,
+    grace_period: chrono::Duration,
+) -> Result<NuisanceReportSummary, Error> {
+    let report = repos
+        .execute(MaybeFindOneNuisanceReportById(id))
+        .await?
+        .ok_or_else(Error::not_found)?;
+
+    if report.user_id != Some(user.id) || Utc::now() > report.created_at + grace_period {
+        return Err(Error::unauthorized());
+    }
+
+    Ok(report)
+}
+
+/// Modifie l'intensité et/ou le type d'un de ses propres signalements.
+pub struct UpdateNuisanceReport {
+    pub id: NuisanceReportId,
+    pub form: UpdateNuisanceReportForm,
+    pub session: Session,
+}
+
+impl ReportingOp for UpdateNuisanceReport {
+    type Return = ();
+
+    fn execute<'fut>(
+        self,
+        reporting: &mut ReportingActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = reporting.repos.clone();
+        let events = reporting.events.clone();
+        let cache = reporting.cache.clone();
+        let grace_period = reporting.settings.report_edition_grace_period;
+
+        Box::pin(async move {
+            let user = self.session.user().ok_or_else(Error::unauthorized)?;
+
+            let mut validator = Validator::default();
+            self.form.assert(&mut validator);
+            validator.check()?;
+
+            fetch_editable_report(&repos, self.id, user, grace_period).await?;
+
+            if let Some(type_id) = self.form.type_id {
+                let exists = repos.execute(NuisanceTypeExists(type_id)).await?;
+                validator.assert_true(
+                    exists,
+                    Some("le type de nuisance n'existe pas"),
+                    ["type_id"],
+                );
+                validator.check()?;
+            }
+
+            let intensity = self
+                .form
+                .intensity
+                .map(i8::try_from)
+                .transpose()
+                .map_err(Error::internal_error_with_source)?;
+
+            let patched = repos
+                .execute(PatchNuisanceReport {
+                    id: self.id,
+                    type_id: self.form.type_id,
                     intensity,
-                    location: location.into(),
+                    grace_period,
                 })
                 .await?;
 
-            Ok(report_id)
+            if !patched {
+                return Err(Error::unauthorized());
+            }
+
+            cache.invalidate(STATISTICS).await;
+            events.notify(NuisanceReportChanged(self.id));
+
+            Ok(())
+        })
+    }
+}
+
+/// Retire un de ses propres signalements.
+///
+/// Le signalement n'est pas supprimé, il est seulement exclu des lectures.
+pub struct RetractNuisanceReport {
+    pub id: NuisanceReportId,
+    pub session: Session,
+}
+
+impl ReportingOp for RetractNuisanceReport {
+    type Return = ();
+
+    fn execute<'fut>(
+        self,
+        reporting: &mut ReportingActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = reporting.repos.clone();
+        let events = reporting.events.clone();
+        let cache = reporting.cache.clone();
+        let grace_period = reporting.settings.report_edition_grace_period;
+
+        Box::pin(async move {
+            let user = self.session.user().ok_or_else(Error::unauthorized)?;
+
+            fetch_editable_report(&repos, self.id, user, grace_period).await?;
+
+            let retracted = repos
+                .execute(SoftDeleteNuisanceReport {
+                    id: self.id,
+                    grace_period,
+                })
+                .await?;
+
+            if !retracted {
+                return Err(Error::unauthorized());
+            }
+
+            cache.invalidate(STATISTICS).await;
+            events.notify(NuisanceReportChanged(self.id));
+
+            Ok(())
+        })
+    }
+}
+
+/// Récupère un signalement avec ses photos et les conditions météorologiques associées.
+pub struct GetNuisanceReport {
+    pub id: NuisanceReportId,
+    pub session: Session,
+}
+
+impl ReportingOp for GetNuisanceReport {
+    type Return = NuisanceReport;
+
+    fn execute<'fut>(
+        self,
+        reporting: &mut ReportingActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = reporting.repos.clone();
+        let policy = reporting.privacy_policy(&self.session);
+
+        Box::pin(async move {
+            let mut report = repos
+                .execute(MaybeFindOneNuisanceReport {
+                    id: self.id,
+                    policy,
+                })
+                .await?
+                .ok_or_else(Error::not_found)?;
+
+            report.photos = repos.execute(FetchNuisanceReportPhotos(self.id)).await?;
+            report.weather = repos
+                .execute(MaybeFindOneNuisanceReportWeather(self.id))
+                .await?;
+
+            Ok(report)
+        })
+    }
+}
+
+/// Liste les signalements de l'utilisateur connecté, du plus récent au plus ancien.
+pub struct ListMyReports {
+    pub page: PageRequest,
+    pub session: Session,
+}
+
+impl ReportingOp for ListMyReports {
+    type Return = Page<NuisanceReportSummary>;
+
+    fn execute<'fut>(
+        self,
+        reporting: &mut ReportingActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = reporting.repos.clone();
+        let cursor_key = reporting.settings.cursor_key.clone();
+
+        Box::pin(async move {
+            let user = self.session.user().ok_or_else(Error::unauthorized)?;
+
+            repos
+                .execute(FetchNuisanceReportsByUser {
+                    user_id: user.id,
+                    page: Keyset::from_request(
+                        self.page,
+                        cursor_key,
+                        FetchNuisanceReportsByUser::CURSOR_NAMESPACE,
+                    )?,
+                })
+                .await
         })
     }
 }
@@ -148,6 +512,8 @@ impl ReportingOp for CreateNuisanceType {
         reporting: &mut ReportingActor,
     ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
         let repos = reporting.repos.clone();
+        let events = reporting.events.clone();
+        let cache = reporting.cache.clone();
 
         Box::pin(async move {
             let mut validator = Validator::default();
@@ -174,30 +540,42 @@ impl ReportingOp for CreateNuisanceType {
                 })
                 .await?;
 
+            cache.invalidate(TAXONOMY).await;
+            events.notify(TaxonomyChanged);
+
             Ok(nuisance_type_id)
         })
     }
 }
-#[derive(Message)]
-#[rtype(result = "Result<Vec<NuisanceFamily>, Error>")]
-pub struct ListNuisanceFamilies {}
 
-impl ListNuisanceFamilies {
-    pub fn all() -> Self {
-        Self {}
-    }
+/// Liste les familles de nuisance, par ordre alphabétique.
+pub struct ListNuisanceFamilies {
+    pub page: PageRequest,
 }
 
 impl ReportingOp for ListNuisanceFamilies {
-    type Return = Vec<NuisanceFamily>;
+    type Return = Page<NuisanceFamily>;
 
     fn execute<'fut>(
         self,
         reporting: &mut ReportingActor,
     ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
         let repos = reporting.repos.clone();
+        let cache = reporting.cache.clone();
+        let ttl = reporting.settings.cache.taxonomy_ttl();
+        let cursor_key = reporting.settings.cursor_key.clone();
 
-        Box::pin(async move { repos.execute(FetchNuisanceFamilies::all()).await })
+        Box::pin(async move {
+            let op = FetchNuisanceFamilies {
+                page: Keyset::from_request(
+                    self.page,
+                    cursor_key,
+                    FetchNuisanceFamilies::CURSOR_NAMESPACE,
+                )?,
+            };
+
+            cache.execute(&repos, op, ttl).await
+        })
     }
 }
 
@@ -214,6 +592,8 @@ impl ReportingOp for CreateNuisanceFamily {
         reporting: &mut ReportingActor,
     ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
         let repos = reporting.repos.clone();
+        let events = reporting.events.clone();
+        let cache = reporting.cache.clone();
 
         Box::pin(async move {
             let mut validator = Validator::default();
@@ -227,7 +607,76 @@ impl ReportingOp for CreateNuisanceFamily {
                 })
                 .await?;
 
+            cache.invalidate(TAXONOMY).await;
+            events.notify(TaxonomyChanged);
+
             Ok(nuisance_family_id)
         })
     }
 }
+
+/// Statistiques des signalements par intervalle de temps, éventuellement regroupés.
+pub struct GetReportStatistics {
+    pub query: ReportStatisticsQuery,
+    pub session: Session,
+}
+
+impl ReportingOp for GetReportStatistics {
+    type Return = Vec<ReportStatistic>;
+
+    fn execute<'fut>(
+        self,
+        reporting: &mut ReportingActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = reporting.repos.clone();
+        let cache = reporting.cache.clone();
+        let ttl = reporting.settings.cache.statistics_ttl();
+        let policy = reporting.privacy_policy(&self.session);
+
+        Box::pin(async move {
+            let mut validator = Validator::default();
+            self.query.assert(&mut validator);
+            validator.check()?;
+
+            let op = FetchReportStatistics {
+                query: self.query,
+                policy,
+            };
+
+            cache.execute(&repos, op, ttl).await
+        })
+    }
+}
+
+/// Matrice jour de la semaine × heure de la journée des signalements.
+pub struct GetWeeklyProfile {
+    pub query: ReportStatisticsQuery,
+    pub session: Session,
+}
+
+impl ReportingOp for GetWeeklyProfile {
+    type Return = Vec<WeeklyProfileCell>;
+
+    fn execute<'fut>(
+        self,
+        reporting: &mut ReportingActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = reporting.repos.clone();
+        let time_zone = reporting.settings.statistics_time_zone.clone();
+        let policy = reporting.privacy_policy(&self.session);
+
+        Box::pin(async move {
+            let mut validator = Validator::default();
+            self.query.assert(&mut validator);
+            validator.check()?;
+
+            repos
+                .execute(FetchWeeklyProfile {
+                    query: self.query,
+                    time_zone,
+                    policy,
+                })
+                .await
+        })
+    }
+}
@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use actix::{Actor, AsyncContext, Context};
+use chrono::Utc;
+use log::{info, warn};
+
+use crate::error::Error;
+use crate::repositories::nuisance_report::{
+    FetchAnonymousNuisanceReportPhotoKeys, PurgeAnonymousNuisanceReports,
+};
+use crate::repositories::Repository;
+use crate::storage::Storage;
+
+/// Purge périodiquement les signalements anonymes ayant dépassé la durée de conservation,
+/// ainsi que leurs photos.
+pub struct RetentionJob {
+    repos: Repository,
+    storage: Storage,
+    retention: chrono::Duration,
+    interval: Duration,
+}
+
+impl RetentionJob {
+    pub fn new(
+        repos: Repository,
+        storage: Storage,
+        retention: chrono::Duration,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            repos,
+            storage,
+            retention,
+            interval,
+        }
+    }
+}
+
+impl Actor for RetentionJob {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        ctx.run_interval(self.interval, |actor, _ctx| {
+            let repos = actor.repos.clone();
+            let storage = actor.storage.clone();
+            let before = Utc::now() - actor.retention;
+
+            actix::spawn(async move {
+                match purge_anonymous_data(&repos, &storage, before).await {
+                    Ok(0) => {}
+                    Ok(count) => {
+                        info!(target: "signuis::retention", "{count} signalement(s) anonyme(s) purgé(s)")
+                    }
+                    Err(error) => {
+                        warn!(target: "signuis::retention", "impossible de purger les données anonymes: {}", error.chain())
+                    }
+                }
+            });
+        });
+    }
+}
+
+/// Supprime les signalements anonymes créés avant la date, ainsi que leurs photos.
+///
+/// Retourne le nombre de signalements supprimés.
+pub async fn purge_anonymous_data(
+    repos: &Repository,
+    storage: &Storage,
+    before: chrono::DateTime<Utc>,
+) -> Result<u64, Error> {
+    let keys = repos
+        .execute(FetchAnonymousNuisanceReportPhotoKeys { before })
+        .await?;
+
+    for key in keys {
+        if let Err(error) = storage.delete(&key).await {
+            warn!(target: "signuis::retention", "impossible de supprimer la photo {key}: {}", error.chain());
+        }
+    }
+
+    repos
+        .execute(PurgeAnonymousNuisanceReports { before })
+        .await
+}
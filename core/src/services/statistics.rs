@@ -0,0 +1,47 @@
+use std::time::Duration;
+
+use actix::{Actor, AsyncContext, Context};
+use log::warn;
+
+use crate::cache::{QueryCache, STATISTICS};
+use crate::repositories::statistics::RefreshReportStatistics;
+use crate::repositories::Repository;
+
+/// Recalcule périodiquement les statistiques agrégées des signalements.
+///
+/// Les agrégats en cache sont invalidés après chaque recalcul.
+pub struct StatisticsRefresher {
+    repos: Repository,
+    cache: QueryCache,
+    interval: Duration,
+}
+
+impl StatisticsRefresher {
+    pub fn new(repos: Repository, cache: QueryCache, interval: Duration) -> Self {
+        Self {
+            repos,
+            cache,
+            interval,
+        }
+    }
+}
+
+impl Actor for StatisticsRefresher {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        ctx.run_interval(self.interval, |actor, _ctx| {
+            let repos = actor.repos.clone();
+            let cache = actor.cache.clone();
+
+            actix::spawn(async move {
+                match repos.execute(RefreshReportStatistics).await {
+                    Ok(()) => cache.invalidate(STATISTICS).await,
+                    Err(error) => {
+                        warn!(target: "signuis::statistics", "impossible de recalculer les statistiques: {}", error.chain());
+                    }
+                }
+            });
+        });
+    }
+}
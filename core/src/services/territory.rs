@@ -0,0 +1,150 @@
+use actix::{Actor, Addr, Context, Handler, Message, ResponseFuture};
+use futures::future::LocalBoxFuture;
+use tracing::{info_span, Instrument, Span};
+
+use crate::error::Error;
+use crate::forms::administrative_area::ImportAdministrativeAreaForm;
+use crate::metrics;
+use crate::models::administrative_area::{AdministrativeArea, AdministrativeAreaId, AreaLevel};
+use crate::models::pagination::{Page, PageRequest};
+use crate::pagination::{CursorKey, Keyset};
+use crate::repositories::administrative_area::{
+    FetchAdministrativeAreas, UpsertAdministrativeArea,
+};
+use crate::repositories::Repository;
+use crate::validation::{Validation, Validator};
+
+/// Service de gestion du découpage administratif (communes, départements).
+#[derive(Clone)]
+pub struct Territory(Addr<TerritoryActor>);
+
+impl Territory {
+    pub fn new(repos: Repository, cursor_key: CursorKey) -> Self {
+        Self(TerritoryActor::new(repos, cursor_key).start())
+    }
+
+    pub async fn execute<O: TerritoryOp>(&self, op: O) -> Result<O::Return, Error> {
+        let span = info_span!("territory", op = metrics::type_label::<O>());
+        self.0.send(ExecuteTerritoryOp(op, span)).await?
+    }
+
+    /// Indique si l'acteur du service est toujours en vie.
+    pub fn connected(&self) -> bool {
+        self.0.connected()
+    }
+}
+
+pub struct TerritoryActor {
+    repos: Repository,
+    cursor_key: CursorKey,
+}
+
+impl TerritoryActor {
+    pub fn new(repos: Repository, cursor_key: CursorKey) -> Self {
+        Self { repos, cursor_key }
+    }
+}
+
+impl Actor for TerritoryActor {
+    type Context = Context<Self>;
+}
+
+impl<O> Handler<ExecuteTerritoryOp<O>> for TerritoryActor
+where
+    O: TerritoryOp,
+{
+    type Result = ResponseFuture<Result<O::Return, Error>>;
+
+    fn handle(&mut self, msg: ExecuteTerritoryOp<O>, _ctx: &mut Self::Context) -> Self::Result {
+        let ExecuteTerritoryOp(op, span) = msg;
+        let fut = span.in_scope(|| op.execute(self));
+
+        Box::pin(fut.instrument(span))
+    }
+}
+
+/// Une opération à executer auprès du service du découpage administratif.
+pub trait TerritoryOp: Sync + Send + 'static {
+    type Return: Sync + Send;
+
+    fn execute<'fut>(
+        self,
+        territory: &mut TerritoryActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>>;
+}
+
+pub struct ExecuteTerritoryOp<O>(O, Span)
+where
+    O: TerritoryOp;
+
+impl<O> Message for ExecuteTerritoryOp<O>
+where
+    O: TerritoryOp,
+{
+    type Result = Result<O::Return, Error>;
+}
+
+/// Importe une zone administrative et y rattache les signalements existants.
+///
+/// Réservé aux outils d'administration (import en ligne de commande) :
+/// l'opération n'est pas exposée par l'API.
+pub struct ImportAdministrativeArea {
+    pub form: ImportAdministrativeAreaForm,
+}
+
+impl TerritoryOp for ImportAdministrativeArea {
+    type Return = AdministrativeAreaId;
+
+    fn execute<'fut>(
+        self,
+        territory: &mut TerritoryActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = territory.repos.clone();
+
+        Box::pin(async move {
+            let mut validator = Validator::default();
+            self.form.assert(&mut validator);
+            validator.check()?;
+
+            repos
+                .execute(UpsertAdministrativeArea {
+                    code: self.form.code,
+                    name: self.form.name,
+                    level: self.form.level,
+                    geometry: self.form.geometry.to_string(),
+                })
+                .await
+        })
+    }
+}
+
+/// Liste les zones administratives, éventuellement d'un seul échelon.
+pub struct ListAdministrativeAreas {
+    pub level: Option<AreaLevel>,
+    pub page: PageRequest,
+}
+
+impl TerritoryOp for ListAdministrativeAreas {
+    type Return = Page<AdministrativeArea>;
+
+    fn execute<'fut>(
+        self,
+        territory: &mut TerritoryActor,
+    ) -> LocalBoxFuture<'fut, Result<Self::Return, Error>> {
+        let repos = territory.repos.clone();
+        let cursor_key = territory.cursor_key.clone();
+
+        Box::pin(async move {
+            repos
+                .execute(FetchAdministrativeAreas {
+                    level: self.level,
+                    page: Keyset::from_request(
+                        self.page,
+                        cursor_key,
+                        FetchAdministrativeAreas::CURSOR_NAMESPACE,
+                    )?,
+                })
+                .await
+        })
+    }
+}
@@ -0,0 +1,65 @@
+use std::{io::ErrorKind, path::PathBuf};
+
+use futures::future::LocalBoxFuture;
+
+use super::{check_key, BlobStorage};
+use crate::error::Error;
+
+/// Stockage des fichiers dans un répertoire du système de fichiers local.
+pub struct LocalFileStorage {
+    root: PathBuf,
+}
+
+impl LocalFileStorage {
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path_of(&self, key: &str) -> Result<PathBuf, Error> {
+        check_key(key)?;
+        Ok(self.root.join(key))
+    }
+}
+
+impl BlobStorage for LocalFileStorage {
+    fn put<'a>(
+        &'a self,
+        key: &'a str,
+        _content_type: &'a str,
+        data: Vec<u8>,
+    ) -> LocalBoxFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            let path = self.path_of(key)?;
+
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent)
+                    .await
+                    .map_err(Error::internal_error_with_source)?;
+            }
+
+            tokio::fs::write(path, data)
+                .await
+                .map_err(Error::internal_error_with_source)
+        })
+    }
+
+    fn get<'a>(&'a self, key: &'a str) -> LocalBoxFuture<'a, Result<Option<Vec<u8>>, Error>> {
+        Box::pin(async move {
+            match tokio::fs::read(self.path_of(key)?).await {
+                Ok(data) => Ok(Some(data)),
+                Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+                Err(err) => Err(Error::internal_error_with_source(err)),
+            }
+        })
+    }
+
+    fn delete<'a>(&'a self, key: &'a str) -> LocalBoxFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            match tokio::fs::remove_file(self.path_of(key)?).await {
+                Ok(()) => Ok(()),
+                Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+                Err(err) => Err(Error::internal_error_with_source(err)),
+            }
+        })
+    }
+}
@@ -0,0 +1,88 @@
+//! Stockage des fichiers binaires (photos, avatars, etc.)
+use std::{path::PathBuf, sync::Arc};
+
+use futures::future::LocalBoxFuture;
+
+use crate::error::Error;
+
+mod local;
+mod s3;
+
+pub use local::LocalFileStorage;
+pub use s3::{S3Settings, S3Storage};
+
+/// Un espace de stockage de fichiers binaires, adressés par une clé.
+pub trait BlobStorage: Send + Sync {
+    /// Enregistre le contenu sous la clé donnée, en écrasant l'éventuel contenu existant.
+    fn put<'a>(
+        &'a self,
+        key: &'a str,
+        content_type: &'a str,
+        data: Vec<u8>,
+    ) -> LocalBoxFuture<'a, Result<(), Error>>;
+
+    /// Récupère le contenu stocké sous la clé, s'il existe.
+    fn get<'a>(&'a self, key: &'a str) -> LocalBoxFuture<'a, Result<Option<Vec<u8>>, Error>>;
+
+    /// Supprime le contenu stocké sous la clé, s'il existe.
+    fn delete<'a>(&'a self, key: &'a str) -> LocalBoxFuture<'a, Result<(), Error>>;
+}
+
+#[derive(Clone)]
+/// Paramètres de l'espace de stockage.
+pub enum StorageSettings {
+    /// Stockage sur le système de fichiers local.
+    Local { root: PathBuf },
+    /// Stockage compatible S3.
+    S3(S3Settings),
+}
+
+impl Default for StorageSettings {
+    fn default() -> Self {
+        Self::Local {
+            root: PathBuf::from("storage"),
+        }
+    }
+}
+
+/// Espace de stockage partagé entre les services.
+#[derive(Clone)]
+pub struct Storage(Arc<dyn BlobStorage>);
+
+impl Storage {
+    pub fn new(settings: &StorageSettings) -> Result<Self, Error> {
+        Ok(match settings {
+            StorageSettings::Local { root } => Self::from(LocalFileStorage::new(root.clone())),
+            StorageSettings::S3(settings) => Self::from(S3Storage::new(settings.clone())?),
+        })
+    }
+}
+
+impl<S: BlobStorage + 'static> From<S> for Storage {
+    fn from(value: S) -> Self {
+        Self(Arc::new(value))
+    }
+}
+
+impl std::ops::Deref for Storage {
+    type Target = dyn BlobStorage;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+/// Vérifie qu'une clé ne permet pas de sortir de l'espace de stockage.
+fn check_key(key: &str) -> Result<(), Error> {
+    let is_valid = !key.is_empty()
+        && !key.starts_with('/')
+        && key
+            .split('/')
+            .all(|segment| !segment.is_empty() && segment != "." && segment != "..");
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(Error::internal_error())
+    }
+}
@@ -0,0 +1,213 @@
+use chrono::{DateTime, Utc};
+use futures::future::LocalBoxFuture;
+use hmac::{Hmac, Mac};
+use reqwest::{Method, StatusCode, Url};
+use sha2::{Digest, Sha256};
+
+use super::{check_key, BlobStorage};
+use crate::error::Error;
+
+#[derive(Clone)]
+/// Paramètres d'accès à un stockage compatible S3 (AWS, MinIO, Garage...)
+pub struct S3Settings {
+    /// Adresse du service, ex: `http://localhost:9000`.
+    pub endpoint: String,
+    pub region: String,
+    pub bucket: String,
+    pub access_key: String,
+    pub secret_key: String,
+}
+
+/// Stockage compatible S3.
+///
+/// Les requêtes sont adressées en mode chemin (`{endpoint}/{bucket}/{key}`)
+/// et signées selon AWS Signature Version 4.
+pub struct S3Storage {
+    settings: S3Settings,
+    endpoint: Url,
+    client: reqwest::Client,
+}
+
+impl S3Storage {
+    pub fn new(settings: S3Settings) -> Result<Self, Error> {
+        let endpoint = Url::parse(&settings.endpoint).map_err(Error::internal_error_with_source)?;
+
+        Ok(Self {
+            settings,
+            endpoint,
+            client: reqwest::Client::new(),
+        })
+    }
+
+    /// Construit la requête signée portant sur la clé donnée, à la date donnée.
+    pub fn build_request(
+        &self,
+        method: Method,
+        key: &str,
+        content_type: Option<&str>,
+        body: Vec<u8>,
+        now: DateTime<Utc>,
+    ) -> Result<reqwest::Request, Error> {
+        check_key(key)?;
+
+        let path = format!("/{}/{}", uri_encode(&self.settings.bucket), uri_encode(key));
+        let mut url = self.endpoint.clone();
+        url.set_path(&path);
+
+        let payload_hash = hex::encode(Sha256::digest(&body));
+        let authorization = self.authorization(&method, &path, &payload_hash, now);
+
+        let mut request = self
+            .client
+            .request(method, url)
+            .header("x-amz-content-sha256", &payload_hash)
+            .header("x-amz-date", &authorization.amz_date)
+            .header("authorization", authorization.header)
+            .body(body);
+
+        if let Some(content_type) = content_type {
+            request = request.header("content-type", content_type);
+        }
+
+        request.build().map_err(Error::internal_error_with_source)
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        key: &str,
+        content_type: Option<&str>,
+        body: Vec<u8>,
+    ) -> Result<reqwest::Response, Error> {
+        let request = self.build_request(method, key, content_type, body, Utc::now())?;
+
+        self.client
+            .execute(request)
+            .await
+            .map_err(Error::internal_error_with_source)
+    }
+
+    fn host(&self) -> String {
+        let host = self.endpoint.host_str().unwrap_or_default();
+
+        match self.endpoint.port() {
+            Some(port) => format!("{host}:{port}"),
+            None => host.to_owned(),
+        }
+    }
+
+    fn authorization(
+        &self,
+        method: &Method,
+        path: &str,
+        payload_hash: &str,
+        now: DateTime<Utc>,
+    ) -> SignedAuthorization {
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let scope = format!("{date}/{}/s3/aws4_request", self.settings.region);
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+
+        let canonical_request = format!(
+            "{method}\n{path}\n\nhost:{}\nx-amz-content-sha256:{payload_hash}\nx-amz-date:{amz_date}\n\n{signed_headers}\n{payload_hash}",
+            self.host()
+        );
+
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let secret = format!("AWS4{}", self.settings.secret_key);
+        let signing_key = [
+            date.as_str(),
+            self.settings.region.as_str(),
+            "s3",
+            "aws4_request",
+        ]
+        .into_iter()
+        .fold(secret.into_bytes(), |key, part| hmac_sha256(&key, part));
+
+        let signature = hex::encode(hmac_sha256(&signing_key, &string_to_sign));
+
+        SignedAuthorization {
+            header: format!(
+                "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+                self.settings.access_key
+            ),
+            amz_date,
+        }
+    }
+}
+
+struct SignedAuthorization {
+    header: String,
+    amz_date: String,
+}
+
+impl BlobStorage for S3Storage {
+    fn put<'a>(
+        &'a self,
+        key: &'a str,
+        content_type: &'a str,
+        data: Vec<u8>,
+    ) -> LocalBoxFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            self.send(Method::PUT, key, Some(content_type), data)
+                .await?
+                .error_for_status()
+                .map_err(Error::internal_error_with_source)?;
+
+            Ok(())
+        })
+    }
+
+    fn get<'a>(&'a self, key: &'a str) -> LocalBoxFuture<'a, Result<Option<Vec<u8>>, Error>> {
+        Box::pin(async move {
+            let response = self.send(Method::GET, key, None, Vec::default()).await?;
+
+            if response.status() == StatusCode::NOT_FOUND {
+                return Ok(None);
+            }
+
+            let data = response
+                .error_for_status()
+                .map_err(Error::internal_error_with_source)?
+                .bytes()
+                .await
+                .map_err(Error::internal_error_with_source)?;
+
+            Ok(Some(data.to_vec()))
+        })
+    }
+
+    fn delete<'a>(&'a self, key: &'a str) -> LocalBoxFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            self.send(Method::DELETE, key, None, Vec::default())
+                .await?
+                .error_for_status()
+                .map_err(Error::internal_error_with_source)?;
+
+            Ok(())
+        })
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Encode un chemin selon les règles de S3 (les `/` sont conservés).
+fn uri_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
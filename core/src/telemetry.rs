@@ -0,0 +1,71 @@
+//! Journalisation du service.
+//!
+//! Les traces sont structurées en spans : requête HTTP (avec son identifiant),
+//! opération d'un service, puis opération du répertoire. Les requêtes SQL sont
+//! journalisées par sqlx (cible `sqlx::query`, niveau `debug`) au sein du span
+//! de l'opération du répertoire qui les a émises.
+//!
+//! Les enregistrements émis via `log` sont redirigés vers les mêmes sorties.
+use serde::Deserialize;
+use tracing_subscriber::EnvFilter;
+
+use crate::error::{Error, ErrorKind};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+/// Format des journaux.
+pub enum LogFormat {
+    /// Lignes lisibles, pour le développement.
+    #[default]
+    Text,
+    /// Un objet JSON par ligne, pour les agrégateurs de journaux.
+    Json,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+/// Paramètres de la journalisation.
+pub struct LogSettings {
+    pub format: LogFormat,
+    /// Directives de filtrage (ex: `info,sqlx::query=debug`) ;
+    /// la variable `RUST_LOG`, si elle est définie, prévaut.
+    pub filter: String,
+}
+
+impl Default for LogSettings {
+    fn default() -> Self {
+        Self {
+            format: LogFormat::Text,
+            filter: "info".to_owned(),
+        }
+    }
+}
+
+impl LogSettings {
+    /// Vérifie que les directives de filtrage sont valides.
+    pub fn is_valid_filter(&self) -> bool {
+        EnvFilter::try_new(&self.filter).is_ok()
+    }
+}
+
+/// Installe la journalisation du processus.
+///
+/// Échoue si une journalisation est déjà installée.
+pub fn init(settings: &LogSettings) -> Result<(), Error> {
+    let filter = EnvFilter::try_from_default_env()
+        .or_else(|_| EnvFilter::try_new(&settings.filter))
+        .map_err(Error::internal_error_with_source)?;
+
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+
+    let result = match settings.format {
+        LogFormat::Text => builder.try_init(),
+        LogFormat::Json => builder
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .try_init(),
+    };
+
+    result.map_err(|err| Error::new_with_source(ErrorKind::InternalError, Some(err)))
+}
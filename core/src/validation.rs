@@ -4,6 +4,7 @@ use email_address::EmailAddress;
 
 use crate::error::Error;
 use crate::issues::{Issue, Issues};
+use crate::password_policy::{contains_personal_data, estimate_entropy, PasswordPolicy};
 
 pub trait Validation {
     fn assert(&self, validator: &mut Validator);
@@ -116,4 +117,135 @@ impl Validator {
             self.issues.add(issue);
         }
     }
+
+    pub fn assert_max_length<S: ToString, P: IntoIterator<Item = S>>(
+        &mut self,
+        value: &str,
+        max: usize,
+        message: Option<&str>,
+        path: P,
+    ) {
+        if value.chars().count() > max {
+            let issue = Issue::new("invalid", message.unwrap_or("element is too long"), path);
+
+            self.issues.add(issue);
+        }
+    }
+
+    pub fn assert_one_of<A: PartialEq, S: ToString, P: IntoIterator<Item = S>>(
+        &mut self,
+        value: &A,
+        accepted: &[A],
+        message: Option<&str>,
+        path: P,
+    ) {
+        if !accepted.contains(value) {
+            let issue = Issue::new("invalid", message.unwrap_or("value is not accepted"), path);
+
+            self.issues.add(issue);
+        }
+    }
+
+    pub fn assert_min_length<S: ToString, P: IntoIterator<Item = S>>(
+        &mut self,
+        value: &str,
+        min: usize,
+        message: Option<&str>,
+        path: P,
+    ) {
+        if value.chars().count() < min {
+            let issue = Issue::new("invalid", message.unwrap_or("element is too short"), path);
+
+            self.issues.add(issue);
+        }
+    }
+
+    /// Vérifie que l'entropie estimée de la valeur atteint le seuil, en bits.
+    pub fn assert_min_entropy<S: ToString, P: IntoIterator<Item = S>>(
+        &mut self,
+        value: &str,
+        bits: f64,
+        message: Option<&str>,
+        path: P,
+    ) {
+        if estimate_entropy(value) < bits {
+            let issue = Issue::new("invalid", message.unwrap_or("element is too weak"), path);
+
+            self.issues.add(issue);
+        }
+    }
+
+    /// Vérifie que la valeur ne contient aucune des données personnelles.
+    pub fn assert_no_personal_data<S: ToString, P: IntoIterator<Item = S>>(
+        &mut self,
+        value: &str,
+        personal_data: &[&str],
+        message: Option<&str>,
+        path: P,
+    ) {
+        if contains_personal_data(value, personal_data) {
+            let issue = Issue::new(
+                "invalid",
+                message.unwrap_or("element contains personal data"),
+                path,
+            );
+
+            self.issues.add(issue);
+        }
+    }
+
+    /// Vérifie qu'un nouveau mot de passe respecte la politique, hors recherche
+    /// dans les fuites de données.
+    ///
+    /// Chaque règle enfreinte produit une issue au code dédié, pour que
+    /// l'interface puisse la traduire.
+    pub fn assert_password_policy<S: ToString, P: IntoIterator<Item = S> + Clone>(
+        &mut self,
+        password: &str,
+        policy: &PasswordPolicy,
+        personal_data: &[&str],
+        path: P,
+    ) {
+        if password.chars().count() < policy.min_length {
+            self.issues.add(Issue::new(
+                "password_too_short",
+                format!(
+                    "le mot de passe doit contenir au moins {} caractères",
+                    policy.min_length
+                ),
+                path.clone(),
+            ));
+        } else if estimate_entropy(password) < policy.min_entropy {
+            self.issues.add(Issue::new(
+                "password_too_weak",
+                "le mot de passe est trop prévisible, variez les caractères ou allongez-le",
+                path.clone(),
+            ));
+        }
+
+        if policy.forbid_personal_data && contains_personal_data(password, personal_data) {
+            self.issues.add(Issue::new(
+                "password_contains_personal_data",
+                "le mot de passe ne doit pas contenir votre nom d'utilisateur ou votre adresse courriel",
+                path,
+            ));
+        }
+    }
+
+    /// Signale un mot de passe figurant dans une fuite de données connue.
+    pub fn assert_not_breached<S: ToString, P: IntoIterator<Item = S>>(
+        &mut self,
+        breached: bool,
+        path: P,
+    ) {
+        if breached {
+            let issue = Issue::new(
+                "password_breached",
+                "ce mot de passe figure dans une fuite de données connue, choisissez-en un autre",
+                path,
+            );
+
+            self.issues.add(issue);
+        }
+    }
 }
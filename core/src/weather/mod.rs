@@ -0,0 +1,75 @@
+//! Fournisseurs de données météorologiques pour l'enrichissement des signalements.
+use std::{path::PathBuf, sync::Arc};
+
+use chrono::{DateTime, Duration, Utc};
+use futures::future::LocalBoxFuture;
+
+use crate::{error::Error, models::weather::WeatherObservation};
+
+mod station_file;
+
+pub use station_file::StationFileProvider;
+
+/// Fournit les conditions météorologiques en un lieu et à un instant donnés.
+pub trait WeatherProvider: Send + Sync {
+    /// Retourne les conditions observées au plus près de la position et de la date,
+    /// ou `None` si aucune donnée n'est disponible.
+    fn observe(
+        &self,
+        longitude: f64,
+        latitude: f64,
+        at: DateTime<Utc>,
+    ) -> LocalBoxFuture<'_, Result<Option<WeatherObservation>, Error>>;
+}
+
+#[derive(Clone, Default)]
+/// Paramètres du fournisseur de données météorologiques.
+pub enum WeatherSettings {
+    /// Aucun enrichissement météorologique.
+    #[default]
+    Disabled,
+    /// Relevés de stations chargés depuis un fichier JSON local.
+    StationFile {
+        path: PathBuf,
+        /// Distance maximale entre le signalement et la station, en kilomètres.
+        max_distance: f64,
+        /// Écart maximal entre la date du signalement et celle du relevé.
+        max_time_gap: Duration,
+    },
+}
+
+/// Fournisseur de données météorologiques partagé entre les services.
+#[derive(Clone)]
+pub struct Weather(Arc<dyn WeatherProvider>);
+
+impl Weather {
+    /// Crée le fournisseur décrit par les paramètres, s'il y en a un.
+    pub fn new(settings: &WeatherSettings) -> Result<Option<Self>, Error> {
+        Ok(match settings {
+            WeatherSettings::Disabled => None,
+            WeatherSettings::StationFile {
+                path,
+                max_distance,
+                max_time_gap,
+            } => Some(Self::from(StationFileProvider::load(
+                path,
+                *max_distance,
+                *max_time_gap,
+            )?)),
+        })
+    }
+}
+
+impl<P: WeatherProvider + 'static> From<P> for Weather {
+    fn from(value: P) -> Self {
+        Self(Arc::new(value))
+    }
+}
+
+impl std::ops::Deref for Weather {
+    type Target = dyn WeatherProvider;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
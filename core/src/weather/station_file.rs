@@ -0,0 +1,133 @@
+use std::path::Path;
+
+use chrono::{DateTime, Duration, Utc};
+use futures::future::LocalBoxFuture;
+use serde::Deserialize;
+
+use super::WeatherProvider;
+use crate::{error::Error, geodesy::haversine_distance, models::weather::WeatherObservation};
+
+#[derive(Deserialize)]
+struct StationFile {
+    stations: Vec<Station>,
+}
+
+#[derive(Deserialize)]
+struct Station {
+    id: String,
+    longitude: f64,
+    latitude: f64,
+    observations: Vec<StationObservation>,
+}
+
+#[derive(Deserialize)]
+struct StationObservation {
+    at: DateTime<Utc>,
+    wind_direction: f64,
+    wind_speed: f64,
+    temperature: f64,
+    humidity: f64,
+}
+
+/// Fournisseur lisant les relevés de stations depuis un fichier JSON local,
+/// pour un fonctionnement hors ligne.
+///
+/// # Format
+/// ```json
+/// {
+///     "stations": [{
+///         "id": "75114001",
+///         "longitude": 2.3377,
+///         "latitude": 48.8217,
+///         "observations": [{
+///             "at": "2024-09-01T12:00:00Z",
+///             "wind_direction": 225.0,
+///             "wind_speed": 3.5,
+///             "temperature": 21.4,
+///             "humidity": 63.0
+///         }]
+///     }]
+/// }
+/// ```
+pub struct StationFileProvider {
+    stations: Vec<Station>,
+    max_distance: f64,
+    max_time_gap: Duration,
+}
+
+impl StationFileProvider {
+    /// Charge les relevés depuis le fichier.
+    pub fn load<P: AsRef<Path>>(
+        path: P,
+        max_distance: f64,
+        max_time_gap: Duration,
+    ) -> Result<Self, Error> {
+        let content = std::fs::read(path).map_err(Error::internal_error_with_source)?;
+        Self::from_slice(&content, max_distance, max_time_gap)
+    }
+
+    /// Charge les relevés depuis le contenu d'un fichier.
+    pub fn from_slice(
+        content: &[u8],
+        max_distance: f64,
+        max_time_gap: Duration,
+    ) -> Result<Self, Error> {
+        let file: StationFile =
+            serde_json::from_slice(content).map_err(Error::internal_error_with_source)?;
+
+        Ok(Self {
+            stations: file.stations,
+            max_distance,
+            max_time_gap,
+        })
+    }
+
+    /// Relevé le plus proche dans le temps, s'il est dans l'écart toléré.
+    fn closest_observation<'a>(
+        &self,
+        station: &'a Station,
+        at: DateTime<Utc>,
+    ) -> Option<&'a StationObservation> {
+        station
+            .observations
+            .iter()
+            .map(|observation| (observation, (observation.at - at).abs()))
+            .filter(|(_, gap)| *gap <= self.max_time_gap)
+            .min_by_key(|(_, gap)| *gap)
+            .map(|(observation, _)| observation)
+    }
+}
+
+impl WeatherProvider for StationFileProvider {
+    fn observe(
+        &self,
+        longitude: f64,
+        latitude: f64,
+        at: DateTime<Utc>,
+    ) -> LocalBoxFuture<'_, Result<Option<WeatherObservation>, Error>> {
+        let observation = self
+            .stations
+            .iter()
+            .map(|station| {
+                let distance =
+                    haversine_distance(longitude, latitude, station.longitude, station.latitude);
+                (station, distance)
+            })
+            .filter(|(_, distance)| *distance <= self.max_distance)
+            .filter_map(|(station, distance)| {
+                self.closest_observation(station, at)
+                    .map(|observation| (station, observation, distance))
+            })
+            .min_by(|(_, _, a), (_, _, b)| a.total_cmp(b))
+            .map(|(station, observation, _)| WeatherObservation {
+                station: Some(station.id.clone()),
+                observed_at: observation.at,
+                wind_direction: observation.wind_direction,
+                wind_speed: observation.wind_speed,
+                temperature: observation.temperature,
+                humidity: observation.humidity,
+            });
+
+        Box::pin(async move { Ok(observation) })
+    }
+}
@@ -0,0 +1,261 @@
+//! Vérification des clés d'accès (WebAuthn).
+//!
+//! Seul l'algorithme ES256 (ECDSA P-256 / SHA-256) est pris en charge, et
+//! l'attestation des authentificateurs n'est pas exigée : la déclaration
+//! d'attestation est ignorée, comme pour le format `none`.
+
+use std::io::Cursor;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use ciborium::Value;
+use p256::{
+    ecdsa::{signature::Verifier, Signature, VerifyingKey},
+    EncodedPoint,
+};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    error::Error,
+    issues::{Issue, Issues},
+};
+
+/// Identifiant COSE de l'algorithme ES256.
+pub const COSE_ALG_ES256: i64 = -7;
+
+/// La présence de l'utilisateur a été constatée.
+const FLAG_USER_PRESENT: u8 = 0x01;
+/// L'utilisateur a été vérifié (code, biométrie).
+const FLAG_USER_VERIFIED: u8 = 0x04;
+/// Les données d'authentification contiennent une clé attestée.
+const FLAG_ATTESTED_CREDENTIAL: u8 = 0x40;
+
+#[derive(Clone)]
+/// Paramètres de la partie de confiance (relying party) WebAuthn.
+pub struct WebAuthnSettings {
+    /// Domaine auquel les clés d'accès sont rattachées, ex: `signuis.fr`.
+    pub rp_id: String,
+    /// Nom affiché par les authentificateurs.
+    pub rp_name: String,
+    /// Origine attendue des cérémonies, ex: `https://signuis.fr`.
+    pub origin: String,
+}
+
+impl Default for WebAuthnSettings {
+    fn default() -> Self {
+        Self {
+            rp_id: "localhost".to_owned(),
+            rp_name: "Signuis".to_owned(),
+            origin: "http://localhost:3000".to_owned(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+/// Données collectées par le navigateur (`clientDataJSON`).
+pub struct ClientData {
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Défi encodé en base64url.
+    pub challenge: String,
+    pub origin: String,
+}
+
+/// Clé attestée lors de l'enregistrement.
+pub struct AttestedCredential {
+    pub credential_id: Vec<u8>,
+    /// Clé publique au format COSE.
+    pub public_key: Vec<u8>,
+}
+
+/// Données d'authentification produites par l'authentificateur.
+pub struct AuthenticatorData {
+    pub rp_id_hash: [u8; 32],
+    pub flags: u8,
+    pub sign_count: u32,
+    pub attested_credential: Option<AttestedCredential>,
+}
+
+impl AuthenticatorData {
+    pub fn user_present(&self) -> bool {
+        self.flags & FLAG_USER_PRESENT != 0
+    }
+
+    pub fn user_verified(&self) -> bool {
+        self.flags & FLAG_USER_VERIFIED != 0
+    }
+}
+
+/// Erreur retournée lorsqu'une clé d'accès n'a pas pu être vérifiée.
+pub fn invalid_passkey() -> Error {
+    Issues::new()
+        .add(Issue::new(
+            "invalid_passkey",
+            "La clé d'accès n'a pas pu être vérifiée",
+            Vec::<String>::default(),
+        ))
+        .to_owned()
+        .into_error()
+}
+
+/// Décode une valeur base64url, sans remplissage.
+pub fn decode_base64url(value: &str) -> Result<Vec<u8>, Error> {
+    URL_SAFE_NO_PAD
+        .decode(value.trim_end_matches('='))
+        .map_err(|_| invalid_passkey())
+}
+
+/// Encode une valeur en base64url, sans remplissage.
+pub fn encode_base64url(value: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(value)
+}
+
+impl WebAuthnSettings {
+    /// Vérifie le type de cérémonie et l'origine des données client,
+    /// et en retourne le défi.
+    pub fn verify_client_data(&self, client_data_json: &[u8], kind: &str) -> Result<String, Error> {
+        let client_data: ClientData =
+            serde_json::from_slice(client_data_json).map_err(|_| invalid_passkey())?;
+
+        if client_data.kind != kind || client_data.origin != self.origin {
+            return Err(invalid_passkey());
+        }
+
+        Ok(client_data.challenge)
+    }
+
+    /// Vérifie que les données d'authentification visent la partie de confiance,
+    /// en présence de l'utilisateur.
+    pub fn verify_authenticator_data(&self, data: &AuthenticatorData) -> Result<(), Error> {
+        let rp_id_hash = Sha256::digest(self.rp_id.as_bytes());
+
+        if data.rp_id_hash[..] != rp_id_hash[..] || !data.user_present() {
+            return Err(invalid_passkey());
+        }
+
+        Ok(())
+    }
+}
+
+/// Lit les données d'authentification binaires.
+pub fn parse_authenticator_data(bytes: &[u8]) -> Result<AuthenticatorData, Error> {
+    if bytes.len() < 37 {
+        return Err(invalid_passkey());
+    }
+
+    let mut rp_id_hash = [0u8; 32];
+    rp_id_hash.copy_from_slice(&bytes[..32]);
+    let flags = bytes[32];
+    let sign_count = u32::from_be_bytes([bytes[33], bytes[34], bytes[35], bytes[36]]);
+
+    let attested_credential = if flags & FLAG_ATTESTED_CREDENTIAL != 0 {
+        // AAGUID (16 octets), longueur de l'identifiant (2 octets), identifiant, clé COSE.
+        let rest = bytes.get(37 + 16..).ok_or_else(invalid_passkey)?;
+        let id_len = u16::from_be_bytes([
+            *rest.first().ok_or_else(invalid_passkey)?,
+            *rest.get(1).ok_or_else(invalid_passkey)?,
+        ]) as usize;
+        let credential_id = rest
+            .get(2..2 + id_len)
+            .ok_or_else(invalid_passkey)?
+            .to_vec();
+        let key_bytes = rest.get(2 + id_len..).ok_or_else(invalid_passkey)?;
+
+        // La clé peut être suivie d'extensions : on ne retient que la valeur CBOR.
+        let mut cursor = Cursor::new(key_bytes);
+        let _: Value = ciborium::de::from_reader(&mut cursor).map_err(|_| invalid_passkey())?;
+        let public_key = key_bytes[..cursor.position() as usize].to_vec();
+
+        Some(AttestedCredential {
+            credential_id,
+            public_key,
+        })
+    } else {
+        None
+    };
+
+    Ok(AuthenticatorData {
+        rp_id_hash,
+        flags,
+        sign_count,
+        attested_credential,
+    })
+}
+
+/// Lit l'objet d'attestation (CBOR) et en extrait les données d'authentification.
+pub fn parse_attestation_object(bytes: &[u8]) -> Result<AuthenticatorData, Error> {
+    let value: Value = ciborium::de::from_reader(bytes).map_err(|_| invalid_passkey())?;
+
+    let auth_data = value
+        .as_map()
+        .and_then(|entries| {
+            entries
+                .iter()
+                .find(|(key, _)| key.as_text() == Some("authData"))
+        })
+        .and_then(|(_, value)| value.as_bytes())
+        .ok_or_else(invalid_passkey)?;
+
+    parse_authenticator_data(auth_data)
+}
+
+/// Extrait la clé de vérification d'une clé publique COSE ES256.
+fn verifying_key(cose_key: &[u8]) -> Result<VerifyingKey, Error> {
+    let value: Value = ciborium::de::from_reader(cose_key).map_err(|_| invalid_passkey())?;
+    let entries = value.as_map().ok_or_else(invalid_passkey)?;
+
+    let field = |label: i64| {
+        entries.iter().find_map(|(key, value)| {
+            key.as_integer()
+                .filter(|key| i128::from(*key) == label as i128)
+                .map(|_| value)
+        })
+    };
+
+    let alg = field(3)
+        .and_then(Value::as_integer)
+        .map(i128::from)
+        .ok_or_else(invalid_passkey)?;
+
+    if alg != COSE_ALG_ES256 as i128 {
+        return Err(invalid_passkey());
+    }
+
+    let x = field(-2)
+        .and_then(Value::as_bytes)
+        .ok_or_else(invalid_passkey)?;
+    let y = field(-3)
+        .and_then(Value::as_bytes)
+        .ok_or_else(invalid_passkey)?;
+
+    if x.len() != 32 || y.len() != 32 {
+        return Err(invalid_passkey());
+    }
+
+    let point =
+        EncodedPoint::from_affine_coordinates(x.as_slice().into(), y.as_slice().into(), false);
+
+    VerifyingKey::from_encoded_point(&point).map_err(|_| invalid_passkey())
+}
+
+/// Vérifie que la clé publique COSE est exploitable.
+pub fn check_public_key(cose_key: &[u8]) -> Result<(), Error> {
+    verifying_key(cose_key).map(|_| ())
+}
+
+/// Vérifie la signature d'une assertion : elle porte sur les données
+/// d'authentification suivies de l'empreinte des données client.
+pub fn verify_assertion_signature(
+    cose_key: &[u8],
+    authenticator_data: &[u8],
+    client_data_json: &[u8],
+    signature: &[u8],
+) -> Result<bool, Error> {
+    let key = verifying_key(cose_key)?;
+    let signature = Signature::from_der(signature).map_err(|_| invalid_passkey())?;
+
+    let mut message = authenticator_data.to_vec();
+    message.extend_from_slice(&Sha256::digest(client_data_json));
+
+    Ok(key.verify(&message, &signature).is_ok())
+}
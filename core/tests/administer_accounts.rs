@@ -0,0 +1,306 @@
+use std::error::Error;
+
+use signuis_core::{
+    forms::{
+        account::{ImpersonateUserForm, ResetPasswordForm},
+        authentication::CredentialForm,
+    },
+    models::{
+        pagination::PageRequest,
+        session::Session,
+        user::{UserRole, UserSearchQuery},
+    },
+    repositories::user::fixtures::InsertUserFixture,
+    services::{
+        account::{
+            ChangeUserRole, EndImpersonation, ForcePasswordReset, ImpersonateUser,
+            ListUserAccounts, ResetPassword, SetUserActivation,
+        },
+        authentication::{AuthenticateWithCredential, CheckUserSessionToken},
+    },
+};
+
+mod setup;
+
+#[tokio::test]
+async fn list_user_accounts_requires_an_administrator() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let session = setup::create_user_session(&sg).await?;
+
+    let result = sg
+        .account
+        .execute(ListUserAccounts {
+            query: UserSearchQuery::default(),
+            page: PageRequest::default(),
+            session,
+        })
+        .await;
+
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn search_user_accounts_by_username() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let admin = Session::User(setup::create_admin_session(&sg).await?);
+
+    let fixture = InsertUserFixture::new();
+    let user_id = sg.repos.execute(fixture.clone()).await?;
+
+    let page = sg
+        .account
+        .execute(ListUserAccounts {
+            query: UserSearchQuery {
+                search: Some(fixture.username.clone()),
+                ..UserSearchQuery::default()
+            },
+            page: PageRequest::default(),
+            session: admin,
+        })
+        .await?;
+
+    assert_eq!(page.total, 1);
+    assert_eq!(page.accounts.items[0].id, user_id);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn change_user_role() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let admin = Session::User(setup::create_admin_session(&sg).await?);
+    let user = setup::create_user_session(&sg).await?;
+    let user_id = user.user().unwrap().id;
+
+    sg.account
+        .execute(ChangeUserRole {
+            user_id,
+            role: UserRole::Administrator,
+            session: admin.clone(),
+        })
+        .await?;
+
+    let page = sg
+        .account
+        .execute(ListUserAccounts {
+            query: UserSearchQuery {
+                search: Some(user.user().unwrap().username.clone()),
+                ..UserSearchQuery::default()
+            },
+            page: PageRequest::default(),
+            session: admin.clone(),
+        })
+        .await?;
+
+    assert!(page.accounts.items[0].role == UserRole::Administrator);
+
+    // un administrateur ne peut pas changer son propre rôle
+    let result = sg
+        .account
+        .execute(ChangeUserRole {
+            user_id: admin.user().unwrap().id,
+            role: UserRole::User,
+            session: admin,
+        })
+        .await;
+
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn deactivated_account_cannot_authenticate() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let admin = Session::User(setup::create_admin_session(&sg).await?);
+    let user = match setup::create_user_session(&sg).await? {
+        Session::User(user) => user,
+        Session::Anonymous => unreachable!(),
+    };
+
+    sg.account
+        .execute(SetUserActivation {
+            user_id: user.user.id,
+            activated: false,
+            session: admin,
+        })
+        .await?;
+
+    let session = sg
+        .auth
+        .execute(CheckUserSessionToken::new(user.token))
+        .await?;
+
+    assert!(session.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn forced_password_reset_requires_a_new_password() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let admin = Session::User(setup::create_admin_session(&sg).await?);
+
+    let fixture = InsertUserFixture::new();
+    let user_id = sg.repos.execute(fixture.clone()).await?;
+
+    let ticket = sg
+        .account
+        .execute(ForcePasswordReset {
+            user_id,
+            session: admin,
+        })
+        .await?;
+
+    let authenticate = |password: String| AuthenticateWithCredential {
+        form: CredentialForm {
+            username_or_email: fixture.username.clone(),
+            password,
+        },
+        session: Session::Anonymous,
+    };
+
+    let result = sg
+        .auth
+        .execute(authenticate(fixture.password.clone().unwrap()))
+        .await;
+    assert!(result.is_err());
+
+    sg.account
+        .execute(ResetPassword {
+            form: ResetPasswordForm {
+                token: ticket.token.clone(),
+                new_password: "un nouveau mot de passe".to_owned(),
+                confirm_password: "un nouveau mot de passe".to_owned(),
+            },
+        })
+        .await?;
+
+    let session = sg
+        .auth
+        .execute(authenticate("un nouveau mot de passe".to_owned()))
+        .await?
+        .session()
+        .ok_or("l'authentification n'est pas complète")?;
+    assert_eq!(session.user_id, user_id);
+
+    // le jeton est à usage unique
+    let result = sg
+        .account
+        .execute(ResetPassword {
+            form: ResetPasswordForm {
+                token: ticket.token,
+                new_password: "encore un autre".to_owned(),
+                confirm_password: "encore un autre".to_owned(),
+            },
+        })
+        .await;
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn impersonate_user() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let admin = Session::User(setup::create_admin_session(&sg).await?);
+    let user = setup::create_user_session(&sg).await?;
+    let user_id = user.user().unwrap().id;
+
+    let created = sg
+        .account
+        .execute(ImpersonateUser {
+            form: ImpersonateUserForm {
+                user_id,
+                reason: "ticket #42 : signalement introuvable".to_owned(),
+            },
+            session: admin.clone(),
+        })
+        .await?;
+
+    let session = sg
+        .auth
+        .execute(CheckUserSessionToken::new(created.token))
+        .await?
+        .ok_or("la session n'a pas été créée")?;
+
+    assert_eq!(session.user.id, user_id);
+    assert_eq!(session.impersonator_id, Some(admin.user().unwrap().id));
+
+    // impossible d'agir au nom d'un autre administrateur
+    let other_admin = setup::create_admin_session(&sg).await?;
+
+    let result = sg
+        .account
+        .execute(ImpersonateUser {
+            form: ImpersonateUserForm {
+                user_id: other_admin.user.id,
+                reason: "curiosité".to_owned(),
+            },
+            session: admin,
+        })
+        .await;
+
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn end_impersonation() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let admin = Session::User(setup::create_admin_session(&sg).await?);
+    let user = setup::create_user_session(&sg).await?;
+
+    let created = sg
+        .account
+        .execute(ImpersonateUser {
+            form: ImpersonateUserForm {
+                user_id: user.user().unwrap().id,
+                reason: "ticket #43".to_owned(),
+            },
+            session: admin,
+        })
+        .await?;
+
+    let impersonation = sg
+        .auth
+        .execute(CheckUserSessionToken::new(created.token.clone()))
+        .await?
+        .ok_or("la session n'a pas été créée")?;
+
+    // une session ordinaire n'est pas une session d'assistance
+    let result = sg
+        .account
+        .execute(EndImpersonation {
+            session: user.clone(),
+        })
+        .await;
+    assert!(result.is_err());
+
+    sg.account
+        .execute(EndImpersonation {
+            session: Session::User(impersonation),
+        })
+        .await?;
+
+    let session = sg
+        .auth
+        .execute(CheckUserSessionToken::new(created.token))
+        .await?;
+    assert!(session.is_none());
+
+    // la session de l'utilisateur lui-même n'est pas révoquée
+    let Session::User(user) = user else {
+        unreachable!()
+    };
+    let session = sg
+        .auth
+        .execute(CheckUserSessionToken::new(user.token))
+        .await?;
+    assert!(session.is_some());
+
+    Ok(())
+}
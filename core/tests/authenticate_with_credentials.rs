@@ -24,7 +24,9 @@ async fn authenticate_with_credentials_with_name_and_valid_password() -> Result<
             },
             session: Session::Anonymous,
         })
-        .await?;
+        .await?
+        .session()
+        .ok_or("l'authentification n'est pas complète")?;
 
     assert_eq!(user_id, session.user_id);
     Ok(())
@@ -47,7 +49,9 @@ async fn authenticate_with_credentials_with_email_and_valid_password() -> Result
             },
             session: Session::Anonymous,
         })
-        .await?;
+        .await?
+        .session()
+        .ok_or("l'authentification n'est pas complète")?;
 
     assert_eq!(user_id, session.user_id);
 
@@ -70,7 +74,9 @@ async fn authenticate_with_credentials_with_invalid_password() -> Result<(), Box
             },
             session: Session::Anonymous,
         })
-        .await?;
+        .await?
+        .session()
+        .ok_or("l'authentification n'est pas complète")?;
 
     assert_eq!(user_id, session.user_id);
 
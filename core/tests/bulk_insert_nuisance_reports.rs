@@ -0,0 +1,54 @@
+use std::error::Error;
+
+use chrono::{TimeZone, Utc};
+use signuis_core::repositories::nuisance_report::{
+    BulkInsertNuisanceReports, MaybeFindOneNuisanceReportById, MaybeFindOneNuisanceReportPosition,
+    NewNuisanceReport,
+};
+
+mod setup;
+
+#[tokio::test]
+async fn bulk_insert_nuisance_reports() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let session = setup::create_user_session(&sg).await?;
+    let type_id = setup::create_nuisance_type(&sg).await?;
+    let user_id = session.user().map(|u| u.id);
+
+    let observed_from = Utc.with_ymd_and_hms(2024, 7, 14, 22, 30, 0).unwrap();
+
+    let mut observed = NewNuisanceReport::new(type_id, user_id, 4.84, 45.76, 4);
+    observed.description = Some("odeur\tâcre\npersistante".to_owned());
+    observed.observed_from = Some(observed_from);
+    observed.perceived_duration = Some(90);
+
+    let reports = vec![
+        NewNuisanceReport::new(type_id, user_id, 2.35, 48.85, 3),
+        observed,
+        NewNuisanceReport::new(type_id, None, 5.37, 43.3, 1),
+    ];
+
+    let ids = sg.repos.execute(BulkInsertNuisanceReports(reports)).await?;
+    assert_eq!(ids.len(), 3);
+
+    let anonymous = sg
+        .repos
+        .execute(MaybeFindOneNuisanceReportById(ids[2]))
+        .await?
+        .ok_or("le signalement n'a pas été inséré")?;
+
+    assert_eq!(anonymous.type_id, type_id);
+    assert_eq!(anonymous.user_id, None);
+    assert_eq!(anonymous.intensity, 1);
+
+    let position = sg
+        .repos
+        .execute(MaybeFindOneNuisanceReportPosition(ids[1]))
+        .await?
+        .ok_or("le signalement n'a pas été inséré")?;
+
+    assert_eq!((position.longitude, position.latitude), (4.84, 45.76));
+    assert_eq!(position.at, observed_from);
+
+    Ok(())
+}
@@ -19,6 +19,8 @@ async fn check_session_token_with_valid_token() -> Result<(), Box<dyn Error>> {
             user_id,
             token: "token1234".to_owned(),
             expires_at: Utc::now().add(Duration::hours(1)),
+            second_factor_verified: false,
+            impersonator_id: None,
         })
         .await?;
 
@@ -47,6 +49,8 @@ async fn check_session_token_with_invalid_token() -> Result<(), Box<dyn Error>>
             user_id,
             token: "token1234".to_owned(),
             expires_at: Utc::now().add(Duration::hours(1)),
+            second_factor_verified: false,
+            impersonator_id: None,
         })
         .await?;
 
@@ -72,6 +76,8 @@ async fn check_session_token_with_expired_session() -> Result<(), Box<dyn Error>
             user_id,
             token: "token1234".to_owned(),
             expires_at: Utc::now().add(Duration::hours(-1)),
+            second_factor_verified: false,
+            impersonator_id: None,
         })
         .await?;
 
@@ -0,0 +1,103 @@
+use std::error::Error;
+
+use serde_json::json;
+use signuis_core::{
+    forms::reporting::{CreateNuisanceReportForm, PhotoUpload},
+    models::pagination::PageRequest,
+    services::reporting::{CreateNuisanceReport, ListMyReports},
+    storage::StorageSettings,
+    SgSettings, Signuis,
+};
+use uuid::Uuid;
+
+mod setup;
+
+const PNG: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+fn report_with_photo(
+    type_id: signuis_core::models::nuisance_type::NuisanceTypeId,
+) -> Result<CreateNuisanceReportForm, Box<dyn Error>> {
+    Ok(CreateNuisanceReportForm {
+        intensity: Some(3),
+        type_id: Some(type_id),
+        location: Some(serde_json::from_value(json!({
+            "type": "Point",
+            "coordinates": [2.35, 48.85]
+        }))?),
+        photos: vec![PhotoUpload {
+            filename: "photo.png".to_owned(),
+            content_type: "image/png".to_owned(),
+            data: PNG.to_vec(),
+        }],
+        ..Default::default()
+    })
+}
+
+async fn setup_with_storage_root(root: std::path::PathBuf) -> Result<Signuis, Box<dyn Error>> {
+    let sg = Signuis::new(
+        SgSettings::default()
+            .set_in_memory(true)
+            .set_storage(StorageSettings::Local { root })
+            .to_owned(),
+    )
+    .await?;
+
+    Ok(sg)
+}
+
+#[tokio::test]
+async fn create_nuisance_report_with_photo() -> Result<(), Box<dyn Error>> {
+    let root = std::env::temp_dir().join(format!("signuis-{}", Uuid::new_v4()));
+    let sg = setup_with_storage_root(root.clone()).await?;
+    let session = setup::create_user_session(&sg).await?;
+    let type_id = setup::create_nuisance_type(&sg).await?;
+
+    let report_id = sg
+        .reporting
+        .execute(CreateNuisanceReport {
+            form: report_with_photo(type_id)?,
+            session,
+        })
+        .await?;
+
+    let stored = std::fs::read_dir(root.join("reports").join(report_id.to_string()))?.count();
+    std::fs::remove_dir_all(&root)?;
+
+    assert_eq!(stored, 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn failed_photo_upload_leaves_no_report() -> Result<(), Box<dyn Error>> {
+    // la racine du stockage est un fichier : aucun dépôt ne peut réussir
+    let root = std::env::temp_dir().join(format!("signuis-{}", Uuid::new_v4()));
+    std::fs::write(&root, b"")?;
+
+    let sg = setup_with_storage_root(root.clone()).await?;
+    let session = setup::create_user_session(&sg).await?;
+    let type_id = setup::create_nuisance_type(&sg).await?;
+
+    let result = sg
+        .reporting
+        .execute(CreateNuisanceReport {
+            form: report_with_photo(type_id)?,
+            session: session.clone(),
+        })
+        .await;
+
+    std::fs::remove_file(&root)?;
+    assert!(result.is_err());
+
+    let reports = sg
+        .reporting
+        .execute(ListMyReports {
+            page: PageRequest::default(),
+            session,
+        })
+        .await?;
+
+    assert!(reports.items.is_empty());
+
+    Ok(())
+}
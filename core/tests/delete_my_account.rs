@@ -0,0 +1,114 @@
+use std::error::Error;
+
+use signuis_core::{
+    repositories::nuisance_report::{
+        FetchNuisanceReportPhotos, InsertNuisanceReport, InsertNuisanceReportPhoto,
+        MaybeFindOneNuisanceReportById,
+    },
+    services::account::{DeleteMyAccount, ExportMyData},
+};
+use sql_gis::types::Point;
+use uuid::Uuid;
+
+mod setup;
+
+#[tokio::test]
+async fn export_my_data_includes_my_reports() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let session = setup::create_user_session(&sg).await?;
+    let type_id = setup::create_nuisance_type(&sg).await?;
+    let user_id = session.user().map(|u| u.id);
+
+    let report_id = sg
+        .repos
+        .execute(InsertNuisanceReport::new(
+            type_id,
+            user_id,
+            Point::new(2.35, 48.85).into(),
+            3,
+        ))
+        .await?;
+
+    let archive = sg.account.execute(ExportMyData { session }).await?;
+
+    assert_eq!(Some(archive.profile.id), user_id);
+    assert_eq!(archive.sessions.len(), 1);
+    assert_eq!(archive.reports.len(), 1);
+    assert_eq!(archive.reports[0].id, report_id);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn delete_my_account_keeps_anonymized_reports() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let session = setup::create_user_session(&sg).await?;
+    let type_id = setup::create_nuisance_type(&sg).await?;
+
+    let report_id = sg
+        .repos
+        .execute(InsertNuisanceReport::new(
+            type_id,
+            session.user().map(|u| u.id),
+            Point::new(2.35, 48.85).into(),
+            3,
+        ))
+        .await?;
+
+    sg.account
+        .execute(DeleteMyAccount {
+            session: session.clone(),
+        })
+        .await?;
+
+    let report = sg
+        .repos
+        .execute(MaybeFindOneNuisanceReportById(report_id))
+        .await?
+        .ok_or("le signalement a été supprimé")?;
+
+    assert!(report.user_id.is_none());
+
+    let result = sg.account.execute(ExportMyData { session }).await;
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn delete_my_account_removes_report_photos() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let session = setup::create_user_session(&sg).await?;
+    let type_id = setup::create_nuisance_type(&sg).await?;
+
+    let report_id = sg
+        .repos
+        .execute(InsertNuisanceReport::new(
+            type_id,
+            session.user().map(|u| u.id),
+            Point::new(2.35, 48.85).into(),
+            3,
+        ))
+        .await?;
+
+    sg.repos
+        .execute(InsertNuisanceReportPhoto {
+            id: Uuid::new_v4(),
+            report_id,
+            storage_key: format!("reports/{report_id}/photo.png"),
+            content_type: "image/png".to_owned(),
+            size: 8,
+        })
+        .await?;
+
+    sg.account.execute(DeleteMyAccount { session }).await?;
+
+    let photos = sg
+        .repos
+        .execute(FetchNuisanceReportPhotos(report_id))
+        .await?;
+
+    assert!(photos.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use signuis_core::{
+    error::Error,
+    issues::{Issue, Issues},
+};
+
+#[test]
+fn error_displays_only_its_kind() {
+    let source = std::io::Error::other("disque plein");
+    let error = Error::internal_error_with_source(source);
+
+    assert_eq!(Error::not_found().to_string(), "not found");
+    assert_eq!(error.to_string(), "internal error");
+
+    let source = std::error::Error::source(&error).map(ToString::to_string);
+    assert_eq!(source.as_deref(), Some("disque plein"));
+}
+
+#[test]
+fn error_chain_displays_its_causes() {
+    let source = std::io::Error::other("disque plein");
+    let error = Error::internal_error_with_source(source);
+
+    assert_eq!(error.chain().to_string(), "internal error: disque plein");
+}
+
+#[test]
+fn invalid_error_displays_its_issues() {
+    let error = Issues::new()
+        .add(Issue::new_invalid_form(
+            "the username is required",
+            ["username"],
+        ))
+        .add(Issue::new_invalid_form(
+            "the form is incomplete",
+            Vec::<String>::new(),
+        ))
+        .to_owned()
+        .into_error();
+
+    assert_eq!(
+        error.to_string(),
+        "invalid: username: the username is required; the form is incomplete"
+    );
+}
@@ -0,0 +1,62 @@
+use std::error::Error;
+use std::sync::Arc;
+
+use futures::TryStreamExt;
+use signuis_core::{
+    fixtures::{
+        self,
+        nuisance_reports::NuisanceReportFixture,
+        nuisance_types::NuisanceTypeFixture,
+        rel::ForeignKeyFixture,
+        spatial::{Cluster, ClusteredPoints},
+        BATCH_SIZE,
+    },
+    geodesy::haversine_distance,
+};
+
+mod setup;
+
+#[tokio::test]
+async fn generate_reports_in_batches() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+
+    // chaque type crée sa propre famille
+    let types: Arc<[_]> = fixtures::generate::<NuisanceTypeFixture>(&sg.repos, 3)
+        .try_concat()
+        .await?
+        .into();
+
+    let count = BATCH_SIZE + 10;
+
+    let batches: Vec<Vec<_>> = fixtures::generate_with(&sg.repos, count, || {
+        NuisanceReportFixture::new()
+            .with_type(ForeignKeyFixture::OneOf(types.clone()))
+            .anonymous()
+    })
+    .try_collect()
+    .await?;
+
+    assert_eq!(batches.len(), 2);
+    assert_eq!(batches.iter().map(Vec::len).sum::<usize>(), count);
+
+    Ok(())
+}
+
+#[test]
+fn clustered_points_stay_around_their_centers() {
+    let points = ClusteredPoints::new(vec![
+        Cluster::new(2.35, 48.85, 1.0, 3.0),
+        Cluster::new(4.84, 45.76, 1.0, 1.0),
+    ]);
+
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..1_000 {
+        let (longitude, latitude) = points.sample(&mut rng);
+
+        let nearest = haversine_distance(longitude, latitude, 2.35, 48.85)
+            .min(haversine_distance(longitude, latitude, 4.84, 45.76));
+
+        assert!(nearest < 10.0);
+    }
+}
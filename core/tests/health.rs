@@ -0,0 +1,73 @@
+use std::error::Error;
+
+use signuis_core::{
+    health::{HealthProbe, HealthStatus},
+    repositories::nuisance_family::InsertNuisanceFamily,
+};
+
+mod setup;
+
+#[tokio::test]
+async fn in_memory_system_is_live_and_ready() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup_in_memory().await?;
+    let report = sg.health().await;
+
+    assert!(report.is_live());
+    assert!(report.is_ready());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn liveness_only_probes_mailboxes() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup_in_memory().await?;
+    let report = sg.liveness();
+
+    let probes: Vec<_> = report.checks.iter().map(|check| check.probe).collect();
+
+    assert_eq!(probes, [HealthProbe::Mailboxes]);
+    assert!(report.is_live());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn database_and_postgis_probes_succeed() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let report = sg.health().await;
+
+    let status = |probe| {
+        report
+            .checks
+            .iter()
+            .find(|check| check.probe == probe)
+            .map(|check| check.status)
+    };
+
+    assert_eq!(status(HealthProbe::Database), Some(HealthStatus::Up));
+    assert_eq!(status(HealthProbe::Postgis), Some(HealthStatus::Up));
+    assert!(report.is_live());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn metrics_include_repository_ops_and_pool_usage() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+
+    sg.repos
+        .execute(InsertNuisanceFamily {
+            label: "odeur".to_owned(),
+            description: "famille de test".to_owned(),
+        })
+        .await?;
+
+    let metrics = sg.metrics().await;
+    let op_count = "signuis_repository_op_duration_seconds_count{op=\"InsertNuisanceFamily\"}";
+
+    assert!(metrics.contains(op_count));
+    assert!(metrics.contains("signuis_db_pool_max_connections"));
+    assert!(metrics.contains("signuis_reports_created_total"));
+
+    Ok(())
+}
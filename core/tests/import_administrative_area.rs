@@ -0,0 +1,79 @@
+use std::error::Error;
+
+use serde_json::json;
+use signuis_core::{
+    forms::administrative_area::ImportAdministrativeAreaForm,
+    models::{administrative_area::AreaLevel, pagination::PageRequest},
+    services::territory::{ImportAdministrativeArea, ListAdministrativeAreas},
+};
+
+mod setup;
+
+fn commune(name: &str, geometry: serde_json::Value) -> ImportAdministrativeAreaForm {
+    ImportAdministrativeAreaForm {
+        code: "75056".to_owned(),
+        name: name.to_owned(),
+        level: AreaLevel::Commune,
+        geometry,
+    }
+}
+
+fn square() -> serde_json::Value {
+    json!({
+        "type": "Polygon",
+        "coordinates": [[[2.2, 48.8], [2.5, 48.8], [2.5, 48.9], [2.2, 48.9], [2.2, 48.8]]]
+    })
+}
+
+#[tokio::test]
+async fn test_import_administrative_area_updates_known_code() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+
+    let first = sg
+        .territory
+        .execute(ImportAdministrativeArea {
+            form: commune("Paris", square()),
+        })
+        .await?;
+
+    let second = sg
+        .territory
+        .execute(ImportAdministrativeArea {
+            form: commune("Paris (ville)", square()),
+        })
+        .await?;
+
+    assert_eq!(first, second);
+
+    let areas = sg
+        .territory
+        .execute(ListAdministrativeAreas {
+            level: Some(AreaLevel::Commune),
+            page: PageRequest::first(PageRequest::MAX_LIMIT),
+        })
+        .await?;
+
+    let area = areas.items.iter().find(|area| area.id == first).unwrap();
+    assert_eq!(area.name, "Paris (ville)");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_import_administrative_area_rejects_points() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+
+    let result = sg
+        .territory
+        .execute(ImportAdministrativeArea {
+            form: commune(
+                "Paris",
+                json!({ "type": "Point", "coordinates": [2.35, 48.85] }),
+            ),
+        })
+        .await;
+
+    assert!(result.is_err());
+
+    Ok(())
+}
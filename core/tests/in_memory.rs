@@ -0,0 +1,269 @@
+use std::cell::RefCell;
+use std::error::Error;
+use std::rc::Rc;
+
+use serde_json::json;
+use signuis_core::{
+    forms::{
+        account::RegisterUserForm, administrative_area::ImportAdministrativeAreaForm,
+        authentication::CredentialForm, reporting::CreateNuisanceReportForm,
+    },
+    models::{administrative_area::AreaLevel, pagination::PageRequest, session::Session},
+    pagination::{CursorKey, Keyset},
+    repositories::{
+        administrative_area::FetchAdministrativeAreas,
+        nuisance_family::{InsertNuisanceFamily, NuisanceFamilyExists},
+    },
+    services::{
+        account::RegisterUser,
+        authentication::{AuthenticateWithCredential, CheckUserSessionToken},
+        reporting::{CreateNuisanceReport, ListMyReports, RetractNuisanceReport},
+        territory::{ImportAdministrativeArea, ListAdministrativeAreas},
+    },
+};
+
+mod setup;
+
+#[tokio::test]
+async fn register_and_authenticate_in_memory() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup_in_memory().await?;
+    let password = "cheval correct agrafe pile";
+
+    let user_id = sg
+        .account
+        .execute(RegisterUser {
+            form: RegisterUserForm {
+                username: "riverain".to_owned(),
+                email: "riverain@example.com".to_owned(),
+                password: password.to_owned(),
+                confirm_password: password.to_owned(),
+            },
+        })
+        .await?;
+
+    let created = sg
+        .auth
+        .execute(AuthenticateWithCredential {
+            form: CredentialForm {
+                username_or_email: "riverain@example.com".to_owned(),
+                password: password.to_owned(),
+            },
+            session: Session::Anonymous,
+        })
+        .await?
+        .session()
+        .ok_or("l'authentification n'est pas complète")?;
+
+    assert_eq!(created.user_id, user_id);
+
+    let session = sg
+        .auth
+        .execute(CheckUserSessionToken::new(created.token))
+        .await?
+        .ok_or("la session n'a pas été trouvée")?;
+
+    assert_eq!(session.user.id, user_id);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn report_and_retract_in_memory() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup_in_memory().await?;
+    let session = setup::create_user_session(&sg).await?;
+    let type_id = setup::create_nuisance_type(&sg).await?;
+
+    let report_id = sg
+        .reporting
+        .execute(CreateNuisanceReport {
+            form: CreateNuisanceReportForm {
+                intensity: Some(3),
+                type_id: Some(type_id),
+                location: Some(serde_json::from_value(json!({
+                    "type": "Point",
+                    "coordinates": [2.35, 48.85]
+                }))?),
+                ..Default::default()
+            },
+            session: session.clone(),
+        })
+        .await?;
+
+    let reports = sg
+        .reporting
+        .execute(ListMyReports {
+            page: PageRequest::default(),
+            session: session.clone(),
+        })
+        .await?;
+
+    assert_eq!(reports.items.len(), 1);
+    assert_eq!(reports.items[0].id, report_id);
+
+    sg.reporting
+        .execute(RetractNuisanceReport {
+            id: report_id,
+            session: session.clone(),
+        })
+        .await?;
+
+    let reports = sg
+        .reporting
+        .execute(ListMyReports {
+            page: PageRequest::default(),
+            session,
+        })
+        .await?;
+
+    assert!(reports.items.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn in_memory_transaction_is_rolled_back_on_error() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup_in_memory().await?;
+
+    let result = sg
+        .repos
+        .transaction(move |tx| {
+            Box::pin(async move {
+                tx.execute(InsertNuisanceFamily {
+                    label: "odeur".to_owned(),
+                    description: "famille de test".to_owned(),
+                })
+                .await?;
+                Err::<(), _>(signuis_core::error::Error::internal_error())
+            })
+        })
+        .await;
+
+    assert!(result.is_err());
+
+    let family_id = sg
+        .repos
+        .execute(InsertNuisanceFamily {
+            label: "odeur".to_owned(),
+            description: "famille de test".to_owned(),
+        })
+        .await?;
+
+    assert!(sg.repos.execute(NuisanceFamilyExists(family_id)).await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn in_memory_transaction_fails_on_concurrent_write() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup_in_memory().await?;
+    let repos = sg.repos.clone();
+    let concurrent = Rc::new(RefCell::new(None));
+    let concurrent_id = concurrent.clone();
+
+    let result = sg
+        .repos
+        .transaction(move |tx| {
+            Box::pin(async move {
+                tx.execute(InsertNuisanceFamily {
+                    label: "odeur".to_owned(),
+                    description: "famille de test".to_owned(),
+                })
+                .await?;
+
+                // écriture hors de la transaction, pendant celle-ci
+                let family_id = repos
+                    .execute(InsertNuisanceFamily {
+                        label: "bruit".to_owned(),
+                        description: "famille de test".to_owned(),
+                    })
+                    .await?;
+
+                *concurrent_id.borrow_mut() = Some(family_id);
+                Ok(())
+            })
+        })
+        .await;
+
+    assert!(result.is_err());
+
+    // l'écriture concurrente n'est pas écrasée par la transaction
+    let family_id = concurrent
+        .take()
+        .ok_or("la famille concurrente est créée")?;
+    assert!(sg.repos.execute(NuisanceFamilyExists(family_id)).await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn in_memory_transaction_tolerates_concurrent_reads() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup_in_memory().await?;
+    let repos = sg.repos.clone();
+
+    let family_id = sg
+        .repos
+        .transaction(move |tx| {
+            Box::pin(async move {
+                let family_id = tx
+                    .execute(InsertNuisanceFamily {
+                        label: "odeur".to_owned(),
+                        description: "famille de test".to_owned(),
+                    })
+                    .await?;
+
+                // lecture seule hors de la transaction, pendant celle-ci
+                repos
+                    .execute(FetchAdministrativeAreas {
+                        level: None,
+                        page: Keyset::first(
+                            PageRequest::MAX_LIMIT,
+                            CursorKey::random(),
+                            FetchAdministrativeAreas::CURSOR_NAMESPACE,
+                        ),
+                    })
+                    .await?;
+
+                Ok(family_id)
+            })
+        })
+        .await?;
+
+    assert!(sg.repos.execute(NuisanceFamilyExists(family_id)).await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn import_administrative_area_in_memory() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup_in_memory().await?;
+
+    let import = |name: &str| ImportAdministrativeArea {
+        form: ImportAdministrativeAreaForm {
+            code: "75056".to_owned(),
+            name: name.to_owned(),
+            level: AreaLevel::Commune,
+            geometry: json!({
+                "type": "MultiPolygon",
+                "coordinates": [[[[2.2, 48.8], [2.5, 48.8], [2.5, 48.9], [2.2, 48.8]]]]
+            }),
+        },
+    };
+
+    let first = sg.territory.execute(import("Paris")).await?;
+    let second = sg.territory.execute(import("Paris (ville)")).await?;
+
+    assert_eq!(first, second);
+
+    let areas = sg
+        .territory
+        .execute(ListAdministrativeAreas {
+            level: Some(AreaLevel::Commune),
+            page: PageRequest::default(),
+        })
+        .await?;
+
+    assert_eq!(areas.items.len(), 1);
+    assert_eq!(areas.items[0].name, "Paris (ville)");
+
+    Ok(())
+}
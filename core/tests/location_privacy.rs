@@ -0,0 +1,121 @@
+use chrono::Utc;
+use signuis_core::{
+    geodesy::haversine_distance,
+    models::{
+        session::{Session, SessionUser, UserSession},
+        user::UserRole,
+    },
+    privacy::{JitterKey, LocationFuzzing, PrivacyPolicy, PrivacySettings},
+};
+use uuid::Uuid;
+
+const PARIS: (f64, f64) = (2.3522, 48.8566);
+
+fn session(role: UserRole) -> (Uuid, Session) {
+    let user_id = Uuid::new_v4();
+
+    let session = Session::User(UserSession {
+        id: Uuid::new_v4(),
+        user: SessionUser {
+            id: user_id,
+            username: "jdoe".to_owned(),
+            email: "jdoe@example.com".to_owned(),
+            avatar: None,
+            role,
+        },
+        token: String::default(),
+        expires_at: Utc::now(),
+        created_at: Utc::now(),
+        second_factor_verified: role.requires_second_factor(),
+        impersonator_id: None,
+    });
+
+    (user_id, session)
+}
+
+fn settings(location_fuzzing: LocationFuzzing) -> PrivacySettings {
+    PrivacySettings {
+        location_fuzzing,
+        ..PrivacySettings::default()
+    }
+}
+
+#[test]
+fn snapped_locations_stay_within_their_cell() {
+    let policy = PrivacyPolicy::new(&settings(LocationFuzzing::Snap), &Session::Anonymous);
+    let (longitude, latitude) = policy.location(Uuid::new_v4(), None, PARIS.0, PARIS.1);
+
+    assert_ne!((longitude, latitude), PARIS);
+    // La diagonale d'une maille de 250 m mesure environ 354 m.
+    assert!(haversine_distance(PARIS.0, PARIS.1, longitude, latitude) * 1000.0 <= 360.0);
+
+    // Deux points proches dans la même maille sont confondus.
+    let neighbour = policy.location(Uuid::new_v4(), None, longitude + 0.0001, latitude);
+    assert_eq!(neighbour, (longitude, latitude));
+}
+
+#[test]
+fn jittered_locations_are_stable_for_a_report() {
+    let policy = PrivacyPolicy::new(&settings(LocationFuzzing::Jitter), &Session::Anonymous);
+    let report_id = Uuid::new_v4();
+
+    let first = policy.location(report_id, None, PARIS.0, PARIS.1);
+    let second = policy.location(report_id, None, PARIS.0, PARIS.1);
+
+    assert_eq!(first, second);
+    assert!(haversine_distance(PARIS.0, PARIS.1, first.0, first.1) * 1000.0 <= 360.0);
+}
+
+#[test]
+fn jitter_offsets_are_uniform_and_keyed() {
+    let key = JitterKey::new(b"0123456789abcdef0123456789abcdef");
+    let offsets: Vec<_> = (0..1000).map(|_| key.offset(Uuid::new_v4())).collect();
+
+    // les bits fixes d'un UUID v4 ne doivent pas cantonner le déplacement
+    let axes: [fn(&(f64, f64)) -> f64; 2] = [|offset| offset.0, |offset| offset.1];
+
+    for axis in axes {
+        assert!(offsets
+            .iter()
+            .map(axis)
+            .all(|offset| (0.0..1.0).contains(&offset)));
+        assert!(offsets.iter().map(axis).any(|offset| offset < 0.25));
+        assert!(offsets.iter().map(axis).any(|offset| offset >= 0.75));
+    }
+
+    // sans la clé, le déplacement ne se déduit pas de l'identifiant
+    let report_id = Uuid::new_v4();
+    assert_ne!(key.offset(report_id), JitterKey::random().offset(report_id));
+}
+
+#[test]
+fn administrators_and_reporters_see_exact_data() {
+    let settings = settings(LocationFuzzing::Snap);
+    let (_, admin) = session(UserRole::Administrator);
+    let (reporter_id, reporter) = session(UserRole::User);
+    let (_, other) = session(UserRole::User);
+    let email = Some("reporter@example.com".to_owned());
+
+    let admin = PrivacyPolicy::new(&settings, &admin);
+    assert_eq!(
+        admin.location(Uuid::new_v4(), Some(reporter_id), PARIS.0, PARIS.1),
+        PARIS
+    );
+    assert_eq!(admin.email(Some(reporter_id), email.clone()), email);
+    assert_eq!(admin.min_aggregate_size(), 0);
+
+    let reporter = PrivacyPolicy::new(&settings, &reporter);
+    assert_eq!(
+        reporter.location(Uuid::new_v4(), Some(reporter_id), PARIS.0, PARIS.1),
+        PARIS
+    );
+    assert_eq!(reporter.email(Some(reporter_id), email.clone()), email);
+
+    let other = PrivacyPolicy::new(&settings, &other);
+    assert_ne!(
+        other.location(Uuid::new_v4(), Some(reporter_id), PARIS.0, PARIS.1),
+        PARIS
+    );
+    assert_eq!(other.email(Some(reporter_id), email), None);
+    assert_eq!(other.min_aggregate_size(), settings.min_aggregate_size);
+}
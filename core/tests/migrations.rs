@@ -0,0 +1,24 @@
+use std::error::Error;
+
+use signuis_core::{repositories::migration::MIGRATOR, SgSettings, Signuis};
+
+#[tokio::test]
+async fn all_migrations_are_applied() -> Result<(), Box<dyn Error>> {
+    let sg = Signuis::new(SgSettings::default()).await?;
+
+    sg.migrate().await?;
+
+    let status = sg.migration_status().await?;
+    let expected = MIGRATOR
+        .iter()
+        .filter(|migration| migration.migration_type.is_up_migration())
+        .count();
+
+    assert_eq!(status.len(), expected);
+    assert!(status.iter().all(|migration| migration.applied));
+    assert!(status
+        .windows(2)
+        .all(|pair| pair[0].version < pair[1].version));
+
+    Ok(())
+}
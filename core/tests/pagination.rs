@@ -0,0 +1,210 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use signuis_core::{
+    error::{Error, ErrorKind},
+    models::{
+        pagination::PageRequest,
+        session::Session,
+        user::{UserRole, UserSearchQuery},
+    },
+    pagination::CursorKey,
+    repositories::{
+        nuisance_family::InsertNuisanceFamily, nuisance_report::InsertNuisanceReport,
+        user::fixtures::InsertUserFixture,
+    },
+    services::{
+        account::ListUserAccounts,
+        reporting::{ListMyReports, ListNuisanceFamilies},
+    },
+};
+use sql_gis::types::Point;
+use uuid::Uuid;
+
+mod setup;
+
+#[tokio::test]
+async fn list_my_reports_by_pages() -> Result<(), Box<dyn std::error::Error>> {
+    let sg = setup::setup().await?;
+    let session = setup::create_user_session(&sg).await?;
+    let type_id = setup::create_nuisance_type(&sg).await?;
+
+    for intensity in 1..=5 {
+        sg.repos
+            .execute(InsertNuisanceReport::new(
+                type_id,
+                session.user().map(|u| u.id),
+                Point::new(2.35, 48.85).into(),
+                intensity,
+            ))
+            .await?;
+    }
+
+    let mut reports = Vec::default();
+    let mut page_sizes = Vec::default();
+    let mut request = Some(PageRequest::first(2));
+
+    while let Some(page) = request {
+        let page = sg
+            .reporting
+            .execute(ListMyReports {
+                page,
+                session: session.clone(),
+            })
+            .await?;
+
+        request = page.next(2);
+        page_sizes.push(page.items.len());
+        reports.extend(page.items);
+    }
+
+    assert_eq!(page_sizes, [2, 2, 1]);
+
+    // du plus récent au plus ancien, sans doublon
+    assert!(reports
+        .windows(2)
+        .all(|pair| (pair[0].created_at, pair[0].id) > (pair[1].created_at, pair[1].id)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_nuisance_families_by_pages() -> Result<(), Box<dyn std::error::Error>> {
+    let sg = setup::setup().await?;
+    let prefix = Uuid::new_v4().to_string();
+
+    for index in 0..3 {
+        sg.repos
+            .execute(InsertNuisanceFamily {
+                label: format!("{prefix}-{index}"),
+                description: "famille de test".to_owned(),
+            })
+            .await?;
+    }
+
+    let mut labels = Vec::default();
+    let mut request = Some(PageRequest::first(2));
+
+    while let Some(page) = request {
+        let page = sg.reporting.execute(ListNuisanceFamilies { page }).await?;
+
+        request = page.next(2);
+        labels.extend(page.items.into_iter().map(|family| family.label));
+    }
+
+    let created: Vec<_> = labels
+        .iter()
+        .filter(|label| label.starts_with(&prefix))
+        .collect();
+
+    let expected: Vec<_> = (0..3).map(|index| format!("{prefix}-{index}")).collect();
+    assert_eq!(created, expected.iter().collect::<Vec<_>>());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_user_accounts_by_pages() -> Result<(), Box<dyn std::error::Error>> {
+    let sg = setup::setup().await?;
+    let admin = Session::User(setup::create_admin_session(&sg).await?);
+    let prefix = Uuid::new_v4().simple().to_string();
+
+    for index in 0..3 {
+        let mut fixture = InsertUserFixture::new();
+        fixture.username = format!("{prefix}-{index}");
+        fixture.role = UserRole::User;
+        sg.repos.execute(fixture).await?;
+    }
+
+    let query = UserSearchQuery {
+        search: Some(prefix.clone()),
+        ..UserSearchQuery::default()
+    };
+
+    let mut usernames = Vec::default();
+    let mut request = Some(PageRequest::first(2));
+
+    while let Some(page) = request {
+        let page = sg
+            .account
+            .execute(ListUserAccounts {
+                query: query.clone(),
+                page,
+                session: admin.clone(),
+            })
+            .await?;
+
+        assert_eq!(page.total, 3);
+        request = page.accounts.next(2);
+        usernames.extend(
+            page.accounts
+                .items
+                .into_iter()
+                .map(|account| account.username),
+        );
+    }
+
+    let expected: Vec<_> = (0..3).map(|index| format!("{prefix}-{index}")).collect();
+    assert_eq!(usernames, expected);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn tampered_cursor_is_rejected() -> Result<(), Box<dyn std::error::Error>> {
+    let sg = setup::setup().await?;
+
+    for index in 0..2 {
+        sg.repos
+            .execute(InsertNuisanceFamily {
+                label: format!("{}-{index}", Uuid::new_v4()),
+                description: "famille de test".to_owned(),
+            })
+            .await?;
+    }
+
+    let page = sg
+        .reporting
+        .execute(ListNuisanceFamilies {
+            page: PageRequest::first(1),
+        })
+        .await?;
+
+    let cursor = page.next_cursor.ok_or("la liste compte plusieurs pages")?;
+    let (_, signature) = cursor.split_once('.').ok_or("le curseur n'est pas signé")?;
+    let payload = URL_SAFE_NO_PAD.encode(r#"["", "00000000-0000-0000-0000-000000000000"]"#);
+    let tampered = format!("{payload}.{signature}");
+
+    let result = sg
+        .reporting
+        .execute(ListNuisanceFamilies {
+            page: PageRequest::after(tampered, 1),
+        })
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(Error {
+            kind: ErrorKind::Invalid(_),
+            ..
+        })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn cursor_is_bound_to_its_namespace() -> Result<(), Box<dyn std::error::Error>> {
+    let key = CursorKey::new(b"0123456789abcdef0123456789abcdef");
+    let cursor = key.encode("nuisance_families", &("bruit", 1))?;
+
+    let decoded: (String, i32) = key.decode("nuisance_families", &cursor)?;
+    assert_eq!(decoded, ("bruit".to_owned(), 1));
+
+    assert!(key
+        .decode::<(String, i32)>("nuisance_reports_by_user", &cursor)
+        .is_err());
+    assert!(CursorKey::random()
+        .decode::<(String, i32)>("nuisance_families", &cursor)
+        .is_err());
+
+    Ok(())
+}
@@ -0,0 +1,381 @@
+use std::error::Error;
+
+use ciborium::Value;
+use p256::ecdsa::{signature::Signer, Signature, SigningKey};
+use sha2::{Digest, Sha256};
+use signuis_core::{
+    error::{Error as SgError, ErrorKind},
+    forms::authentication::{CredentialForm, PasskeyAssertionForm, PasskeyRegistrationForm},
+    models::{
+        pagination::PageRequest,
+        passkey::{PasskeyCreationOptions, PasskeyRequestOptions},
+        session::Session,
+        user::UserRole,
+    },
+    repositories::user::fixtures::InsertUserFixture,
+    services::{
+        account::ForcePasswordReset,
+        authentication::{
+            AuthenticateWithCredential, AuthenticateWithPasskey, AuthenticationOutcome,
+            BeginPasskeyAuthentication, BeginPasskeyRegistration, CheckUserSessionToken,
+            FinishPasskeyRegistration, ListMyPasskeys,
+        },
+    },
+    webauthn::{encode_base64url, WebAuthnSettings},
+};
+
+mod setup;
+
+/// Authentificateur logiciel, pour jouer les cérémonies WebAuthn sans navigateur.
+struct SoftwareAuthenticator {
+    key: SigningKey,
+    credential_id: Vec<u8>,
+    sign_count: u32,
+}
+
+impl SoftwareAuthenticator {
+    fn new() -> Self {
+        Self {
+            key: SigningKey::from_slice(&rand::random::<[u8; 32]>()).unwrap(),
+            credential_id: rand::random::<[u8; 16]>().to_vec(),
+            sign_count: 0,
+        }
+    }
+
+    fn client_data(kind: &str, challenge: &str) -> Vec<u8> {
+        serde_json::to_vec(&serde_json::json!({
+            "type": kind,
+            "challenge": challenge,
+            "origin": WebAuthnSettings::default().origin,
+        }))
+        .unwrap()
+    }
+
+    fn authenticator_data(&self, rp_id: &str, flags: u8) -> Vec<u8> {
+        let mut data = Sha256::digest(rp_id.as_bytes()).to_vec();
+        data.push(flags);
+        data.extend_from_slice(&self.sign_count.to_be_bytes());
+        data
+    }
+
+    fn cose_key(&self) -> Vec<u8> {
+        let point = self.key.verifying_key().to_encoded_point(false);
+        let key = Value::Map(vec![
+            (Value::Integer(1.into()), Value::Integer(2.into())),
+            (Value::Integer(3.into()), Value::Integer((-7).into())),
+            (Value::Integer((-1).into()), Value::Integer(1.into())),
+            (
+                Value::Integer((-2).into()),
+                Value::Bytes(point.x().unwrap().to_vec()),
+            ),
+            (
+                Value::Integer((-3).into()),
+                Value::Bytes(point.y().unwrap().to_vec()),
+            ),
+        ]);
+
+        let mut bytes = Vec::new();
+        ciborium::ser::into_writer(&key, &mut bytes).unwrap();
+        bytes
+    }
+
+    /// Crée la clé, avec une attestation au format `none`.
+    fn register(&self, options: &PasskeyCreationOptions) -> PasskeyRegistrationForm {
+        // présence (UP), vérification (UV) et clé attestée (AT)
+        let mut auth_data = self.authenticator_data(&options.rp.id, 0x45);
+        auth_data.extend_from_slice(&[0u8; 16]);
+        auth_data.extend_from_slice(&(self.credential_id.len() as u16).to_be_bytes());
+        auth_data.extend_from_slice(&self.credential_id);
+        auth_data.extend_from_slice(&self.cose_key());
+
+        let attestation = Value::Map(vec![
+            (Value::Text("fmt".into()), Value::Text("none".into())),
+            (Value::Text("attStmt".into()), Value::Map(vec![])),
+            (Value::Text("authData".into()), Value::Bytes(auth_data)),
+        ]);
+
+        let mut attestation_object = Vec::new();
+        ciborium::ser::into_writer(&attestation, &mut attestation_object).unwrap();
+
+        PasskeyRegistrationForm {
+            name: "Clé de test".to_owned(),
+            credential_id: encode_base64url(&self.credential_id),
+            client_data_json: encode_base64url(&Self::client_data(
+                "webauthn.create",
+                &options.challenge,
+            )),
+            attestation_object: encode_base64url(&attestation_object),
+        }
+    }
+
+    /// Signe une assertion, en incrémentant le compteur de signatures.
+    fn assert(&mut self, options: &PasskeyRequestOptions) -> PasskeyAssertionForm {
+        self.sign_count += 1;
+
+        let auth_data = self.authenticator_data(&options.rp_id, 0x05);
+        let client_data = Self::client_data("webauthn.get", &options.challenge);
+
+        let mut message = auth_data.clone();
+        message.extend_from_slice(&Sha256::digest(&client_data));
+        let signature: Signature = self.key.sign(&message);
+
+        PasskeyAssertionForm {
+            credential_id: encode_base64url(&self.credential_id),
+            client_data_json: encode_base64url(&client_data),
+            authenticator_data: encode_base64url(&auth_data),
+            signature: encode_base64url(signature.to_der().as_bytes()),
+        }
+    }
+}
+
+#[tokio::test]
+async fn authenticate_with_passkey() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let session = setup::create_user_session(&sg).await?;
+    let user_id = session.user().unwrap().id;
+
+    let mut authenticator = SoftwareAuthenticator::new();
+
+    let options = sg
+        .auth
+        .execute(BeginPasskeyRegistration {
+            session: session.clone(),
+        })
+        .await?;
+
+    sg.auth
+        .execute(FinishPasskeyRegistration {
+            form: authenticator.register(&options),
+            session: session.clone(),
+        })
+        .await?;
+
+    let passkeys = sg
+        .auth
+        .execute(ListMyPasskeys {
+            page: PageRequest::default(),
+            session,
+        })
+        .await?;
+    assert_eq!(passkeys.items.len(), 1);
+
+    let options = sg.auth.execute(BeginPasskeyAuthentication).await?;
+
+    let created = sg
+        .auth
+        .execute(AuthenticateWithPasskey {
+            form: authenticator.assert(&options),
+        })
+        .await?;
+
+    let user_session = sg
+        .auth
+        .execute(CheckUserSessionToken::new(created.token))
+        .await?
+        .ok_or("la session n'a pas été créée")?;
+
+    assert_eq!(user_session.user.id, user_id);
+    assert!(user_session.second_factor_verified);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn cloned_passkey_is_rejected() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let session = setup::create_user_session(&sg).await?;
+
+    let mut authenticator = SoftwareAuthenticator::new();
+
+    let options = sg
+        .auth
+        .execute(BeginPasskeyRegistration {
+            session: session.clone(),
+        })
+        .await?;
+
+    sg.auth
+        .execute(FinishPasskeyRegistration {
+            form: authenticator.register(&options),
+            session,
+        })
+        .await?;
+
+    let options = sg.auth.execute(BeginPasskeyAuthentication).await?;
+    sg.auth
+        .execute(AuthenticateWithPasskey {
+            form: authenticator.assert(&options),
+        })
+        .await?;
+
+    // un clone repart du même compteur de signatures
+    authenticator.sign_count -= 1;
+
+    let options = sg.auth.execute(BeginPasskeyAuthentication).await?;
+    let result = sg
+        .auth
+        .execute(AuthenticateWithPasskey {
+            form: authenticator.assert(&options),
+        })
+        .await;
+
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn passkey_challenge_is_single_use() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let session = setup::create_user_session(&sg).await?;
+
+    let mut authenticator = SoftwareAuthenticator::new();
+
+    let options = sg
+        .auth
+        .execute(BeginPasskeyRegistration {
+            session: session.clone(),
+        })
+        .await?;
+
+    sg.auth
+        .execute(FinishPasskeyRegistration {
+            form: authenticator.register(&options),
+            session,
+        })
+        .await?;
+
+    let options = sg.auth.execute(BeginPasskeyAuthentication).await?;
+    sg.auth
+        .execute(AuthenticateWithPasskey {
+            form: authenticator.assert(&options),
+        })
+        .await?;
+
+    let result = sg
+        .auth
+        .execute(AuthenticateWithPasskey {
+            form: authenticator.assert(&options),
+        })
+        .await;
+
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn passkey_is_rejected_while_password_reset_is_pending() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let session = setup::create_user_session(&sg).await?;
+    let user_id = session.user().unwrap().id;
+    let admin = Session::User(setup::create_admin_session(&sg).await?);
+
+    let mut authenticator = SoftwareAuthenticator::new();
+
+    let options = sg
+        .auth
+        .execute(BeginPasskeyRegistration {
+            session: session.clone(),
+        })
+        .await?;
+
+    sg.auth
+        .execute(FinishPasskeyRegistration {
+            form: authenticator.register(&options),
+            session,
+        })
+        .await?;
+
+    sg.account
+        .execute(ForcePasswordReset {
+            user_id,
+            session: admin,
+        })
+        .await?;
+
+    let options = sg.auth.execute(BeginPasskeyAuthentication).await?;
+    let result = sg
+        .auth
+        .execute(AuthenticateWithPasskey {
+            form: authenticator.assert(&options),
+        })
+        .await;
+
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn passkey_cannot_replace_second_factor_enrolment() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+
+    let mut fixture = InsertUserFixture::new();
+    fixture.role = UserRole::Administrator;
+    sg.repos.execute(fixture.clone()).await?;
+
+    // mot de passe seul : la session n'ouvre que l'inscription au second facteur
+    let outcome = sg
+        .auth
+        .execute(AuthenticateWithCredential {
+            form: CredentialForm {
+                username_or_email: fixture.username.clone(),
+                password: fixture.password.clone().unwrap(),
+            },
+            session: Session::Anonymous,
+        })
+        .await?;
+
+    let created = match outcome {
+        AuthenticationOutcome::SecondFactorEnrolmentRequired(created) => created,
+        _ => return Err("l'inscription au second facteur est requise".into()),
+    };
+
+    let session = Session::User(
+        sg.auth
+            .execute(CheckUserSessionToken::new(created.token))
+            .await?
+            .ok_or("la session n'a pas été créée")?,
+    );
+
+    let result = sg
+        .auth
+        .execute(BeginPasskeyRegistration {
+            session: session.clone(),
+        })
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(SgError {
+            kind: ErrorKind::Unauthorized,
+            ..
+        })
+    ));
+
+    // ni avec des options obtenues par ailleurs
+    let other = setup::create_user_session(&sg).await?;
+    let options = sg
+        .auth
+        .execute(BeginPasskeyRegistration { session: other })
+        .await?;
+
+    let result = sg
+        .auth
+        .execute(FinishPasskeyRegistration {
+            form: SoftwareAuthenticator::new().register(&options),
+            session,
+        })
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(SgError {
+            kind: ErrorKind::Unauthorized,
+            ..
+        })
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use std::error::Error;
+
+use argon2::{password_hash::SaltString, Algorithm, Argon2, Params, PasswordHasher, Version};
+use signuis_core::{
+    forms::{account::ImportUserForm, authentication::CredentialForm},
+    models::{session::Session, user::UserRole},
+    password_policy::PasswordHashing,
+    repositories::{credential::MaybeFindOneCredentialById, user::InsertImportedUser},
+    services::{
+        account::ImportUserAccounts,
+        authentication::{AuthenticateWithCredential, CheckUserSessionToken},
+    },
+};
+use uuid::Uuid;
+
+mod setup;
+
+fn authenticate(username: &str, password: &str) -> AuthenticateWithCredential {
+    AuthenticateWithCredential {
+        form: CredentialForm::new(username, password),
+        session: Session::Anonymous,
+    }
+}
+
+#[tokio::test]
+async fn legacy_bcrypt_password_is_upgraded_on_login() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let admin = Session::User(setup::create_admin_session(&sg).await?);
+
+    let username = Uuid::new_v4().to_string();
+    let password = "mot de passe hérité";
+
+    let user_ids = sg
+        .account
+        .execute(ImportUserAccounts {
+            accounts: vec![ImportUserForm {
+                username: username.clone(),
+                email: format!("{username}@example.com"),
+                password_hash: bcrypt::hash(password, 4)?,
+            }],
+            session: admin,
+        })
+        .await?;
+
+    let created = sg
+        .auth
+        .execute(authenticate(&username, password))
+        .await?
+        .session()
+        .ok_or("l'authentification n'est pas complète")?;
+
+    sg.auth
+        .execute(CheckUserSessionToken::new(created.token))
+        .await?
+        .ok_or("la session n'a pas été créée")?;
+
+    let credential = sg
+        .repos
+        .execute(MaybeFindOneCredentialById(user_ids[0]))
+        .await?
+        .ok_or("le compte n'existe pas")?;
+
+    assert!(credential.password.starts_with("$argon2id$"));
+    assert!(!credential.needs_rehash(&PasswordHashing::default()));
+
+    // l'empreinte recalculée reste valide
+    sg.auth
+        .execute(authenticate(&username, password))
+        .await?
+        .session()
+        .ok_or("l'authentification n'est pas complète")?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn weak_argon2_password_is_rehashed_on_login() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+
+    let username = Uuid::new_v4().to_string();
+    let password = "un mot de passe ancien";
+
+    let weak = Argon2::new(
+        Algorithm::Argon2i,
+        Version::V0x13,
+        Params::new(1024, 1, 1, None).map_err(|err| err.to_string())?,
+    );
+    let salt = SaltString::generate(rand::thread_rng());
+
+    let user_id = sg
+        .repos
+        .execute(InsertImportedUser {
+            username: username.clone(),
+            email: format!("{username}@example.com"),
+            password_hash: weak
+                .hash_password(password.as_bytes(), &salt)
+                .map_err(|err| err.to_string())?
+                .to_string(),
+            role: UserRole::User,
+        })
+        .await?;
+
+    sg.auth
+        .execute(authenticate(&username, password))
+        .await?
+        .session()
+        .ok_or("l'authentification n'est pas complète")?;
+
+    let credential = sg
+        .repos
+        .execute(MaybeFindOneCredentialById(user_id))
+        .await?
+        .ok_or("le compte n'existe pas")?;
+
+    assert!(credential.password.starts_with("$argon2id$"));
+    assert!(!credential.needs_rehash(&PasswordHashing::default()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn import_rejects_unknown_password_hash() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let admin = Session::User(setup::create_admin_session(&sg).await?);
+
+    let username = Uuid::new_v4().to_string();
+
+    let result = sg
+        .account
+        .execute(ImportUserAccounts {
+            accounts: vec![ImportUserForm {
+                username: username.clone(),
+                email: format!("{username}@example.com"),
+                password_hash: "5f4dcc3b5aa765d61d8327deb882cf99".to_owned(),
+            }],
+            session: admin,
+        })
+        .await;
+
+    assert!(result.is_err());
+
+    Ok(())
+}
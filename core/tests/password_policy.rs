@@ -0,0 +1,102 @@
+use std::error::Error;
+
+use sha1::{Digest, Sha1};
+use signuis_core::{
+    error::ErrorKind, forms::account::RegisterUserForm, password_policy::PasswordPolicy,
+    services::account::RegisterUser, SgSettings,
+};
+use uuid::Uuid;
+
+mod setup;
+
+fn register(username: &str, password: &str) -> RegisterUser {
+    RegisterUser {
+        form: RegisterUserForm {
+            username: username.to_owned(),
+            email: format!("{username}@example.com"),
+            password: password.to_owned(),
+            confirm_password: password.to_owned(),
+        },
+    }
+}
+
+/// Codes des issues d'une erreur de validation.
+fn issue_codes<T>(result: Result<T, signuis_core::error::Error>) -> Vec<String> {
+    match result {
+        Err(signuis_core::error::Error {
+            kind: ErrorKind::Invalid(issues),
+            ..
+        }) => issues.iter().map(|issue| issue.code.clone()).collect(),
+        _ => Vec::default(),
+    }
+}
+
+#[tokio::test]
+async fn register_user_with_a_strong_password() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let username = Uuid::new_v4().to_string();
+
+    sg.account
+        .execute(register(&username, "cheval correct agrafe pile"))
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn register_user_rejects_weak_passwords() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let username = format!("nuisance{}", Uuid::new_v4().simple());
+
+    let result = sg.account.execute(register(&username, "c0urt!")).await;
+    assert_eq!(issue_codes(result), ["password_too_short"]);
+
+    let result = sg
+        .account
+        .execute(register(&username, "aaaaaaaaaaaaaaaa"))
+        .await;
+    assert_eq!(issue_codes(result), ["password_too_weak"]);
+
+    let password = format!("{}-Sign@lement", username.to_uppercase());
+    let result = sg.account.execute(register(&username, &password)).await;
+    assert_eq!(issue_codes(result), ["password_contains_personal_data"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn register_user_rejects_breached_password() -> Result<(), Box<dyn Error>> {
+    let password = "cheval correct agrafe pile";
+
+    // copie locale réduite au seul préfixe de l'empreinte du mot de passe
+    let hash = hex::encode_upper(Sha1::digest(password.as_bytes()));
+    let (prefix, suffix) = hash.split_at(5);
+
+    let directory = std::env::temp_dir().join(Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&directory)?;
+    std::fs::write(
+        directory.join(format!("{prefix}.txt")),
+        format!("0018A45C4D1DEF81644B54AB7F969B88D65:1\r\n{suffix}:42\r\n"),
+    )?;
+
+    let sg = setup::setup_with_settings(
+        SgSettings::default()
+            .set_password_policy(PasswordPolicy {
+                breached_passwords: Some(directory.clone()),
+                ..PasswordPolicy::default()
+            })
+            .to_owned(),
+    )
+    .await?;
+
+    let result = sg
+        .account
+        .execute(register(&Uuid::new_v4().to_string(), password))
+        .await;
+
+    std::fs::remove_dir_all(&directory)?;
+
+    assert_eq!(issue_codes(result), ["password_breached"]);
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use std::error::Error;
+use std::time::Duration;
+
+use signuis_core::{
+    cache::{CacheBackend, LruCache},
+    forms::reporting::CreateNuisanceFamilyForm,
+    models::{pagination::PageRequest, session::Session},
+    repositories::nuisance_family::InsertNuisanceFamily,
+    services::reporting::{CreateNuisanceFamily, ListNuisanceFamilies},
+    Signuis,
+};
+
+mod setup;
+
+const TTL: Duration = Duration::from_secs(60);
+
+#[tokio::test]
+async fn lru_cache_evicts_least_recently_used_entry() {
+    let cache = LruCache::new(2);
+
+    cache.put("taxonomy:a".to_owned(), b"a".to_vec(), TTL).await;
+    cache.put("taxonomy:b".to_owned(), b"b".to_vec(), TTL).await;
+    cache.get("taxonomy:a").await;
+    cache.put("taxonomy:c".to_owned(), b"c".to_vec(), TTL).await;
+
+    assert_eq!(cache.get("taxonomy:a").await, Some(b"a".to_vec()));
+    assert_eq!(cache.get("taxonomy:b").await, None);
+    assert_eq!(cache.get("taxonomy:c").await, Some(b"c".to_vec()));
+}
+
+#[tokio::test]
+async fn lru_cache_expires_entries() {
+    let cache = LruCache::new(10);
+
+    cache
+        .put("statistics:a".to_owned(), b"a".to_vec(), Duration::ZERO)
+        .await;
+
+    assert_eq!(cache.get("statistics:a").await, None);
+    assert!(cache.is_empty());
+}
+
+#[tokio::test]
+async fn lru_cache_invalidates_a_namespace() {
+    let cache = LruCache::new(10);
+
+    cache.put("taxonomy:a".to_owned(), b"a".to_vec(), TTL).await;
+    cache
+        .put("statistics:a".to_owned(), b"a".to_vec(), TTL)
+        .await;
+    cache.invalidate("taxonomy").await;
+
+    assert_eq!(cache.get("taxonomy:a").await, None);
+    assert_eq!(cache.get("statistics:a").await, Some(b"a".to_vec()));
+}
+
+async fn count_nuisance_families(sg: &Signuis) -> Result<usize, Box<dyn Error>> {
+    let page = sg
+        .reporting
+        .execute(ListNuisanceFamilies {
+            page: PageRequest::default(),
+        })
+        .await?;
+
+    Ok(page.items.len())
+}
+
+#[tokio::test]
+async fn nuisance_families_are_cached_until_the_taxonomy_changes() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup_in_memory().await?;
+
+    assert_eq!(count_nuisance_families(&sg).await?, 0);
+
+    // inséré sans passer par le service : le cache n'est pas invalidé
+    sg.repos
+        .execute(InsertNuisanceFamily {
+            label: "odeur".to_owned(),
+            description: "famille de test".to_owned(),
+        })
+        .await?;
+
+    assert_eq!(count_nuisance_families(&sg).await?, 0);
+
+    sg.reporting
+        .execute(CreateNuisanceFamily {
+            form: CreateNuisanceFamilyForm {
+                label: "bruit".to_owned(),
+                description: "famille de test".to_owned(),
+            },
+            session: Session::Anonymous,
+        })
+        .await?;
+
+    assert_eq!(count_nuisance_families(&sg).await?, 2);
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use signuis_core::{
+    models::emitter::{Emitter, EmitterKind},
+    repositories::emitter::AttributionObservation,
+    services::attribution::{rank_candidate_emitters, AttributionSettings},
+};
+use uuid::Uuid;
+
+fn emitter(name: &str, longitude: f64, latitude: f64, family_id: Uuid) -> Emitter {
+    Emitter {
+        id: Uuid::new_v4(),
+        name: name.to_owned(),
+        kind: EmitterKind::Industrial,
+        description: String::default(),
+        longitude,
+        latitude,
+        family_ids: vec![family_id],
+    }
+}
+
+/// Un signalement à Paris, avec un vent de secteur ouest.
+fn observation(family_id: Uuid) -> AttributionObservation {
+    AttributionObservation {
+        report_id: Uuid::new_v4(),
+        family_id,
+        longitude: 2.35,
+        latitude: 48.85,
+        intensity: 4,
+        wind_direction: Some(270.0),
+        wind_speed: Some(4.0),
+    }
+}
+
+#[test]
+fn rank_upwind_emitter_first() {
+    let family_id = Uuid::new_v4();
+    let observations = vec![observation(family_id), observation(family_id)];
+
+    let emitters = vec![
+        emitter("downwind", 2.40, 48.85, family_id),
+        emitter("upwind", 2.30, 48.85, family_id),
+    ];
+
+    let candidates =
+        rank_candidate_emitters(&observations, emitters, &AttributionSettings::default());
+
+    assert_eq!(candidates.len(), 2);
+    assert_eq!(candidates[0].emitter.name, "upwind");
+    assert_eq!(candidates[0].supporting_reports, 2);
+    assert_eq!(candidates[1].supporting_reports, 0);
+    assert_eq!(candidates[1].score, 0.0);
+}
+
+#[test]
+fn ignore_emitters_of_other_families_or_out_of_radius() {
+    let family_id = Uuid::new_v4();
+    let observations = vec![observation(family_id)];
+
+    let emitters = vec![
+        emitter("other family", 2.30, 48.85, Uuid::new_v4()),
+        emitter("too far", 1.00, 48.85, family_id),
+    ];
+
+    let candidates =
+        rank_candidate_emitters(&observations, emitters, &AttributionSettings::default());
+
+    assert!(candidates.is_empty());
+}
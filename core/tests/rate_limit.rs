@@ -0,0 +1,109 @@
+mod setup;
+
+use serde_json::json;
+use signuis_core::{
+    error::ErrorKind,
+    forms::{authentication::CredentialForm, reporting::CreateNuisanceReportForm},
+    models::session::Session,
+    repositories::user::fixtures::InsertUserFixture,
+    services::{
+        authentication::AuthenticateWithCredential, reporting::CreateNuisanceReport,
+        RateLimitSettings,
+    },
+    SgSettings,
+};
+use std::error::Error;
+
+#[tokio::test]
+async fn login_attempts_are_rate_limited() -> Result<(), Box<dyn Error>> {
+    let mut settings = SgSettings::default();
+    settings.set_rate_limits(RateLimitSettings {
+        enabled: true,
+        login_attempts_per_minute: 2,
+        reports_per_hour: 30,
+    });
+
+    let sg = setup::setup_with_settings(settings).await?;
+
+    let fixture = InsertUserFixture::new();
+    sg.repos.execute(fixture.clone()).await?;
+
+    let authenticate = |password: String| AuthenticateWithCredential {
+        form: CredentialForm {
+            username_or_email: fixture.username.clone(),
+            password,
+        },
+        session: Session::Anonymous,
+    };
+
+    for _ in 0..2 {
+        let result = sg
+            .auth
+            .execute(authenticate("mauvais mot de passe".to_owned()))
+            .await;
+        assert!(result.is_err());
+    }
+
+    // même le bon mot de passe est refusé une fois la limite atteinte
+    let result = sg
+        .auth
+        .execute(authenticate(fixture.password.clone().unwrap()))
+        .await;
+
+    let codes: Vec<String> = match result {
+        Err(signuis_core::error::Error {
+            kind: ErrorKind::Invalid(issues),
+            ..
+        }) => issues.iter().map(|issue| issue.code.clone()).collect(),
+        _ => Vec::default(),
+    };
+
+    assert_eq!(codes, ["rate_limited"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn invalid_reports_do_not_consume_the_quota() -> Result<(), Box<dyn Error>> {
+    let mut settings = SgSettings::default();
+    settings.set_rate_limits(RateLimitSettings {
+        enabled: true,
+        login_attempts_per_minute: 5,
+        reports_per_hour: 1,
+    });
+
+    let sg = setup::setup_with_settings(settings).await?;
+    let session = setup::create_user_session(&sg).await?;
+    let type_id = setup::create_nuisance_type(&sg).await?;
+
+    // l'intensité manquante rend le formulaire invalide
+    let result = sg
+        .reporting
+        .execute(CreateNuisanceReport {
+            form: CreateNuisanceReportForm {
+                type_id: Some(type_id),
+                ..Default::default()
+            },
+            session: session.clone(),
+        })
+        .await;
+
+    assert!(result.is_err());
+
+    sg.reporting
+        .execute(CreateNuisanceReport {
+            form: CreateNuisanceReportForm {
+                intensity: Some(3),
+                type_id: Some(type_id),
+                location: Some(serde_json::from_value(json!({
+                    "type": "Point",
+                    "coordinates": [2.35, 48.85]
+                }))?),
+                ..Default::default()
+            },
+            session,
+        })
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use std::error::Error;
+
+use signuis_core::{
+    models::pagination::PageRequest,
+    pagination::{CursorKey, Keyset},
+    repositories::{emitter::FetchEmitters, Repository, RepositorySettings},
+};
+
+fn fetch_emitters() -> FetchEmitters {
+    FetchEmitters {
+        family_ids: None,
+        page: Keyset::first(
+            PageRequest::MAX_LIMIT,
+            CursorKey::random(),
+            FetchEmitters::CURSOR_NAMESPACE,
+        ),
+    }
+}
+
+#[tokio::test]
+async fn read_only_op_runs_on_replica() -> Result<(), Box<dyn Error>> {
+    let mut settings = RepositorySettings::default();
+    let replica = settings.database_url.clone();
+    settings.set_read_replicas(vec![replica]);
+
+    let repos = Repository::new(&settings).await?;
+    repos.execute(fetch_emitters()).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_only_op_falls_back_to_primary() -> Result<(), Box<dyn Error>> {
+    let mut settings = RepositorySettings::default();
+    settings.set_read_replicas(vec!["postgres://signuis@127.0.0.1:1/signuis".to_owned()]);
+
+    let repos = Repository::new(&settings).await?;
+    repos.execute(fetch_emitters()).await?;
+
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use std::error::Error;
+
+use signuis_core::{
+    models::{pagination::PageRequest, session::Session},
+    repositories::nuisance_report::InsertNuisanceReport,
+    services::reporting::{ListMyReports, RetractNuisanceReport},
+};
+use sql_gis::types::Point;
+
+mod setup;
+
+#[tokio::test]
+async fn retract_nuisance_report_by_its_owner() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let session = setup::create_user_session(&sg).await?;
+    let type_id = setup::create_nuisance_type(&sg).await?;
+
+    let report_id = sg
+        .repos
+        .execute(InsertNuisanceReport::new(
+            type_id,
+            session.user().map(|u| u.id),
+            Point::new(2.35, 48.85).into(),
+            3,
+        ))
+        .await?;
+
+    sg.reporting
+        .execute(RetractNuisanceReport {
+            id: report_id,
+            session: session.clone(),
+        })
+        .await?;
+
+    let reports = sg
+        .reporting
+        .execute(ListMyReports {
+            page: PageRequest::default(),
+            session,
+        })
+        .await?;
+
+    assert!(reports.items.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn retract_nuisance_report_of_another_user() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let owner = setup::create_user_session(&sg).await?;
+    let other = setup::create_user_session(&sg).await?;
+    let type_id = setup::create_nuisance_type(&sg).await?;
+
+    let report_id = sg
+        .repos
+        .execute(InsertNuisanceReport::new(
+            type_id,
+            owner.user().map(|u| u.id),
+            Point::new(2.35, 48.85).into(),
+            3,
+        ))
+        .await?;
+
+    let result = sg
+        .reporting
+        .execute(RetractNuisanceReport {
+            id: report_id,
+            session: other,
+        })
+        .await;
+
+    assert!(result.is_err());
+
+    let reports = sg
+        .reporting
+        .execute(ListMyReports {
+            page: PageRequest::default(),
+            session: owner,
+        })
+        .await?;
+
+    assert_eq!(reports.items.len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn retract_nuisance_report_anonymously() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let owner = setup::create_user_session(&sg).await?;
+    let type_id = setup::create_nuisance_type(&sg).await?;
+
+    let report_id = sg
+        .repos
+        .execute(InsertNuisanceReport::new(
+            type_id,
+            owner.user().map(|u| u.id),
+            Point::new(2.35, 48.85).into(),
+            3,
+        ))
+        .await?;
+
+    let result = sg
+        .reporting
+        .execute(RetractNuisanceReport {
+            id: report_id,
+            session: Session::Anonymous,
+        })
+        .await;
+
+    assert!(result.is_err());
+
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use std::error::Error;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::thread;
+
+use chrono::{TimeZone, Utc};
+use signuis_core::storage::{BlobStorage, S3Settings, S3Storage};
+
+fn settings(endpoint: String) -> S3Settings {
+    S3Settings {
+        endpoint,
+        region: "us-east-1".to_owned(),
+        bucket: "signuis".to_owned(),
+        access_key: "minio".to_owned(),
+        secret_key: "minio123".to_owned(),
+    }
+}
+
+#[test]
+fn s3_request_is_signed_with_sigv4() -> Result<(), Box<dyn Error>> {
+    let storage = S3Storage::new(settings("http://localhost:9000".to_owned()))?;
+    let now = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+
+    let request = storage.build_request(
+        "PUT".parse()?,
+        "reports/a b.jpg",
+        Some("image/jpeg"),
+        b"photo".to_vec(),
+        now,
+    )?;
+
+    let header = |name: &str| {
+        request
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned)
+    };
+
+    // signature calculée indépendamment, selon la documentation d'AWS Signature Version 4
+    assert_eq!(
+        request.url().as_str(),
+        "http://localhost:9000/signuis/reports/a%20b.jpg"
+    );
+    assert_eq!(header("x-amz-date").as_deref(), Some("20240102T030405Z"));
+    assert_eq!(
+        header("x-amz-content-sha256").as_deref(),
+        Some("55c64d0fcd6f9d5f7c828093857e3fdfda68478bb4e9bd24d481ef391c7804e8")
+    );
+    assert_eq!(
+        header("authorization").as_deref(),
+        Some(concat!(
+            "AWS4-HMAC-SHA256 Credential=minio/20240102/us-east-1/s3/aws4_request, ",
+            "SignedHeaders=host;x-amz-content-sha256;x-amz-date, ",
+            "Signature=61c07b078c1067ae7f48bc83ac0ddbcb43c47088b54624bce41e90ab654339ab"
+        ))
+    );
+    assert_eq!(header("content-type").as_deref(), Some("image/jpeg"));
+
+    Ok(())
+}
+
+#[test]
+fn s3_rejects_keys_escaping_the_bucket() -> Result<(), Box<dyn Error>> {
+    let storage = S3Storage::new(settings("http://localhost:9000".to_owned()))?;
+
+    let result = storage.build_request("GET".parse()?, "../secret", None, Vec::new(), Utc::now());
+
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+/// Serveur S3 de substitution : accepte une requête, la restitue, et répond par le statut donné.
+fn stand_in(status: &'static str) -> Result<(String, thread::JoinHandle<String>), Box<dyn Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let endpoint = format!("http://{}", listener.local_addr()?);
+
+    let handle = thread::spawn(move || {
+        let (stream, _) = listener.accept().expect("no request received");
+        let mut reader = BufReader::new(stream);
+        let mut head = String::new();
+        let mut content_length = 0;
+
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).expect("invalid request");
+            if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                content_length = value.trim().parse().unwrap_or_default();
+            }
+            head.push_str(&line);
+            if line == "\r\n" {
+                break;
+            }
+        }
+
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).expect("truncated body");
+
+        let response = format!("HTTP/1.1 {status}\r\ncontent-length: 0\r\n\r\n");
+        reader
+            .get_mut()
+            .write_all(response.as_bytes())
+            .expect("cannot respond");
+
+        head + &String::from_utf8_lossy(&body)
+    });
+
+    Ok((endpoint, handle))
+}
+
+#[tokio::test]
+async fn s3_put_sends_a_signed_request() -> Result<(), Box<dyn Error>> {
+    let (endpoint, server) = stand_in("200 OK")?;
+    let storage = S3Storage::new(settings(endpoint))?;
+
+    storage
+        .put("reports/photo.jpg", "image/jpeg", b"photo".to_vec())
+        .await?;
+
+    let request = server.join().map_err(|_| "stand-in server panicked")?;
+    let lowercase = request.to_ascii_lowercase();
+
+    assert!(request.starts_with("PUT /signuis/reports/photo.jpg HTTP/1.1\r\n"));
+    assert!(lowercase.contains("authorization: aws4-hmac-sha256 credential=minio/"));
+    assert!(lowercase.contains("x-amz-content-sha256: "));
+    assert!(request.ends_with("\r\n\r\nphoto"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn s3_put_fails_on_error_status() -> Result<(), Box<dyn Error>> {
+    let (endpoint, server) = stand_in("403 Forbidden")?;
+    let storage = S3Storage::new(settings(endpoint))?;
+
+    let result = storage
+        .put("reports/photo.jpg", "image/jpeg", b"photo".to_vec())
+        .await;
+
+    server.join().map_err(|_| "stand-in server panicked")?;
+    assert!(result.is_err());
+
+    Ok(())
+}
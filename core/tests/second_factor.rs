@@ -0,0 +1,225 @@
+use std::error::Error;
+
+use signuis_core::{
+    forms::authentication::{CredentialForm, SecondFactorForm},
+    models::{session::Session, user::UserRole},
+    repositories::user::fixtures::InsertUserFixture,
+    services::authentication::{
+        AuthenticateWithCredential, AuthenticationOutcome, BeginTotpEnrolment,
+        CheckUserSessionToken, ConfirmTotpEnrolment, VerifySecondFactor,
+    },
+};
+use totp_rs::TOTP;
+
+mod setup;
+
+#[tokio::test]
+async fn authenticate_with_second_factor() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+
+    let fixture = InsertUserFixture::new();
+    sg.repos.execute(fixture.clone()).await?;
+
+    let authenticate = || AuthenticateWithCredential {
+        form: CredentialForm {
+            username_or_email: fixture.username.clone(),
+            password: fixture.password.clone().unwrap(),
+        },
+        session: Session::Anonymous,
+    };
+
+    let created = sg
+        .auth
+        .execute(authenticate())
+        .await?
+        .session()
+        .ok_or("l'authentification n'est pas complète")?;
+
+    let session = Session::User(
+        sg.auth
+            .execute(CheckUserSessionToken::new(created.token))
+            .await?
+            .ok_or("la session n'a pas été créée")?,
+    );
+
+    let enrolment = sg
+        .auth
+        .execute(BeginTotpEnrolment {
+            session: session.clone(),
+        })
+        .await?;
+
+    let generator = TOTP::from_url(&enrolment.provisioning_uri)?;
+
+    let recovery_codes = sg
+        .auth
+        .execute(ConfirmTotpEnrolment {
+            code: generator.generate_current()?,
+            session,
+        })
+        .await?;
+
+    assert_eq!(recovery_codes.len(), 10);
+
+    // les identifiants ne suffisent plus
+    let pending = match sg.auth.execute(authenticate()).await? {
+        AuthenticationOutcome::SecondFactorRequired(pending) => pending,
+        _ => return Err("le second facteur est requis".into()),
+    };
+
+    // le code de l'inscription a été consommé : on présente celui du pas suivant,
+    // encore toléré
+    let code = generator.generate(generator.next_step_current()?);
+
+    let created = sg
+        .auth
+        .execute(VerifySecondFactor {
+            form: SecondFactorForm {
+                token: pending.token,
+                code: code.clone(),
+            },
+        })
+        .await?;
+
+    // un code accepté ne peut pas être rejoué
+    let pending = match sg.auth.execute(authenticate()).await? {
+        AuthenticationOutcome::SecondFactorRequired(pending) => pending,
+        _ => return Err("le second facteur est requis".into()),
+    };
+
+    let replayed = sg
+        .auth
+        .execute(VerifySecondFactor {
+            form: SecondFactorForm {
+                token: pending.token,
+                code,
+            },
+        })
+        .await;
+
+    assert!(replayed.is_err());
+
+    let session = sg
+        .auth
+        .execute(CheckUserSessionToken::new(created.token))
+        .await?
+        .ok_or("la session n'a pas été créée")?;
+
+    assert!(session.second_factor_verified);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn recovery_codes_are_single_use() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+
+    let fixture = InsertUserFixture::new();
+    sg.repos.execute(fixture.clone()).await?;
+
+    let authenticate = || AuthenticateWithCredential {
+        form: CredentialForm {
+            username_or_email: fixture.username.clone(),
+            password: fixture.password.clone().unwrap(),
+        },
+        session: Session::Anonymous,
+    };
+
+    let created = sg
+        .auth
+        .execute(authenticate())
+        .await?
+        .session()
+        .ok_or("l'authentification n'est pas complète")?;
+
+    let session = Session::User(
+        sg.auth
+            .execute(CheckUserSessionToken::new(created.token))
+            .await?
+            .ok_or("la session n'a pas été créée")?,
+    );
+
+    let enrolment = sg
+        .auth
+        .execute(BeginTotpEnrolment {
+            session: session.clone(),
+        })
+        .await?;
+
+    let recovery_codes = sg
+        .auth
+        .execute(ConfirmTotpEnrolment {
+            code: TOTP::from_url(&enrolment.provisioning_uri)?.generate_current()?,
+            session,
+        })
+        .await?;
+
+    let verify_with_recovery_code = |token: String| VerifySecondFactor {
+        form: SecondFactorForm {
+            token,
+            code: recovery_codes[0].clone(),
+        },
+    };
+
+    let pending = match sg.auth.execute(authenticate()).await? {
+        AuthenticationOutcome::SecondFactorRequired(pending) => pending,
+        _ => return Err("le second facteur est requis".into()),
+    };
+
+    sg.auth
+        .execute(verify_with_recovery_code(pending.token))
+        .await?;
+
+    let pending = match sg.auth.execute(authenticate()).await? {
+        AuthenticationOutcome::SecondFactorRequired(pending) => pending,
+        _ => return Err("le second facteur est requis".into()),
+    };
+
+    let result = sg
+        .auth
+        .execute(verify_with_recovery_code(pending.token))
+        .await;
+
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn privileged_roles_must_enrol() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+
+    let mut fixture = InsertUserFixture::new();
+    fixture.role = UserRole::Administrator;
+    sg.repos.execute(fixture.clone()).await?;
+
+    let outcome = sg
+        .auth
+        .execute(AuthenticateWithCredential {
+            form: CredentialForm {
+                username_or_email: fixture.username.clone(),
+                password: fixture.password.clone().unwrap(),
+            },
+            session: Session::Anonymous,
+        })
+        .await?;
+
+    let created = match outcome {
+        AuthenticationOutcome::SecondFactorEnrolmentRequired(created) => created,
+        _ => return Err("l'inscription au second facteur est requise".into()),
+    };
+
+    let session = Session::User(
+        sg.auth
+            .execute(CheckUserSessionToken::new(created.token))
+            .await?
+            .ok_or("la session n'a pas été créée")?,
+    );
+
+    assert!(!session.is_admin());
+
+    // la session permet de s'inscrire
+    sg.auth.execute(BeginTotpEnrolment { session }).await?;
+
+    Ok(())
+}
@@ -1,23 +1,101 @@
 use std::error::Error;
+use std::ops::Add;
 
+use chrono::{Duration, Utc};
 use signuis_core::{
-    models::{session::UserSession, user::UserRole},
-    repositories::{user::fixtures::InsertUserFixture, BeginTx},
+    models::{
+        nuisance_type::NuisanceTypeId,
+        session::{Session, UserSession},
+        user::UserRole,
+    },
+    repositories::{
+        nuisance_family::InsertNuisanceFamily, nuisance_type::InsertNuisanceType,
+        user::fixtures::InsertUserFixture, user_session::InsertUserSession,
+    },
+    services::authentication::CheckUserSessionToken,
     SgSettings, Signuis,
 };
+use uuid::Uuid;
 
-/// Démarre le système Signuis avec un répertoire en mode transaction.
+/// Démarre le système Signuis avec un répertoire en mode transaction, jamais validée.
 pub async fn setup() -> Result<Signuis, Box<dyn Error>> {
-    let sg = Signuis::new(SgSettings::default().set_max_connections(1).to_owned()).await?;
-    sg.repos.execute(BeginTx {}).await?;
+    setup_with_settings(SgSettings::default()).await
+}
+
+/// Démarre Signuis avec les paramètres donnés, et un répertoire en mode transaction.
+pub async fn setup_with_settings(mut settings: SgSettings) -> Result<Signuis, Box<dyn Error>> {
+    let sg = Signuis::new(settings.set_rollback_only(true).to_owned()).await?;
+    Ok(sg)
+}
+
+/// Démarre Signuis avec un répertoire en mémoire, sans base de données.
+pub async fn setup_in_memory() -> Result<Signuis, Box<dyn Error>> {
+    let sg = Signuis::new(SgSettings::default().set_in_memory(true).to_owned()).await?;
     Ok(sg)
 }
 
+/// Crée une session utilisateur avec le rôle donné.
+///
+/// La session est réputée ouverte avec un second facteur si le rôle l'exige.
+pub async fn create_session_with_role(
+    sg: &Signuis,
+    role: UserRole,
+) -> Result<UserSession, Box<dyn Error>> {
+    let mut user = InsertUserFixture::default();
+    user.role = role;
+
+    let user_id = sg.repos.execute(user).await?;
+    let token = Uuid::new_v4().to_string();
+
+    sg.repos
+        .execute(InsertUserSession {
+            user_id,
+            token: token.clone(),
+            expires_at: Utc::now().add(Duration::hours(1)),
+            second_factor_verified: role.requires_second_factor(),
+            impersonator_id: None,
+        })
+        .await?;
+
+    let session = sg
+        .auth
+        .execute(CheckUserSessionToken::new(token))
+        .await?
+        .ok_or("la session n'a pas été créée")?;
+
+    Ok(session)
+}
+
 /// Crée une session avec le rôle d'administrateur.
 pub async fn create_admin_session(sg: &Signuis) -> Result<UserSession, Box<dyn Error>> {
-    let user = InsertUserFixture::default();
-    user.role = UserRole::Administrator;
+    create_session_with_role(sg, UserRole::Administrator).await
+}
 
-    let user_id = sg.repos.execute(user);
+/// Crée une session avec le rôle d'utilisateur.
+pub async fn create_user_session(sg: &Signuis) -> Result<Session, Box<dyn Error>> {
+    Ok(Session::User(
+        create_session_with_role(sg, UserRole::User).await?,
+    ))
 }
 
+/// Crée une famille et un type de nuisance.
+pub async fn create_nuisance_type(sg: &Signuis) -> Result<NuisanceTypeId, Box<dyn Error>> {
+    let family_id = sg
+        .repos
+        .execute(InsertNuisanceFamily {
+            label: Uuid::new_v4().to_string(),
+            description: "famille de test".to_owned(),
+        })
+        .await?;
+
+    let type_id = sg
+        .repos
+        .execute(InsertNuisanceType {
+            label: Uuid::new_v4().to_string(),
+            description: "type de test".to_owned(),
+            family_id,
+        })
+        .await?;
+
+    Ok(type_id)
+}
@@ -0,0 +1,72 @@
+use chrono::{DateTime, Duration, Utc};
+use signuis_core::weather::{StationFileProvider, WeatherProvider};
+use std::error::Error;
+
+const STATIONS: &str = r#"{
+    "stations": [
+        {
+            "id": "paris",
+            "longitude": 2.3377,
+            "latitude": 48.8217,
+            "observations": [
+                { "at": "2024-09-01T12:00:00Z", "wind_direction": 225.0, "wind_speed": 3.5, "temperature": 21.4, "humidity": 63.0 },
+                { "at": "2024-09-01T13:00:00Z", "wind_direction": 270.0, "wind_speed": 4.0, "temperature": 22.0, "humidity": 60.0 }
+            ]
+        },
+        {
+            "id": "lyon",
+            "longitude": 4.9489,
+            "latitude": 45.7264,
+            "observations": [
+                { "at": "2024-09-01T12:00:00Z", "wind_direction": 0.0, "wind_speed": 1.0, "temperature": 25.0, "humidity": 40.0 }
+            ]
+        }
+    ]
+}"#;
+
+fn provider() -> Result<StationFileProvider, Box<dyn Error>> {
+    Ok(StationFileProvider::from_slice(
+        STATIONS.as_bytes(),
+        50.0,
+        Duration::hours(2),
+    )?)
+}
+
+fn at(date: &str) -> Result<DateTime<Utc>, Box<dyn Error>> {
+    Ok(DateTime::parse_from_rfc3339(date)?.with_timezone(&Utc))
+}
+
+#[tokio::test]
+async fn observe_weather_from_closest_station_and_time() -> Result<(), Box<dyn Error>> {
+    let observation = provider()?
+        .observe(2.35, 48.85, at("2024-09-01T12:50:00Z")?)
+        .await?
+        .ok_or("aucun relevé")?;
+
+    assert_eq!(observation.station.as_deref(), Some("paris"));
+    assert_eq!(observation.wind_direction, 270.0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn observe_weather_too_far_from_any_station() -> Result<(), Box<dyn Error>> {
+    let observation = provider()?
+        .observe(-1.55, 47.21, at("2024-09-01T12:00:00Z")?)
+        .await?;
+
+    assert!(observation.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn observe_weather_too_far_in_time() -> Result<(), Box<dyn Error>> {
+    let observation = provider()?
+        .observe(2.35, 48.85, at("2024-09-02T12:00:00Z")?)
+        .await?;
+
+    assert!(observation.is_none());
+
+    Ok(())
+}
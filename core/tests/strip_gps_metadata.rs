@@ -0,0 +1,76 @@
+use signuis_core::media::{sniff_content_type, strip_gps_metadata};
+
+const JPEG_EOI: [u8; 9] = [0xFF, 0xDA, 0x00, 0x02, 0x01, 0x02, 0x03, 0xFF, 0xD9];
+
+/// Construit un JPEG minimal dont le bloc EXIF contient une latitude GPS.
+fn jpeg_with_gps() -> Vec<u8> {
+    let mut tiff = Vec::new();
+    tiff.extend(b"II");
+    tiff.extend(42u16.to_le_bytes());
+    tiff.extend(8u32.to_le_bytes());
+    // IFD0 : un pointeur vers le répertoire GPS.
+    tiff.extend(1u16.to_le_bytes());
+    tiff.extend(0x8825u16.to_le_bytes());
+    tiff.extend(4u16.to_le_bytes());
+    tiff.extend(1u32.to_le_bytes());
+    tiff.extend(26u32.to_le_bytes());
+    tiff.extend(0u32.to_le_bytes());
+    // Répertoire GPS : GPSLatitude, trois rationnels stockés à l'offset 44.
+    tiff.extend(1u16.to_le_bytes());
+    tiff.extend(2u16.to_le_bytes());
+    tiff.extend(5u16.to_le_bytes());
+    tiff.extend(3u32.to_le_bytes());
+    tiff.extend(44u32.to_le_bytes());
+    tiff.extend(0u32.to_le_bytes());
+    (1..=6u32).for_each(|i| tiff.extend((i * 7).to_le_bytes()));
+
+    let mut app1 = b"Exif\0\0".to_vec();
+    app1.extend(&tiff);
+
+    let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE1];
+    jpeg.extend(((app1.len() + 2) as u16).to_be_bytes());
+    jpeg.extend(&app1);
+    jpeg.extend(JPEG_EOI);
+    jpeg
+}
+
+#[test]
+fn strip_gps_metadata_from_jpeg() {
+    let jpeg = jpeg_with_gps();
+    let stripped = strip_gps_metadata("image/jpeg", jpeg.clone());
+
+    assert_eq!(stripped.len(), jpeg.len());
+    assert_eq!(sniff_content_type(&stripped), Some("image/jpeg"));
+
+    let tiff = &stripped[12..];
+    assert_eq!(&tiff[26..28], &[0, 0]);
+    assert!(tiff[28..68].iter().all(|byte| *byte == 0));
+    assert!(stripped.ends_with(&JPEG_EOI));
+}
+
+#[test]
+fn strip_malformed_exif_from_jpeg() {
+    let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE1, 0x00, 0x08];
+    jpeg.extend(b"Exif\0\0");
+    jpeg.extend([0xFF, 0xD9]);
+
+    let stripped = strip_gps_metadata("image/jpeg", jpeg);
+
+    assert_eq!(stripped, vec![0xFF, 0xD8, 0xFF, 0xD9]);
+}
+
+#[test]
+fn strip_exif_chunk_from_png() {
+    let mut png = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+    png.extend(2u32.to_be_bytes());
+    png.extend(b"eXIf");
+    png.extend([1, 2, 0, 0, 0, 0]);
+    png.extend(0u32.to_be_bytes());
+    png.extend(b"IEND");
+    png.extend([0xAE, 0x42, 0x60, 0x82]);
+
+    let stripped = strip_gps_metadata("image/png", png);
+
+    assert_eq!(stripped.len(), 8 + 12);
+    assert_eq!(&stripped[12..16], b"IEND");
+}
@@ -0,0 +1,90 @@
+use std::error::Error;
+
+use signuis_core::repositories::{
+    user::{fixtures::InsertUserFixture, UserWithUsernameOrEmailExists},
+    Repository,
+};
+
+mod setup;
+
+/// Vérifie si l'utilisateur a été inséré.
+async fn user_exists(repos: &Repository, user: &InsertUserFixture) -> Result<bool, Box<dyn Error>> {
+    let exists = repos
+        .execute(UserWithUsernameOrEmailExists {
+            username: user.username.clone(),
+            email: user.email.clone(),
+        })
+        .await?;
+
+    Ok(exists.username_exists)
+}
+
+#[tokio::test]
+async fn transaction_is_committed_on_success() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let user = InsertUserFixture::new();
+
+    let insert = user.clone();
+    sg.repos
+        .transaction(move |tx| Box::pin(async move { tx.execute(insert).await }))
+        .await?;
+
+    assert!(user_exists(&sg.repos, &user).await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn transaction_is_rolled_back_on_error() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let user = InsertUserFixture::new();
+
+    let insert = user.clone();
+    let result = sg
+        .repos
+        .transaction(move |tx| {
+            Box::pin(async move {
+                tx.execute(insert).await?;
+                Err::<(), _>(signuis_core::error::Error::internal_error())
+            })
+        })
+        .await;
+
+    assert!(result.is_err());
+    assert!(!user_exists(&sg.repos, &user).await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn repository_remains_usable_within_a_transaction() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let user = InsertUserFixture::new();
+
+    let insert = user.clone();
+    let repos = sg.repos.clone();
+    let transaction = sg.repos.transaction(move |tx| {
+        Box::pin(async move {
+            tx.execute(insert.clone()).await?;
+
+            // hors de `tx`, sur la même transaction épinglée
+            let exists = repos
+                .execute(UserWithUsernameOrEmailExists {
+                    username: insert.username,
+                    email: insert.email,
+                })
+                .await?;
+
+            Ok(exists.username_exists)
+        })
+    });
+
+    let seen = tokio::time::timeout(std::time::Duration::from_secs(5), transaction)
+        .await
+        .map_err(|_| "la transaction est bloquée")??;
+
+    assert!(seen);
+    assert!(user_exists(&sg.repos, &user).await?);
+
+    Ok(())
+}
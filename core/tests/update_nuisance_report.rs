@@ -0,0 +1,140 @@
+use std::error::Error;
+
+use chrono::Duration;
+use signuis_core::{
+    error::ErrorKind,
+    forms::reporting::UpdateNuisanceReportForm,
+    models::{nuisance_report::NuisanceReportId, session::Session},
+    repositories::nuisance_report::{
+        InsertNuisanceReport, MaybeFindOneNuisanceReportById, PatchNuisanceReport,
+    },
+    services::reporting::UpdateNuisanceReport,
+    SgSettings, Signuis,
+};
+use sql_gis::types::Point;
+
+mod setup;
+
+/// Insère un signalement d'intensité 3 appartenant à l'utilisateur de la session.
+async fn insert_report(
+    sg: &Signuis,
+    session: &Session,
+) -> Result<NuisanceReportId, Box<dyn Error>> {
+    let type_id = setup::create_nuisance_type(sg).await?;
+
+    let report_id = sg
+        .repos
+        .execute(InsertNuisanceReport::new(
+            type_id,
+            session.user().map(|u| u.id),
+            Point::new(2.35, 48.85).into(),
+            3,
+        ))
+        .await?;
+
+    Ok(report_id)
+}
+
+async fn intensity_of(sg: &Signuis, id: NuisanceReportId) -> Result<i8, Box<dyn Error>> {
+    let report = sg
+        .repos
+        .execute(MaybeFindOneNuisanceReportById(id))
+        .await?
+        .ok_or("le signalement n'a pas été trouvé")?;
+
+    Ok(report.intensity)
+}
+
+fn amend(id: NuisanceReportId, session: &Session) -> UpdateNuisanceReport {
+    UpdateNuisanceReport {
+        id,
+        form: UpdateNuisanceReportForm {
+            intensity: Some(5),
+            ..Default::default()
+        },
+        session: session.clone(),
+    }
+}
+
+#[tokio::test]
+async fn amend_nuisance_report_within_grace_period() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let session = setup::create_user_session(&sg).await?;
+    let report_id = insert_report(&sg, &session).await?;
+
+    sg.reporting.execute(amend(report_id, &session)).await?;
+
+    assert_eq!(intensity_of(&sg, report_id).await?, 5);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn amend_nuisance_report_after_grace_period() -> Result<(), Box<dyn Error>> {
+    // le signalement est créé au début de la transaction de test : le délai est dépassé
+    let sg = setup::setup_with_settings(
+        SgSettings::default()
+            .set_report_edition_grace_period(Duration::zero())
+            .to_owned(),
+    )
+    .await?;
+    let session = setup::create_user_session(&sg).await?;
+    let report_id = insert_report(&sg, &session).await?;
+
+    let result = sg.reporting.execute(amend(report_id, &session)).await;
+
+    assert!(matches!(
+        result,
+        Err(signuis_core::error::Error {
+            kind: ErrorKind::Unauthorized,
+            ..
+        })
+    ));
+    assert_eq!(intensity_of(&sg, report_id).await?, 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn patch_checks_grace_period_on_write() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let session = setup::create_user_session(&sg).await?;
+    let report_id = insert_report(&sg, &session).await?;
+
+    // le délai expire entre la vérification et l'écriture
+    let patched = sg
+        .repos
+        .execute(PatchNuisanceReport {
+            id: report_id,
+            type_id: None,
+            intensity: Some(5),
+            grace_period: Duration::zero(),
+        })
+        .await?;
+
+    assert!(!patched);
+    assert_eq!(intensity_of(&sg, report_id).await?, 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn amend_nuisance_report_of_another_user() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let owner = setup::create_user_session(&sg).await?;
+    let other = setup::create_user_session(&sg).await?;
+    let report_id = insert_report(&sg, &owner).await?;
+
+    let result = sg.reporting.execute(amend(report_id, &other)).await;
+
+    assert!(matches!(
+        result,
+        Err(signuis_core::error::Error {
+            kind: ErrorKind::Unauthorized,
+            ..
+        })
+    ));
+    assert_eq!(intensity_of(&sg, report_id).await?, 3);
+
+    Ok(())
+}
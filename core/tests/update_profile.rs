@@ -0,0 +1,78 @@
+use std::error::Error;
+
+use signuis_core::{
+    forms::account::{ChangePasswordForm, UpdateProfileForm},
+    services::account::{ChangePassword, GetMyProfile, UpdateProfile},
+};
+
+mod setup;
+
+#[tokio::test]
+async fn update_profile_of_current_user() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let session = setup::create_user_session(&sg).await?;
+
+    let profile = sg
+        .account
+        .execute(UpdateProfile {
+            form: UpdateProfileForm {
+                email: Some("nouvelle.adresse@example.com".to_owned()),
+                locale: Some("en".to_owned()),
+                ..UpdateProfileForm::default()
+            },
+            session: session.clone(),
+        })
+        .await?;
+
+    assert_eq!(profile.email, "nouvelle.adresse@example.com");
+    assert_eq!(profile.locale.as_deref(), Some("en"));
+    assert!(profile.email_verified_at.is_none());
+
+    let fetched = sg.account.execute(GetMyProfile { session }).await?;
+    assert_eq!(fetched.email, profile.email);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn update_profile_rejects_unknown_locale() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let session = setup::create_user_session(&sg).await?;
+
+    let result = sg
+        .account
+        .execute(UpdateProfile {
+            form: UpdateProfileForm {
+                locale: Some("tlh".to_owned()),
+                ..UpdateProfileForm::default()
+            },
+            session,
+        })
+        .await;
+
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn change_password_requires_current_password() -> Result<(), Box<dyn Error>> {
+    let sg = setup::setup().await?;
+    let session = setup::create_user_session(&sg).await?;
+
+    let result = sg
+        .account
+        .execute(ChangePassword {
+            form: ChangePasswordForm {
+                current_password: "pas le bon mot de passe".to_owned(),
+                new_password: "un nouveau mot de passe".to_owned(),
+                confirm_password: "un nouveau mot de passe".to_owned(),
+            },
+            session,
+        })
+        .await;
+
+    assert!(result.is_err());
+
+    Ok(())
+}
@@ -1,3 +0,0 @@
--- Add down migration script here
-DROP TABLE User;
-DROP TABLE Session;
\ No newline at end of file
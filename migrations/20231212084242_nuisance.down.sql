@@ -1,3 +0,0 @@
--- Add down migration script here
-DROP TABLE NuisanceType;
-DROP TABLE NuisanceFamily;
\ No newline at end of file
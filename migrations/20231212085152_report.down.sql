@@ -1,2 +0,0 @@
--- Add down migration script here
-DROP TABLE Report;
\ No newline at end of file